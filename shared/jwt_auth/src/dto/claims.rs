@@ -5,6 +5,12 @@ use uuid::Uuid;
 pub struct Claims {
     pub sub: Uuid,
     pub realm_access: JwtClaimsRealmAccess,
+
+    ///
+    /// Preferred locale of the user, e.g. "en-US"
+    ///
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -0,0 +1,151 @@
+///
+/// Locales preferred by the user, the most preferred first
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Locales(Vec<String>);
+
+impl Locales {
+    ///
+    /// Locale from the JWT claim is preferred over locales from Accept-Language header,
+    /// which are ordered by their quality. Wildcard and locales with zero quality are skipped
+    ///
+    pub fn new(claim: Option<&str>, accept_language: Option<&str>) -> Self {
+        let mut locales = Vec::new();
+        if let Some(claim) = claim.map(str::trim).filter(|claim| !claim.is_empty()) {
+            locales.push(claim.to_string());
+        }
+
+        if let Some(accept_language) = accept_language {
+            let mut weighted_locales = accept_language
+                .split(',')
+                .filter_map(|range| {
+                    let mut params = range.split(';').map(str::trim);
+                    let locale = params
+                        .next()
+                        .filter(|locale| !locale.is_empty() && *locale != "*")?;
+                    let quality = params
+                        .find_map(|param| param.strip_prefix("q="))
+                        .map(|quality| quality.parse::<f32>().unwrap_or(0.0))
+                        .unwrap_or(1.0);
+
+                    (quality > 0.0).then_some((locale, quality))
+                })
+                .collect::<Vec<_>>();
+
+            // Sort is stable so locales with the same quality keep their order
+            weighted_locales.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            locales.extend(
+                weighted_locales
+                    .into_iter()
+                    .map(|(locale, _)| locale.to_string()),
+            );
+        }
+
+        Self(locales)
+    }
+
+    ///
+    /// Finds candidate best matching the locales. For every locale, starting
+    /// from the most preferred one, candidate with the same locale is matched first
+    /// and then candidate with the same language (e.g. "en-GB" for "en-US")
+    ///
+    /// ### Returns
+    /// index of the matched candidate or None when no candidate matches
+    ///
+    pub fn best_match<'a>(
+        &self,
+        candidates: impl Iterator<Item = &'a str> + Clone,
+    ) -> Option<usize> {
+        self.0.iter().find_map(|locale| {
+            candidates
+                .clone()
+                .position(|candidate| candidate.eq_ignore_ascii_case(locale))
+                .or_else(|| {
+                    candidates.clone().position(|candidate| {
+                        Self::language(candidate).eq_ignore_ascii_case(Self::language(locale))
+                    })
+                })
+        })
+    }
+
+    pub fn as_slice(&self) -> &[String] {
+        &self.0
+    }
+
+    fn language(locale: &str) -> &str {
+        locale.split(['-', '_']).next().unwrap_or(locale)
+    }
+}
+
+impl From<Vec<String>> for Locales {
+    fn from(value: Vec<String>) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locales_new_claim_preferred() {
+        let locales = Locales::new(Some("pl-PL"), Some("en-US,en;q=0.9"));
+
+        assert_eq!(
+            locales,
+            Locales(vec![
+                "pl-PL".to_string(),
+                "en-US".to_string(),
+                "en".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn locales_new_accept_language_ordered_by_quality() {
+        let locales = Locales::new(None, Some("de;q=0.5, fr-CH, *;q=0.8, en;q=0.8, it;q=0"));
+
+        assert_eq!(
+            locales,
+            Locales(vec![
+                "fr-CH".to_string(),
+                "en".to_string(),
+                "de".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn locales_new_empty() {
+        let locales = Locales::new(Some(" "), None);
+
+        assert_eq!(locales, Locales::default());
+    }
+
+    #[test]
+    fn locales_best_match_exact_locale_first() {
+        let locales = Locales::new(None, Some("en-GB"));
+
+        let best_match = locales.best_match(["en-US", "en-gb", "de-DE"].into_iter());
+
+        assert_eq!(best_match, Some(1));
+    }
+
+    #[test]
+    fn locales_best_match_same_language() {
+        let locales = Locales::new(None, Some("de-AT, en"));
+
+        let best_match = locales.best_match(["en", "de-DE"].into_iter());
+
+        assert_eq!(best_match, Some(1));
+    }
+
+    #[test]
+    fn locales_best_match_none() {
+        let locales = Locales::new(None, Some("fr"));
+
+        let best_match = locales.best_match(["en", "de-DE"].into_iter());
+
+        assert_eq!(best_match, None);
+    }
+}
@@ -1,5 +1,7 @@
 mod claims;
+mod locales;
 mod user;
 
 pub use claims::*;
+pub use locales::*;
 pub use user::*;
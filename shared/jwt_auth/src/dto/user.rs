@@ -20,12 +20,13 @@ pub struct User {
 pub struct InnerUser {
     pub id: Uuid,
     pub roles: Vec<String>,
+    pub locale: Option<String>,
 }
 
 impl User {
-    pub fn new(id: Uuid, roles: Vec<String>) -> Self {
+    pub fn new(id: Uuid, roles: Vec<String>, locale: Option<String>) -> Self {
         Self {
-            inner: Arc::new(InnerUser { id, roles }),
+            inner: Arc::new(InnerUser { id, roles, locale }),
         }
     }
 }
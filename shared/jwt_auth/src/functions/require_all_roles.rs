@@ -35,6 +35,7 @@ mod test {
                 required_role.to_string(),
                 "second_other_application_role".to_string(),
             ],
+            None,
         );
 
         let result = require_all_roles(&user, &[required_role]);
@@ -52,6 +53,7 @@ mod test {
                 "first_other_application_role".to_string(),
                 "second_other_application_role".to_string(),
             ],
+            None,
         );
 
         let result = require_all_roles(&user, &[missing_role]);
@@ -6,4 +6,4 @@ pub mod middleware;
 pub mod test;
 pub mod util;
 
-pub use dto::User;
+pub use dto::{Locales, User};
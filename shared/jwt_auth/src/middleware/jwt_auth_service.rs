@@ -53,6 +53,7 @@ impl<S> JwtAuthService<S> {
         Ok(User::new(
             token_data.claims.sub,
             token_data.claims.realm_access.roles,
+            token_data.claims.locale,
         ))
     }
 }
@@ -1,7 +1,13 @@
 use amqprs::BasicProperties;
+use tokio::sync::oneshot;
 
 pub struct Message {
     pub routing_key: String,
     pub basic_properties: BasicProperties,
     pub content: Vec<u8>,
+
+    ///
+    /// Channel used to inform sender that message was acked by the broker
+    ///
+    pub confirm_tx: Option<oneshot::Sender<()>>,
 }
@@ -6,7 +6,7 @@ use amqprs::{
 };
 use std::sync::Arc;
 use tokio::{
-    sync::{mpsc, watch, Notify},
+    sync::{mpsc, oneshot, watch, Notify},
     task::JoinHandle,
 };
 
@@ -102,10 +102,40 @@ impl RabbitmqProducer {
     }
 
     pub fn send(&self, routing_key: String, basic_properties: BasicProperties, content: Vec<u8>) {
+        self.publish(routing_key, basic_properties, content, None);
+    }
+
+    ///
+    /// Sends message the same way as [RabbitmqProducer::send]
+    ///
+    /// ### Returns
+    /// receiver that completes when message is acked by the broker.
+    /// Receiver fails when producer is closed before message gets acked
+    ///
+    pub fn send_confirmed(
+        &self,
+        routing_key: String,
+        basic_properties: BasicProperties,
+        content: Vec<u8>,
+    ) -> oneshot::Receiver<()> {
+        let (confirm_tx, confirm_rx) = oneshot::channel();
+        self.publish(routing_key, basic_properties, content, Some(confirm_tx));
+
+        confirm_rx
+    }
+
+    fn publish(
+        &self,
+        routing_key: String,
+        basic_properties: BasicProperties,
+        content: Vec<u8>,
+        confirm_tx: Option<oneshot::Sender<()>>,
+    ) {
         let message = Box::new(Message {
             routing_key,
             basic_properties,
             content,
+            confirm_tx,
         });
 
         // messages_rx always exist in external task that
@@ -316,7 +316,13 @@ impl StateMachine {
             "processing publisher confirm"
         );
 
-        let on_remove_ack = |_message_count, _message| {};
+        let on_remove_ack = |message_count, message: Box<Message>| {
+            if let Some(confirm_tx) = message.confirm_tx {
+                // Sender may not be interested in confirmation anymore
+                let _ = confirm_tx.send(());
+                tracing::trace!(message_count, "sender informed about ack");
+            }
+        };
         let on_remove_nack = |message_count, message| {
             self.messages_tx.send(message).unwrap();
             tracing::trace!(message_count, "nacked message scheduled to be resent");
@@ -84,6 +84,36 @@ async fn produced_messages_received_by_the_consumer() {
     rabbitmq_connection.close().await;
 }
 
+#[tokio::test]
+#[serial]
+async fn send_confirmed_completes_after_ack() {
+    BEFORE_ALL.call_once(init_test_environment);
+
+    let exchange_name = "test rabbitmq_client send_confirmed_completes_after_ack".to_string();
+    let routing_key = "send_confirmed_completes_after_ack".to_string();
+
+    let rabbitmq_connection = create_rabbitmq_connection().await;
+    let exchange_declare_args =
+        ExchangeDeclareArguments::of_type(&exchange_name, ExchangeType::Direct);
+    let rabbitmq_producer =
+        RabbitmqProducer::new(rabbitmq_connection.clone(), exchange_declare_args)
+            .await
+            .unwrap();
+
+    let content = b"send_confirmed_completes_after_ack".to_vec();
+
+    let confirm_rx =
+        rabbitmq_producer.send_confirmed(routing_key, BasicProperties::default(), content);
+
+    timeout(Duration::from_secs(5), confirm_rx)
+        .await
+        .unwrap() // timeout
+        .unwrap(); // confirm
+
+    rabbitmq_producer.close().await;
+    rabbitmq_connection.close().await;
+}
+
 #[tokio::test]
 #[serial]
 async fn produced_messages_received_by_the_consumer_after_connection_failure() {
@@ -1,5 +1,5 @@
 use super::{ApplicationMiddleware, ApplicationState};
-use crate::routing::routing;
+use crate::routing::{attachments_routing, routing};
 use axum::Router;
 
 pub fn create_application(
@@ -7,8 +7,9 @@ pub fn create_application(
     application_middleware: ApplicationMiddleware,
 ) -> Router {
     routing()
-        .with_state(application_state)
         .layer(application_middleware.body_limit)
+        .merge(attachments_routing().layer(application_middleware.attachment_body_limit))
+        .with_state(application_state)
         .route_layer(application_middleware.auth)
         .layer(application_middleware.trace)
 }
@@ -1,7 +1,15 @@
 use anyhow::anyhow;
 use jsonwebtoken::{Algorithm, DecodingKey};
 use jwt_auth::util::{parse_jwt_algorithms, parse_jwt_key};
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    time::Duration,
+};
+use uuid::Uuid;
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
 
 pub struct ApplicationEnv {
     pub log_directory: String,
@@ -11,9 +19,12 @@ pub struct ApplicationEnv {
 
     pub db_connection_string: String,
     pub db_name: String,
+    /// Content of at least this many bytes is stored compressed, `None` disables compression
+    pub db_compression_threshold: Option<usize>,
 
     pub max_notification_content_len: usize,
     pub max_http_content_len: usize,
+    pub max_attachment_len: usize,
 
     /// Algorithms must belong to the same family
     pub jwt_algorithms: Vec<Algorithm>,
@@ -24,6 +35,29 @@ pub struct ApplicationEnv {
     pub rabbitmq_confirmations_exchange_name: String,
     pub rabbitmq_confirmations_queue_name: String,
     pub rabbitmq_retry_interval: Duration,
+    /// Content of at least this many bytes is published compressed, `None` disables compression
+    pub rabbitmq_compression_threshold: Option<usize>,
+
+    pub outbox_relay_interval: Duration,
+    pub outbox_relay_batch_size: u32,
+    pub outbox_relay_confirm_timeout: Duration,
+
+    pub retention_interval: Duration,
+    pub retention_batch_size: u32,
+    pub retention_max_age: Duration,
+    pub retention_producers_max_age: HashMap<Uuid, Duration>,
+    pub retention_invalidated: Duration,
+    pub retention_deleted: Duration,
+    pub retention_orphaned_attachments: Duration,
+
+    pub webhook_relay_interval: Duration,
+    pub webhook_relay_batch_size: u32,
+    pub webhook_relay_timeout: Duration,
+    pub webhook_relay_max_attempts: u32,
+    pub webhook_relay_backoff: Duration,
+    pub webhook_relay_max_backoff: Duration,
+    /// Hosts of webhooks allowed to be loopback, private or other non-public addresses
+    pub webhook_allowed_hosts: HashSet<String>,
 }
 
 impl ApplicationEnv {
@@ -33,10 +67,14 @@ impl ApplicationEnv {
         let bind_address = Self::env_var("TOM_NOTIFIER_CORE_BIND_ADDRESS")?.parse()?;
         let db_connection_string = Self::env_var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")?;
         let db_name = Self::env_var("TOM_NOTIFIER_CORE_DB_NAME")?;
+        let db_compression_threshold = Self::parse_compression_threshold(&Self::env_var(
+            "TOM_NOTIFIER_CORE_DB_COMPRESSION_THRESHOLD",
+        )?)?;
         let max_notification_content_len =
             Self::env_var("TOM_NOTIFIER_CORE_MAX_NOTIFICATION_CONTENT_LEN")?.parse()?;
         let max_http_content_len =
             Self::env_var("TOM_NOTIFIER_CORE_MAX_HTTP_CONTENT_LEN")?.parse()?;
+        let max_attachment_len = Self::env_var("TOM_NOTIFIER_CORE_MAX_ATTACHMENT_LEN")?.parse()?;
         let jwt_algorithms =
             parse_jwt_algorithms(Self::env_var("TOM_NOTIFIER_CORE_JWT_ALGORITHMS")?)?;
         let jwt_algorithm = jwt_algorithms.first().ok_or(anyhow!(
@@ -54,6 +92,54 @@ impl ApplicationEnv {
         let rabbitmq_retry_interval =
             Self::env_var("TOM_NOTIFIER_CORE_RABBITMQ_RETRY_INTERVAL")?.parse()?;
         let rabbitmq_retry_interval = Duration::from_secs(rabbitmq_retry_interval);
+        let rabbitmq_compression_threshold = Self::parse_compression_threshold(&Self::env_var(
+            "TOM_NOTIFIER_CORE_RABBITMQ_COMPRESSION_THRESHOLD",
+        )?)?;
+        let outbox_relay_interval =
+            Self::env_var("TOM_NOTIFIER_CORE_OUTBOX_RELAY_INTERVAL")?.parse()?;
+        let outbox_relay_interval = Duration::from_secs(outbox_relay_interval);
+        let outbox_relay_batch_size =
+            Self::env_var("TOM_NOTIFIER_CORE_OUTBOX_RELAY_BATCH_SIZE")?.parse()?;
+        let outbox_relay_confirm_timeout =
+            Self::env_var("TOM_NOTIFIER_CORE_OUTBOX_RELAY_CONFIRM_TIMEOUT")?.parse()?;
+        let outbox_relay_confirm_timeout = Duration::from_secs(outbox_relay_confirm_timeout);
+        let retention_interval = Self::env_var("TOM_NOTIFIER_CORE_RETENTION_INTERVAL")?.parse()?;
+        let retention_interval = Duration::from_secs(retention_interval);
+        let retention_batch_size =
+            Self::env_var("TOM_NOTIFIER_CORE_RETENTION_BATCH_SIZE")?.parse()?;
+        let retention_max_age: u32 =
+            Self::env_var("TOM_NOTIFIER_CORE_RETENTION_MAX_AGE")?.parse()?;
+        let retention_max_age = DAY * retention_max_age;
+        let retention_producers_max_age = Self::parse_producers_max_age(&Self::env_var(
+            "TOM_NOTIFIER_CORE_RETENTION_PRODUCERS_MAX_AGE",
+        )?)?;
+        let retention_invalidated: u32 =
+            Self::env_var("TOM_NOTIFIER_CORE_RETENTION_INVALIDATED")?.parse()?;
+        let retention_invalidated = DAY * retention_invalidated;
+        let retention_deleted: u32 =
+            Self::env_var("TOM_NOTIFIER_CORE_RETENTION_DELETED")?.parse()?;
+        let retention_deleted = DAY * retention_deleted;
+        let retention_orphaned_attachments: u32 =
+            Self::env_var("TOM_NOTIFIER_CORE_RETENTION_ORPHANED_ATTACHMENTS")?.parse()?;
+        let retention_orphaned_attachments = HOUR * retention_orphaned_attachments;
+        let webhook_relay_interval =
+            Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_RELAY_INTERVAL")?.parse()?;
+        let webhook_relay_interval = Duration::from_secs(webhook_relay_interval);
+        let webhook_relay_batch_size =
+            Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_RELAY_BATCH_SIZE")?.parse()?;
+        let webhook_relay_timeout =
+            Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_RELAY_TIMEOUT")?.parse()?;
+        let webhook_relay_timeout = Duration::from_secs(webhook_relay_timeout);
+        let webhook_relay_max_attempts =
+            Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_RELAY_MAX_ATTEMPTS")?.parse()?;
+        let webhook_relay_backoff =
+            Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_RELAY_BACKOFF")?.parse()?;
+        let webhook_relay_backoff = Duration::from_secs(webhook_relay_backoff);
+        let webhook_relay_max_backoff =
+            Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_RELAY_MAX_BACKOFF")?.parse()?;
+        let webhook_relay_max_backoff = Duration::from_secs(webhook_relay_max_backoff);
+        let webhook_allowed_hosts =
+            Self::parse_hosts(&Self::env_var("TOM_NOTIFIER_CORE_WEBHOOK_ALLOWED_HOSTS")?);
 
         Ok(Self {
             log_directory,
@@ -61,8 +147,10 @@ impl ApplicationEnv {
             bind_address,
             db_connection_string,
             db_name,
+            db_compression_threshold,
             max_notification_content_len,
             max_http_content_len,
+            max_attachment_len,
             jwt_algorithms,
             jwt_key,
             rabbitmq_connection_string,
@@ -70,10 +158,134 @@ impl ApplicationEnv {
             rabbitmq_confirmations_exchange_name,
             rabbitmq_confirmations_queue_name,
             rabbitmq_retry_interval,
+            rabbitmq_compression_threshold,
+            outbox_relay_interval,
+            outbox_relay_batch_size,
+            outbox_relay_confirm_timeout,
+            retention_interval,
+            retention_batch_size,
+            retention_max_age,
+            retention_producers_max_age,
+            retention_invalidated,
+            retention_deleted,
+            retention_orphaned_attachments,
+            webhook_relay_interval,
+            webhook_relay_batch_size,
+            webhook_relay_timeout,
+            webhook_relay_max_attempts,
+            webhook_relay_backoff,
+            webhook_relay_max_backoff,
+            webhook_allowed_hosts,
         })
     }
 
+    ///
+    /// Parses comma separated list of `<producer_id>=<days>` pairs
+    ///
+    fn parse_producers_max_age(value: &str) -> anyhow::Result<HashMap<Uuid, Duration>> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| -> anyhow::Result<(Uuid, Duration)> {
+                let (producer_id, days) = pair
+                    .split_once('=')
+                    .ok_or(anyhow!("invalid producer max age {pair}"))?;
+                let producer_id = producer_id.trim().parse()?;
+                let days: u32 = days.trim().parse()?;
+
+                Ok((producer_id, DAY * days))
+            })
+            .collect()
+    }
+
+    ///
+    /// Parses comma separated list of hosts, hosts are case insensitive
+    ///
+    fn parse_hosts(value: &str) -> HashSet<String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect()
+    }
+
+    ///
+    /// Parses compression threshold in bytes, empty value disables the compression
+    ///
+    fn parse_compression_threshold(value: &str) -> anyhow::Result<Option<usize>> {
+        match value.trim() {
+            "" => Ok(None),
+            value => Ok(Some(value.parse()?)),
+        }
+    }
+
     fn env_var(name: &'static str) -> anyhow::Result<String> {
         std::env::var(name).map_err(|_| anyhow!("environment variable {name} not set"))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_producers_max_age_ok() {
+        let producers_max_age = ApplicationEnv::parse_producers_max_age(
+            "00000000-0000-0000-0000-000000000001=30, 00000000-0000-0000-0000-000000000002=365",
+        )
+        .unwrap();
+
+        assert_eq!(
+            producers_max_age,
+            HashMap::from([
+                (Uuid::from_u128(1), DAY * 30),
+                (Uuid::from_u128(2), DAY * 365),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_producers_max_age_empty() {
+        let producers_max_age = ApplicationEnv::parse_producers_max_age("").unwrap();
+
+        assert!(producers_max_age.is_empty());
+    }
+
+    #[test]
+    fn parse_producers_max_age_invalid() {
+        assert!(ApplicationEnv::parse_producers_max_age("30").is_err());
+        assert!(ApplicationEnv::parse_producers_max_age("producer=30").is_err());
+    }
+
+    #[test]
+    fn parse_hosts_ok() {
+        let hosts = ApplicationEnv::parse_hosts(" Receipts.internal, 10.0.0.1,,");
+
+        assert_eq!(
+            hosts,
+            HashSet::from(["receipts.internal".to_string(), "10.0.0.1".to_string()])
+        );
+    }
+
+    #[test]
+    fn parse_compression_threshold_ok() {
+        let compression_threshold = ApplicationEnv::parse_compression_threshold("1024").unwrap();
+
+        assert_eq!(compression_threshold, Some(1024));
+    }
+
+    #[test]
+    fn parse_compression_threshold_empty() {
+        let compression_threshold = ApplicationEnv::parse_compression_threshold("").unwrap();
+
+        assert_eq!(compression_threshold, None);
+    }
+
+    #[test]
+    fn parse_compression_threshold_invalid() {
+        assert!(ApplicationEnv::parse_compression_threshold("1KiB").is_err());
+        assert!(ApplicationEnv::parse_compression_threshold("-1").is_err());
+    }
+}
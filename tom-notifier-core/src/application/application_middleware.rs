@@ -11,17 +11,20 @@ use uuid::Uuid;
 pub struct ApplicationMiddleware {
     pub auth: JwtAuthLayer,
     pub body_limit: RequestBodyLimitLayer,
+    pub attachment_body_limit: RequestBodyLimitLayer,
     pub trace: TraceLayer<SharedClassifier<ServerErrorsAsFailures>, MyMakeSpan>,
 }
 
 pub fn create_middleware(env: &ApplicationEnv) -> ApplicationMiddleware {
     let auth = JwtAuthLayer::new(env.jwt_key.clone(), env.jwt_algorithms.clone());
     let body_limit = RequestBodyLimitLayer::new(env.max_http_content_len);
+    let attachment_body_limit = RequestBodyLimitLayer::new(env.max_attachment_len);
     let trace = TraceLayer::new_for_http().make_span_with(MyMakeSpan);
 
     ApplicationMiddleware {
         auth,
         body_limit,
+        attachment_body_limit,
         trace,
     }
 }
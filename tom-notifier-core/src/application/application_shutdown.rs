@@ -2,6 +2,20 @@ use super::ApplicationStateToClose;
 use std::sync::Arc;
 
 pub async fn close(state: ApplicationStateToClose) {
+    tracing::info!("closing webhook relay");
+    state.webhook_relay_service.close().await;
+
+    tracing::info!("closing retention");
+    state.retention_service.close().await;
+
+    tracing::info!("closing outbox relay");
+    match Arc::try_unwrap(state.outbox_relay_service) {
+        Ok(outbox_relay_service) => {
+            outbox_relay_service.close().await;
+        }
+        Err(_) => tracing::error!("cannot close outbox relay"),
+    }
+
     tracing::info!("closing rabbitmq notifications producer");
     match Arc::try_unwrap(state.rabbitmq_notifications_producer_service) {
         Ok(rabbitmq_notifications_producer) => {
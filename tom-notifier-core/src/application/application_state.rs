@@ -1,16 +1,33 @@
 use super::ApplicationEnv;
 use crate::{
-    repository::NotificationsRepositoryImpl,
+    repository::{
+        AttachmentsRepositoryImpl, ContentTypesRepositoryImpl, GroupsRepositoryImpl,
+        NotificationsRepositoryImpl, OutboxRepositoryImpl, PreferencesRepositoryImpl,
+        ReceiptsRepositoryImpl, SubscriptionsRepositoryImpl, TemplatesRepositoryImpl,
+        WebhooksRepositoryImpl,
+    },
     service::{
+        attachments_service::{
+            AttachmentsService, AttachmentsServiceConfig, AttachmentsServiceImpl,
+        },
         confirmations_consumer_service::{
             ConfirmationsConsumerService, ConfirmationsConsumerServiceConfig,
         },
+        content_types_service::{ContentTypesService, ContentTypesServiceImpl},
+        groups_service::{GroupsService, GroupsServiceImpl},
         notifications_producer_service::{
             NotificationsProducerServiceConfig, NotificationsProducerServiceImpl,
         },
         notifications_service::{
             NotificationsService, NotificationsServiceConfig, NotificationsServiceImpl,
         },
+        outbox_relay_service::{OutboxRelayServiceConfig, OutboxRelayServiceImpl},
+        preferences_service::{PreferencesService, PreferencesServiceImpl},
+        retention_service::{RetentionService, RetentionServiceConfig},
+        subscriptions_service::{SubscriptionsService, SubscriptionsServiceImpl},
+        templates_service::{TemplatesService, TemplatesServiceImpl},
+        webhook_relay_service::{WebhookRelayService, WebhookRelayServiceConfig},
+        webhooks_service::{WebhooksService, WebhooksServiceConfig, WebhooksServiceImpl},
     },
 };
 use amqprs::connection::OpenConnectionArguments;
@@ -22,6 +39,13 @@ use std::sync::Arc;
 #[derive(Clone, FromRef)]
 pub struct ApplicationState {
     pub notifications_service: Arc<dyn NotificationsService>,
+    pub groups_service: Arc<dyn GroupsService>,
+    pub subscriptions_service: Arc<dyn SubscriptionsService>,
+    pub preferences_service: Arc<dyn PreferencesService>,
+    pub templates_service: Arc<dyn TemplatesService>,
+    pub content_types_service: Arc<dyn ContentTypesService>,
+    pub attachments_service: Arc<dyn AttachmentsService>,
+    pub webhooks_service: Arc<dyn WebhooksService>,
 }
 
 pub struct ApplicationStateToClose {
@@ -29,6 +53,9 @@ pub struct ApplicationStateToClose {
     pub rabbitmq_connection: RabbitmqConnection,
     pub rabbitmq_notifications_producer_service: Arc<NotificationsProducerServiceImpl>,
     pub rabbitmq_confirmations_consumer_service: ConfirmationsConsumerService,
+    pub outbox_relay_service: Arc<OutboxRelayServiceImpl>,
+    pub retention_service: RetentionService,
+    pub webhook_relay_service: WebhookRelayService,
 }
 
 pub async fn create_state(
@@ -40,8 +67,27 @@ pub async fn create_state(
     let db = db_client.database(&env.db_name);
 
     tracing::info!("creating repositories");
-    let notifications_repository = NotificationsRepositoryImpl::new(db).await?;
+    let notifications_repository =
+        NotificationsRepositoryImpl::new(db.clone(), env.db_compression_threshold).await?;
     let notifications_repository = Arc::new(notifications_repository);
+    let outbox_repository = OutboxRepositoryImpl::new(db.clone()).await?;
+    let outbox_repository = Arc::new(outbox_repository);
+    let groups_repository = GroupsRepositoryImpl::new(db.clone()).await?;
+    let groups_repository = Arc::new(groups_repository);
+    let subscriptions_repository = SubscriptionsRepositoryImpl::new(db.clone()).await?;
+    let subscriptions_repository = Arc::new(subscriptions_repository);
+    let preferences_repository = PreferencesRepositoryImpl::new(db.clone()).await?;
+    let preferences_repository = Arc::new(preferences_repository);
+    let templates_repository = TemplatesRepositoryImpl::new(db.clone()).await?;
+    let templates_repository = Arc::new(templates_repository);
+    let content_types_repository = ContentTypesRepositoryImpl::new(db.clone()).await?;
+    let content_types_repository = Arc::new(content_types_repository);
+    let attachments_repository = AttachmentsRepositoryImpl::new(db.clone()).await?;
+    let attachments_repository = Arc::new(attachments_repository);
+    let webhooks_repository = WebhooksRepositoryImpl::new(db.clone()).await?;
+    let webhooks_repository = Arc::new(webhooks_repository);
+    let receipts_repository = ReceiptsRepositoryImpl::new(db).await?;
+    let receipts_repository = Arc::new(receipts_repository);
 
     tracing::info!("creating services");
     let config = RabbitmqConnectionConfig {
@@ -53,6 +99,7 @@ pub async fn create_state(
 
     let config = NotificationsProducerServiceConfig {
         exchange_name: env.rabbitmq_notifications_exchange_name.clone(),
+        compression_threshold: env.rabbitmq_compression_threshold,
     };
     let rabbitmq_notifications_producer_service =
         NotificationsProducerServiceImpl::new(config, rabbitmq_connection.clone()).await?;
@@ -69,25 +116,103 @@ pub async fn create_state(
     )
     .await?;
 
+    let config = OutboxRelayServiceConfig {
+        interval: env.outbox_relay_interval,
+        batch_size: env.outbox_relay_batch_size,
+        confirm_timeout: env.outbox_relay_confirm_timeout,
+    };
+    let outbox_relay_service = OutboxRelayServiceImpl::new(
+        config,
+        outbox_repository,
+        rabbitmq_notifications_producer_service.clone(),
+    );
+    let outbox_relay_service = Arc::new(outbox_relay_service);
+
+    let config = RetentionServiceConfig {
+        interval: env.retention_interval,
+        batch_size: env.retention_batch_size,
+        max_age: env.retention_max_age,
+        producers_max_age: env.retention_producers_max_age.clone(),
+        invalidated_retention: env.retention_invalidated,
+        deleted_retention: env.retention_deleted,
+        orphaned_attachments_retention: env.retention_orphaned_attachments,
+    };
+    let retention_service = RetentionService::new(config, notifications_repository.clone());
+
+    let config = WebhookRelayServiceConfig {
+        interval: env.webhook_relay_interval,
+        batch_size: env.webhook_relay_batch_size,
+        timeout: env.webhook_relay_timeout,
+        max_attempts: env.webhook_relay_max_attempts,
+        backoff: env.webhook_relay_backoff,
+        max_backoff: env.webhook_relay_max_backoff,
+        allowed_hosts: env.webhook_allowed_hosts.clone(),
+    };
+    let webhook_relay_service =
+        WebhookRelayService::new(config, receipts_repository, webhooks_repository.clone())?;
+
+    let content_types_service = ContentTypesServiceImpl::new(content_types_repository);
+    let content_types_service = Arc::new(content_types_service);
+
+    let attachments_service_config = AttachmentsServiceConfig {
+        max_attachment_len: env.max_attachment_len,
+    };
+    let attachments_service = AttachmentsServiceImpl::new(
+        attachments_service_config,
+        attachments_repository,
+        notifications_repository.clone(),
+    );
+    let attachments_service = Arc::new(attachments_service);
+
     let notifications_service_config = NotificationsServiceConfig {
         max_content_len: env.max_notification_content_len,
     };
     let notifications_service = NotificationsServiceImpl::new(
         notifications_service_config,
         notifications_repository,
-        rabbitmq_notifications_producer_service.clone(),
+        outbox_relay_service.clone(),
+        content_types_service.clone(),
+        attachments_service.clone(),
     );
     let notifications_service = Arc::new(notifications_service);
 
+    let groups_service = GroupsServiceImpl::new(groups_repository);
+    let groups_service = Arc::new(groups_service);
+
+    let subscriptions_service = SubscriptionsServiceImpl::new(subscriptions_repository);
+    let subscriptions_service = Arc::new(subscriptions_service);
+
+    let preferences_service = PreferencesServiceImpl::new(preferences_repository);
+    let preferences_service = Arc::new(preferences_service);
+
+    let templates_service = TemplatesServiceImpl::new(templates_repository);
+    let templates_service = Arc::new(templates_service);
+
+    let config = WebhooksServiceConfig {
+        allowed_hosts: env.webhook_allowed_hosts.clone(),
+    };
+    let webhooks_service = WebhooksServiceImpl::new(config, webhooks_repository);
+    let webhooks_service = Arc::new(webhooks_service);
+
     Ok((
         ApplicationState {
             notifications_service,
+            groups_service,
+            subscriptions_service,
+            preferences_service,
+            templates_service,
+            content_types_service,
+            attachments_service,
+            webhooks_service,
         },
         ApplicationStateToClose {
             db_client,
             rabbitmq_connection,
             rabbitmq_notifications_producer_service,
             rabbitmq_confirmations_consumer_service,
+            outbox_relay_service,
+            retention_service,
+            webhook_relay_service,
         },
     ))
 }
@@ -8,6 +8,9 @@ use strum::AsRefStr;
 pub enum Role {
     #[strum(serialize = "tom_notifier_produce_notifications")]
     ProduceNotifications,
+
+    #[strum(serialize = "tom_notifier_manage_groups")]
+    ManageGroups,
 }
 
 #[cfg(test)]
@@ -19,4 +22,10 @@ mod test {
         let role = Role::ProduceNotifications.as_ref();
         assert_eq!(role, "tom_notifier_produce_notifications");
     }
+
+    #[test]
+    fn manage_groups() {
+        let role = Role::ManageGroups.as_ref();
+        assert_eq!(role, "tom_notifier_manage_groups");
+    }
 }
@@ -0,0 +1,61 @@
+use super::cursor_encoding::{decode, encode};
+use bson::oid::ObjectId;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::OffsetDateTime;
+
+///
+/// Position in the list of confirmations of the notification.
+/// Points at the last confirmation of the previous page.
+///
+/// Users receive it as an opaque string
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationsCursor {
+    pub delivered_at: OffsetDateTime,
+    pub id: ObjectId,
+}
+
+impl Serialize for ConfirmationsCursor {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        String::serialize(&encode(self.delivered_at, self.id), s)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfirmationsCursor {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(d)?;
+
+        let (delivered_at, id) =
+            decode(&string).ok_or_else(|| serde::de::Error::custom("invalid cursor"))?;
+
+        Ok(Self { delivered_at, id })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn confirmations_cursor_serialize_deserialize() {
+        let cursor = ConfirmationsCursor {
+            delivered_at: datetime!(2024-05-12 16:20:13.512 UTC),
+            id: ObjectId::new(),
+        };
+
+        let json = serde_json::to_string(&cursor).unwrap();
+        let deserialized_cursor = serde_json::from_str::<ConfirmationsCursor>(&json).unwrap();
+
+        assert_eq!(deserialized_cursor, cursor);
+    }
+
+    #[test]
+    fn confirmations_cursor_deserialize_invalid() {
+        let json = r#""invalid""#;
+
+        let cursor = serde_json::from_str::<ConfirmationsCursor>(json);
+
+        assert!(cursor.is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use bson::oid::ObjectId;
+use time::OffsetDateTime;
+
+const MILLIS_LEN: usize = 8;
+const ENCODED_LEN: usize = MILLIS_LEN + 12;
+
+///
+/// Encodes position of the item sorted by timestamp and id as opaque string
+///
+pub(super) fn encode(timestamp: OffsetDateTime, id: ObjectId) -> String {
+    // Mongo keeps datetime in milliseconds so more precision is not needed
+    let millis = (timestamp.unix_timestamp_nanos() / 1_000_000) as i64;
+
+    let mut bytes = Vec::with_capacity(ENCODED_LEN);
+    bytes.extend_from_slice(&millis.to_be_bytes());
+    bytes.extend_from_slice(&id.bytes());
+
+    BASE64_URL_SAFE_NO_PAD.encode(bytes)
+}
+
+///
+/// Decodes string created by [encode]
+///
+/// ### Returns
+/// None when the string is not a valid cursor
+///
+pub(super) fn decode(encoded: &str) -> Option<(OffsetDateTime, ObjectId)> {
+    let bytes = BASE64_URL_SAFE_NO_PAD.decode(encoded).ok()?;
+    if bytes.len() != ENCODED_LEN {
+        return None;
+    }
+
+    let (millis, id) = bytes.split_at(MILLIS_LEN);
+    let millis = i64::from_be_bytes(millis.try_into().ok()?);
+    let timestamp = OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000).ok()?;
+    let id = ObjectId::from_bytes(id.try_into().ok()?);
+
+    Some((timestamp, id))
+}
@@ -0,0 +1,8 @@
+mod confirmations_cursor;
+mod cursor_encoding;
+mod notification_priority;
+mod notifications_cursor;
+
+pub use confirmations_cursor::*;
+pub use notification_priority::*;
+pub use notifications_cursor::*;
@@ -0,0 +1,80 @@
+use crate::dto::output::NotificationPriorityProtobuf;
+use serde::{Deserialize, Serialize};
+
+///
+/// Priority of the notification. Undelivered notifications
+/// are listed and pushed to users ordered by priority
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
+}
+
+impl NotificationPriority {
+    ///
+    /// Value kept in the database, higher value means higher priority
+    ///
+    pub fn as_i32(self) -> i32 {
+        NotificationPriorityProtobuf::from(self) as i32
+    }
+
+    ///
+    /// Unknown values are treated as [NotificationPriority::Normal]
+    ///
+    pub fn from_i32(value: i32) -> Self {
+        NotificationPriorityProtobuf::try_from(value)
+            .map(Self::from)
+            .unwrap_or_default()
+    }
+}
+
+impl From<NotificationPriority> for NotificationPriorityProtobuf {
+    fn from(value: NotificationPriority) -> Self {
+        match value {
+            NotificationPriority::Low => Self::Low,
+            NotificationPriority::Normal => Self::Normal,
+            NotificationPriority::High => Self::High,
+            NotificationPriority::Urgent => Self::Urgent,
+        }
+    }
+}
+
+impl From<NotificationPriorityProtobuf> for NotificationPriority {
+    fn from(value: NotificationPriorityProtobuf) -> Self {
+        match value {
+            NotificationPriorityProtobuf::Low => Self::Low,
+            NotificationPriorityProtobuf::Normal => Self::Normal,
+            NotificationPriorityProtobuf::High => Self::High,
+            NotificationPriorityProtobuf::Urgent => Self::Urgent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn notification_priority_i32_ordered() {
+        assert!(NotificationPriority::Low.as_i32() < NotificationPriority::Normal.as_i32());
+        assert!(NotificationPriority::Normal.as_i32() < NotificationPriority::High.as_i32());
+        assert!(NotificationPriority::High.as_i32() < NotificationPriority::Urgent.as_i32());
+    }
+
+    #[test]
+    fn notification_priority_from_i32_unknown_normal() {
+        assert_eq!(
+            NotificationPriority::from_i32(NotificationPriority::Urgent.as_i32()),
+            NotificationPriority::Urgent
+        );
+        assert_eq!(
+            NotificationPriority::from_i32(-1),
+            NotificationPriority::Normal
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::cursor_encoding::{decode, encode};
+use bson::oid::ObjectId;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::OffsetDateTime;
+
+///
+/// Position in the list of delivered or produced notifications.
+/// Points at the last notification of the previous page.
+///
+/// Users receive it as an opaque string
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationsCursor {
+    pub created_at: OffsetDateTime,
+    pub id: ObjectId,
+}
+
+impl Serialize for NotificationsCursor {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        String::serialize(&encode(self.created_at, self.id), s)
+    }
+}
+
+impl<'de> Deserialize<'de> for NotificationsCursor {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(d)?;
+
+        let (created_at, id) =
+            decode(&string).ok_or_else(|| serde::de::Error::custom("invalid cursor"))?;
+
+        Ok(Self { created_at, id })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+    use time::macros::datetime;
+
+    #[test]
+    fn notifications_cursor_serialize_deserialize() {
+        let cursor = NotificationsCursor {
+            created_at: datetime!(2024-05-12 16:20:13.512 UTC),
+            id: ObjectId::new(),
+        };
+
+        let json = serde_json::to_string(&cursor).unwrap();
+        let deserialized_cursor = serde_json::from_str::<NotificationsCursor>(&json).unwrap();
+
+        assert_eq!(deserialized_cursor, cursor);
+    }
+
+    #[test]
+    fn notifications_cursor_deserialize_invalid_base64() {
+        let json = r#""¢≠³² ¢²≠³≠²¢12""#;
+
+        let cursor = serde_json::from_str::<NotificationsCursor>(json);
+
+        assert!(cursor.is_err());
+    }
+
+    #[test]
+    fn notifications_cursor_deserialize_invalid_len() {
+        let json = format!(r#""{}""#, BASE64_URL_SAFE_NO_PAD.encode(b"too short"));
+
+        let cursor = serde_json::from_str::<NotificationsCursor>(&json);
+
+        assert!(cursor.is_err());
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ContentType {
+    pub content_type: String,
+
+    ///
+    /// JSON Schema that content of notifications with the content type has to match
+    ///
+    pub schema: serde_json::Value,
+}
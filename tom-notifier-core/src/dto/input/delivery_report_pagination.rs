@@ -0,0 +1,23 @@
+use super::ConfirmationsCursor;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct DeliveryReportPagination {
+    ///
+    /// Max number of confirmations and max number of pending recipients returned
+    ///
+    pub page_size: u32,
+
+    ///
+    /// next_confirmations_cursor returned with the previous page.
+    /// None when fetching the first page of confirmations
+    ///
+    pub confirmations_cursor: Option<ConfirmationsCursor>,
+
+    ///
+    /// next_pending_cursor returned with the previous page.
+    /// None when fetching the first page of pending recipients
+    ///
+    pub pending_cursor: Option<Uuid>,
+}
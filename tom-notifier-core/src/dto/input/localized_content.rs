@@ -0,0 +1,23 @@
+use crate::dto::output::LocalizedContentProtobuf;
+use serde::Deserialize;
+
+///
+/// Variant of the notification content in a single locale
+///
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LocalizedContent {
+    pub locale: String,
+    pub content_type: String,
+    #[serde(with = "super::notification::de_base64")]
+    pub content: Vec<u8>,
+}
+
+impl From<LocalizedContent> for LocalizedContentProtobuf {
+    fn from(value: LocalizedContent) -> Self {
+        Self {
+            locale: value.locale,
+            content_type: value.content_type,
+            content: value.content,
+        }
+    }
+}
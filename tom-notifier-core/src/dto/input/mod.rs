@@ -1,13 +1,49 @@
+mod attachment_filename;
+mod content_type;
+mod content_type_name;
+mod delivery_report_pagination;
+mod group;
+mod group_members;
+mod localized_content;
 mod notification;
+mod notification_content;
 mod notification_filters;
 mod notification_invalidate_at;
 mod notification_seen;
+mod notification_template;
+mod notifications_seen;
+mod notifications_selection;
 mod pagination;
+mod preferences;
+mod produced_notification_filters;
+mod template;
+mod template_content;
+mod template_version;
+mod webhook;
 
+pub use attachment_filename::*;
+pub use content_type::*;
+pub use content_type_name::*;
+pub use delivery_report_pagination::*;
+pub use group::*;
+pub use group_members::*;
+pub use localized_content::*;
 pub use notification::*;
+pub use notification_content::*;
 pub use notification_filters::*;
 pub use notification_invalidate_at::*;
 pub use notification_seen::*;
+pub use notification_template::*;
+pub use notifications_seen::*;
+pub use notifications_selection::*;
 pub use pagination::*;
+pub use preferences::*;
+pub use produced_notification_filters::*;
+pub use template::*;
+pub use template_content::*;
+pub use template_version::*;
+pub use webhook::*;
 
+pub use super::inoutput::{ConfirmationsCursor, NotificationPriority, NotificationsCursor};
 pub use super::protobuf::rabbitmq_confirmation::RabbitmqConfirmationProtobuf;
+pub use jwt_auth::Locales;
@@ -1,3 +1,5 @@
+use super::{LocalizedContent, NotificationPriority, NotificationTemplate};
+use bson::oid::ObjectId;
 use serde::Deserialize;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -5,14 +7,72 @@ use uuid::Uuid;
 #[derive(Debug, Deserialize)]
 pub struct Notification {
     pub invalidate_at: Option<OffsetDateTime>,
+
+    ///
+    /// Notification is not delivered before deliver_at.
+    /// None means it can be delivered immediately
+    ///
+    pub deliver_at: Option<OffsetDateTime>,
     pub user_ids: Vec<Uuid>,
+
+    ///
+    /// Notification is delivered to members of the groups
+    /// in addition to user_ids. Notification without
+    /// user_ids, groups and topic is a broadcast
+    ///
+    #[serde(default)]
+    pub groups: Vec<String>,
+
+    ///
+    /// Notification is delivered to subscribers of the topic
+    /// in addition to user_ids and members of groups
+    ///
+    pub topic: Option<String>,
+
+    ///
+    /// Undelivered notifications are listed and pushed ordered by priority.
+    /// Notification without priority has normal priority
+    ///
+    #[serde(default)]
+    pub priority: NotificationPriority,
+
+    ///
+    /// Notification supersedes notifications of the producer with the same
+    /// collapse_key, user_ids, groups and topic, so only the latest one is visible
+    ///
+    pub collapse_key: Option<String>,
     pub producer_notification_id: i64,
+
+    ///
+    /// Content type and content can be omitted only when template is set
+    ///
+    #[serde(default)]
     pub content_type: String,
-    #[serde(with = "de_base64")]
+    #[serde(default, with = "de_base64")]
     pub content: Vec<u8>,
+
+    ///
+    /// Variants of the content in other locales. Users are given
+    /// the variant best matching their locale, content otherwise
+    ///
+    #[serde(default)]
+    pub localized_contents: Vec<LocalizedContent>,
+
+    ///
+    /// Attachments uploaded by the producer, recipients
+    /// download them through the delivered notification
+    ///
+    #[serde(default)]
+    pub attachment_ids: Vec<ObjectId>,
+
+    ///
+    /// Template of the producer rendered into content_type and content
+    /// when the notification is created
+    ///
+    pub template: Option<NotificationTemplate>,
 }
 
-mod de_base64 {
+pub(in crate::dto) mod de_base64 {
     //!
     //! Module allows to deserialize JSON base64 string directly
     //! to bytes, so it's not neccessary to do it in services
@@ -71,4 +131,121 @@ mod test {
 
         assert!(notification.is_err());
     }
+
+    #[test]
+    fn notification_json_deserialize_groups_default_empty() {
+        let json = r#"{
+            "invalidate_at": null,
+            "user_ids": [],
+            "producer_notification_id": 1,
+            "content_type": "utf-8",
+            "content": "MTIzNA=="
+        }"#;
+
+        let notification = serde_json::from_str::<Notification>(&json).unwrap();
+
+        assert!(notification.groups.is_empty());
+    }
+
+    #[test]
+    fn notification_json_deserialize_topic_default_none() {
+        let json = r#"{
+            "invalidate_at": null,
+            "user_ids": [],
+            "producer_notification_id": 1,
+            "content_type": "utf-8",
+            "content": "MTIzNA=="
+        }"#;
+
+        let notification = serde_json::from_str::<Notification>(&json).unwrap();
+
+        assert!(notification.topic.is_none());
+    }
+
+    #[test]
+    fn notification_json_deserialize_priority() {
+        let json = r#"{
+            "invalidate_at": null,
+            "user_ids": [],
+            "priority": "urgent",
+            "producer_notification_id": 1,
+            "content_type": "utf-8",
+            "content": "MTIzNA=="
+        }"#;
+
+        let notification = serde_json::from_str::<Notification>(&json).unwrap();
+
+        assert_eq!(notification.priority, NotificationPriority::Urgent);
+    }
+
+    #[test]
+    fn notification_json_deserialize_priority_default_normal() {
+        let json = r#"{
+            "invalidate_at": null,
+            "user_ids": [],
+            "producer_notification_id": 1,
+            "content_type": "utf-8",
+            "content": "MTIzNA=="
+        }"#;
+
+        let notification = serde_json::from_str::<Notification>(&json).unwrap();
+
+        assert_eq!(notification.priority, NotificationPriority::Normal);
+    }
+
+    #[test]
+    fn notification_json_deserialize_localized_contents() {
+        let json = r#"{
+            "invalidate_at": null,
+            "user_ids": [],
+            "producer_notification_id": 1,
+            "content_type": "utf-8",
+            "content": "MTIzNA==",
+            "localized_contents": [
+                {
+                    "locale": "pl-PL",
+                    "content_type": "utf-8",
+                    "content": "NDMyMQ=="
+                }
+            ]
+        }"#;
+
+        let notification = serde_json::from_str::<Notification>(&json).unwrap();
+
+        assert_eq!(
+            notification.localized_contents,
+            vec![LocalizedContent {
+                locale: "pl-PL".to_string(),
+                content_type: "utf-8".to_string(),
+                content: b"4321".to_vec(),
+            }]
+        );
+    }
+
+    #[test]
+    fn notification_json_deserialize_template() {
+        let json = r#"{
+            "invalidate_at": null,
+            "user_ids": [],
+            "producer_notification_id": 1,
+            "template": {
+                "id": "order_shipped",
+                "parameters": {
+                    "order": "1234"
+                }
+            }
+        }"#;
+
+        let notification = serde_json::from_str::<Notification>(&json).unwrap();
+
+        let template = notification.template.unwrap();
+        assert_eq!(template.id, "order_shipped");
+        assert_eq!(template.version, None);
+        assert_eq!(
+            template.parameters.get("order").map(String::as_str),
+            Some("1234")
+        );
+        assert!(notification.content_type.is_empty());
+        assert!(notification.content.is_empty());
+    }
 }
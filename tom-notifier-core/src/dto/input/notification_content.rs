@@ -0,0 +1,15 @@
+use super::LocalizedContent;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationContent {
+    pub content_type: String,
+    #[serde(with = "super::notification::de_base64")]
+    pub content: Vec<u8>,
+
+    ///
+    /// Replaces all variants of the content in other locales
+    ///
+    #[serde(default)]
+    pub localized_contents: Vec<LocalizedContent>,
+}
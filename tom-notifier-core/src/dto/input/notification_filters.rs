@@ -1,6 +1,39 @@
 use serde::Deserialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Clone, Deserialize)]
 pub struct NotificationFilters {
     pub seen: Option<bool>,
+    pub created_by: Option<Uuid>,
+    pub content_type: Option<String>,
+
+    /// inclusive
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at_from: Option<OffsetDateTime>,
+
+    /// exclusive
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at_to: Option<OffsetDateTime>,
+
+    /// inclusive
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub delivered_at_from: Option<OffsetDateTime>,
+
+    /// exclusive
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub delivered_at_to: Option<OffsetDateTime>,
+
+    ///
+    /// Order by created_at. Descending (newest first) when not set
+    ///
+    pub order: Option<NotificationsOrder>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationsOrder {
+    Asc,
+    #[default]
+    Desc,
 }
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationTemplate {
+    pub id: String,
+
+    ///
+    /// None means the latest version
+    ///
+    pub version: Option<u32>,
+
+    ///
+    /// Values of all parameters declared by the template
+    ///
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+}
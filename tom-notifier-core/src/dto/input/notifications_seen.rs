@@ -0,0 +1,9 @@
+use super::NotificationsSelection;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationsSeen {
+    pub seen: bool,
+    #[serde(flatten)]
+    pub selection: NotificationsSelection,
+}
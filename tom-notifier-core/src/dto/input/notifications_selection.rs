@@ -0,0 +1,16 @@
+use super::NotificationFilters;
+use bson::oid::ObjectId;
+use serde::Deserialize;
+
+///
+/// Selects many delivered notifications of the user.
+/// When both ids and filters are set only notifications
+/// with matching ids that also match filters are selected.
+///
+#[derive(Debug, Deserialize)]
+pub struct NotificationsSelection {
+    pub ids: Option<Vec<ObjectId>>,
+
+    /// order is ignored
+    pub filters: Option<NotificationFilters>,
+}
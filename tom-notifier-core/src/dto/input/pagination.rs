@@ -1,10 +1,19 @@
+use super::NotificationsCursor;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
 pub struct Pagination {
     ///
-    /// indexing starts at 0
+    /// indexing starts at 0.
     ///
-    pub page_idx: u32,
+    /// Kept for compatibility, cursor should be preferred
+    ///
+    pub page_idx: Option<u32>,
     pub page_size: u32,
+
+    ///
+    /// next_cursor returned with the previous page.
+    /// None when fetching the first page
+    ///
+    pub cursor: Option<NotificationsCursor>,
 }
@@ -0,0 +1,77 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct Preferences {
+    ///
+    /// Notifications created by these producers are hidden from the user
+    ///
+    #[serde(default)]
+    pub muted_producers: Vec<Uuid>,
+
+    ///
+    /// Notifications with these content types are hidden from the user
+    ///
+    #[serde(default)]
+    pub muted_content_types: Vec<String>,
+
+    ///
+    /// Notifications are not pushed to the user in quiet hours,
+    /// but they stay undelivered. None means no quiet hours
+    ///
+    pub quiet_hours: Option<QuietHours>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuietHours {
+    ///
+    /// Inclusive start in "HH:MM" format of user's local time
+    ///
+    pub from: String,
+
+    ///
+    /// Exclusive end in "HH:MM" format of user's local time.
+    /// Quiet hours wrap around midnight when to is before from
+    ///
+    pub to: String,
+
+    ///
+    /// Offset of user's local time from UTC in minutes
+    ///
+    #[serde(default)]
+    pub utc_offset: i32,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn preferences_json_deserialize_defaults() {
+        let json = "{}";
+
+        let preferences = serde_json::from_str::<Preferences>(json).unwrap();
+
+        assert!(preferences.muted_producers.is_empty());
+        assert!(preferences.muted_content_types.is_empty());
+        assert!(preferences.quiet_hours.is_none());
+    }
+
+    #[test]
+    fn preferences_json_deserialize_ok() {
+        let json = r#"{
+            "muted_producers": ["00000000-0000-0000-0000-000000000001"],
+            "muted_content_types": ["marketing"],
+            "quiet_hours": { "from": "22:00", "to": "07:00", "utc_offset": 120 }
+        }"#;
+
+        let preferences = serde_json::from_str::<Preferences>(json).unwrap();
+
+        assert_eq!(preferences.muted_producers, vec![Uuid::from_u128(1)]);
+        assert_eq!(preferences.muted_content_types, vec!["marketing"]);
+        let quiet_hours = preferences.quiet_hours.unwrap();
+        assert_eq!(quiet_hours.from, "22:00");
+        assert_eq!(quiet_hours.to, "07:00");
+        assert_eq!(quiet_hours.utc_offset, 120);
+    }
+}
@@ -0,0 +1,27 @@
+use super::NotificationsOrder;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ProducedNotificationFilters {
+    pub content_type: Option<String>,
+
+    /// inclusive
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at_from: Option<OffsetDateTime>,
+
+    /// exclusive
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at_to: Option<OffsetDateTime>,
+
+    ///
+    /// Only retracted notifications when true,
+    /// only not retracted ones when false
+    ///
+    pub retracted: Option<bool>,
+
+    ///
+    /// Order by created_at. Descending (newest first) when not set
+    ///
+    pub order: Option<NotificationsOrder>,
+}
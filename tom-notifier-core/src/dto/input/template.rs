@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Template {
+    pub id: String,
+    pub content_type: String,
+
+    ///
+    /// Content of the notification with `{{parameter}}` placeholders
+    ///
+    pub body: String,
+
+    ///
+    /// Names of the parameters that have to be passed when the template is rendered
+    ///
+    #[serde(default)]
+    pub parameters: Vec<String>,
+}
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateContent {
+    pub content_type: String,
+    pub body: String,
+    #[serde(default)]
+    pub parameters: Vec<String>,
+}
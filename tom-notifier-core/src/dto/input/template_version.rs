@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateVersion {
+    ///
+    /// None means the latest version
+    ///
+    pub version: Option<u32>,
+}
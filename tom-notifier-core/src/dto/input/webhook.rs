@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Webhook {
+    ///
+    /// Absolute http(s) URL receipts are posted to
+    ///
+    pub url: String,
+}
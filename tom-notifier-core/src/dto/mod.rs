@@ -2,6 +2,7 @@
 //! Module with all dtos that are passed between server and users
 //!
 
+mod inoutput;
 pub mod input;
 pub mod output;
 mod protobuf;
@@ -0,0 +1,17 @@
+use crate::repository;
+
+pub struct Attachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+impl From<repository::Attachment> for Attachment {
+    fn from(value: repository::Attachment) -> Self {
+        Self {
+            filename: value.filename,
+            content_type: value.content_type,
+            content: value.content,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Serialize)]
+pub struct ContentType {
+    pub content_type: String,
+    pub schema: serde_json::Value,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<repository::ContentType> for ContentType {
+    fn from(value: repository::ContentType) -> Self {
+        Self {
+            content_type: value.content_type,
+            // Schema is validated before it is saved, so it is always valid JSON
+            schema: serde_json::from_str(&value.schema).unwrap_or_default(),
+            updated_at: value.updated_at,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use super::{ConfirmationsCursor, RecipientConfirmation};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct DeliveryReport {
+    pub confirmed_count: u64,
+    pub confirmations: Vec<RecipientConfirmation>,
+
+    ///
+    /// None when there are no more confirmations
+    ///
+    pub next_confirmations_cursor: Option<ConfirmationsCursor>,
+
+    ///
+    /// Recipients that did not confirm the notification yet,
+    /// None for broadcast notifications whose recipients are not known
+    ///
+    pub pending_count: Option<u64>,
+    pub pending_user_ids: Option<Vec<Uuid>>,
+
+    ///
+    /// None when there are no more pending recipients
+    ///
+    pub next_pending_cursor: Option<Uuid>,
+}
@@ -0,0 +1,18 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Serialize)]
+pub struct Group {
+    pub name: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<repository::Group> for Group {
+    fn from(value: repository::Group) -> Self {
+        Self {
+            name: value.name,
+            created_at: value.created_at,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct GroupMembers {
+    pub user_ids: Vec<Uuid>,
+}
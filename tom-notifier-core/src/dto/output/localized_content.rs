@@ -0,0 +1,20 @@
+use crate::dto::input;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct LocalizedContent {
+    pub locale: String,
+    pub content_type: String,
+    #[serde(with = "super::notification::se_base64")]
+    pub content: Vec<u8>,
+}
+
+impl From<input::LocalizedContent> for LocalizedContent {
+    fn from(value: input::LocalizedContent) -> Self {
+        Self {
+            locale: value.locale,
+            content_type: value.content_type,
+            content: value.content,
+        }
+    }
+}
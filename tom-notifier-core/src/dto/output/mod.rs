@@ -1,8 +1,54 @@
+mod attachment;
+mod attachment_id;
+mod content_type;
+mod delivery_report;
+mod group;
+mod group_members;
+mod localized_content;
 mod notification;
 mod notification_id;
+mod notification_save_result;
+mod notifications_affected;
+mod notifications_count;
+mod notifications_page;
+mod preferences;
+mod produced_notification;
+mod produced_notifications_page;
+mod receipt;
+mod recipient_confirmation;
+mod subscription;
+mod template;
+mod template_version;
+mod webhook;
+mod webhook_secret;
 
+pub use attachment::*;
+pub use attachment_id::*;
+pub use content_type::*;
+pub use delivery_report::*;
+pub use group::*;
+pub use group_members::*;
+pub use localized_content::*;
 pub use notification::*;
 pub use notification_id::*;
+pub use notification_save_result::*;
+pub use notifications_affected::*;
+pub use notifications_count::*;
+pub use notifications_page::*;
+pub use preferences::*;
+pub use produced_notification::*;
+pub use produced_notifications_page::*;
+pub use receipt::*;
+pub use recipient_confirmation::*;
+pub use subscription::*;
+pub use template::*;
+pub use template_version::*;
+pub use webhook::*;
+pub use webhook_secret::*;
 
-pub use super::protobuf::notification::{NotificationProtobuf, NotificationStatusProtobuf};
+pub use super::inoutput::{ConfirmationsCursor, NotificationPriority, NotificationsCursor};
+pub use super::protobuf::notification::{
+    LocalizedContentProtobuf, NotificationPriorityProtobuf, NotificationProtobuf,
+    NotificationStatusProtobuf,
+};
 pub use super::protobuf::rabbitmq_notification::RabbitmqNotificationProtobuf;
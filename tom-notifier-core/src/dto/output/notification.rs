@@ -1,4 +1,5 @@
-use crate::repository;
+use super::NotificationPriority;
+use crate::{dto::input, repository};
 use serde::Serialize;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -9,12 +10,19 @@ pub struct Notification {
     pub created_at: OffsetDateTime,
     pub created_by: Uuid,
     pub seen: bool,
+    pub priority: NotificationPriority,
+
+    ///
+    /// Locale of the content, None when it is not localized
+    ///
+    pub locale: Option<String>,
     pub content_type: String,
     #[serde(with = "se_base64")]
     pub content: Vec<u8>,
+    pub attachment_ids: Vec<String>,
 }
 
-mod se_base64 {
+pub(in crate::dto) mod se_base64 {
     use base64::{prelude::BASE64_STANDARD, Engine};
     use serde::{Serialize, Serializer};
 
@@ -26,15 +34,45 @@ mod se_base64 {
     }
 }
 
-impl From<repository::Notification> for Notification {
-    fn from(value: repository::Notification) -> Self {
+impl Notification {
+    ///
+    /// Creates notification with the content variant best matching locales.
+    /// When none of the variants matches, content that is not localized is used
+    ///
+    pub fn localized(value: repository::Notification, locales: &input::Locales) -> Self {
+        let mut localized_contents = value.localized_contents;
+        let best_match = locales.best_match(
+            localized_contents
+                .iter()
+                .map(|localized_content| localized_content.locale.as_str()),
+        );
+
+        let (locale, content_type, content) = match best_match {
+            Some(idx) => {
+                let localized_content = localized_contents.swap_remove(idx);
+                (
+                    Some(localized_content.locale),
+                    localized_content.content_type,
+                    localized_content.content,
+                )
+            }
+            None => (None, value.content_type, value.content),
+        };
+
         Self {
             id: value.id.to_hex(),
             created_at: value.created_at,
             created_by: value.producer_id.into(),
             seen: value.seen,
-            content_type: value.content_type,
-            content: value.content,
+            priority: value.priority,
+            locale,
+            content_type,
+            content,
+            attachment_ids: value
+                .attachment_ids
+                .into_iter()
+                .map(|attachment_id| attachment_id.to_hex())
+                .collect(),
         }
     }
 }
@@ -46,6 +84,31 @@ mod test {
     use serde_json::Value;
     use time::OffsetDateTime;
 
+    fn repository_notification() -> repository::Notification {
+        repository::Notification {
+            id: bson::oid::ObjectId::new(),
+            created_at: OffsetDateTime::now_utc(),
+            producer_id: Uuid::new_v4().into(),
+            seen: false,
+            priority: NotificationPriority::Normal,
+            content_type: "utf-8".to_string(),
+            content: b"default".to_vec(),
+            localized_contents: vec![
+                input::LocalizedContent {
+                    locale: "en".to_string(),
+                    content_type: "utf-8".to_string(),
+                    content: b"english".to_vec(),
+                },
+                input::LocalizedContent {
+                    locale: "pl-PL".to_string(),
+                    content_type: "utf-8".to_string(),
+                    content: b"polish".to_vec(),
+                },
+            ],
+            attachment_ids: vec![],
+        }
+    }
+
     #[test]
     fn notification_json_serialize_ok() {
         let content = b"my bytes".to_vec();
@@ -54,8 +117,11 @@ mod test {
             created_at: OffsetDateTime::now_utc(),
             created_by: Uuid::new_v4(),
             seen: false,
+            priority: NotificationPriority::Normal,
+            locale: None,
             content_type: "utf-8".to_string(),
             content: content.clone(),
+            attachment_ids: vec![],
         };
 
         let json = serde_json::to_string(&notification).unwrap();
@@ -70,4 +136,24 @@ mod test {
             .unwrap();
         assert_eq!(json_content, BASE64_STANDARD.encode(content))
     }
+
+    #[test]
+    fn notification_localized_best_match() {
+        let locales = input::Locales::new(None, Some("pl, en;q=0.5"));
+
+        let notification = Notification::localized(repository_notification(), &locales);
+
+        assert_eq!(notification.locale.as_deref(), Some("pl-PL"));
+        assert_eq!(notification.content, b"polish");
+    }
+
+    #[test]
+    fn notification_localized_fallback() {
+        let locales = input::Locales::new(None, Some("de"));
+
+        let notification = Notification::localized(repository_notification(), &locales);
+
+        assert_eq!(notification.locale, None);
+        assert_eq!(notification.content, b"default");
+    }
 }
@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+///
+/// Result of saving single notification of the batch
+///
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum NotificationSaveResult {
+    Created {
+        id: String,
+    },
+
+    /// notification with producer_notification_id was already created by the producer
+    Conflict,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn notification_save_result_json_serialize_ok() {
+        let results = vec![
+            NotificationSaveResult::Created {
+                id: "1".to_string(),
+            },
+            NotificationSaveResult::Conflict,
+        ];
+
+        let json = serde_json::to_string(&results).unwrap();
+
+        let value = serde_json::from_str::<Value>(&json).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                { "status": "created", "id": "1" },
+                { "status": "conflict" },
+            ])
+        );
+    }
+}
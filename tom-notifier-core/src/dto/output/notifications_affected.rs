@@ -0,0 +1,6 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct NotificationsAffected {
+    pub count: u64,
+}
@@ -0,0 +1,19 @@
+use crate::repository;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct NotificationsCount {
+    pub undelivered: u64,
+    pub delivered: u64,
+    pub delivered_unseen: u64,
+}
+
+impl From<repository::NotificationsCount> for NotificationsCount {
+    fn from(value: repository::NotificationsCount) -> Self {
+        Self {
+            undelivered: value.undelivered,
+            delivered: value.delivered,
+            delivered_unseen: value.delivered_unseen,
+        }
+    }
+}
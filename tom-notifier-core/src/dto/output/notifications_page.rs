@@ -0,0 +1,12 @@
+use super::{Notification, NotificationsCursor};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct NotificationsPage {
+    pub notifications: Vec<Notification>,
+
+    ///
+    /// None when there are no more notifications
+    ///
+    pub next_cursor: Option<NotificationsCursor>,
+}
@@ -0,0 +1,46 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Default, Serialize)]
+pub struct Preferences {
+    pub muted_producers: Vec<Uuid>,
+    pub muted_content_types: Vec<String>,
+    pub quiet_hours: Option<QuietHours>,
+
+    ///
+    /// None when user has never saved preferences
+    ///
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+#[derive(Serialize)]
+pub struct QuietHours {
+    pub from: String,
+    pub to: String,
+    pub utc_offset: i32,
+}
+
+impl From<repository::Preferences> for Preferences {
+    fn from(value: repository::Preferences) -> Self {
+        Self {
+            muted_producers: value.muted_producers,
+            muted_content_types: value.muted_content_types,
+            quiet_hours: value.quiet_hours.map(QuietHours::from),
+            updated_at: Some(value.updated_at),
+        }
+    }
+}
+
+impl From<repository::QuietHours> for QuietHours {
+    fn from(value: repository::QuietHours) -> Self {
+        let format_minute = |minute: u32| format!("{:02}:{:02}", minute / 60, minute % 60);
+
+        Self {
+            from: format_minute(value.from_minute),
+            to: format_minute(value.to_minute),
+            utc_offset: value.utc_offset_minutes,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::{LocalizedContent, NotificationPriority};
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Notification as created by its producer
+///
+#[derive(Serialize)]
+pub struct ProducedNotification {
+    pub id: String,
+    pub producer_notification_id: i64,
+    pub created_at: OffsetDateTime,
+    pub invalidate_at: Option<OffsetDateTime>,
+    pub deliver_at: Option<OffsetDateTime>,
+
+    ///
+    /// None when notification has not been retracted
+    ///
+    pub retracted_at: Option<OffsetDateTime>,
+    pub user_ids: Vec<Uuid>,
+    pub groups: Vec<String>,
+    pub topic: Option<String>,
+    pub priority: NotificationPriority,
+    pub collapse_key: Option<String>,
+    pub content_type: String,
+    #[serde(with = "super::notification::se_base64")]
+    pub content: Vec<u8>,
+    pub localized_contents: Vec<LocalizedContent>,
+    pub attachment_ids: Vec<String>,
+
+    ///
+    /// Number of recipients that confirmed delivery of the notification
+    ///
+    pub confirmed_count: u64,
+
+    ///
+    /// Number of recipients that have seen the notification
+    ///
+    pub seen_count: u64,
+
+    ///
+    /// Number of recipients that deleted the notification
+    ///
+    pub deleted_count: u64,
+}
+
+impl From<repository::ProducedNotification> for ProducedNotification {
+    fn from(value: repository::ProducedNotification) -> Self {
+        Self {
+            id: value.id.to_hex(),
+            producer_notification_id: value.producer_notification_id,
+            created_at: value.created_at,
+            invalidate_at: value.invalidate_at,
+            deliver_at: value.deliver_at,
+            retracted_at: value.retracted_at,
+            user_ids: value.user_ids,
+            groups: value.groups,
+            topic: value.topic,
+            priority: value.priority,
+            collapse_key: value.collapse_key,
+            content_type: value.content_type,
+            content: value.content,
+            localized_contents: value
+                .localized_contents
+                .into_iter()
+                .map(LocalizedContent::from)
+                .collect(),
+            attachment_ids: value
+                .attachment_ids
+                .into_iter()
+                .map(|attachment_id| attachment_id.to_hex())
+                .collect(),
+            confirmed_count: value.confirmed_count,
+            seen_count: value.seen_count,
+            deleted_count: value.deleted_count,
+        }
+    }
+}
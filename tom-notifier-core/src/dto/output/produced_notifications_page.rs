@@ -0,0 +1,12 @@
+use super::{NotificationsCursor, ProducedNotification};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct ProducedNotificationsPage {
+    pub notifications: Vec<ProducedNotification>,
+
+    ///
+    /// None when there are no more notifications
+    ///
+    pub next_cursor: Option<NotificationsCursor>,
+}
@@ -0,0 +1,32 @@
+use crate::repository::{self, ReceiptEvent};
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Body of the request posted to the webhook of the producer
+///
+#[derive(Serialize)]
+pub struct Receipt {
+    ///
+    /// Unique id of the receipt, the same receipt
+    /// can be posted more than once
+    ///
+    pub id: String,
+    pub event: ReceiptEvent,
+    pub notification_id: String,
+    pub user_id: Uuid,
+    pub timestamp: OffsetDateTime,
+}
+
+impl From<&repository::Receipt> for Receipt {
+    fn from(value: &repository::Receipt) -> Self {
+        Self {
+            id: value.id.to_hex(),
+            event: value.event,
+            notification_id: value.notification_id.to_hex(),
+            user_id: value.user_id,
+            timestamp: value.timestamp,
+        }
+    }
+}
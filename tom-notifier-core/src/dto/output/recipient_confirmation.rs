@@ -0,0 +1,23 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct RecipientConfirmation {
+    pub user_id: Uuid,
+    pub delivered_at: OffsetDateTime,
+    pub seen: bool,
+    pub deleted: bool,
+}
+
+impl From<repository::RecipientConfirmation> for RecipientConfirmation {
+    fn from(value: repository::RecipientConfirmation) -> Self {
+        Self {
+            user_id: value.user_id,
+            delivered_at: value.delivered_at,
+            seen: value.seen,
+            deleted: value.deleted,
+        }
+    }
+}
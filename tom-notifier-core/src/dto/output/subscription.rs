@@ -0,0 +1,18 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Serialize)]
+pub struct Subscription {
+    pub topic: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<repository::Subscription> for Subscription {
+    fn from(value: repository::Subscription) -> Self {
+        Self {
+            topic: value.topic,
+            created_at: value.created_at,
+        }
+    }
+}
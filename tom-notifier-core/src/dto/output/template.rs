@@ -0,0 +1,26 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Serialize)]
+pub struct Template {
+    pub id: String,
+    pub version: u32,
+    pub created_at: OffsetDateTime,
+    pub content_type: String,
+    pub body: String,
+    pub parameters: Vec<String>,
+}
+
+impl From<repository::Template> for Template {
+    fn from(value: repository::Template) -> Self {
+        Self {
+            id: value.id,
+            version: value.version,
+            created_at: value.created_at,
+            content_type: value.content_type,
+            body: value.body,
+            parameters: value.parameters,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct TemplateVersion {
+    pub id: String,
+    pub version: u32,
+}
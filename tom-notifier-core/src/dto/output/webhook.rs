@@ -0,0 +1,18 @@
+use crate::repository;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Serialize)]
+pub struct Webhook {
+    pub url: String,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<repository::Webhook> for Webhook {
+    fn from(value: repository::Webhook) -> Self {
+        Self {
+            url: value.url,
+            updated_at: value.updated_at,
+        }
+    }
+}
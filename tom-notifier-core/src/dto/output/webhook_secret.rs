@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct WebhookSecret {
+    ///
+    /// Key of HMAC-SHA256 signatures of receipts, returned only once
+    ///
+    pub secret: String,
+}
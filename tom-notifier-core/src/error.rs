@@ -19,6 +19,33 @@ pub enum Error {
     #[error("validation error: notification too large {size}/{max_size}B")]
     ValidationNotificationTooLarge { size: usize, max_size: usize },
 
+    #[error("group not exist")]
+    GroupNotExist,
+
+    #[error("group already exist")]
+    GroupAlreadyExist,
+
+    #[error("subscription not exist")]
+    SubscriptionNotExist,
+
+    #[error("template not exist")]
+    TemplateNotExist,
+
+    #[error("template already exist")]
+    TemplateAlreadyExist,
+
+    #[error("content type not exist")]
+    ContentTypeNotExist,
+
+    #[error("attachment not exist")]
+    AttachmentNotExist,
+
+    #[error("validation error: attachment too large {size}/{max_size}B")]
+    ValidationAttachmentTooLarge { size: usize, max_size: usize },
+
+    #[error("webhook not exist")]
+    WebhookNotExist,
+
     #[error("auth error: {0}")]
     Auth(#[from] MissingRoleError),
 
@@ -38,6 +65,18 @@ impl IntoResponse for Error {
                 max_size: _,
             } => StatusCode::PAYLOAD_TOO_LARGE,
             Error::NotificationAlreadySaved => StatusCode::CONFLICT,
+            Error::GroupNotExist => StatusCode::NOT_FOUND,
+            Error::GroupAlreadyExist => StatusCode::CONFLICT,
+            Error::SubscriptionNotExist => StatusCode::NOT_FOUND,
+            Error::TemplateNotExist => StatusCode::NOT_FOUND,
+            Error::TemplateAlreadyExist => StatusCode::CONFLICT,
+            Error::ContentTypeNotExist => StatusCode::NOT_FOUND,
+            Error::AttachmentNotExist => StatusCode::NOT_FOUND,
+            Error::ValidationAttachmentTooLarge {
+                size: _,
+                max_size: _,
+            } => StatusCode::PAYLOAD_TOO_LARGE,
+            Error::WebhookNotExist => StatusCode::NOT_FOUND,
             Error::Auth(_) => StatusCode::FORBIDDEN,
             Error::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
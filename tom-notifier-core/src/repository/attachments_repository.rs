@@ -0,0 +1,36 @@
+use super::{dto::Attachment, Error};
+use axum::async_trait;
+use bson::oid::ObjectId;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AttachmentsRepository: Send + Sync {
+    ///
+    /// Uploads attachment of the producer
+    ///
+    /// ### Returns
+    /// id of the uploaded attachment
+    ///
+    async fn insert(
+        &self,
+        producer_id: Uuid,
+        filename: String,
+        content_type: String,
+        content: Vec<u8>,
+    ) -> Result<ObjectId, Error>;
+
+    ///
+    /// Finds ids of attachments of the producer among passed ids
+    ///
+    async fn find_many_ids(
+        &self,
+        producer_id: Uuid,
+        ids: Vec<ObjectId>,
+    ) -> Result<Vec<ObjectId>, Error>;
+
+    ///
+    /// Finds attachment together with its content
+    ///
+    async fn find(&self, id: ObjectId) -> Result<Option<Attachment>, Error>;
+}
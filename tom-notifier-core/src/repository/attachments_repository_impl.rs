@@ -0,0 +1,224 @@
+use super::{dto::Attachment, entity::AttachmentMetadataEntity, AttachmentsRepository, Error};
+use axum::async_trait;
+use bson::{doc, oid::ObjectId};
+use futures_util::{AsyncReadExt, AsyncWriteExt, TryStreamExt};
+use mongodb::{gridfs::GridFsBucket, options::GridFsBucketOptions, Database};
+use uuid::Uuid;
+
+const ATTACHMENTS: &str = "attachments";
+pub(super) const ATTACHMENTS_FILES: &str = "attachments.files";
+pub(super) const ATTACHMENTS_CHUNKS: &str = "attachments.chunks";
+
+pub struct AttachmentsRepositoryImpl {
+    bucket: GridFsBucket,
+}
+
+impl AttachmentsRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(ATTACHMENTS_FILES).await?;
+        database.create_collection(ATTACHMENTS_CHUNKS).await?;
+
+        let bucket = database.gridfs_bucket(
+            GridFsBucketOptions::builder()
+                .bucket_name(ATTACHMENTS.to_string())
+                .build(),
+        );
+
+        Ok(Self { bucket })
+    }
+}
+
+#[async_trait]
+impl AttachmentsRepository for AttachmentsRepositoryImpl {
+    async fn insert(
+        &self,
+        producer_id: Uuid,
+        filename: String,
+        content_type: String,
+        content: Vec<u8>,
+    ) -> Result<ObjectId, Error> {
+        let id = ObjectId::new();
+        let metadata = bson::to_document(&AttachmentMetadataEntity {
+            producer_id: bson::Uuid::from(producer_id),
+            content_type,
+        })
+        .map_err(mongodb::error::Error::from)?;
+
+        let mut upload_stream = self
+            .bucket
+            .open_upload_stream(filename)
+            .id(id.into())
+            .metadata(metadata)
+            .await?;
+
+        upload_stream
+            .write_all(&content)
+            .await
+            .map_err(mongodb::error::Error::from)?;
+        upload_stream
+            .close()
+            .await
+            .map_err(mongodb::error::Error::from)?;
+
+        Ok(id)
+    }
+
+    async fn find_many_ids(
+        &self,
+        producer_id: Uuid,
+        ids: Vec<ObjectId>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let ids = self
+            .bucket
+            .find(doc! {
+                "_id": { "$in": ids },
+                "metadata.producer_id": bson::Uuid::from(producer_id),
+            })
+            .await?
+            .try_filter_map(|file| async move { Ok(file.id.as_object_id()) })
+            .try_collect()
+            .await?;
+
+        Ok(ids)
+    }
+
+    async fn find(&self, id: ObjectId) -> Result<Option<Attachment>, Error> {
+        let Some(file) = self.bucket.find_one(doc! { "_id": id }).await? else {
+            return Ok(None);
+        };
+
+        let metadata =
+            bson::from_document::<AttachmentMetadataEntity>(file.metadata.unwrap_or_default())
+                .map_err(mongodb::error::Error::from)?;
+
+        let mut content = Vec::with_capacity(file.length as usize);
+        self.bucket
+            .open_download_stream(file.id)
+            .await?
+            .read_to_end(&mut content)
+            .await
+            .map_err(mongodb::error::Error::from)?;
+
+        Ok(Some(Attachment {
+            id,
+            filename: file.filename.unwrap_or_default(),
+            content_type: metadata.content_type,
+            content,
+        }))
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn insert_and_find() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = AttachmentsRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+        let content = "attachment content ".repeat(32 * 1024).into_bytes();
+
+        let id = repository
+            .insert(
+                producer_id,
+                "report.txt".to_string(),
+                "text/plain".to_string(),
+                content.clone(),
+            )
+            .await?;
+
+        let attachment = repository.find(id).await?.unwrap();
+        assert_eq!(attachment.id, id);
+        assert_eq!(attachment.filename, "report.txt");
+        assert_eq!(attachment.content_type, "text/plain");
+        assert_eq!(attachment.content, content);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_not_existing() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = AttachmentsRepositoryImpl::new(database.clone()).await?;
+
+        let attachment = repository.find(ObjectId::new()).await?;
+        assert!(attachment.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_ids_of_producer() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = AttachmentsRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+        let other_producer_id = Uuid::new_v4();
+
+        let id_1 = repository
+            .insert(
+                producer_id,
+                "1.txt".to_string(),
+                "text/plain".to_string(),
+                b"1".to_vec(),
+            )
+            .await?;
+        let id_2 = repository
+            .insert(
+                other_producer_id,
+                "2.txt".to_string(),
+                "text/plain".to_string(),
+                b"2".to_vec(),
+            )
+            .await?;
+
+        let ids = repository
+            .find_many_ids(producer_id, vec![id_1, id_2, ObjectId::new()])
+            .await?;
+        assert_eq!(ids, vec![id_1]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use super::{dto::ContentType, Error};
+use axum::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ContentTypesRepository: Send + Sync {
+    ///
+    /// Inserts JSON Schema of the producer content type or replaces the existing one
+    ///
+    async fn upsert(
+        &self,
+        producer_id: Uuid,
+        content_type: String,
+        schema: String,
+        updated_at: OffsetDateTime,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Finds all content types of the producer sorted ascending by content type
+    ///
+    async fn find_many(&self, producer_id: Uuid) -> Result<Vec<ContentType>, Error>;
+
+    ///
+    /// Finds content types of the producer among passed content types
+    ///
+    async fn find_many_by_content_types(
+        &self,
+        producer_id: Uuid,
+        content_types: Vec<String>,
+    ) -> Result<Vec<ContentType>, Error>;
+
+    ///
+    /// Deletes JSON Schema of the producer content type
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when content type does not exist
+    ///
+    async fn delete(&self, producer_id: Uuid, content_type: String) -> Result<(), Error>;
+}
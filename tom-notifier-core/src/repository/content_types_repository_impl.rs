@@ -0,0 +1,291 @@
+use super::{dto::ContentType, entity::ContentTypeEntity, ContentTypesRepository, Error};
+use axum::async_trait;
+use bson::{doc, DateTime, Document};
+use futures_util::TryStreamExt;
+use mongodb::{options::IndexOptions, Collection, Database, IndexModel};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub(super) const CONTENT_TYPES: &str = "content_types";
+const INDEX_NAME_UNIQUE_PRODUCER_CONTENT_TYPE: &str = "unique_index_producer_id_content_type";
+
+pub struct ContentTypesRepositoryImpl {
+    collection: Collection<ContentTypeEntity>,
+}
+
+impl ContentTypesRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(CONTENT_TYPES).await?;
+
+        let collection = database.collection(CONTENT_TYPES);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_UNIQUE_PRODUCER_CONTENT_TYPE.to_string()) {
+            Self::create_unique_producer_content_type_index(&collection).await?;
+            tracing::debug!(
+                "created index {CONTENT_TYPES}.{INDEX_NAME_UNIQUE_PRODUCER_CONTENT_TYPE}"
+            );
+        }
+
+        Ok(Self {
+            collection: database.collection(CONTENT_TYPES),
+        })
+    }
+
+    async fn create_unique_producer_content_type_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "producer_id": 1,
+                "content_type": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_UNIQUE_PRODUCER_CONTENT_TYPE.to_string())
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ContentTypesRepository for ContentTypesRepositoryImpl {
+    async fn upsert(
+        &self,
+        producer_id: Uuid,
+        content_type: String,
+        schema: String,
+        updated_at: OffsetDateTime,
+    ) -> Result<(), Error> {
+        let producer_id = bson::Uuid::from(producer_id);
+
+        self.collection
+            .replace_one(
+                doc! {
+                    "producer_id": producer_id,
+                    "content_type": &content_type,
+                },
+                ContentTypeEntity {
+                    producer_id,
+                    content_type,
+                    schema,
+                    updated_at: DateTime::from(updated_at),
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_many(&self, producer_id: Uuid) -> Result<Vec<ContentType>, Error> {
+        let content_types = self
+            .collection
+            .find(doc! { "producer_id": bson::Uuid::from(producer_id) })
+            .sort(doc! { "content_type": 1 })
+            .await?
+            .map_ok(ContentType::from)
+            .try_collect()
+            .await?;
+
+        Ok(content_types)
+    }
+
+    async fn find_many_by_content_types(
+        &self,
+        producer_id: Uuid,
+        content_types: Vec<String>,
+    ) -> Result<Vec<ContentType>, Error> {
+        let content_types = self
+            .collection
+            .find(doc! {
+                "producer_id": bson::Uuid::from(producer_id),
+                "content_type": { "$in": content_types },
+            })
+            .await?
+            .map_ok(ContentType::from)
+            .try_collect()
+            .await?;
+
+        Ok(content_types)
+    }
+
+    async fn delete(&self, producer_id: Uuid, content_type: String) -> Result<(), Error> {
+        let delete_result = self
+            .collection
+            .delete_one(doc! {
+                "producer_id": bson::Uuid::from(producer_id),
+                "content_type": content_type,
+            })
+            .await?;
+
+        match delete_result.deleted_count {
+            1 => Ok(()),
+            _ => Err(Error::NoDocumentUpdated),
+        }
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use time::macros::datetime;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_schema() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ContentTypesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+        let updated_at = datetime!(2024-01-02 0:00 UTC);
+
+        repository
+            .upsert(
+                producer_id,
+                "order".to_string(),
+                r#"{"type":"object"}"#.to_string(),
+                datetime!(2024-01-01 0:00 UTC),
+            )
+            .await?;
+        repository
+            .upsert(
+                producer_id,
+                "order".to_string(),
+                r#"{"type":"array"}"#.to_string(),
+                updated_at,
+            )
+            .await?;
+
+        let content_types = repository.find_many(producer_id).await?;
+
+        assert_eq!(content_types.len(), 1);
+        assert_eq!(content_types[0].content_type, "order");
+        assert_eq!(content_types[0].schema, r#"{"type":"array"}"#);
+        assert_eq!(content_types[0].updated_at, updated_at);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_sorted_by_content_type() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ContentTypesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+        for content_type in ["shipment", "order"] {
+            repository
+                .upsert(
+                    producer_id,
+                    content_type.to_string(),
+                    "true".to_string(),
+                    OffsetDateTime::now_utc(),
+                )
+                .await?;
+        }
+        repository
+            .upsert(
+                Uuid::new_v4(),
+                "invoice".to_string(),
+                "true".to_string(),
+                OffsetDateTime::now_utc(),
+            )
+            .await?;
+
+        let content_types = repository.find_many(producer_id).await?;
+        let content_types = content_types
+            .into_iter()
+            .map(|content_type| content_type.content_type)
+            .collect::<Vec<_>>();
+
+        assert_eq!(content_types, vec!["order", "shipment"]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_by_content_types_only_passed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ContentTypesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+        for content_type in ["shipment", "order"] {
+            repository
+                .upsert(
+                    producer_id,
+                    content_type.to_string(),
+                    "true".to_string(),
+                    OffsetDateTime::now_utc(),
+                )
+                .await?;
+        }
+
+        let content_types = repository
+            .find_many_by_content_types(producer_id, vec!["order".to_string(), "utf-8".to_string()])
+            .await?;
+
+        assert_eq!(content_types.len(), 1);
+        assert_eq!(content_types[0].content_type, "order");
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ContentTypesRepositoryImpl::new(database.clone()).await?;
+
+        let delete_result = repository.delete(Uuid::new_v4(), "order".to_string()).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
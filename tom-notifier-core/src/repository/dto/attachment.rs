@@ -0,0 +1,8 @@
+use bson::oid::ObjectId;
+
+pub struct Attachment {
+    pub id: ObjectId,
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
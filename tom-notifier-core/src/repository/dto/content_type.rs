@@ -0,0 +1,18 @@
+use crate::repository::entity::ContentTypeEntity;
+use time::OffsetDateTime;
+
+pub struct ContentType {
+    pub content_type: String,
+    pub schema: String,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<ContentTypeEntity> for ContentType {
+    fn from(entity: ContentTypeEntity) -> Self {
+        Self {
+            content_type: entity.content_type,
+            schema: entity.schema,
+            updated_at: OffsetDateTime::from(entity.updated_at),
+        }
+    }
+}
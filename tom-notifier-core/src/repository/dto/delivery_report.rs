@@ -0,0 +1,23 @@
+use super::RecipientConfirmation;
+use uuid::Uuid;
+
+pub struct DeliveryReport {
+    pub confirmed_count: u64,
+
+    ///
+    /// Page of confirmations sorted by delivery date
+    ///
+    pub confirmations: Vec<RecipientConfirmation>,
+
+    ///
+    /// Number of recipients that did not confirm the notification yet,
+    /// None for broadcast notifications whose recipients are not known
+    ///
+    pub pending_count: Option<u64>,
+
+    ///
+    /// Page of pending recipients sorted by their ids,
+    /// None for broadcast notifications
+    ///
+    pub pending_user_ids: Option<Vec<Uuid>>,
+}
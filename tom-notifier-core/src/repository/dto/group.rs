@@ -0,0 +1,16 @@
+use crate::repository::entity::GroupFindEntity;
+use time::OffsetDateTime;
+
+pub struct Group {
+    pub name: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<GroupFindEntity> for Group {
+    fn from(entity: GroupFindEntity) -> Self {
+        Self {
+            name: entity._id,
+            created_at: OffsetDateTime::from(entity.created_at),
+        }
+    }
+}
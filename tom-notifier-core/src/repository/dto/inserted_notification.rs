@@ -1,3 +1,4 @@
+use crate::dto::input::NotificationPriority;
 use bson::oid::ObjectId;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -7,7 +8,11 @@ pub struct InsertedNotification {
     pub id: ObjectId,
     pub created_at: OffsetDateTime,
     pub invalidate_at: Option<OffsetDateTime>,
+    pub deliver_at: Option<OffsetDateTime>,
     pub user_ids: Vec<Uuid>,
+    pub groups: Vec<String>,
+    pub topic: Option<String>,
+    pub priority: NotificationPriority,
     pub producer_id: Uuid,
     pub producer_notification_id: i64,
     pub content_type: String,
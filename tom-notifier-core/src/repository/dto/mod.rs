@@ -1,5 +1,33 @@
+mod attachment;
+mod content_type;
+mod delivery_report;
+mod group;
 mod inserted_notification;
 mod notification;
+mod notifications_count;
+mod outbox_message;
+mod preferences;
+mod produced_notification;
+mod purge_producers;
+mod receipt;
+mod recipient_confirmation;
+mod subscription;
+mod template;
+mod webhook;
 
+pub use attachment::*;
+pub use content_type::*;
+pub use delivery_report::*;
+pub use group::*;
 pub use inserted_notification::*;
 pub use notification::*;
+pub use notifications_count::*;
+pub use outbox_message::*;
+pub use preferences::*;
+pub use produced_notification::*;
+pub use purge_producers::*;
+pub use receipt::*;
+pub use recipient_confirmation::*;
+pub use subscription::*;
+pub use template::*;
+pub use webhook::*;
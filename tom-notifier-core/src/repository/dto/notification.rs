@@ -1,4 +1,7 @@
-use crate::repository::entity::NotificationFindEntity;
+use crate::{
+    dto::input::{LocalizedContent, NotificationPriority},
+    repository::entity::NotificationFindEntity,
+};
 use bson::{oid::ObjectId, Uuid};
 use time::OffsetDateTime;
 
@@ -7,8 +10,11 @@ pub struct Notification {
     pub created_at: OffsetDateTime,
     pub producer_id: Uuid,
     pub seen: bool,
+    pub priority: NotificationPriority,
     pub content_type: String,
     pub content: Vec<u8>,
+    pub localized_contents: Vec<LocalizedContent>,
+    pub attachment_ids: Vec<ObjectId>,
 }
 
 impl From<NotificationFindEntity> for Notification {
@@ -22,8 +28,18 @@ impl From<NotificationFindEntity> for Notification {
                 .first()
                 .map(|confirmation| confirmation.notification_seen)
                 .unwrap_or(false),
+            priority: entity
+                .priority
+                .map(NotificationPriority::from_i32)
+                .unwrap_or_default(),
             content_type: entity.content_type,
             content: entity.content.bytes,
+            localized_contents: entity
+                .localized_contents
+                .into_iter()
+                .map(LocalizedContent::from)
+                .collect(),
+            attachment_ids: entity.attachment_ids,
         }
     }
 }
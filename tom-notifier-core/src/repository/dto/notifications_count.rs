@@ -0,0 +1,17 @@
+use crate::repository::entity::NotificationsCountFindEntity;
+
+pub struct NotificationsCount {
+    pub undelivered: u64,
+    pub delivered: u64,
+    pub delivered_unseen: u64,
+}
+
+impl From<NotificationsCountFindEntity> for NotificationsCount {
+    fn from(entity: NotificationsCountFindEntity) -> Self {
+        Self {
+            undelivered: entity.undelivered as u64,
+            delivered: entity.delivered as u64,
+            delivered_unseen: entity.delivered_unseen as u64,
+        }
+    }
+}
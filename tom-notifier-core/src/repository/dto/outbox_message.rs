@@ -0,0 +1,91 @@
+use crate::{
+    dto::input::{LocalizedContent, NotificationPriority},
+    repository::entity::OutboxMessageFindEntity,
+};
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OutboxMessageStatus {
+    New,
+    Updated,
+    Deleted,
+}
+
+///
+/// Notification state change waiting to be published
+///
+pub struct OutboxMessage {
+    pub id: ObjectId,
+    pub status: OutboxMessageStatus,
+    pub user_ids: Vec<Uuid>,
+
+    ///
+    /// Groups of the notification. Their members are
+    /// added to user_ids when message is locked
+    ///
+    pub groups: Vec<String>,
+
+    ///
+    /// Topic of the notification. Its subscribers are
+    /// added to user_ids when message is locked
+    ///
+    pub topic: Option<String>,
+
+    ///
+    /// Recipients that muted the notification or are in their quiet hours.
+    /// Resolved only for NEW messages when message is locked
+    ///
+    pub excluded_user_ids: Vec<Uuid>,
+    pub notification_id: ObjectId,
+
+    ///
+    /// All notifications changed by a single UPDATED message of seen,
+    /// empty when message changes only notification_id
+    ///
+    pub notification_ids: Vec<ObjectId>,
+
+    ///
+    /// Priority of the notification, set only for NEW messages
+    ///
+    pub priority: Option<NotificationPriority>,
+    pub timestamp: OffsetDateTime,
+    pub created_by: Option<Uuid>,
+    pub seen: Option<bool>,
+    pub content_type: Option<String>,
+    pub content: Option<Vec<u8>>,
+
+    ///
+    /// Variants of the content in other locales, set together with content
+    ///
+    pub localized_contents: Vec<LocalizedContent>,
+}
+
+impl From<OutboxMessageFindEntity> for OutboxMessage {
+    fn from(entity: OutboxMessageFindEntity) -> Self {
+        Self {
+            id: entity._id,
+            status: entity.status,
+            user_ids: entity.user_ids.into_iter().map(Uuid::from).collect(),
+            groups: entity.groups,
+            topic: entity.topic,
+            excluded_user_ids: Vec::new(),
+            notification_id: entity.notification_id,
+            notification_ids: entity.notification_ids,
+            priority: entity.priority.map(NotificationPriority::from_i32),
+            timestamp: OffsetDateTime::from(entity.timestamp),
+            created_by: entity.created_by.map(Uuid::from),
+            seen: entity.seen,
+            content_type: entity.content_type,
+            content: entity.content.map(|content| content.bytes),
+            localized_contents: entity
+                .localized_contents
+                .into_iter()
+                .map(LocalizedContent::from)
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::repository::entity::{PreferencesEntity, QuietHoursEntity};
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+pub struct Preferences {
+    pub muted_producers: Vec<Uuid>,
+    pub muted_content_types: Vec<String>,
+    pub quiet_hours: Option<QuietHours>,
+    pub updated_at: OffsetDateTime,
+}
+
+///
+/// Daily period in which notifications are not pushed to the user.
+/// Period wraps around midnight when `from_minute` is greater than `to_minute`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    ///
+    /// Inclusive start as minute of the day in user's local time
+    ///
+    pub from_minute: u32,
+
+    ///
+    /// Exclusive end as minute of the day in user's local time
+    ///
+    pub to_minute: u32,
+    pub utc_offset_minutes: i32,
+}
+
+impl QuietHours {
+    pub fn contains(&self, at: OffsetDateTime) -> bool {
+        let offset =
+            UtcOffset::from_whole_seconds(self.utc_offset_minutes * 60).unwrap_or(UtcOffset::UTC);
+        let local = at.to_offset(offset);
+        let minute = local.hour() as u32 * 60 + local.minute() as u32;
+
+        match self.from_minute <= self.to_minute {
+            true => self.from_minute <= minute && minute < self.to_minute,
+            false => self.from_minute <= minute || minute < self.to_minute,
+        }
+    }
+
+    ///
+    /// Converts the period to minutes of the day in UTC,
+    /// so active periods can be found without knowing user's offset
+    ///
+    /// ### Returns
+    /// inclusive start and exclusive end in UTC
+    ///
+    pub fn utc_minutes(&self) -> (u32, u32) {
+        let to_utc = |minute: u32| {
+            (minute as i32 - self.utc_offset_minutes).rem_euclid(MINUTES_PER_DAY) as u32
+        };
+
+        (to_utc(self.from_minute), to_utc(self.to_minute))
+    }
+}
+
+impl From<PreferencesEntity> for Preferences {
+    fn from(entity: PreferencesEntity) -> Self {
+        Self {
+            muted_producers: entity.muted_producers.into_iter().map(Uuid::from).collect(),
+            muted_content_types: entity.muted_content_types,
+            quiet_hours: entity.quiet_hours.map(QuietHours::from),
+            updated_at: OffsetDateTime::from(entity.updated_at),
+        }
+    }
+}
+
+impl From<QuietHoursEntity> for QuietHours {
+    fn from(entity: QuietHoursEntity) -> Self {
+        Self {
+            from_minute: entity.from_minute,
+            to_minute: entity.to_minute,
+            utc_offset_minutes: entity.utc_offset_minutes,
+        }
+    }
+}
+
+impl From<QuietHours> for QuietHoursEntity {
+    fn from(quiet_hours: QuietHours) -> Self {
+        let (utc_from_minute, utc_to_minute) = quiet_hours.utc_minutes();
+
+        Self {
+            from_minute: quiet_hours.from_minute,
+            to_minute: quiet_hours.to_minute,
+            utc_offset_minutes: quiet_hours.utc_offset_minutes,
+            utc_from_minute,
+            utc_to_minute,
+            utc_wraps: utc_from_minute > utc_to_minute,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn quiet_hours_contains_same_day_period() {
+        let quiet_hours = QuietHours {
+            from_minute: 12 * 60,
+            to_minute: 14 * 60,
+            utc_offset_minutes: 0,
+        };
+
+        assert!(quiet_hours.contains(datetime!(2024-01-01 12:00 UTC)));
+        assert!(quiet_hours.contains(datetime!(2024-01-01 13:59 UTC)));
+        assert!(!quiet_hours.contains(datetime!(2024-01-01 14:00 UTC)));
+        assert!(!quiet_hours.contains(datetime!(2024-01-01 11:59 UTC)));
+    }
+
+    #[test]
+    fn quiet_hours_contains_period_over_midnight() {
+        let quiet_hours = QuietHours {
+            from_minute: 22 * 60,
+            to_minute: 7 * 60,
+            utc_offset_minutes: 0,
+        };
+
+        assert!(quiet_hours.contains(datetime!(2024-01-01 23:00 UTC)));
+        assert!(quiet_hours.contains(datetime!(2024-01-01 3:00 UTC)));
+        assert!(!quiet_hours.contains(datetime!(2024-01-01 7:00 UTC)));
+        assert!(!quiet_hours.contains(datetime!(2024-01-01 12:00 UTC)));
+    }
+
+    #[test]
+    fn quiet_hours_contains_local_time() {
+        let quiet_hours = QuietHours {
+            from_minute: 22 * 60,
+            to_minute: 7 * 60,
+            utc_offset_minutes: 120,
+        };
+
+        // 21:00 UTC is 23:00 in UTC+2
+        assert!(quiet_hours.contains(datetime!(2024-01-01 21:00 UTC)));
+        // 06:00 UTC is 08:00 in UTC+2
+        assert!(!quiet_hours.contains(datetime!(2024-01-01 6:00 UTC)));
+    }
+
+    #[test]
+    fn quiet_hours_utc_minutes() {
+        let quiet_hours = QuietHours {
+            from_minute: 60,
+            to_minute: 23 * 60,
+            utc_offset_minutes: 120,
+        };
+
+        assert_eq!(quiet_hours.utc_minutes(), (23 * 60, 21 * 60));
+    }
+}
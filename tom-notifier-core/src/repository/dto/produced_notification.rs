@@ -0,0 +1,62 @@
+use crate::{
+    dto::input::{LocalizedContent, NotificationPriority},
+    repository::entity::ProducedNotificationFindEntity,
+};
+use bson::oid::ObjectId;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub struct ProducedNotification {
+    pub id: ObjectId,
+    pub producer_notification_id: i64,
+    pub created_at: OffsetDateTime,
+    pub invalidate_at: Option<OffsetDateTime>,
+    pub deliver_at: Option<OffsetDateTime>,
+    pub retracted_at: Option<OffsetDateTime>,
+    pub user_ids: Vec<Uuid>,
+    pub groups: Vec<String>,
+    pub topic: Option<String>,
+    pub priority: NotificationPriority,
+    pub collapse_key: Option<String>,
+    pub content_type: String,
+    pub content: Vec<u8>,
+    pub localized_contents: Vec<LocalizedContent>,
+    pub attachment_ids: Vec<ObjectId>,
+    pub confirmed_count: u64,
+    pub seen_count: u64,
+    pub deleted_count: u64,
+}
+
+impl From<ProducedNotificationFindEntity> for ProducedNotification {
+    fn from(entity: ProducedNotificationFindEntity) -> Self {
+        let delivery = entity.delivery.unwrap_or_default();
+
+        Self {
+            id: entity._id,
+            producer_notification_id: entity.producer_notification_id,
+            created_at: OffsetDateTime::from(entity.created_at),
+            invalidate_at: entity.invalidate_at.map(OffsetDateTime::from),
+            deliver_at: entity.deliver_at.map(OffsetDateTime::from),
+            retracted_at: entity.retracted_at.map(OffsetDateTime::from),
+            user_ids: entity.user_ids.into_iter().map(Uuid::from).collect(),
+            groups: entity.groups,
+            topic: entity.topic,
+            priority: entity
+                .priority
+                .map(NotificationPriority::from_i32)
+                .unwrap_or_default(),
+            collapse_key: entity.collapse_key,
+            content_type: entity.content_type,
+            content: entity.content.bytes,
+            localized_contents: entity
+                .localized_contents
+                .into_iter()
+                .map(LocalizedContent::from)
+                .collect(),
+            attachment_ids: entity.attachment_ids,
+            confirmed_count: delivery.confirmed as u64,
+            seen_count: delivery.seen as u64,
+            deleted_count: delivery.deleted as u64,
+        }
+    }
+}
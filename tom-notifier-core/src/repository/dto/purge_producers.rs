@@ -0,0 +1,10 @@
+use uuid::Uuid;
+
+///
+/// Producers whose notifications are purged
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PurgeProducers {
+    Only(Uuid),
+    AllExcept(Vec<Uuid>),
+}
@@ -0,0 +1,46 @@
+use crate::repository::entity::ReceiptFindEntity;
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ReceiptEvent {
+    Delivered,
+    Seen,
+    Unseen,
+    Deleted,
+}
+
+///
+/// Change of the notification state made by its recipient
+/// waiting to be sent to the webhook of the producer
+///
+pub struct Receipt {
+    pub id: ObjectId,
+
+    ///
+    /// Number of failed attempts of sending the receipt
+    ///
+    pub attempts: u32,
+    pub producer_id: Uuid,
+    pub event: ReceiptEvent,
+    pub notification_id: ObjectId,
+    pub user_id: Uuid,
+    pub timestamp: OffsetDateTime,
+}
+
+impl From<ReceiptFindEntity> for Receipt {
+    fn from(entity: ReceiptFindEntity) -> Self {
+        Self {
+            id: entity._id,
+            attempts: entity.attempts,
+            producer_id: Uuid::from(entity.producer_id),
+            event: entity.event,
+            notification_id: entity.notification_id,
+            user_id: Uuid::from(entity.user_id),
+            timestamp: OffsetDateTime::from(entity.timestamp),
+        }
+    }
+}
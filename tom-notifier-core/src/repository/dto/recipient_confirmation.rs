@@ -0,0 +1,24 @@
+use crate::repository::entity::ConfirmationFindEntity;
+use bson::oid::ObjectId;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub struct RecipientConfirmation {
+    pub id: ObjectId,
+    pub user_id: Uuid,
+    pub delivered_at: OffsetDateTime,
+    pub seen: bool,
+    pub deleted: bool,
+}
+
+impl From<ConfirmationFindEntity> for RecipientConfirmation {
+    fn from(entity: ConfirmationFindEntity) -> Self {
+        Self {
+            id: entity.id,
+            user_id: Uuid::from(entity.user_id),
+            delivered_at: OffsetDateTime::from(entity.notification_delivered_at),
+            seen: entity.notification_seen,
+            deleted: entity.notification_deleted,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::repository::entity::SubscriptionEntity;
+use time::OffsetDateTime;
+
+pub struct Subscription {
+    pub topic: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<SubscriptionEntity> for Subscription {
+    fn from(entity: SubscriptionEntity) -> Self {
+        Self {
+            topic: entity.topic,
+            created_at: OffsetDateTime::from(entity.created_at),
+        }
+    }
+}
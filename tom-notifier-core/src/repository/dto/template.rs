@@ -0,0 +1,24 @@
+use crate::repository::entity::TemplateEntity;
+use time::OffsetDateTime;
+
+pub struct Template {
+    pub id: String,
+    pub version: u32,
+    pub created_at: OffsetDateTime,
+    pub content_type: String,
+    pub body: String,
+    pub parameters: Vec<String>,
+}
+
+impl From<TemplateEntity> for Template {
+    fn from(entity: TemplateEntity) -> Self {
+        Self {
+            id: entity.template_id,
+            version: entity.version as u32,
+            created_at: OffsetDateTime::from(entity.created_at),
+            content_type: entity.content_type,
+            body: entity.body,
+            parameters: entity.parameters,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::repository::entity::WebhookEntity;
+use time::OffsetDateTime;
+
+pub struct Webhook {
+    pub url: String,
+
+    ///
+    /// Key of HMAC signatures of receipts sent to the webhook
+    ///
+    pub secret: String,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<WebhookEntity> for Webhook {
+    fn from(entity: WebhookEntity) -> Self {
+        Self {
+            url: entity.url,
+            secret: entity.secret,
+            updated_at: OffsetDateTime::from(entity.updated_at),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use bson::Uuid;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct AttachmentMetadataEntity {
+    pub producer_id: Uuid,
+    pub content_type: String,
+}
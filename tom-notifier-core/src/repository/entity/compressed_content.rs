@@ -0,0 +1,171 @@
+use bson::{spec::BinarySubtype, Binary};
+use serde::{Deserialize, Deserializer};
+
+///
+/// User defined binary subtype of the content compressed with zstd,
+/// content saved as generic binary is stored uncompressed
+///
+pub const COMPRESSED_CONTENT_SUBTYPE: BinarySubtype = BinarySubtype::UserDefined(0x80);
+
+const COMPRESSION_LEVEL: i32 = 3;
+
+///
+/// Creates binary of the content, compressed when the content is
+/// at least `compression_threshold` bytes long and compression
+/// actually makes it smaller
+///
+pub fn content_binary(content: &[u8], compression_threshold: Option<usize>) -> Binary {
+    let compressed = match compression_threshold {
+        Some(compression_threshold) if content.len() >= compression_threshold => {
+            zstd::bulk::compress(content, COMPRESSION_LEVEL)
+                .inspect_err(|err| tracing::warn!(%err, "failed to compress content"))
+                .ok()
+                .filter(|compressed| compressed.len() < content.len())
+        }
+        _ => None,
+    };
+
+    match compressed {
+        Some(bytes) => Binary {
+            subtype: COMPRESSED_CONTENT_SUBTYPE,
+            bytes,
+        },
+        None => Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: content.to_vec(),
+        },
+    }
+}
+
+///
+/// Deserializes content binary, decompressing it when stored compressed
+///
+pub fn deserialize_content<'de, D>(deserializer: D) -> Result<Binary, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let binary = Binary::deserialize(deserializer)?;
+    decompress(binary).map_err(serde::de::Error::custom)
+}
+
+///
+/// Deserializes optional content binary, decompressing it when stored compressed
+///
+pub fn deserialize_optional_content<'de, D>(deserializer: D) -> Result<Option<Binary>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let binary = Option::<Binary>::deserialize(deserializer)?;
+    binary
+        .map(decompress)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+fn decompress(binary: Binary) -> std::io::Result<Binary> {
+    if binary.subtype != COMPRESSED_CONTENT_SUBTYPE {
+        return Ok(binary);
+    }
+
+    Ok(Binary {
+        subtype: BinarySubtype::Generic,
+        bytes: zstd::stream::decode_all(binary.bytes.as_slice())?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bson::doc;
+
+    #[derive(Deserialize)]
+    struct ContentEntity {
+        #[serde(deserialize_with = "deserialize_content")]
+        content: Binary,
+        #[serde(default, deserialize_with = "deserialize_optional_content")]
+        optional_content: Option<Binary>,
+    }
+
+    #[test]
+    fn content_binary_compressed() {
+        let content = "compressible content ".repeat(64).into_bytes();
+
+        let binary = content_binary(&content, Some(256));
+
+        assert_eq!(binary.subtype, COMPRESSED_CONTENT_SUBTYPE);
+        assert!(binary.bytes.len() < content.len());
+    }
+
+    #[test]
+    fn content_binary_below_threshold() {
+        let content = "compressible content ".repeat(64).into_bytes();
+
+        let binary = content_binary(&content, Some(content.len() + 1));
+
+        assert_eq!(binary.subtype, BinarySubtype::Generic);
+        assert_eq!(binary.bytes, content);
+    }
+
+    #[test]
+    fn content_binary_compression_disabled() {
+        let content = "compressible content ".repeat(64).into_bytes();
+
+        let binary = content_binary(&content, None);
+
+        assert_eq!(binary.subtype, BinarySubtype::Generic);
+        assert_eq!(binary.bytes, content);
+    }
+
+    #[test]
+    fn content_binary_incompressible() {
+        let content = b"abc".to_vec();
+
+        let binary = content_binary(&content, Some(0));
+
+        assert_eq!(binary.subtype, BinarySubtype::Generic);
+        assert_eq!(binary.bytes, content);
+    }
+
+    #[test]
+    fn deserialize_compressed_content() {
+        let content = "compressible content ".repeat(64).into_bytes();
+        let document = doc! {
+            "content": content_binary(&content, Some(0)),
+            "optional_content": content_binary(&content, Some(0)),
+        };
+
+        let entity = bson::from_document::<ContentEntity>(document).unwrap();
+
+        assert_eq!(entity.content.subtype, BinarySubtype::Generic);
+        assert_eq!(entity.content.bytes, content);
+        let optional_content = entity.optional_content.unwrap();
+        assert_eq!(optional_content.subtype, BinarySubtype::Generic);
+        assert_eq!(optional_content.bytes, content);
+    }
+
+    #[test]
+    fn deserialize_uncompressed_content() {
+        let content = b"content".to_vec();
+        let document = doc! {
+            "content": content_binary(&content, None),
+            "optional_content": None as Option<Binary>,
+        };
+
+        let entity = bson::from_document::<ContentEntity>(document).unwrap();
+
+        assert_eq!(entity.content.bytes, content);
+        assert!(entity.optional_content.is_none());
+    }
+
+    #[test]
+    fn deserialize_corrupted_content() {
+        let document = doc! {
+            "content": Binary {
+                subtype: COMPRESSED_CONTENT_SUBTYPE,
+                bytes: b"not zstd".to_vec(),
+            },
+        };
+
+        assert!(bson::from_document::<ContentEntity>(document).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+use bson::{oid::ObjectId, DateTime, Uuid};
+use serde::Deserialize;
+
+///
+/// Confirmation of delivering notification to the user
+/// as seen by the producer of the notification
+///
+#[derive(Deserialize)]
+pub struct ConfirmationFindEntity {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub user_id: Uuid,
+    pub notification_delivered_at: DateTime,
+    pub notification_seen: bool,
+    pub notification_deleted: bool,
+}
@@ -0,0 +1,38 @@
+use super::NotificationConfirmedFindEntity;
+use bson::{oid::ObjectId, DateTime, Uuid};
+use serde::Serialize;
+
+///
+/// Confirmation of delivering notification to the user.
+/// Creation date and producer of the notification are copied,
+/// so delivered notifications can be filtered and sorted without
+/// looking up notifications
+///
+#[derive(Serialize)]
+pub struct ConfirmationInsertEntity {
+    pub notification_id: ObjectId,
+    pub user_id: Uuid,
+    pub notification_created_at: DateTime,
+    pub notification_producer_id: Uuid,
+    pub notification_delivered_at: DateTime,
+    pub notification_seen: bool,
+    pub notification_deleted: bool,
+}
+
+impl ConfirmationInsertEntity {
+    pub fn new(
+        notification: NotificationConfirmedFindEntity,
+        user_id: Uuid,
+        delivered_at: DateTime,
+    ) -> Self {
+        Self {
+            notification_id: notification.id,
+            user_id,
+            notification_created_at: notification.created_at,
+            notification_producer_id: notification.producer_id,
+            notification_delivered_at: delivered_at,
+            notification_seen: false,
+            notification_deleted: false,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use bson::{oid::ObjectId, Uuid};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ConfirmationProducerFindEntity {
+    pub notification_id: ObjectId,
+    pub notification_producer_id: Uuid,
+}
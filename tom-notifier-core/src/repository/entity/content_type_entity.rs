@@ -0,0 +1,10 @@
+use bson::{DateTime, Uuid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ContentTypeEntity {
+    pub producer_id: Uuid,
+    pub content_type: String,
+    pub schema: String,
+    pub updated_at: DateTime,
+}
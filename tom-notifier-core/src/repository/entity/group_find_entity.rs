@@ -0,0 +1,8 @@
+use bson::DateTime;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct GroupFindEntity {
+    pub _id: String,
+    pub created_at: DateTime,
+}
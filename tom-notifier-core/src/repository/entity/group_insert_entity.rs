@@ -0,0 +1,8 @@
+use bson::DateTime;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct GroupInsertEntity {
+    pub _id: String,
+    pub created_at: DateTime,
+}
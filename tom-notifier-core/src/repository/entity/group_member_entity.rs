@@ -0,0 +1,8 @@
+use bson::Uuid;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GroupMemberEntity {
+    pub group: String,
+    pub user_id: Uuid,
+}
@@ -0,0 +1,32 @@
+use super::{content_binary, deserialize_content};
+use crate::dto::input::LocalizedContent;
+use bson::Binary;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LocalizedContentEntity {
+    pub locale: String,
+    pub content_type: String,
+    #[serde(deserialize_with = "deserialize_content")]
+    pub content: Binary,
+}
+
+impl LocalizedContentEntity {
+    pub fn new(value: LocalizedContent, compression_threshold: Option<usize>) -> Self {
+        Self {
+            content: content_binary(&value.content, compression_threshold),
+            locale: value.locale,
+            content_type: value.content_type,
+        }
+    }
+}
+
+impl From<LocalizedContentEntity> for LocalizedContent {
+    fn from(entity: LocalizedContentEntity) -> Self {
+        Self {
+            locale: entity.locale,
+            content_type: entity.content_type,
+            content: entity.content.bytes,
+        }
+    }
+}
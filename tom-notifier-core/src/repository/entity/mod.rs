@@ -1,5 +1,53 @@
+mod attachment_metadata_entity;
+mod compressed_content;
+mod confirmation_find_entity;
+mod confirmation_insert_entity;
+mod confirmation_producer_find_entity;
+mod content_type_entity;
+mod group_find_entity;
+mod group_insert_entity;
+mod group_member_entity;
+mod localized_content_entity;
+mod notification_confirmed_find_entity;
 mod notification_find_entity;
+mod notification_id_find_entity;
 mod notification_insert_entity;
+mod notification_user_ids_find_entity;
+mod notifications_count_find_entity;
+mod outbox_message_find_entity;
+mod outbox_message_insert_entity;
+mod pending_recipients_find_entity;
+mod preferences_entity;
+mod produced_notification_find_entity;
+mod receipt_find_entity;
+mod receipt_insert_entity;
+mod subscription_entity;
+mod template_entity;
+mod webhook_entity;
 
+pub use attachment_metadata_entity::*;
+pub use compressed_content::*;
+pub use confirmation_find_entity::*;
+pub use confirmation_insert_entity::*;
+pub use confirmation_producer_find_entity::*;
+pub use content_type_entity::*;
+pub use group_find_entity::*;
+pub use group_insert_entity::*;
+pub use group_member_entity::*;
+pub use localized_content_entity::*;
+pub use notification_confirmed_find_entity::*;
 pub use notification_find_entity::*;
+pub use notification_id_find_entity::*;
 pub use notification_insert_entity::*;
+pub use notification_user_ids_find_entity::*;
+pub use notifications_count_find_entity::*;
+pub use outbox_message_find_entity::*;
+pub use outbox_message_insert_entity::*;
+pub use pending_recipients_find_entity::*;
+pub use preferences_entity::*;
+pub use produced_notification_find_entity::*;
+pub use receipt_find_entity::*;
+pub use receipt_insert_entity::*;
+pub use subscription_entity::*;
+pub use template_entity::*;
+pub use webhook_entity::*;
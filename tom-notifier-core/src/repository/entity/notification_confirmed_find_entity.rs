@@ -0,0 +1,13 @@
+use bson::{oid::ObjectId, DateTime, Uuid};
+use serde::Deserialize;
+
+///
+/// Notification properties copied to its confirmations
+///
+#[derive(Deserialize)]
+pub struct NotificationConfirmedFindEntity {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub created_at: DateTime,
+    pub producer_id: Uuid,
+}
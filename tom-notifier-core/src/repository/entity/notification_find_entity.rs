@@ -1,3 +1,4 @@
+use super::{deserialize_content, LocalizedContentEntity};
 use bson::{oid::ObjectId, Binary, DateTime, Uuid};
 use serde::Deserialize;
 
@@ -6,8 +7,19 @@ pub struct NotificationFindEntity {
     pub _id: ObjectId,
     pub created_at: DateTime,
     pub producer_id: Uuid,
+
+    ///
+    /// Missing in notifications saved before priorities were introduced
+    ///
+    #[serde(default)]
+    pub priority: Option<i32>,
     pub content_type: String,
+    #[serde(deserialize_with = "deserialize_content")]
     pub content: Binary,
+    #[serde(default)]
+    pub localized_contents: Vec<LocalizedContentEntity>,
+    #[serde(default)]
+    pub attachment_ids: Vec<ObjectId>,
 
     #[serde(default)]
     pub confirmations: Vec<NotificationConfirmationFindEntity>,
@@ -0,0 +1,8 @@
+use bson::oid::ObjectId;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct NotificationIdFindEntity {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+}
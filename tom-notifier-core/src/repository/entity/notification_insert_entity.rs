@@ -1,14 +1,22 @@
-use bson::{Binary, DateTime, Uuid};
+use super::LocalizedContentEntity;
+use bson::{oid::ObjectId, Binary, DateTime, Uuid};
 use serde::Serialize;
 
 #[derive(Serialize)]
 pub struct NotificationInsertEntity {
     pub created_at: DateTime,
     pub invalidate_at: Option<DateTime>,
+    pub deliver_at: Option<DateTime>,
+    pub retracted_at: Option<DateTime>,
     pub user_ids: Vec<Uuid>,
+    pub groups: Vec<String>,
+    pub topic: Option<String>,
+    pub priority: i32,
+    pub collapse_key: Option<String>,
     pub producer_id: Uuid,
     pub producer_notification_id: i64,
     pub content_type: String,
     pub content: Binary,
-    pub confirmations: [(); 0],
+    pub localized_contents: Vec<LocalizedContentEntity>,
+    pub attachment_ids: Vec<ObjectId>,
 }
@@ -0,0 +1,11 @@
+use bson::Uuid;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct NotificationUserIdsFindEntity {
+    pub user_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub groups: Vec<String>,
+    #[serde(default)]
+    pub topic: Option<String>,
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+///
+/// Numbers of notifications of the user, fields missing in the result are 0
+///
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct NotificationsCountFindEntity {
+    pub undelivered: i64,
+    pub delivered: i64,
+    pub delivered_unseen: i64,
+}
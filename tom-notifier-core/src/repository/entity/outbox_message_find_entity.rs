@@ -0,0 +1,28 @@
+use super::{deserialize_optional_content, LocalizedContentEntity};
+use crate::repository::OutboxMessageStatus;
+use bson::{oid::ObjectId, Binary, DateTime, Uuid};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct OutboxMessageFindEntity {
+    pub _id: ObjectId,
+    pub status: OutboxMessageStatus,
+    pub user_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub groups: Vec<String>,
+    #[serde(default)]
+    pub topic: Option<String>,
+    pub notification_id: ObjectId,
+    #[serde(default)]
+    pub notification_ids: Vec<ObjectId>,
+    #[serde(default)]
+    pub priority: Option<i32>,
+    pub timestamp: DateTime,
+    pub created_by: Option<Uuid>,
+    pub seen: Option<bool>,
+    pub content_type: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_content")]
+    pub content: Option<Binary>,
+    #[serde(default)]
+    pub localized_contents: Vec<LocalizedContentEntity>,
+}
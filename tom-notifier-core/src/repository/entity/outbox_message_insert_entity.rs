@@ -0,0 +1,169 @@
+use super::{LocalizedContentEntity, NotificationInsertEntity, NotificationUserIdsFindEntity};
+use crate::repository::OutboxMessageStatus;
+use bson::{oid::ObjectId, Binary, DateTime, Uuid};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct OutboxMessageInsertEntity {
+    pub created_at: DateTime,
+    pub locked_until: Option<DateTime>,
+    pub lock_id: Option<ObjectId>,
+    pub published_at: Option<DateTime>,
+    pub publish_at: Option<DateTime>,
+
+    pub status: OutboxMessageStatus,
+    pub user_ids: Vec<Uuid>,
+    pub groups: Vec<String>,
+    pub topic: Option<String>,
+    pub notification_id: ObjectId,
+    pub notification_ids: Vec<ObjectId>,
+    pub priority: Option<i32>,
+    pub timestamp: DateTime,
+    pub created_by: Option<Uuid>,
+    pub seen: Option<bool>,
+    pub content_type: Option<String>,
+    pub content: Option<Binary>,
+    pub localized_contents: Vec<LocalizedContentEntity>,
+}
+
+impl OutboxMessageInsertEntity {
+    pub fn new(id: ObjectId, notification: &NotificationInsertEntity) -> Self {
+        Self {
+            created_at: DateTime::now(),
+            locked_until: None,
+            lock_id: None,
+            published_at: None,
+            publish_at: notification.deliver_at,
+            status: OutboxMessageStatus::New,
+            user_ids: notification.user_ids.clone(),
+            groups: notification.groups.clone(),
+            topic: notification.topic.clone(),
+            notification_id: id,
+            notification_ids: Vec::new(),
+            priority: Some(notification.priority),
+            timestamp: notification.created_at,
+            created_by: Some(notification.producer_id),
+            seen: Some(false),
+            content_type: Some(notification.content_type.clone()),
+            content: Some(notification.content.clone()),
+            localized_contents: notification.localized_contents.clone(),
+        }
+    }
+
+    pub fn updated_seen(id: ObjectId, user_id: Uuid, seen: bool, timestamp: DateTime) -> Self {
+        Self {
+            created_at: DateTime::now(),
+            locked_until: None,
+            lock_id: None,
+            published_at: None,
+            publish_at: None,
+            status: OutboxMessageStatus::Updated,
+            user_ids: vec![user_id],
+            groups: Vec::new(),
+            topic: None,
+            notification_id: id,
+            notification_ids: Vec::new(),
+            priority: None,
+            timestamp,
+            created_by: None,
+            seen: Some(seen),
+            content_type: None,
+            content: None,
+            localized_contents: Vec::new(),
+        }
+    }
+
+    ///
+    /// Single UPDATED message of seen for all notifications in ids,
+    /// notification_id is the first of them
+    ///
+    pub fn updated_many_seen(
+        ids: Vec<ObjectId>,
+        user_id: Uuid,
+        seen: bool,
+        timestamp: DateTime,
+    ) -> Self {
+        Self {
+            created_at: DateTime::now(),
+            locked_until: None,
+            lock_id: None,
+            published_at: None,
+            publish_at: None,
+            status: OutboxMessageStatus::Updated,
+            user_ids: vec![user_id],
+            groups: Vec::new(),
+            topic: None,
+            notification_id: ids[0],
+            notification_ids: ids,
+            priority: None,
+            timestamp,
+            created_by: None,
+            seen: Some(seen),
+            content_type: None,
+            content: None,
+            localized_contents: Vec::new(),
+        }
+    }
+
+    ///
+    /// UPDATED message of content delivered to the same audience as the notification
+    ///
+    pub fn updated_content(
+        id: ObjectId,
+        notification: NotificationUserIdsFindEntity,
+        content_type: String,
+        content: Binary,
+        localized_contents: Vec<LocalizedContentEntity>,
+        timestamp: DateTime,
+    ) -> Self {
+        Self {
+            created_at: DateTime::now(),
+            locked_until: None,
+            lock_id: None,
+            published_at: None,
+            publish_at: None,
+            status: OutboxMessageStatus::Updated,
+            user_ids: notification.user_ids,
+            groups: notification.groups,
+            topic: notification.topic,
+            notification_id: id,
+            notification_ids: Vec::new(),
+            priority: None,
+            timestamp,
+            created_by: None,
+            seen: None,
+            content_type: Some(content_type),
+            content: Some(content),
+            localized_contents,
+        }
+    }
+
+    pub fn deleted(
+        id: ObjectId,
+        user_ids: Vec<Uuid>,
+        groups: Vec<String>,
+        topic: Option<String>,
+        timestamp: DateTime,
+    ) -> Self {
+        Self {
+            created_at: DateTime::now(),
+            locked_until: None,
+            lock_id: None,
+            published_at: None,
+            publish_at: None,
+            status: OutboxMessageStatus::Deleted,
+            user_ids,
+            groups,
+            topic,
+            notification_id: id,
+            notification_ids: Vec::new(),
+            priority: None,
+            timestamp,
+            created_by: None,
+            seen: None,
+            content_type: None,
+            content: None,
+            localized_contents: Vec::new(),
+        }
+    }
+}
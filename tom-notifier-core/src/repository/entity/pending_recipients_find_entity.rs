@@ -0,0 +1,15 @@
+use bson::Uuid;
+use serde::Deserialize;
+
+///
+/// Recipients of the notification that did not confirm it yet
+///
+#[derive(Default, Deserialize)]
+pub struct PendingRecipientsFindEntity {
+    pub count: i64,
+
+    ///
+    /// Page of pending recipients sorted by their ids
+    ///
+    pub user_ids: Vec<Uuid>,
+}
@@ -0,0 +1,29 @@
+use bson::{DateTime, Uuid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct PreferencesEntity {
+    pub _id: Uuid,
+    pub muted_producers: Vec<Uuid>,
+    pub muted_content_types: Vec<String>,
+    pub quiet_hours: Option<QuietHoursEntity>,
+    pub updated_at: DateTime,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct QuietHoursEntity {
+    pub from_minute: u32,
+    pub to_minute: u32,
+    pub utc_offset_minutes: i32,
+
+    ///
+    /// Period in UTC, used to find users in their quiet hours
+    ///
+    pub utc_from_minute: u32,
+    pub utc_to_minute: u32,
+
+    ///
+    /// Period in UTC wraps around midnight
+    ///
+    pub utc_wraps: bool,
+}
@@ -0,0 +1,50 @@
+use super::{deserialize_content, LocalizedContentEntity};
+use bson::{oid::ObjectId, Binary, DateTime, Uuid};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ProducedNotificationFindEntity {
+    pub _id: ObjectId,
+    pub producer_notification_id: i64,
+    pub created_at: DateTime,
+    #[serde(default)]
+    pub invalidate_at: Option<DateTime>,
+    #[serde(default)]
+    pub deliver_at: Option<DateTime>,
+    #[serde(default)]
+    pub retracted_at: Option<DateTime>,
+    #[serde(default)]
+    pub user_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub groups: Vec<String>,
+    #[serde(default)]
+    pub topic: Option<String>,
+
+    ///
+    /// Missing in notifications saved before priorities were introduced
+    ///
+    #[serde(default)]
+    pub priority: Option<i32>,
+    #[serde(default)]
+    pub collapse_key: Option<String>,
+    pub content_type: String,
+    #[serde(deserialize_with = "deserialize_content")]
+    pub content: Binary,
+    #[serde(default)]
+    pub localized_contents: Vec<LocalizedContentEntity>,
+    #[serde(default)]
+    pub attachment_ids: Vec<ObjectId>,
+
+    ///
+    /// Missing when nobody confirmed the notification yet
+    ///
+    #[serde(default)]
+    pub delivery: Option<DeliveryCountsFindEntity>,
+}
+
+#[derive(Default, Deserialize)]
+pub struct DeliveryCountsFindEntity {
+    pub confirmed: i64,
+    pub seen: i64,
+    pub deleted: i64,
+}
@@ -0,0 +1,14 @@
+use crate::repository::ReceiptEvent;
+use bson::{oid::ObjectId, DateTime, Uuid};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ReceiptFindEntity {
+    pub _id: ObjectId,
+    pub attempts: u32,
+    pub producer_id: Uuid,
+    pub event: ReceiptEvent,
+    pub notification_id: ObjectId,
+    pub user_id: Uuid,
+    pub timestamp: DateTime,
+}
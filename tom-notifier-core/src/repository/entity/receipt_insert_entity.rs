@@ -0,0 +1,47 @@
+use crate::repository::ReceiptEvent;
+use bson::{oid::ObjectId, DateTime, Uuid};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct ReceiptInsertEntity {
+    pub created_at: DateTime,
+    pub locked_until: Option<DateTime>,
+    pub lock_id: Option<ObjectId>,
+    pub delivered_at: Option<DateTime>,
+    pub dead_at: Option<DateTime>,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime,
+    pub last_error: Option<String>,
+
+    pub producer_id: Uuid,
+    pub event: ReceiptEvent,
+    pub notification_id: ObjectId,
+    pub user_id: Uuid,
+    pub timestamp: DateTime,
+}
+
+impl ReceiptInsertEntity {
+    pub fn new(
+        producer_id: Uuid,
+        event: ReceiptEvent,
+        notification_id: ObjectId,
+        user_id: Uuid,
+        timestamp: DateTime,
+    ) -> Self {
+        Self {
+            created_at: DateTime::now(),
+            locked_until: None,
+            lock_id: None,
+            delivered_at: None,
+            dead_at: None,
+            attempts: 0,
+            next_attempt_at: timestamp,
+            last_error: None,
+            producer_id,
+            event,
+            notification_id,
+            user_id,
+            timestamp,
+        }
+    }
+}
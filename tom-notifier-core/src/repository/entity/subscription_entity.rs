@@ -0,0 +1,9 @@
+use bson::{DateTime, Uuid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct SubscriptionEntity {
+    pub user_id: Uuid,
+    pub topic: String,
+    pub created_at: DateTime,
+}
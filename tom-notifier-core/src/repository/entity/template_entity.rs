@@ -0,0 +1,13 @@
+use bson::{DateTime, Uuid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct TemplateEntity {
+    pub producer_id: Uuid,
+    pub template_id: String,
+    pub version: i32,
+    pub created_at: DateTime,
+    pub content_type: String,
+    pub body: String,
+    pub parameters: Vec<String>,
+}
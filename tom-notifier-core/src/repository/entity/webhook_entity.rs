@@ -0,0 +1,10 @@
+use bson::{DateTime, Uuid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookEntity {
+    pub _id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub updated_at: DateTime,
+}
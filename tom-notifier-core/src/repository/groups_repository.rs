@@ -0,0 +1,61 @@
+use super::{dto::Group, Error};
+use axum::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GroupsRepository: Send + Sync {
+    ///
+    /// Inserts new group without members
+    ///
+    /// ### Errors
+    /// - [Error::InsertUniqueViolation] when group with the name already exists
+    ///
+    async fn insert(&self, name: String, created_at: OffsetDateTime) -> Result<(), Error>;
+
+    ///
+    /// Finds all groups sorted ascending by name
+    ///
+    async fn find_many(&self) -> Result<Vec<Group>, Error>;
+
+    ///
+    /// Finds ids of the group members
+    ///
+    /// ### Returns
+    /// None when group does not exist
+    ///
+    async fn find_members(&self, name: String) -> Result<Option<Vec<Uuid>>, Error>;
+
+    ///
+    /// Deletes group together with its members
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when group does not exist
+    ///
+    async fn delete(&self, name: String) -> Result<(), Error>;
+
+    ///
+    /// Adds users to the group.
+    /// Users that are already members of the group are skipped
+    ///
+    /// ### Returns
+    /// number of added members
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when group does not exist
+    ///
+    async fn insert_members(&self, name: String, user_ids: Vec<Uuid>) -> Result<u64, Error>;
+
+    ///
+    /// Removes users from the group.
+    /// Users that are not members of the group are skipped
+    ///
+    /// ### Returns
+    /// number of removed members
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when group does not exist
+    ///
+    async fn delete_members(&self, name: String, user_ids: Vec<Uuid>) -> Result<u64, Error>;
+}
@@ -0,0 +1,527 @@
+use super::{
+    dto::Group,
+    entity::{GroupFindEntity, GroupInsertEntity, GroupMemberEntity},
+    notifications_repository_impl::DUPLICATE_KEY_CODE,
+    Error, GroupsRepository,
+};
+use axum::async_trait;
+use bson::{doc, Bson, DateTime, Document};
+use futures_util::TryStreamExt;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::IndexOptions,
+    ClientSession, Collection, Database, IndexModel,
+};
+use std::collections::HashSet;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub(super) const GROUPS: &str = "groups";
+pub(super) const GROUP_MEMBERS: &str = "group_members";
+const INDEX_NAME_UNIQUE_GROUP_USER: &str = "unique_index_group_user_id";
+const INDEX_NAME_USER_ID: &str = "index_user_id";
+
+pub struct GroupsRepositoryImpl {
+    database: Database,
+}
+
+impl GroupsRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(GROUPS).await?;
+        database.create_collection(GROUP_MEMBERS).await?;
+
+        let collection = database.collection(GROUP_MEMBERS);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_UNIQUE_GROUP_USER.to_string()) {
+            Self::create_unique_group_user_index(&collection).await?;
+            tracing::debug!("created index {GROUP_MEMBERS}.{INDEX_NAME_UNIQUE_GROUP_USER}");
+        }
+        if !index_names.contains(&INDEX_NAME_USER_ID.to_string()) {
+            Self::create_user_id_index(&collection).await?;
+            tracing::debug!("created index {GROUP_MEMBERS}.{INDEX_NAME_USER_ID}");
+        }
+
+        Ok(Self { database })
+    }
+
+    async fn create_unique_group_user_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "group": 1,
+                "user_id": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_UNIQUE_GROUP_USER.to_string())
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_user_id_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "user_id": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_USER_ID.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    ///
+    /// Checks within session that group exists
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when group does not exist
+    ///
+    async fn find_existing(&self, name: &str, session: &mut ClientSession) -> Result<(), Error> {
+        self.database
+            .collection::<Document>(GROUPS)
+            .find_one(doc! { "_id": name })
+            .projection(doc! { "_id": 1 })
+            .session(session)
+            .await?
+            .ok_or(Error::NoDocumentUpdated)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl GroupsRepository for GroupsRepositoryImpl {
+    async fn insert(&self, name: String, created_at: OffsetDateTime) -> Result<(), Error> {
+        let insert_result = self
+            .database
+            .collection::<GroupInsertEntity>(GROUPS)
+            .insert_one(GroupInsertEntity {
+                _id: name,
+                created_at: DateTime::from(created_at),
+            })
+            .await;
+
+        let Err(err) = insert_result else {
+            return Ok(());
+        };
+        let is_duplicate_key = matches!(
+            *err.kind,
+            ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                if write_error.code == DUPLICATE_KEY_CODE
+        );
+
+        match is_duplicate_key {
+            true => Err(Error::InsertUniqueViolation),
+            false => Err(Error::Mongo(err)),
+        }
+    }
+
+    async fn find_many(&self) -> Result<Vec<Group>, Error> {
+        let groups = self
+            .database
+            .collection::<GroupFindEntity>(GROUPS)
+            .find(doc! {})
+            .sort(doc! { "_id": 1 })
+            .await?
+            .map_ok(Group::from)
+            .try_collect()
+            .await?;
+
+        Ok(groups)
+    }
+
+    async fn find_members(&self, name: String) -> Result<Option<Vec<Uuid>>, Error> {
+        let group = self
+            .database
+            .collection::<Document>(GROUPS)
+            .find_one(doc! { "_id": &name })
+            .projection(doc! { "_id": 1 })
+            .await?;
+        if group.is_none() {
+            return Ok(None);
+        }
+
+        let user_ids = self
+            .database
+            .collection::<GroupMemberEntity>(GROUP_MEMBERS)
+            .find(doc! { "group": name })
+            .sort(doc! { "user_id": 1 })
+            .await?
+            .map_ok(|member| Uuid::from(member.user_id))
+            .try_collect()
+            .await?;
+
+        Ok(Some(user_ids))
+    }
+
+    async fn delete(&self, name: String) -> Result<(), Error> {
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let delete_result = self
+            .database
+            .collection::<Document>(GROUPS)
+            .delete_one(doc! { "_id": &name })
+            .session(&mut session)
+            .await?;
+
+        if delete_result.deleted_count != 1 {
+            return Err(Error::NoDocumentUpdated);
+        }
+
+        self.database
+            .collection::<Document>(GROUP_MEMBERS)
+            .delete_many(doc! { "group": name })
+            .session(&mut session)
+            .await?;
+
+        session.commit_transaction().await?;
+
+        Ok(())
+    }
+
+    async fn insert_members(&self, name: String, user_ids: Vec<Uuid>) -> Result<u64, Error> {
+        // Duplicated key aborts the whole transaction so existing
+        // members have to be found before inserting
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        self.find_existing(&name, &mut session).await?;
+
+        let user_ids = user_ids
+            .into_iter()
+            .map(bson::Uuid::from)
+            .collect::<Vec<_>>();
+        let mut member_ids = self
+            .database
+            .collection::<Document>(GROUP_MEMBERS)
+            .distinct(
+                "user_id",
+                doc! {
+                    "group": &name,
+                    "user_id": { "$in": &user_ids },
+                },
+            )
+            .session(&mut session)
+            .await?
+            .into_iter()
+            .filter_map(|user_id| match user_id {
+                Bson::Binary(binary) => binary.to_uuid().ok(),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        let members = user_ids
+            .into_iter()
+            .filter(|user_id| member_ids.insert(*user_id))
+            .map(|user_id| GroupMemberEntity {
+                group: name.clone(),
+                user_id,
+            })
+            .collect::<Vec<_>>();
+        let inserted_count = members.len() as u64;
+
+        if !members.is_empty() {
+            self.database
+                .collection::<GroupMemberEntity>(GROUP_MEMBERS)
+                .insert_many(members)
+                .session(&mut session)
+                .await?;
+        }
+
+        session.commit_transaction().await?;
+
+        Ok(inserted_count)
+    }
+
+    async fn delete_members(&self, name: String, user_ids: Vec<Uuid>) -> Result<u64, Error> {
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        self.find_existing(&name, &mut session).await?;
+
+        let delete_result = self
+            .database
+            .collection::<Document>(GROUP_MEMBERS)
+            .delete_many(doc! {
+                "group": name,
+                "user_id": {
+                    "$in": user_ids
+                        .into_iter()
+                        .map(bson::Uuid::from)
+                        .collect::<Vec<_>>(),
+                },
+            })
+            .session(&mut session)
+            .await?;
+
+        session.commit_transaction().await?;
+
+        Ok(delete_result.deleted_count)
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use time::macros::datetime;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn insert_correct_created_at() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository.insert("admins".to_string(), created_at).await?;
+
+        let groups = repository.find_many().await?;
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "admins");
+        assert_eq!(groups[0].created_at, created_at);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_already_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository.insert("admins".to_string(), created_at).await?;
+        let insert_result = repository.insert("admins".to_string(), created_at).await;
+
+        assert!(matches!(insert_result, Err(Error::InsertUniqueViolation)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_sorted_by_name_asc() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository.insert("b".to_string(), created_at).await?;
+        repository.insert("c".to_string(), created_at).await?;
+        repository.insert("a".to_string(), created_at).await?;
+
+        let names = repository
+            .find_many()
+            .await?
+            .into_iter()
+            .map(|group| group.name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["a", "b", "c"]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_members_group_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let members = repository.find_members("admins".to_string()).await?;
+
+        assert!(members.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_members_skips_existing_members() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let user_id_1 = Uuid::from_u128(1);
+        let user_id_2 = Uuid::from_u128(2);
+
+        repository
+            .insert("admins".to_string(), datetime!(2024-01-01 0:00 UTC))
+            .await?;
+
+        let inserted_count = repository
+            .insert_members("admins".to_string(), vec![user_id_1])
+            .await?;
+        assert_eq!(inserted_count, 1);
+
+        let inserted_count = repository
+            .insert_members("admins".to_string(), vec![user_id_1, user_id_2, user_id_2])
+            .await?;
+        assert_eq!(inserted_count, 1);
+
+        let members = repository.find_members("admins".to_string()).await?;
+
+        assert_eq!(members, Some(vec![user_id_1, user_id_2]));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_members_group_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let insert_result = repository
+            .insert_members("admins".to_string(), vec![Uuid::from_u128(1)])
+            .await;
+
+        assert!(matches!(insert_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_members_only_selected_removed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let user_id_1 = Uuid::from_u128(1);
+        let user_id_2 = Uuid::from_u128(2);
+
+        repository
+            .insert("admins".to_string(), datetime!(2024-01-01 0:00 UTC))
+            .await?;
+        repository
+            .insert_members("admins".to_string(), vec![user_id_1, user_id_2])
+            .await?;
+
+        let deleted_count = repository
+            .delete_members("admins".to_string(), vec![user_id_1, Uuid::from_u128(3)])
+            .await?;
+
+        let members = repository.find_members("admins".to_string()).await?;
+
+        assert_eq!(deleted_count, 1);
+        assert_eq!(members, Some(vec![user_id_2]));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_members_group_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let delete_result = repository
+            .delete_members("admins".to_string(), vec![Uuid::from_u128(1)])
+            .await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_removes_members() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        repository
+            .insert("admins".to_string(), datetime!(2024-01-01 0:00 UTC))
+            .await?;
+        repository
+            .insert_members("admins".to_string(), vec![Uuid::from_u128(1)])
+            .await?;
+
+        repository.delete("admins".to_string()).await?;
+
+        let groups = repository.find_many().await?;
+        let members_count = database
+            .collection::<Document>(GROUP_MEMBERS)
+            .count_documents(doc! {})
+            .await?;
+
+        assert!(groups.is_empty());
+        assert_eq!(members_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_group_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = GroupsRepositoryImpl::new(database.clone()).await?;
+
+        let delete_result = repository.delete("admins".to_string()).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
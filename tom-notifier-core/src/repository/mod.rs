@@ -1,10 +1,46 @@
+mod attachments_repository;
+mod attachments_repository_impl;
+mod content_types_repository;
+mod content_types_repository_impl;
 mod dto;
 mod entity;
 mod error;
+mod groups_repository;
+mod groups_repository_impl;
 mod notifications_repository;
 mod notifications_repository_impl;
+mod outbox_repository;
+mod outbox_repository_impl;
+mod preferences_repository;
+mod preferences_repository_impl;
+mod receipts_repository;
+mod receipts_repository_impl;
+mod subscriptions_repository;
+mod subscriptions_repository_impl;
+mod templates_repository;
+mod templates_repository_impl;
+mod webhooks_repository;
+mod webhooks_repository_impl;
 
+pub use attachments_repository::*;
+pub use attachments_repository_impl::*;
+pub use content_types_repository::*;
+pub use content_types_repository_impl::*;
 pub use dto::*;
 pub use error::*;
+pub use groups_repository::*;
+pub use groups_repository_impl::*;
 pub use notifications_repository::*;
 pub use notifications_repository_impl::*;
+pub use outbox_repository::*;
+pub use outbox_repository_impl::*;
+pub use preferences_repository::*;
+pub use preferences_repository_impl::*;
+pub use receipts_repository::*;
+pub use receipts_repository_impl::*;
+pub use subscriptions_repository::*;
+pub use subscriptions_repository_impl::*;
+pub use templates_repository::*;
+pub use templates_repository_impl::*;
+pub use webhooks_repository::*;
+pub use webhooks_repository_impl::*;
@@ -1,5 +1,8 @@
 use super::{
-    dto::{InsertedNotification, Notification},
+    dto::{
+        DeliveryReport, InsertedNotification, Notification, NotificationsCount,
+        ProducedNotification, PurgeProducers,
+    },
     Error,
 };
 use crate::dto::input;
@@ -13,7 +16,13 @@ use uuid::Uuid;
 pub trait NotificationsRepository: Send + Sync {
     ///
     /// Inserts new notification.
-    /// If user_ids is empty, inserts broadcast notification
+    /// Notification is delivered to user_ids, members of groups and subscribers of topic.
+    /// If user_ids and groups are empty and topic is None, inserts broadcast notification.
+    /// If deliver_at is set, NEW message is not published before deliver_at.
+    /// If collapse_key is set, notifications of the producer with the same collapse_key,
+    /// user_ids, groups and topic are retracted.
+    /// Attachments are deleted together with the last notification referencing them.
+    /// Template of the notification has to be already rendered into its content
     ///
     /// ### Errors
     /// - [Error::InsertUniqueViolation]
@@ -21,15 +30,34 @@ pub trait NotificationsRepository: Send + Sync {
     ///
     async fn insert(
         &self,
-        user_ids: Vec<Uuid>,
         created_at: OffsetDateTime,
-        invalidate_at: Option<OffsetDateTime>,
         producer_id: Uuid,
-        producer_notification_id: i64,
-        content_type: String,
-        content: Vec<u8>,
+        notification: input::Notification,
     ) -> Result<InsertedNotification, Error>;
 
+    ///
+    /// Inserts many notifications of the producer at once.
+    /// Notifications are inserted in a single transaction together
+    /// with their outbox messages. Notifications are collapsed like in [Self::insert],
+    /// notifications superseded by later ones in the same batch are never published.
+    ///
+    /// ### Returns
+    /// result of every notification in the same order as notifications.
+    /// [Error::InsertUniqueViolation] is returned for notifications
+    /// whose producer_notification_id was already used by the producer
+    /// (either before or earlier in the same batch)
+    ///
+    /// ### Errors
+    /// - [Error::InsertUniqueViolation]
+    /// when conflicting notification was inserted concurrently
+    ///
+    async fn insert_many(
+        &self,
+        created_at: OffsetDateTime,
+        producer_id: Uuid,
+        notifications: Vec<input::Notification>,
+    ) -> Result<Vec<Result<InsertedNotification, Error>>, Error>;
+
     ///
     /// Updates notification invalidate_at
     ///
@@ -37,6 +65,7 @@ pub trait NotificationsRepository: Send + Sync {
     /// - [Error::NoDocumentUpdated] when
     ///     - notification does not exist
     ///     - notification was not produced by producer
+    ///     - notification has been retracted
     ///
     async fn update_invalidate_at(
         &self,
@@ -45,6 +74,24 @@ pub trait NotificationsRepository: Send + Sync {
         invalidate_at: Option<OffsetDateTime>,
     ) -> Result<(), Error>;
 
+    ///
+    /// Replaces notification content together with all of its localized variants
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when
+    ///     - notification does not exist
+    ///     - notification was not produced by producer
+    ///     - notification has been retracted
+    ///
+    async fn update_content(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        content_type: String,
+        content: Vec<u8>,
+        localized_contents: Vec<input::LocalizedContent>,
+    ) -> Result<(), Error>;
+
     ///
     /// Inserts new confirmation for the notification.
     ///
@@ -53,6 +100,7 @@ pub trait NotificationsRepository: Send + Sync {
     ///     - notification already has user's confirmation
     ///     - notification does not belong to the user and it's not a broadcast notification
     ///     - notification was invalidated
+    ///     - notification has been retracted
     ///
     async fn insert_confirmation(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error>;
 
@@ -71,6 +119,7 @@ pub trait NotificationsRepository: Send + Sync {
     ///     - notification does not belong to the user and it's not a brodcast notification
     ///     - user didn't confirm receiving notification
     ///     - notification has property deleted = true
+    ///     - notification has been retracted
     ///
     async fn update_confirmation_seen(
         &self,
@@ -79,6 +128,21 @@ pub trait NotificationsRepository: Send + Sync {
         seen: bool,
     ) -> Result<(), Error>;
 
+    ///
+    /// Updates confirmation seen value of all delivered notifications
+    /// selected by the user. Confirmations that already have requested
+    /// seen value are not updated.
+    ///
+    /// ### Returns
+    /// number of updated notifications
+    ///
+    async fn update_many_confirmations_seen(
+        &self,
+        user_id: Uuid,
+        selection: input::NotificationsSelection,
+        seen: bool,
+    ) -> Result<u64, Error>;
+
     ///
     /// Marks notification as deleted
     ///
@@ -88,9 +152,79 @@ pub trait NotificationsRepository: Send + Sync {
     ///     - notification does not belong to the user
     ///     - user didn't confirm receiving notification
     ///     - notification has property deleted = true
+    ///     - notification has been retracted
     ///
     async fn delete(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error>;
 
+    ///
+    /// Marks all delivered notifications selected by the user as deleted
+    ///
+    /// ### Returns
+    /// number of deleted notifications
+    ///
+    async fn delete_many(
+        &self,
+        user_id: Uuid,
+        selection: input::NotificationsSelection,
+    ) -> Result<u64, Error>;
+
+    ///
+    /// Marks notification as retracted for all of its recipients
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when
+    ///     - notification does not exist
+    ///     - notification was not produced by producer
+    ///     - notification has already been retracted
+    ///
+    async fn retract(&self, id: ObjectId, producer_id: Uuid) -> Result<(), Error>;
+
+    ///
+    /// Finds delivery report of the notification of the producer:
+    /// numbers of confirmed and pending recipients with a page of confirmations
+    /// sorted by delivery date and a page of recipients that did not confirm
+    /// it yet sorted by their ids. Pending recipients are resolved from user_ids,
+    /// current members of groups and current subscribers of topic
+    ///
+    /// ### Returns
+    /// None when notification does not exist or was not produced by producer
+    ///
+    async fn find_delivery_report(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        pagination: input::DeliveryReportPagination,
+    ) -> Result<Option<DeliveryReport>, Error>;
+
+    ///
+    /// Finds notifications of the producer, including retracted ones,
+    /// with numbers of recipients that confirmed, have seen and deleted them.
+    /// Notifications are sorted by creation date in order specified
+    /// by filters (descending by default).
+    ///
+    /// When pagination contains cursor only notifications
+    /// placed after the cursor are returned
+    ///
+    async fn find_many_produced(
+        &self,
+        producer_id: Uuid,
+        pagination: input::Pagination,
+        filters: input::ProducedNotificationFilters,
+    ) -> Result<Vec<ProducedNotification>, Error>;
+
+    ///
+    /// Finds notification of the producer by producer_notification_id
+    /// with numbers of recipients that confirmed, have seen and deleted it
+    ///
+    /// ### Returns
+    /// None when producer has not created notification with producer_notification_id
+    ///
+    async fn find_produced(
+        &self,
+        producer_id: Uuid,
+        producer_notification_id: i64,
+    ) -> Result<Option<ProducedNotification>, Error>;
+
     ///
     /// Finds one delivered notification notification
     ///
@@ -102,7 +236,12 @@ pub trait NotificationsRepository: Send + Sync {
 
     ///
     /// Finds notifications that were already delivered to the user.
-    /// Notifications are sorted descending by creation date.
+    /// Notifications are sorted by creation date in order specified
+    /// by filters (descending by default).
+    /// Muted notifications and notifications of unsubscribed topics are skipped.
+    ///
+    /// When pagination contains cursor only notifications
+    /// placed after the cursor are returned
     ///
     async fn find_many_delivered(
         &self,
@@ -113,7 +252,77 @@ pub trait NotificationsRepository: Send + Sync {
 
     ///
     /// Finds all notifications that were not received by the user.
-    /// Notifications are sorted ascending by creation date.
+    /// Notifications are sorted descending by priority, then ascending by creation date.
+    /// Notifications of producers and content types muted by the user are skipped
     ///
     async fn find_many_undelivered(&self, user_id: Uuid) -> Result<Vec<Notification>, Error>;
+
+    ///
+    /// Counts notifications that were not received by the user
+    /// and notifications that were delivered to the user
+    /// (all of them and only unseen ones).
+    /// Notifications muted by the user are not counted
+    ///
+    async fn count(&self, user_id: Uuid) -> Result<NotificationsCount, Error>;
+
+    ///
+    /// Permanently deletes at most `limit` notifications of `producers`
+    /// created before `created_before` regardless of their state
+    ///
+    /// ### Returns
+    /// number of deleted notifications
+    ///
+    async fn purge_created_before(
+        &self,
+        created_before: OffsetDateTime,
+        producers: PurgeProducers,
+        limit: u32,
+    ) -> Result<u64, Error>;
+
+    ///
+    /// Permanently deletes at most `limit` notifications
+    /// whose invalidate_at passed before `invalidated_before`.
+    /// Notifications delivered to any user who has not deleted them
+    /// are kept, because they are still listed to the user
+    ///
+    /// ### Returns
+    /// number of deleted notifications
+    ///
+    async fn purge_invalidated_before(
+        &self,
+        invalidated_before: OffsetDateTime,
+        limit: u32,
+    ) -> Result<u64, Error>;
+
+    ///
+    /// Permanently deletes at most `limit` notifications that are not visible
+    /// to anyone anymore:
+    /// - notifications retracted before `deleted_before`
+    /// - (uni/multi)cast notifications created before `deleted_before`
+    ///   that were deleted by all of their recipients
+    ///
+    /// Broadcast notifications deleted by users are kept, because
+    /// removing them would deliver them again to those users
+    ///
+    /// ### Returns
+    /// number of deleted notifications
+    ///
+    async fn purge_deleted_before(
+        &self,
+        deleted_before: OffsetDateTime,
+        limit: u32,
+    ) -> Result<u64, Error>;
+
+    ///
+    /// Permanently deletes at most `limit` attachments uploaded before
+    /// `uploaded_before` that are not referenced by any notification
+    ///
+    /// ### Returns
+    /// number of deleted attachments
+    ///
+    async fn purge_orphaned_attachments(
+        &self,
+        uploaded_before: OffsetDateTime,
+        limit: u32,
+    ) -> Result<u64, Error>;
 }
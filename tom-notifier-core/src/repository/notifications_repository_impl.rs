@@ -1,33 +1,89 @@
 use super::{
-    dto::{InsertedNotification, Notification},
-    entity::NotificationFindEntity,
+    attachments_repository_impl::{ATTACHMENTS_CHUNKS, ATTACHMENTS_FILES},
+    dto::{
+        DeliveryReport, InsertedNotification, Notification, NotificationsCount,
+        ProducedNotification, PurgeProducers, ReceiptEvent, RecipientConfirmation,
+    },
+    entity::{
+        content_binary, ConfirmationFindEntity, ConfirmationInsertEntity,
+        ConfirmationProducerFindEntity, LocalizedContentEntity, NotificationConfirmedFindEntity,
+        NotificationFindEntity, NotificationIdFindEntity, NotificationUserIdsFindEntity,
+        NotificationsCountFindEntity, OutboxMessageInsertEntity, PendingRecipientsFindEntity,
+        ProducedNotificationFindEntity, ReceiptInsertEntity,
+    },
+    groups_repository_impl::GROUP_MEMBERS,
+    outbox_repository_impl::OUTBOX,
+    preferences_repository_impl::PREFERENCES,
+    receipts_repository_impl::RECEIPTS,
+    subscriptions_repository_impl::SUBSCRIPTIONS,
+    webhooks_repository_impl::WEBHOOKS,
     Error, NotificationsRepository,
 };
-use crate::{dto::input, repository::entity::NotificationInsertEntity};
+use crate::{
+    dto::input::{self, NotificationPriority},
+    repository::entity::NotificationInsertEntity,
+};
 use axum::async_trait;
-use bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Bson, DateTime, Document};
+use bson::{doc, oid::ObjectId, Bson, DateTime, Document};
 use futures_util::TryStreamExt;
 use mongodb::{
     error::{ErrorKind, WriteFailure},
     options::IndexOptions,
-    Collection, Database, IndexModel,
+    ClientSession, Collection, Database, IndexModel,
 };
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 const NOTIFICATIONS: &str = "notifications";
+const CONFIRMATIONS: &str = "confirmations";
 const INDEX_NAME_UNIQUE_PRODUCER_NOTIFICATION: &str =
     "unique_index_producer_id_producer_notification_id";
-const INDEX_NAME_CONFIRMATION_USER_ID: &str = "index_confirmation_user_id";
+const INDEX_NAME_UNIQUE_NOTIFICATION_USER: &str = "unique_index_notification_id_user_id";
+const INDEX_NAME_USER_ID_CREATED_AT: &str = "index_user_id_notification_created_at_id";
+const INDEX_NAME_USER_ID_PRODUCER_ID: &str = "index_user_id_notification_producer_id_created_at";
+const INDEX_NAME_USER_ID_DELIVERED_AT: &str = "index_user_id_notification_delivered_at";
+const INDEX_NAME_NOTIFICATION_ID_DELIVERED_AT: &str =
+    "index_notification_id_notification_delivered_at_id";
+const INDEX_NAME_PRODUCER_ID_COLLAPSE_KEY: &str = "index_producer_id_collapse_key";
+const INDEX_NAME_PRODUCER_ID_CREATED_AT: &str = "index_producer_id_created_at_id";
+const INDEX_NAME_ATTACHMENT_IDS: &str = "index_attachment_ids";
+
+///
+/// Maximum number of notifications selected by the user
+/// that are updated or deleted within a single transaction
+///
+const SELECTION_BATCH_SIZE: i64 = 1000;
+
+///
+/// Indexes of confirmations that used to be embedded in notifications
+///
+const OBSOLETE_INDEX_NAMES: [&str; 5] = [
+    "index_confirmation_user_id",
+    "index_confirmation_user_id_created_at_id",
+    "index_confirmation_user_id_producer_id_created_at",
+    "index_confirmation_user_id_content_type_created_at",
+    "index_confirmation_user_id_confirmation_delivered_at",
+];
+
+pub(super) const DUPLICATE_KEY_CODE: i32 = 11000;
 
 pub struct NotificationsRepositoryImpl {
     database: Database,
+    compression_threshold: Option<usize>,
 }
 
 impl NotificationsRepositoryImpl {
-    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+    ///
+    /// Content of at least `compression_threshold` bytes is stored compressed,
+    /// `None` disables the compression
+    ///
+    pub async fn new(
+        database: Database,
+        compression_threshold: Option<usize>,
+    ) -> Result<Self, mongodb::error::Error> {
         database.create_collection(NOTIFICATIONS).await?;
+        database.create_collection(CONFIRMATIONS).await?;
 
         let collection = database.collection(NOTIFICATIONS);
         let index_names = collection.list_index_names().await?;
@@ -38,12 +94,92 @@ impl NotificationsRepositoryImpl {
                 "created index {NOTIFICATIONS}.{INDEX_NAME_UNIQUE_PRODUCER_NOTIFICATION}"
             );
         }
-        if !index_names.contains(&INDEX_NAME_CONFIRMATION_USER_ID.to_string()) {
-            Self::create_confirmations_user_id_index(&collection).await?;
-            tracing::debug!("created index {NOTIFICATIONS}.{INDEX_NAME_CONFIRMATION_USER_ID}");
+        if !index_names.contains(&INDEX_NAME_PRODUCER_ID_COLLAPSE_KEY.to_string()) {
+            Self::create_producer_collapse_key_index(&collection).await?;
+            tracing::debug!("created index {NOTIFICATIONS}.{INDEX_NAME_PRODUCER_ID_COLLAPSE_KEY}");
+        }
+        if !index_names.contains(&INDEX_NAME_PRODUCER_ID_CREATED_AT.to_string()) {
+            Self::create_produced_index(&collection).await?;
+            tracing::debug!("created index {NOTIFICATIONS}.{INDEX_NAME_PRODUCER_ID_CREATED_AT}");
+        }
+        if !index_names.contains(&INDEX_NAME_ATTACHMENT_IDS.to_string()) {
+            Self::create_attachment_ids_index(&collection).await?;
+            tracing::debug!("created index {NOTIFICATIONS}.{INDEX_NAME_ATTACHMENT_IDS}");
+        }
+        for index_name in OBSOLETE_INDEX_NAMES {
+            if index_names.contains(&index_name.to_string()) {
+                collection.drop_index(index_name).await?;
+                tracing::debug!("dropped index {NOTIFICATIONS}.{index_name}");
+            }
+        }
+
+        let collection = database.collection(CONFIRMATIONS);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_UNIQUE_NOTIFICATION_USER.to_string()) {
+            Self::create_unique_notification_user_index(&collection).await?;
+            tracing::debug!("created index {CONFIRMATIONS}.{INDEX_NAME_UNIQUE_NOTIFICATION_USER}");
+        }
+        if !index_names.contains(&INDEX_NAME_USER_ID_CREATED_AT.to_string()) {
+            Self::create_delivered_index(
+                &collection,
+                INDEX_NAME_USER_ID_CREATED_AT,
+                doc! {
+                    "user_id": 1,
+                    "notification_created_at": -1,
+                    "notification_id": -1,
+                },
+            )
+            .await?;
+            tracing::debug!("created index {CONFIRMATIONS}.{INDEX_NAME_USER_ID_CREATED_AT}");
+        }
+        if !index_names.contains(&INDEX_NAME_USER_ID_PRODUCER_ID.to_string()) {
+            Self::create_delivered_index(
+                &collection,
+                INDEX_NAME_USER_ID_PRODUCER_ID,
+                doc! {
+                    "user_id": 1,
+                    "notification_producer_id": 1,
+                    "notification_created_at": -1,
+                },
+            )
+            .await?;
+            tracing::debug!("created index {CONFIRMATIONS}.{INDEX_NAME_USER_ID_PRODUCER_ID}");
+        }
+        if !index_names.contains(&INDEX_NAME_USER_ID_DELIVERED_AT.to_string()) {
+            Self::create_delivered_index(
+                &collection,
+                INDEX_NAME_USER_ID_DELIVERED_AT,
+                doc! {
+                    "user_id": 1,
+                    "notification_delivered_at": -1,
+                },
+            )
+            .await?;
+            tracing::debug!("created index {CONFIRMATIONS}.{INDEX_NAME_USER_ID_DELIVERED_AT}");
+        }
+        if !index_names.contains(&INDEX_NAME_NOTIFICATION_ID_DELIVERED_AT.to_string()) {
+            Self::create_delivered_index(
+                &collection,
+                INDEX_NAME_NOTIFICATION_ID_DELIVERED_AT,
+                doc! {
+                    "notification_id": 1,
+                    "notification_delivered_at": 1,
+                    "_id": 1,
+                },
+            )
+            .await?;
+            tracing::debug!(
+                "created index {CONFIRMATIONS}.{INDEX_NAME_NOTIFICATION_ID_DELIVERED_AT}"
+            );
         }
 
-        Ok(Self { database })
+        Self::migrate_embedded_confirmations(&database).await?;
+
+        Ok(Self {
+            database,
+            compression_threshold,
+        })
     }
 
     async fn create_unique_producer_notification_index(
@@ -67,16 +203,24 @@ impl NotificationsRepositoryImpl {
         Ok(())
     }
 
-    async fn create_confirmations_user_id_index(
+    ///
+    /// Creates index supporting lookup of notifications superseded by collapse key.
+    /// Only notifications with collapse key are indexed
+    ///
+    async fn create_producer_collapse_key_index(
         collection: &Collection<Document>,
     ) -> Result<(), mongodb::error::Error> {
         let index = IndexModel::builder()
             .keys(doc! {
-                "confirmations.user_id": 1,
+                "producer_id": 1,
+                "collapse_key": 1,
             })
             .options(
                 IndexOptions::builder()
-                    .name(INDEX_NAME_CONFIRMATION_USER_ID.to_string())
+                    .name(INDEX_NAME_PRODUCER_ID_COLLAPSE_KEY.to_string())
+                    .partial_filter_expression(doc! {
+                        "collapse_key": { "$type": "string" },
+                    })
                     .build(),
             )
             .build();
@@ -85,1300 +229,2633 @@ impl NotificationsRepositoryImpl {
 
         Ok(())
     }
-}
 
-#[async_trait]
-impl NotificationsRepository for NotificationsRepositoryImpl {
-    async fn insert(
-        &self,
-        user_ids: Vec<Uuid>,
-        created_at: OffsetDateTime,
-        invalidate_at: Option<OffsetDateTime>,
-        producer_id: Uuid,
-        producer_notification_id: i64,
-        content_type: String,
-        content: Vec<u8>,
-    ) -> Result<InsertedNotification, Error> {
-        let insert_entity = NotificationInsertEntity {
-            created_at: DateTime::from(created_at),
-            invalidate_at: invalidate_at.map(DateTime::from),
-            user_ids: user_ids
-                .iter()
-                .map(|user_id| bson::Uuid::from(*user_id))
-                .collect(),
-            producer_id: producer_id.into(),
-            producer_notification_id,
-            content_type,
-            content: Binary {
-                subtype: BinarySubtype::Generic,
-                bytes: content,
-            },
-            confirmations: [],
-        };
+    ///
+    /// Creates index supporting listing of notifications of the producer
+    ///
+    async fn create_produced_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "producer_id": 1,
+                "created_at": -1,
+                "_id": -1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_PRODUCER_ID_CREATED_AT.to_string())
+                    .build(),
+            )
+            .build();
 
-        let insert_result = self
-            .database
-            .collection::<NotificationInsertEntity>(NOTIFICATIONS)
-            .insert_one(&insert_entity)
-            .await
-            .map_err(|err| {
-                let ErrorKind::Write(ref write_failure) = *err.kind else {
-                    return Error::Mongo(err);
-                };
+        collection.create_index(index).await?;
 
-                let WriteFailure::WriteError(write_error) = write_failure else {
-                    return Error::Mongo(err);
-                };
+        Ok(())
+    }
 
-                const DUPLICATE_KEY_CODE: i32 = 11000;
-                match write_error.code == DUPLICATE_KEY_CODE {
-                    true => Error::InsertUniqueViolation,
-                    false => Error::Mongo(err),
-                }
-            })?;
+    ///
+    /// Creates index supporting lookup of notifications referencing attachments
+    ///
+    async fn create_attachment_ids_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "attachment_ids": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_ATTACHMENT_IDS.to_string())
+                    .build(),
+            )
+            .build();
 
-        let Bson::ObjectId(id) = insert_result.inserted_id else {
-            tracing::error!("invalid type of inserted '_id'");
-            return Err(Error::Mongo(
-                ErrorKind::Custom(Arc::new("invalid type of inserted '_id'")).into(),
-            ));
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_unique_notification_user_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "notification_id": 1,
+                "user_id": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_UNIQUE_NOTIFICATION_USER.to_string())
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    ///
+    /// Creates index supporting filters and ordering of delivered notifications
+    ///
+    async fn create_delivered_index(
+        collection: &Collection<Document>,
+        name: &str,
+        keys: Document,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(keys)
+            .options(IndexOptions::builder().name(name.to_string()).build())
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    ///
+    /// Moves confirmations embedded in notifications to their own collection,
+    /// so broadcast notifications don't grow with every recipient.
+    /// Migration can be interrupted and run again
+    ///
+    async fn migrate_embedded_confirmations(
+        database: &Database,
+    ) -> Result<(), mongodb::error::Error> {
+        let collection = database.collection::<Document>(NOTIFICATIONS);
+
+        let mut notifications = collection
+            .find(doc! {
+                "confirmations": { "$exists": true },
+            })
+            .projection(doc! { "confirmations": 1 })
+            .await?;
+
+        let mut migrated_count = 0;
+        while let Some(notification) = notifications.try_next().await? {
+            let (Some(id), Some(confirmations)) =
+                (notification.get("_id"), notification.get("confirmations"))
+            else {
+                continue;
+            };
+
+            // Only confirmations that were moved are unset, confirmations
+            // pushed by older instances in the meantime are moved by the next run
+            let migrated_filter = doc! {
+                "_id": id,
+                "confirmations": confirmations,
+            };
+
+            // Confirmations that were already moved are kept untouched
+            let pipeline = [
+                doc! { "$match": migrated_filter.clone() },
+                doc! { "$unwind": "$confirmations" },
+                doc! {
+                    "$project": {
+                        "_id": 0,
+                        "notification_id": "$_id",
+                        "user_id": "$confirmations.user_id",
+                        "notification_created_at": "$created_at",
+                        "notification_producer_id": "$producer_id",
+                        "notification_delivered_at": "$confirmations.notification_delivered_at",
+                        "notification_seen": "$confirmations.notification_seen",
+                        "notification_deleted": "$confirmations.notification_deleted",
+                    }
+                },
+                doc! {
+                    "$merge": {
+                        "into": CONFIRMATIONS,
+                        "on": ["notification_id", "user_id"],
+                        "whenMatched": "keepExisting",
+                        "whenNotMatched": "insert",
+                    }
+                },
+            ];
+            collection.aggregate(pipeline).await?;
+
+            let update_result = collection
+                .update_one(
+                    migrated_filter,
+                    doc! {
+                        "$unset": {
+                            "confirmations": "",
+                        }
+                    },
+                )
+                .await?;
+            migrated_count += update_result.modified_count;
+        }
+
+        if migrated_count > 0 {
+            tracing::info!(
+                count = migrated_count,
+                "moved embedded confirmations to {CONFIRMATIONS}"
+            );
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Maps duplicate key error to [Error::InsertUniqueViolation]
+    ///
+    fn map_insert_error(err: mongodb::error::Error) -> Error {
+        let is_duplicate_key = match *err.kind {
+            ErrorKind::Write(WriteFailure::WriteError(ref write_error)) => {
+                write_error.code == DUPLICATE_KEY_CODE
+            }
+            ErrorKind::InsertMany(ref insert_many_error) => insert_many_error
+                .write_errors
+                .iter()
+                .flatten()
+                .any(|write_error| write_error.code == DUPLICATE_KEY_CODE),
+            _ => false,
         };
 
-        Ok(InsertedNotification {
-            id,
-            created_at,
-            invalidate_at,
-            user_ids,
-            producer_id,
-            producer_notification_id,
-            content_type: insert_entity.content_type,
-            content: insert_entity.content.bytes,
-        })
+        match is_duplicate_key {
+            true => Error::InsertUniqueViolation,
+            false => Error::Mongo(err),
+        }
     }
 
-    async fn update_invalidate_at(
+    ///
+    /// Checks within session that notification exists and is not retracted
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when notification does not exist or is retracted
+    ///
+    async fn find_not_retracted(
         &self,
         id: ObjectId,
-        producer_id: Uuid,
-        invalidate_at: Option<OffsetDateTime>,
+        session: &mut ClientSession,
     ) -> Result<(), Error> {
-        let producer_id = bson::Uuid::from(producer_id);
-        let invalidate_at = invalidate_at.map(DateTime::from);
+        self.database
+            .collection::<NotificationIdFindEntity>(NOTIFICATIONS)
+            .find_one(doc! {
+                "_id": id,
+                "retracted_at": None as Option<DateTime>,
+            })
+            .projection(doc! { "_id": 1 })
+            .session(session)
+            .await?
+            .ok_or(Error::NoDocumentUpdated)?;
+
+        Ok(())
+    }
+
+    ///
+    /// Finds ids of at most SELECTION_BATCH_SIZE delivered notifications
+    /// selected by the user within session
+    ///
+    async fn find_selected_ids(
+        &self,
+        user_id: bson::Uuid,
+        selection: &input::NotificationsSelection,
+        session: &mut ClientSession,
+    ) -> Result<Vec<ObjectId>, mongodb::error::Error> {
+        let filters = selection.filters.clone().unwrap_or_default();
+        let mut filter = Self::delivered_filter(user_id, &filters);
+        if let Some(ids) = &selection.ids {
+            filter.insert("notification_id", doc! { "$in": ids });
+        }
+        let audience = self.audience_filter(user_id).await?;
+        let unmuted = self.unmuted_filter(user_id).await?;
+
+        let mut pipeline = vec![doc! { "$match": filter }];
+        pipeline.extend(Self::delivered_notification_lookup(
+            audience,
+            unmuted,
+            filters.content_type,
+            doc! { "_id": 1 },
+        ));
+        pipeline.push(doc! { "$limit": SELECTION_BATCH_SIZE });
+        pipeline.push(doc! { "$project": { "_id": "$notification_id" } });
+
+        self.database
+            .collection::<Document>(CONFIRMATIONS)
+            .aggregate(pipeline)
+            .session(&mut *session)
+            .with_type::<NotificationIdFindEntity>()
+            .await?
+            .stream(session)
+            .map_ok(|entity| entity.id)
+            .try_collect()
+            .await
+    }
+
+    ///
+    /// Updates seen of a single batch of notifications selected by the user
+    /// in its own transaction and saves one UPDATED message for all of them
+    ///
+    /// ### Returns
+    ///
+    /// number of selected notifications and number of updated confirmations
+    ///
+    async fn update_confirmations_seen_batch(
+        &self,
+        user_id: bson::Uuid,
+        selection: &input::NotificationsSelection,
+        seen: bool,
+    ) -> Result<(usize, u64), Error> {
+        let now = DateTime::from(OffsetDateTime::now_utc());
+
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let ids = self
+            .find_selected_ids(user_id, selection, &mut session)
+            .await?;
+        if ids.is_empty() {
+            return Ok((0, 0));
+        }
 
         let update_result = self
             .database
-            .collection::<Document>(NOTIFICATIONS)
-            .update_one(
+            .collection::<Document>(CONFIRMATIONS)
+            .update_many(
                 doc! {
-                    "_id": id,
-                    "producer_id": producer_id,
+                    "notification_id": { "$in": &ids },
+                    "user_id": user_id,
+                    "notification_deleted": false,
                 },
                 doc! {
                     "$set": {
-                        "invalidate_at": invalidate_at,
+                        "notification_seen": seen,
                     }
                 },
             )
+            .session(&mut session)
             .await?;
 
-        match update_result.matched_count == 1 {
-            true => Ok(()),
-            false => Err(Error::NoDocumentUpdated),
-        }
+        self.insert_receipts(&ids, user_id, Self::seen_event(seen), now, &mut session)
+            .await?;
+
+        let selected_count = ids.len();
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_one(OutboxMessageInsertEntity::updated_many_seen(
+                ids, user_id, seen, now,
+            ))
+            .session(&mut session)
+            .await?;
+
+        session.commit_transaction().await?;
+
+        Ok((selected_count, update_result.modified_count))
     }
 
-    async fn insert_confirmation(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error> {
-        let user_id = bson::Uuid::from(user_id);
+    ///
+    /// Deletes a single batch of notifications selected by the user
+    /// in its own transaction
+    ///
+    /// ### Returns
+    ///
+    /// number of selected notifications and number of deleted confirmations
+    ///
+    async fn delete_batch(
+        &self,
+        user_id: bson::Uuid,
+        selection: &input::NotificationsSelection,
+    ) -> Result<(usize, u64), Error> {
         let now = DateTime::from(OffsetDateTime::now_utc());
 
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let ids = self
+            .find_selected_ids(user_id, selection, &mut session)
+            .await?;
+        if ids.is_empty() {
+            return Ok((0, 0));
+        }
+
         let update_result = self
             .database
-            .collection::<Document>(NOTIFICATIONS)
-            .update_one(
+            .collection::<Document>(CONFIRMATIONS)
+            .update_many(
                 doc! {
-                    "_id": id,
-                    "$and": [
-                        {
-                            "$or": [
-                                { "user_ids": user_id },
-                                { "user_ids": { "$size": 0 } },
-                            ]
-                        },
-                        {
-                            "$or": [
-                                { "invalidate_at": None as Option<DateTime> },
-                                { "invalidate_at": { "$gt": now } },
-                            ]
-                        },
-                    ],
-                    "confirmations": {
-                        "$not": {
-                            "$elemMatch": {
-                                "user_id": user_id,
-                            }
-                        }
-                    }
+                    "notification_id": { "$in": &ids },
+                    "user_id": user_id,
+                    "notification_deleted": false,
                 },
                 doc! {
-                    "$push": {
-                        "confirmations": {
-                            "user_id": user_id,
-                            "notification_delivered_at": now,
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
+                    "$set": {
+                        "notification_deleted": true,
                     }
                 },
             )
+            .session(&mut session)
             .await?;
 
-        match update_result.modified_count > 0 {
-            true => Ok(()),
-            false => Err(Error::NoDocumentUpdated),
-        }
-    }
+        let outbox_messages = ids
+            .iter()
+            .map(|&id| OutboxMessageInsertEntity::deleted(id, vec![user_id], vec![], None, now));
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_many(outbox_messages)
+            .session(&mut session)
+            .await?;
 
-    async fn insert_many_confirmations(
+        self.insert_receipts(&ids, user_id, ReceiptEvent::Deleted, now, &mut session)
+            .await?;
+
+        session.commit_transaction().await?;
+
+        Ok((ids.len(), update_result.modified_count))
+    }
+
+    ///
+    /// Saves receipts of the event of the user's confirmations of notifications
+    /// within session. Receipts are saved only for producers that registered webhook
+    ///
+    async fn insert_receipts(
         &self,
         ids: &[ObjectId],
-        user_id: Uuid,
-    ) -> Result<(), Error> {
-        let user_id = bson::Uuid::from(user_id);
-        let now = DateTime::from(OffsetDateTime::now_utc());
+        user_id: bson::Uuid,
+        event: ReceiptEvent,
+        timestamp: DateTime,
+        session: &mut ClientSession,
+    ) -> Result<(), mongodb::error::Error> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let confirmations = self
+            .database
+            .collection::<ConfirmationProducerFindEntity>(CONFIRMATIONS)
+            .find(doc! {
+                "notification_id": { "$in": ids },
+                "user_id": user_id,
+            })
+            .projection(doc! {
+                "_id": 0,
+                "notification_id": 1,
+                "notification_producer_id": 1,
+            })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let producer_ids = confirmations
+            .iter()
+            .map(|confirmation| confirmation.notification_producer_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let webhook_producer_ids = self
+            .database
+            .collection::<Document>(WEBHOOKS)
+            .distinct("_id", doc! { "_id": { "$in": producer_ids } })
+            .session(&mut *session)
+            .await?
+            .into_iter()
+            .filter_map(|producer_id| bson::from_bson::<bson::Uuid>(producer_id).ok())
+            .collect::<HashSet<_>>();
+
+        let receipts = confirmations
+            .into_iter()
+            .filter(|confirmation| {
+                webhook_producer_ids.contains(&confirmation.notification_producer_id)
+            })
+            .map(|confirmation| {
+                ReceiptInsertEntity::new(
+                    confirmation.notification_producer_id,
+                    event,
+                    confirmation.notification_id,
+                    user_id,
+                    timestamp,
+                )
+            })
+            .collect::<Vec<_>>();
+        if receipts.is_empty() {
+            return Ok(());
+        }
 
         self.database
-            .collection::<Document>(NOTIFICATIONS)
-            .update_many(
-                doc! {
-                    "_id": { "$in": ids },
-                    "$and": [
-                        {
-                            "$or": [
-                                { "user_ids": user_id },
-                                { "user_ids": { "$size": 0 } },
-                            ]
-                        },
-                        {
-                            "$or": [
-                                { "invalidate_at": None as Option<DateTime> },
-                                { "invalidate_at": { "$gt": now } },
-                            ]
-                        },
-                    ],
-                    "confirmations": {
-                        "$not": {
-                            "$elemMatch": {
-                                "user_id": user_id,
-                            }
-                        }
-                    }
-                },
-                doc! {
-                    "$push": {
-                        "confirmations": {
-                            "user_id": user_id,
-                            "notification_delivered_at": now,
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    }
-                },
-            )
+            .collection::<ReceiptInsertEntity>(RECEIPTS)
+            .insert_many(receipts)
+            .session(&mut *session)
             .await?;
 
         Ok(())
     }
 
-    async fn update_confirmation_seen(
+    fn seen_event(seen: bool) -> ReceiptEvent {
+        match seen {
+            true => ReceiptEvent::Seen,
+            false => ReceiptEvent::Unseen,
+        }
+    }
+
+    ///
+    /// Finds ids of at most `limit` notifications matching filter
+    ///
+    async fn find_purged_ids(
         &self,
-        id: ObjectId,
-        user_id: Uuid,
-        seen: bool,
-    ) -> Result<(), Error> {
-        let user_id = bson::Uuid::from(user_id);
+        filter: Document,
+        limit: u32,
+    ) -> Result<Vec<ObjectId>, mongodb::error::Error> {
+        self.database
+            .collection::<NotificationIdFindEntity>(NOTIFICATIONS)
+            .find(filter)
+            .projection(doc! { "_id": 1 })
+            .limit(limit as i64)
+            .await?
+            .map_ok(|entity| entity.id)
+            .try_collect()
+            .await
+    }
 
-        let update_result = self
+    ///
+    /// Permanently deletes notifications, their confirmations,
+    /// their messages that have not been published yet
+    /// and their receipts that have not been sent yet
+    ///
+    async fn purge(&self, ids: Vec<ObjectId>) -> Result<u64, Error> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let attachment_ids = self
+            .find_attachment_ids(doc! { "_id": { "$in": &ids } })
+            .await?;
+
+        // Otherwise messages and receipts of deleted notifications
+        // could be published after the notifications are gone
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let delete_result = self
             .database
             .collection::<Document>(NOTIFICATIONS)
-            .update_one(
-                doc! {
-                    "_id": id,
-                    "confirmations": {
-                        "$elemMatch": {
-                            "user_id": user_id,
-                            "notification_deleted": false,
-                        }
-                    }
-                },
-                doc! {
-                    "$set": {
-                        "confirmations.$.notification_seen": seen,
-                    }
-                },
-            )
+            .delete_many(doc! { "_id": { "$in": &ids } })
+            .session(&mut session)
             .await?;
 
-        // matched_count instead of modified_count becaouse replacing
-        // false with false doesn't count as modification
-        match update_result.matched_count == 1 {
-            true => Ok(()),
-            false => Err(Error::NoDocumentUpdated),
-        }
-    }
+        self.database
+            .collection::<Document>(CONFIRMATIONS)
+            .delete_many(doc! { "notification_id": { "$in": &ids } })
+            .session(&mut session)
+            .await?;
 
-    async fn delete(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error> {
-        let user_id = bson::Uuid::from(user_id);
+        // UPDATED message of seen is kept while it changes any notification still existing
+        self.database
+            .collection::<Document>(OUTBOX)
+            .delete_many(doc! {
+                "notification_id": { "$in": &ids },
+                "notification_ids": { "$not": { "$elemMatch": { "$nin": &ids } } },
+                "published_at": None as Option<DateTime>,
+            })
+            .session(&mut session)
+            .await?;
 
-        let update_result = self
+        // Dead letters are kept, the same as dead letters of existing notifications
+        self.database
+            .collection::<Document>(RECEIPTS)
+            .delete_many(doc! {
+                "notification_id": { "$in": &ids },
+                "delivered_at": None as Option<DateTime>,
+                "dead_at": None as Option<DateTime>,
+            })
+            .session(&mut session)
+            .await?;
+
+        session.commit_transaction().await?;
+
+        self.purge_unreferenced_attachments(attachment_ids).await?;
+
+        Ok(delete_result.deleted_count)
+    }
+
+    ///
+    /// Finds distinct ids of attachments referenced by notifications matching the filter
+    ///
+    async fn find_attachment_ids(&self, filter: Document) -> Result<Vec<ObjectId>, Error> {
+        let attachment_ids = self
             .database
             .collection::<Document>(NOTIFICATIONS)
-            .update_one(
-                doc! {
-                    "_id": id,
-                    "confirmations": {
-                        "$elemMatch": {
-                            "user_id": user_id,
-                        }
-                    }
-                },
-                doc! {
-                    "$set": {
-                        "confirmations.$.notification_deleted": true,
-                    }
-                },
-            )
-            .await?;
+            .distinct("attachment_ids", filter)
+            .await?
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect();
 
-        match update_result.modified_count == 1 {
-            true => Ok(()),
-            false => Err(Error::NoDocumentUpdated),
-        }
+        Ok(attachment_ids)
     }
 
-    async fn find_delivered(
+    ///
+    /// Permanently deletes attachments no longer referenced by any notification.
+    /// Attachments are deleted last, so when it fails only files
+    /// that are never read again are left
+    ///
+    async fn purge_unreferenced_attachments(
         &self,
-        id: ObjectId,
-        user_id: Uuid,
-    ) -> Result<Option<Notification>, Error> {
-        let user_id = bson::Uuid::from(user_id);
+        attachment_ids: Vec<ObjectId>,
+    ) -> Result<(), Error> {
+        if attachment_ids.is_empty() {
+            return Ok(());
+        }
 
-        let notification_entity = self
-            .database
-            .collection::<NotificationFindEntity>(NOTIFICATIONS)
-            .find_one(doc! {
-                "_id": id,
-                "confirmations": {
-                    "$elemMatch": {
-                        "user_id": user_id,
-                        "notification_deleted": false,
-                    }
-                }
-            })
-            .projection(doc! {
-                "_id": 1,
-                "created_at": 1,
-                "producer_id": 1,
-                "content_type": 1,
-                "content": 1,
-                "confirmations.$": 1,
-            })
-            .await?;
+        let referenced_ids = self
+            .find_attachment_ids(doc! { "attachment_ids": { "$in": &attachment_ids } })
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>();
 
-        let notification = notification_entity.map(Notification::from);
+        let unreferenced_ids = attachment_ids
+            .into_iter()
+            .filter(|id| !referenced_ids.contains(id))
+            .collect::<Vec<_>>();
+        self.delete_attachments(unreferenced_ids).await?;
 
-        Ok(notification)
+        Ok(())
     }
 
-    async fn find_many_delivered(
-        &self,
-        user_id: Uuid,
-        pagination: input::Pagination,
-        input::NotificationFilters { seen }: input::NotificationFilters,
-    ) -> Result<Vec<Notification>, Error> {
-        let user_id = bson::Uuid::from(user_id);
-        let mut confirmation_filter = doc! {
-            "user_id": user_id,
-            "notification_deleted": false,
-        };
-        if let Some(seen) = seen {
-            confirmation_filter.insert("notification_seen", seen);
+    ///
+    /// Permanently deletes attachments with their content
+    ///
+    /// ### Returns
+    /// number of deleted attachments
+    ///
+    async fn delete_attachments(&self, ids: Vec<ObjectId>) -> Result<u64, Error> {
+        if ids.is_empty() {
+            return Ok(0);
         }
 
-        let cursor = self
+        let delete_result = self
             .database
-            .collection::<NotificationFindEntity>(NOTIFICATIONS)
-            .find(doc! {
-                "confirmations": {
-                    "$elemMatch": confirmation_filter,
-                }
-            })
-            .projection(doc! {
-                "_id": 1,
-                "created_at": 1,
-                "producer_id": 1,
-                "content_type": 1,
-                "content": 1,
-                "confirmations.$": 1,
-            })
-            .sort(doc! {
-                "created_at": -1
-            })
-            .skip((pagination.page_size * pagination.page_idx) as u64)
-            .limit(pagination.page_size as i64)
+            .collection::<Document>(ATTACHMENTS_FILES)
+            .delete_many(doc! { "_id": { "$in": &ids } })
+            .await?;
+        self.database
+            .collection::<Document>(ATTACHMENTS_CHUNKS)
+            .delete_many(doc! { "files_id": { "$in": ids } })
             .await?;
 
-        let notifications = cursor.map_ok(Notification::from).try_collect().await?;
-
-        Ok(notifications)
+        Ok(delete_result.deleted_count)
     }
 
-    async fn find_many_undelivered(&self, user_id: Uuid) -> Result<Vec<Notification>, Error> {
-        let user_id = bson::Uuid::from(user_id);
-        let now = DateTime::from(OffsetDateTime::now_utc());
+    ///
+    /// Retracts within session notifications of the producer superseded by
+    /// the inserted notification. Notification is superseded when it is not retracted,
+    /// has the same collapse_key and targets the same user_ids, groups and topic.
+    /// DELETED message is saved for every superseded notification
+    ///
+    async fn retract_collapsed(
+        &self,
+        insert_entity: &NotificationInsertEntity,
+        session: &mut ClientSession,
+    ) -> Result<(), Error> {
+        let Some(collapse_key) = &insert_entity.collapse_key else {
+            return Ok(());
+        };
 
-        let notifications = self
+        let ids = self
             .database
-            .collection::<NotificationFindEntity>(NOTIFICATIONS)
+            .collection::<NotificationIdFindEntity>(NOTIFICATIONS)
             .find(doc! {
-                "$and": [
-                    {
-                        "$or": [
-                            { "user_ids": user_id },
-                            { "user_ids": { "$size": 0 } },
-                        ]
-                    },
-                    {
-                        "$or": [
-                            { "invalidate_at": { "$gt": now } },
-                            { "invalidate_at": None as Option<DateTime> },
-                        ],
-                    }
-                ],
-                "confirmations": {
-                    "$not": {
-                        "$elemMatch": {
-                            "user_id": user_id,
-                        }
-                    }
-                },
-            })
-            .projection(doc! {
-                "_id": 1,
-                "created_at": 1,
-                "producer_id": 1,
-                "content_type": 1,
-                "content": 1,
+                "producer_id": insert_entity.producer_id,
+                "collapse_key": collapse_key.as_str(),
+                "user_ids": Self::same_values_filter(&insert_entity.user_ids),
+                "groups": Self::same_values_filter(&insert_entity.groups),
+                "topic": insert_entity.topic.as_deref(),
+                "retracted_at": None as Option<DateTime>,
             })
-            .sort(doc! { "created_at": 1 })
+            .projection(doc! { "_id": 1 })
+            .session(&mut *session)
             .await?
-            .map_ok(Notification::from)
-            .try_collect()
+            .stream(&mut *session)
+            .map_ok(|entity| entity.id)
+            .try_collect::<Vec<_>>()
             .await?;
+        if ids.is_empty() {
+            return Ok(());
+        }
 
-        Ok(notifications)
-    }
-}
+        self.database
+            .collection::<Document>(NOTIFICATIONS)
+            .update_many(
+                doc! { "_id": { "$in": &ids } },
+                doc! {
+                    "$set": {
+                        "retracted_at": insert_entity.created_at,
+                    }
+                },
+            )
+            .session(&mut *session)
+            .await?;
 
-///
-/// Tests require env variables to be set and database to be running
-///
-#[cfg(test)]
-mod test {
-    use super::*;
-    use anyhow::anyhow;
-    use mongodb::{options::ClientOptions, Client};
-    use std::{sync::Once, time::Duration};
-    use time::macros::datetime;
+        // Superseded scheduled notification must never be published
+        for id in &ids {
+            self.database
+                .collection::<Document>(OUTBOX)
+                .delete_many(Self::unpublished_new_message_filter(*id))
+                .session(&mut *session)
+                .await?;
+        }
 
-    static BEFORE_ALL: Once = Once::new();
+        let outbox_messages = ids.into_iter().map(|id| {
+            OutboxMessageInsertEntity::deleted(
+                id,
+                insert_entity.user_ids.clone(),
+                insert_entity.groups.clone(),
+                insert_entity.topic.clone(),
+                insert_entity.created_at,
+            )
+        });
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_many(outbox_messages)
+            .session(&mut *session)
+            .await?;
 
-    fn init_env() {
-        let _ = dotenvy::dotenv();
+        Ok(())
     }
 
-    async fn create_test_database() -> anyhow::Result<Database> {
-        BEFORE_ALL.call_once(init_env);
-
-        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
-            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
-        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
-            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
-
-        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
-
-        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
-        let db_client = Client::with_options(db_client_options)?;
-        let db = db_client.database(&db_name);
-
-        Ok(db)
+    ///
+    /// Creates filter matching array with the same values regardless of their order.
+    /// Empty values match missing array as well
+    ///
+    fn same_values_filter<T: Clone + Into<Bson>>(values: &[T]) -> Document {
+        match values.is_empty() {
+            true => doc! { "$in": [Bson::Null, Bson::Array(Vec::new())] },
+            false => doc! {
+                "$size": values.len() as i32,
+                "$all": values.to_vec(),
+            },
+        }
     }
 
-    async fn destroy_test_database(database: Database) {
-        let _ = database.drop().await;
-        database.client().clone().shutdown().await;
+    ///
+    /// Creates outbox filter matching NEW message of the notification
+    /// that has not been published yet
+    ///
+    fn unpublished_new_message_filter(id: ObjectId) -> Document {
+        doc! {
+            "notification_id": id,
+            "status": "NEW",
+            "published_at": None as Option<DateTime>,
+        }
     }
 
-    #[tokio::test]
-    async fn insert_correct_created_at() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    ///
+    /// Finds distinct string values of `field` in documents
+    /// of the user in `collection`
+    ///
+    async fn find_user_values(
+        &self,
+        collection: &str,
+        field: &str,
+        user_id: bson::Uuid,
+    ) -> Result<Vec<String>, mongodb::error::Error> {
+        let values = self
+            .database
+            .collection::<Document>(collection)
+            .distinct(field, doc! { "user_id": user_id })
+            .await?
+            .into_iter()
+            .filter_map(|value| match value {
+                Bson::String(value) => Some(value),
+                _ => None,
+            })
+            .collect();
 
-        let mut inserted_created_at = OffsetDateTime::now_utc();
+        Ok(values)
+    }
 
-        let mut notification = repository
-            .insert(
-                vec![],
-                inserted_created_at,
-                None,
-                Uuid::from_u128(3214098123091),
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
-            )
-            .await?;
+    ///
+    /// Finds current recipients of (uni/multi)cast notification (user_ids,
+    /// members of groups and subscribers of topic) that did not confirm it yet.
+    /// Recipients are resolved and matched against confirmations by the database,
+    /// only their number and the requested page are returned.
+    /// Recipients of broadcast notification are not known
+    ///
+    async fn find_pending_recipients(
+        &self,
+        id: ObjectId,
+        notification: &NotificationUserIdsFindEntity,
+        pagination: &input::DeliveryReportPagination,
+    ) -> Result<Option<PendingRecipientsFindEntity>, mongodb::error::Error> {
+        if notification.user_ids.is_empty()
+            && notification.groups.is_empty()
+            && notification.topic.is_none()
+        {
+            return Ok(None);
+        }
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
+        let mut pipeline = vec![
+            doc! { "$match": { "_id": id } },
+            doc! { "$unwind": "$user_ids" },
+            doc! { "$project": { "_id": 0, "user_id": "$user_ids" } },
+        ];
+        if !notification.groups.is_empty() {
+            pipeline.push(doc! {
+                "$unionWith": {
+                    "coll": GROUP_MEMBERS,
+                    "pipeline": [
+                        { "$match": { "group": { "$in": &notification.groups } } },
+                        { "$project": { "_id": 0, "user_id": 1 } },
+                    ],
+                }
+            });
+        }
+        if let Some(topic) = &notification.topic {
+            pipeline.push(doc! {
+                "$unionWith": {
+                    "coll": SUBSCRIPTIONS,
+                    "pipeline": [
+                        { "$match": { "topic": topic } },
+                        { "$project": { "_id": 0, "user_id": 1 } },
+                    ],
+                }
+            });
+        }
+
+        let mut page = vec![];
+        if let Some(cursor) = pagination.pending_cursor {
+            page.push(doc! { "$match": { "_id": { "$gt": bson::Uuid::from(cursor) } } });
+        }
+        page.push(doc! { "$sort": { "_id": 1 } });
+        page.push(doc! { "$limit": pagination.page_size as i64 });
+
+        pipeline.extend([
+            // User can be a recipient through more than one audience
+            doc! { "$group": { "_id": "$user_id" } },
+            doc! {
+                "$lookup": {
+                    "from": CONFIRMATIONS,
+                    "localField": "_id",
+                    "foreignField": "user_id",
+                    "pipeline": [
+                        { "$match": { "notification_id": id } },
+                        { "$limit": 1 },
+                        { "$project": { "_id": 1 } },
+                    ],
+                    "as": "confirmations",
+                }
+            },
+            doc! { "$match": { "confirmations": { "$size": 0 } } },
+            doc! {
+                "$facet": {
+                    "count": [{ "$count": "count" }],
+                    "user_ids": page,
+                }
+            },
+            doc! {
+                "$project": {
+                    "count": { "$ifNull": [{ "$first": "$count.count" }, 0] },
+                    "user_ids": "$user_ids._id",
+                }
+            },
+        ]);
+
+        let pending_recipients = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<PendingRecipientsFindEntity>()
             .await?
-            .unwrap();
-        let datetime = document.get_datetime("created_at")?;
-        let created_at = OffsetDateTime::from(*datetime);
+            .try_next()
+            .await?
+            .unwrap_or_default();
 
-        // Mongo keeps datetime in milliseconds so it's
-        // necessary to set micro and nanoseconds to 0
-        inserted_created_at =
-            inserted_created_at.replace_millisecond(inserted_created_at.millisecond())?;
-        notification.created_at = notification
-            .created_at
-            .replace_millisecond(notification.created_at.millisecond())?;
+        Ok(Some(pending_recipients))
+    }
 
-        assert_eq!(created_at, inserted_created_at);
-        assert_eq!(created_at, notification.created_at);
+    ///
+    /// Creates filter matching notifications addressed to the user
+    /// either directly, through one of the user groups, through one
+    /// of the topics the user is subscribed to or as a broadcast
+    ///
+    async fn audience_filter(
+        &self,
+        user_id: bson::Uuid,
+    ) -> Result<Document, mongodb::error::Error> {
+        let groups = self
+            .find_user_values(GROUP_MEMBERS, "group", user_id)
+            .await?;
+        let topics = self
+            .find_user_values(SUBSCRIPTIONS, "topic", user_id)
+            .await?;
 
-        destroy_test_database(database).await;
+        Ok(doc! {
+            "$or": [
+                { "user_ids": user_id },
+                { "groups": { "$in": groups } },
+                { "topic": { "$in": topics } },
+                {
+                    "user_ids": { "$size": 0 },
+                    "groups.0": { "$exists": false },
+                    "topic": None as Option<String>,
+                },
+            ]
+        })
+    }
 
-        Ok(())
+    ///
+    /// Creates filter matching notifications whose producer
+    /// or content type was not muted by the user
+    ///
+    async fn unmuted_filter(&self, user_id: bson::Uuid) -> Result<Document, mongodb::error::Error> {
+        let preferences = self
+            .database
+            .collection::<Document>(PREFERENCES)
+            .find_one(doc! { "_id": user_id })
+            .projection(doc! { "muted_producers": 1, "muted_content_types": 1 })
+            .await?
+            .unwrap_or_default();
+        let muted_producers = preferences
+            .get_array("muted_producers")
+            .cloned()
+            .unwrap_or_default();
+        let muted_content_types = preferences
+            .get_array("muted_content_types")
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(doc! {
+            "$nor": [
+                { "producer_id": { "$in": muted_producers } },
+                { "content_type": { "$in": muted_content_types } },
+            ]
+        })
     }
 
-    #[tokio::test]
-    async fn insert_correct_invalidate_at() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    ///
+    /// Creates filter matching notifications that can be delivered to the user now
+    ///
+    fn deliverable_filter(audience: Document, now: DateTime) -> Document {
+        doc! {
+            "$and": [
+                audience,
+                {
+                    "$or": [
+                        { "invalidate_at": None as Option<DateTime> },
+                        { "invalidate_at": { "$gt": now } },
+                    ]
+                },
+                {
+                    "$or": [
+                        { "deliver_at": None as Option<DateTime> },
+                        { "deliver_at": { "$lte": now } },
+                    ]
+                },
+            ],
+            "retracted_at": None as Option<DateTime>,
+        }
+    }
 
-        let inserted_invalidate_at = datetime!(2024-01-21 19:06:25 UTC);
+    ///
+    /// Creates filter matching notifications without topic and
+    /// notifications that are still addressed to the user, so delivered
+    /// notifications of unsubscribed topics are hidden
+    ///
+    fn subscribed_filter(audience: Document) -> Document {
+        doc! {
+            "$or": [
+                { "topic": None as Option<String> },
+                audience,
+            ]
+        }
+    }
 
-        let notification = repository
-            .insert(
-                vec![],
-                OffsetDateTime::now_utc(),
-                Some(inserted_invalidate_at),
-                Uuid::from_u128(3214098123091),
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
-            )
-            .await?;
+    ///
+    /// Creates stage joining notifications with their confirmations
+    /// matching filter as `confirmations` array
+    ///
+    fn confirmations_lookup(confirmation_filter: Document) -> Document {
+        doc! {
+            "$lookup": {
+                "from": CONFIRMATIONS,
+                "localField": "_id",
+                "foreignField": "notification_id",
+                "pipeline": [
+                    { "$match": confirmation_filter },
+                ],
+                "as": "confirmations",
+            }
+        }
+    }
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
-            .await?
-            .unwrap();
-        let datetime = document.get_datetime("invalidate_at")?;
-        let invalidate_at = OffsetDateTime::from(*datetime);
+    ///
+    /// Creates filter matching confirmations of notifications delivered
+    /// to the user (not deleted by the user) that match filters.
+    /// Content type and order are ignored
+    ///
+    fn delivered_filter(user_id: bson::Uuid, filters: &input::NotificationFilters) -> Document {
+        let mut filter = doc! {
+            "user_id": user_id,
+            "notification_deleted": false,
+        };
+        if let Some(seen) = filters.seen {
+            filter.insert("notification_seen", seen);
+        }
+        if let Some(delivered_at) =
+            Self::range_filter(filters.delivered_at_from, filters.delivered_at_to)
+        {
+            filter.insert("notification_delivered_at", delivered_at);
+        }
+        if let Some(created_by) = filters.created_by {
+            filter.insert("notification_producer_id", bson::Uuid::from(created_by));
+        }
+        if let Some(created_at) = Self::range_filter(filters.created_at_from, filters.created_at_to)
+        {
+            filter.insert("notification_created_at", created_at);
+        }
 
-        assert_eq!(invalidate_at, inserted_invalidate_at);
-        assert_eq!(invalidate_at, notification.invalidate_at.unwrap());
+        filter
+    }
 
-        destroy_test_database(database).await;
+    ///
+    /// Creates stages joining confirmations with their notification
+    /// as `notification`. Confirmations of retracted notifications,
+    /// notifications of other content type, notifications of topics
+    /// the user is no longer subscribed to and muted notifications are dropped
+    ///
+    fn delivered_notification_lookup(
+        audience: Document,
+        unmuted: Document,
+        content_type: Option<String>,
+        projection: Document,
+    ) -> [Document; 2] {
+        let mut notification_filter = Self::subscribed_filter(audience);
+        notification_filter.extend(unmuted);
+        notification_filter.insert("retracted_at", None as Option<DateTime>);
+        if let Some(content_type) = content_type {
+            notification_filter.insert("content_type", content_type);
+        }
 
-        Ok(())
+        [
+            doc! {
+                "$lookup": {
+                    "from": NOTIFICATIONS,
+                    "localField": "notification_id",
+                    "foreignField": "_id",
+                    "pipeline": [
+                        { "$match": notification_filter },
+                        { "$project": projection },
+                    ],
+                    "as": "notification",
+                }
+            },
+            doc! { "$unwind": "$notification" },
+        ]
     }
 
-    #[tokio::test]
-    async fn insert_correct_invalidate_at_none() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let inserted_invalidate_at = None;
+    ///
+    /// Creates filter matching notifications of the producer that match filters.
+    /// Order is ignored
+    ///
+    fn produced_filter(
+        producer_id: bson::Uuid,
+        filters: &input::ProducedNotificationFilters,
+    ) -> Document {
+        let mut filter = doc! { "producer_id": producer_id };
+        if let Some(content_type) = &filters.content_type {
+            filter.insert("content_type", content_type.as_str());
+        }
+        if let Some(created_at) = Self::range_filter(filters.created_at_from, filters.created_at_to)
+        {
+            filter.insert("created_at", created_at);
+        }
+        match filters.retracted {
+            Some(true) => {
+                filter.insert("retracted_at", doc! { "$ne": None as Option<DateTime> });
+            }
+            Some(false) => {
+                filter.insert("retracted_at", None as Option<DateTime>);
+            }
+            None => {}
+        }
 
-        let notification = repository
-            .insert(
-                vec![],
-                OffsetDateTime::now_utc(),
-                inserted_invalidate_at,
-                Uuid::from_u128(3214098123091),
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
-            )
-            .await?;
+        filter
+    }
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
-            .await?
-            .unwrap();
-        let invalidate_at = document.get("invalidate_at").unwrap().as_null();
+    ///
+    /// Creates stages counting confirmations of notifications as `delivery`
+    /// and projecting fields of notifications returned to producers
+    ///
+    fn produced_notification_stages() -> [Document; 3] {
+        [
+            doc! {
+                "$lookup": {
+                    "from": CONFIRMATIONS,
+                    "localField": "_id",
+                    "foreignField": "notification_id",
+                    "pipeline": [
+                        {
+                            "$group": {
+                                "_id": Bson::Null,
+                                "confirmed": { "$sum": 1 },
+                                "seen": { "$sum": { "$cond": ["$notification_seen", 1, 0] } },
+                                "deleted": {
+                                    "$sum": { "$cond": ["$notification_deleted", 1, 0] },
+                                },
+                            }
+                        },
+                    ],
+                    "as": "delivery",
+                }
+            },
+            doc! {
+                "$set": {
+                    "delivery": { "$arrayElemAt": ["$delivery", 0] },
+                }
+            },
+            doc! {
+                "$project": {
+                    "_id": 1,
+                    "producer_notification_id": 1,
+                    "created_at": 1,
+                    "invalidate_at": 1,
+                    "deliver_at": 1,
+                    "retracted_at": 1,
+                    "user_ids": 1,
+                    "groups": 1,
+                    "topic": 1,
+                    "priority": 1,
+                    "collapse_key": 1,
+                    "content_type": 1,
+                    "content": 1,
+                    "localized_contents": 1,
+                    "attachment_ids": 1,
+                    "delivery": 1,
+                }
+            },
+        ]
+    }
 
-        assert!(matches!(invalidate_at, Some(())));
-        assert!(notification.invalidate_at.is_none());
+    ///
+    /// Creates range query document from inclusive `from`
+    /// and exclusive `to` bounds. Returns None when both bounds are missing
+    ///
+    fn range_filter(from: Option<OffsetDateTime>, to: Option<OffsetDateTime>) -> Option<Document> {
+        if from.is_none() && to.is_none() {
+            return None;
+        }
 
-        destroy_test_database(database).await;
+        let mut range = Document::new();
+        if let Some(from) = from {
+            range.insert("$gte", DateTime::from(from));
+        }
+        if let Some(to) = to {
+            range.insert("$lt", DateTime::from(to));
+        }
 
-        Ok(())
+        Some(range)
     }
+}
 
-    #[tokio::test]
-    async fn insert_correct_user_ids_unicast() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+#[async_trait]
+impl NotificationsRepository for NotificationsRepositoryImpl {
+    async fn insert(
+        &self,
+        created_at: OffsetDateTime,
+        producer_id: Uuid,
+        notification: input::Notification,
+    ) -> Result<InsertedNotification, Error> {
+        let input::Notification {
+            invalidate_at,
+            deliver_at,
+            user_ids,
+            groups,
+            topic,
+            priority,
+            collapse_key,
+            producer_notification_id,
+            content_type,
+            content,
+            localized_contents,
+            attachment_ids,
+            ..
+        } = notification;
+        let insert_entity = NotificationInsertEntity {
+            created_at: DateTime::from(created_at),
+            invalidate_at: invalidate_at.map(DateTime::from),
+            deliver_at: deliver_at.map(DateTime::from),
+            retracted_at: None,
+            user_ids: user_ids
+                .iter()
+                .map(|user_id| bson::Uuid::from(*user_id))
+                .collect(),
+            groups,
+            topic,
+            priority: priority.as_i32(),
+            collapse_key,
+            producer_id: producer_id.into(),
+            producer_notification_id,
+            content_type,
+            content: content_binary(&content, self.compression_threshold),
+            localized_contents: localized_contents
+                .into_iter()
+                .map(|localized_content| {
+                    LocalizedContentEntity::new(localized_content, self.compression_threshold)
+                })
+                .collect(),
+            attachment_ids,
+        };
 
-        let inserted_user_id = Uuid::from_u128(48190238120);
+        // Notification and outbox message have to be saved atomically,
+        // otherwise notification could be saved without ever being published.
+        // Transaction is aborted when session is dropped before commit
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
 
-        let notification = repository
-            .insert(
-                vec![inserted_user_id],
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(3214098123091),
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
-            )
-            .await?;
+        self.retract_collapsed(&insert_entity, &mut session).await?;
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
-            .await?
-            .unwrap();
-        let user_ids = document.get_array("user_ids")?;
-        assert_eq!(user_ids.len(), 1);
+        let insert_result = self
+            .database
+            .collection::<NotificationInsertEntity>(NOTIFICATIONS)
+            .insert_one(&insert_entity)
+            .session(&mut session)
+            .await
+            .map_err(Self::map_insert_error)?;
 
-        let user_id = user_ids.first().unwrap();
-        let Bson::Binary(binary) = user_id else {
-            panic!("invalid user_id type");
+        let Bson::ObjectId(id) = insert_result.inserted_id else {
+            tracing::error!("invalid type of inserted '_id'");
+            return Err(Error::Mongo(
+                ErrorKind::Custom(Arc::new("invalid type of inserted '_id'")).into(),
+            ));
         };
-        let user_id = binary.to_uuid()?;
-        let user_id = Uuid::from(user_id);
 
-        assert_eq!(user_id, inserted_user_id);
-        assert_eq!(user_id, *notification.user_ids.first().unwrap());
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_one(OutboxMessageInsertEntity::new(id, &insert_entity))
+            .session(&mut session)
+            .await?;
 
-        destroy_test_database(database).await;
+        session.commit_transaction().await?;
 
-        Ok(())
+        Ok(InsertedNotification {
+            id,
+            created_at,
+            invalidate_at,
+            deliver_at,
+            user_ids,
+            groups: insert_entity.groups,
+            topic: insert_entity.topic,
+            priority,
+            producer_id,
+            producer_notification_id,
+            content_type: insert_entity.content_type,
+            content,
+        })
     }
 
-    #[tokio::test]
-    async fn insert_correct_user_ids_multicast() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let inserted_user_ids = vec![Uuid::from_u128(48190238120), Uuid::from_u128(11212039809)];
+    async fn insert_many(
+        &self,
+        created_at: OffsetDateTime,
+        producer_id: Uuid,
+        notifications: Vec<input::Notification>,
+    ) -> Result<Vec<Result<InsertedNotification, Error>>, Error> {
+        let producer_id_bson = bson::Uuid::from(producer_id);
+        let producer_notification_ids = notifications
+            .iter()
+            .map(|notification| notification.producer_notification_id)
+            .collect::<Vec<_>>();
 
-        let notification = repository
-            .insert(
-                inserted_user_ids.clone(),
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(3214098123091),
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
-            )
-            .await?;
+        // Duplicated key aborts the whole transaction so conflicting
+        // notifications have to be found before inserting
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
+        let mut used_producer_notification_ids = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .distinct(
+                "producer_notification_id",
+                doc! {
+                    "producer_id": producer_id_bson,
+                    "producer_notification_id": { "$in": producer_notification_ids },
+                },
+            )
+            .session(&mut session)
             .await?
-            .unwrap();
-        let user_ids = document
-            .get_array("user_ids")?
             .into_iter()
-            .filter_map(|bson| {
-                let Bson::Binary(binary) = bson else {
-                    return None;
-                };
-                let Ok(user_id) = binary.to_uuid() else {
-                    return None;
-                };
-                Some(user_id.into())
-            })
-            .collect::<Vec<Uuid>>();
-        assert_eq!(user_ids.len(), inserted_user_ids.len());
-        assert_eq!(user_ids.len(), notification.user_ids.len());
+            .filter_map(|producer_notification_id| producer_notification_id.as_i64())
+            .collect::<HashSet<_>>();
 
-        let database_ok = inserted_user_ids
-            .iter()
-            .all(|inserted_user_id| user_ids.contains(inserted_user_id));
-        let notification_ok = inserted_user_ids
-            .iter()
-            .all(|inserted_user_id| notification.user_ids.contains(inserted_user_id));
+        let mut insert_entities = Vec::new();
+        let mut contents = Vec::new();
+        let mut inserted = Vec::with_capacity(notifications.len());
+        for notification in notifications {
+            let is_unique =
+                used_producer_notification_ids.insert(notification.producer_notification_id);
+            inserted.push(is_unique);
+            if !is_unique {
+                continue;
+            }
 
-        assert!(database_ok);
-        assert!(notification_ok);
+            insert_entities.push(NotificationInsertEntity {
+                created_at: DateTime::from(created_at),
+                invalidate_at: notification.invalidate_at.map(DateTime::from),
+                deliver_at: notification.deliver_at.map(DateTime::from),
+                retracted_at: None,
+                user_ids: notification
+                    .user_ids
+                    .into_iter()
+                    .map(bson::Uuid::from)
+                    .collect(),
+                groups: notification.groups,
+                topic: notification.topic,
+                priority: notification.priority.as_i32(),
+                collapse_key: notification.collapse_key,
+                producer_id: producer_id_bson,
+                producer_notification_id: notification.producer_notification_id,
+                content_type: notification.content_type,
+                content: content_binary(&notification.content, self.compression_threshold),
+                localized_contents: notification
+                    .localized_contents
+                    .into_iter()
+                    .map(|localized_content| {
+                        LocalizedContentEntity::new(localized_content, self.compression_threshold)
+                    })
+                    .collect(),
+                attachment_ids: notification.attachment_ids,
+            });
+            contents.push(notification.content);
+        }
 
-        destroy_test_database(database).await;
+        // Only the last notification of the batch with the same collapse key
+        // and audience is published, the others are inserted already retracted
+        let mut collapse_identities = HashSet::new();
+        for insert_entity in insert_entities.iter_mut().rev() {
+            let Some(collapse_key) = &insert_entity.collapse_key else {
+                continue;
+            };
 
-        Ok(())
-    }
+            let mut user_ids = insert_entity
+                .user_ids
+                .iter()
+                .map(|user_id| Uuid::from(*user_id))
+                .collect::<Vec<_>>();
+            user_ids.sort_unstable();
+            user_ids.dedup();
+            let mut groups = insert_entity.groups.clone();
+            groups.sort_unstable();
+            groups.dedup();
+
+            let identity = (
+                collapse_key.clone(),
+                user_ids,
+                groups,
+                insert_entity.topic.clone(),
+            );
+            if !collapse_identities.insert(identity) {
+                insert_entity.retracted_at = Some(insert_entity.created_at);
+            }
+        }
 
-    #[tokio::test]
-    async fn insert_correct_user_ids_empty() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        for insert_entity in &insert_entities {
+            if insert_entity.retracted_at.is_none() {
+                self.retract_collapsed(insert_entity, &mut session).await?;
+            }
+        }
 
-        let notification = repository
-            .insert(
-                vec![],
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(3214098123091),
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
-            )
-            .await?;
+        let mut ids = Vec::with_capacity(insert_entities.len());
+        if !insert_entities.is_empty() {
+            let insert_result = self
+                .database
+                .collection::<NotificationInsertEntity>(NOTIFICATIONS)
+                .insert_many(&insert_entities)
+                .session(&mut session)
+                .await
+                .map_err(Self::map_insert_error)?;
+
+            for idx in 0..insert_entities.len() {
+                let Some(Bson::ObjectId(id)) = insert_result.inserted_ids.get(&idx) else {
+                    tracing::error!("invalid type of inserted '_id'");
+                    return Err(Error::Mongo(
+                        ErrorKind::Custom(Arc::new("invalid type of inserted '_id'")).into(),
+                    ));
+                };
+                ids.push(*id);
+            }
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
-            .await?
-            .unwrap();
-        let user_ids = document.get_array("user_ids")?;
+            let outbox_messages = std::iter::zip(&ids, &insert_entities)
+                .filter(|(_, insert_entity)| insert_entity.retracted_at.is_none())
+                .map(|(id, insert_entity)| OutboxMessageInsertEntity::new(*id, insert_entity));
+            self.database
+                .collection::<OutboxMessageInsertEntity>(OUTBOX)
+                .insert_many(outbox_messages)
+                .session(&mut session)
+                .await?;
+        }
 
-        assert!(user_ids.is_empty());
-        assert!(notification.user_ids.is_empty());
+        session.commit_transaction().await?;
 
-        destroy_test_database(database).await;
+        let mut inserted_notifications =
+            std::iter::zip(ids, std::iter::zip(insert_entities, contents));
+        let results = inserted
+            .into_iter()
+            .map(|is_inserted| {
+                if !is_inserted {
+                    return Err(Error::InsertUniqueViolation);
+                }
 
-        Ok(())
-    }
+                let Some((id, (insert_entity, content))) = inserted_notifications.next() else {
+                    unreachable!("every inserted notification has an entity");
+                };
 
-    #[tokio::test]
-    async fn insert_correct_producer_id() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+                Ok(InsertedNotification {
+                    id,
+                    created_at,
+                    invalidate_at: insert_entity.invalidate_at.map(OffsetDateTime::from),
+                    deliver_at: insert_entity.deliver_at.map(OffsetDateTime::from),
+                    user_ids: insert_entity.user_ids.into_iter().map(Uuid::from).collect(),
+                    groups: insert_entity.groups,
+                    topic: insert_entity.topic,
+                    priority: NotificationPriority::from_i32(insert_entity.priority),
+                    producer_id,
+                    producer_notification_id: insert_entity.producer_notification_id,
+                    content_type: insert_entity.content_type,
+                    content,
+                })
+            })
+            .collect();
 
-        let inserted_producer_id = Uuid::from_u128(4812038102);
+        Ok(results)
+    }
 
-        let notification = repository
-            .insert(
-                vec![Uuid::from_u128(8129381)],
-                OffsetDateTime::now_utc(),
-                None,
-                inserted_producer_id,
-                1,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
+    async fn update_invalidate_at(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        invalidate_at: Option<OffsetDateTime>,
+    ) -> Result<(), Error> {
+        let producer_id = bson::Uuid::from(producer_id);
+        let invalidate_at = invalidate_at.map(DateTime::from);
+
+        let update_result = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    "producer_id": producer_id,
+                    "retracted_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "$set": {
+                        "invalidate_at": invalidate_at,
+                    }
+                },
             )
             .await?;
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
-            .await?
-            .unwrap();
-        let producer_id = document.get("producer_id").unwrap();
-        let Bson::Binary(binary) = producer_id else {
-            panic!("invalid type");
-        };
-        let producer_id = binary.to_uuid()?;
-        let producer_id = Uuid::from(producer_id);
-
-        assert_eq!(producer_id, inserted_producer_id);
-        assert_eq!(producer_id, notification.producer_id);
-
-        destroy_test_database(database).await;
-
-        Ok(())
+        match update_result.matched_count == 1 {
+            true => Ok(()),
+            false => Err(Error::NoDocumentUpdated),
+        }
     }
 
-    #[tokio::test]
-    async fn insert_correct_producer_notification_id() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    async fn update_content(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        content_type: String,
+        content: Vec<u8>,
+        localized_contents: Vec<input::LocalizedContent>,
+    ) -> Result<(), Error> {
+        let producer_id = bson::Uuid::from(producer_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
+        let content = content_binary(&content, self.compression_threshold);
+        let localized_contents = localized_contents
+            .into_iter()
+            .map(|localized_content| {
+                LocalizedContentEntity::new(localized_content, self.compression_threshold)
+            })
+            .collect::<Vec<_>>();
+        let localized_contents_bson =
+            bson::to_bson(&localized_contents).map_err(mongodb::error::Error::from)?;
 
-        let inserted_producer_notification_id = 721;
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
 
-        let notification = repository
-            .insert(
-                vec![],
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(3214098123091),
-                inserted_producer_notification_id,
-                "utf-8".to_string(),
-                b"not important content".to_vec(),
+        let notification = self
+            .database
+            .collection::<NotificationUserIdsFindEntity>(NOTIFICATIONS)
+            .find_one_and_update(
+                doc! {
+                    "_id": id,
+                    "producer_id": producer_id,
+                    "retracted_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "$set": {
+                        "content_type": &content_type,
+                        "content": content.clone(),
+                        "localized_contents": localized_contents_bson.clone(),
+                    }
+                },
             )
-            .await?;
-
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
+            .projection(doc! { "user_ids": 1, "groups": 1, "topic": 1 })
+            .session(&mut session)
             .await?
-            .unwrap();
-        let producer_notification_id = document.get_i64("producer_notification_id")?;
+            .ok_or(Error::NoDocumentUpdated)?;
 
-        assert_eq!(producer_notification_id, inserted_producer_notification_id);
-        assert_eq!(
-            producer_notification_id,
-            notification.producer_notification_id
+        // NEW message of scheduled notification may still wait for
+        // publishing, so it has to carry the new content as well
+        self.database
+            .collection::<Document>(OUTBOX)
+            .update_many(
+                Self::unpublished_new_message_filter(id),
+                doc! {
+                    "$set": {
+                        "content_type": &content_type,
+                        "content": content.clone(),
+                        "localized_contents": localized_contents_bson.clone(),
+                    }
+                },
+            )
+            .session(&mut session)
+            .await?;
+
+        // NEW message not picked up for publishing yet delivers the new content
+        // on its own, UPDATED message could be even delivered before it
+        let mut pending_new_message_filter = Self::unpublished_new_message_filter(id);
+        pending_new_message_filter.insert(
+            "$or",
+            vec![
+                doc! { "locked_until": None as Option<DateTime> },
+                doc! { "locked_until": { "$lte": now } },
+            ],
         );
+        let pending_new_messages_count = self
+            .database
+            .collection::<Document>(OUTBOX)
+            .count_documents(pending_new_message_filter)
+            .session(&mut session)
+            .await?;
 
-        destroy_test_database(database).await;
+        if pending_new_messages_count == 0 {
+            self.database
+                .collection::<OutboxMessageInsertEntity>(OUTBOX)
+                .insert_one(OutboxMessageInsertEntity::updated_content(
+                    id,
+                    notification,
+                    content_type,
+                    content,
+                    localized_contents,
+                    now,
+                ))
+                .session(&mut session)
+                .await?;
+        }
+
+        session.commit_transaction().await?;
 
         Ok(())
     }
 
-    #[tokio::test]
-    async fn insert_correct_content_type() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    async fn insert_confirmation(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
 
-        let inserted_content_type = "json".to_string();
+        let audience = self.audience_filter(user_id).await?;
 
-        let notification = repository
-            .insert(
-                vec![Uuid::from_u128(8129381)],
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(1231203),
-                1,
-                inserted_content_type.clone(),
-                br#"{"value":null}"#.to_vec(),
-            )
-            .await?;
+        let mut filter = Self::deliverable_filter(audience, now);
+        filter.insert("_id", id);
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
+        let notification = self
+            .database
+            .collection::<NotificationConfirmedFindEntity>(NOTIFICATIONS)
+            .find_one(filter)
+            .projection(doc! {
+                "_id": 1,
+                "created_at": 1,
+                "producer_id": 1,
+            })
             .await?
-            .unwrap();
-        let content_type = document.get_str("content_type")?;
+            .ok_or(Error::NoDocumentUpdated)?;
 
-        assert_eq!(content_type, inserted_content_type);
-        assert_eq!(content_type, notification.content_type);
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
 
-        destroy_test_database(database).await;
+        let insert_result = self
+            .database
+            .collection::<ConfirmationInsertEntity>(CONFIRMATIONS)
+            .insert_one(ConfirmationInsertEntity::new(notification, user_id, now))
+            .session(&mut session)
+            .await;
+
+        // Unique index makes sure that notification
+        // is delivered to the user only once
+        match insert_result.map_err(Self::map_insert_error) {
+            Ok(_) => {}
+            Err(Error::InsertUniqueViolation) => return Err(Error::NoDocumentUpdated),
+            Err(err) => return Err(err),
+        }
+
+        self.insert_receipts(&[id], user_id, ReceiptEvent::Delivered, now, &mut session)
+            .await?;
+
+        session.commit_transaction().await?;
 
         Ok(())
     }
 
-    #[tokio::test]
-    async fn insert_correct_content() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    async fn insert_many_confirmations(
+        &self,
+        ids: &[ObjectId],
+        user_id: Uuid,
+    ) -> Result<(), Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
 
-        let inserted_content = br#"{"value": null}"#.to_vec();
+        let audience = self.audience_filter(user_id).await?;
 
-        let notification = repository
-            .insert(
-                vec![Uuid::from_u128(8129381)],
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(1231203),
-                1,
-                "json".to_string(),
-                inserted_content.clone(),
-            )
-            .await?;
+        let mut filter = Self::deliverable_filter(audience, now);
+        filter.insert("_id", doc! { "$in": ids });
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
+        let confirmations = self
+            .database
+            .collection::<NotificationConfirmedFindEntity>(NOTIFICATIONS)
+            .find(filter)
+            .projection(doc! {
+                "_id": 1,
+                "created_at": 1,
+                "producer_id": 1,
+            })
             .await?
-            .unwrap();
-        let content = document.get_binary_generic("content")?;
-
-        assert_eq!(*content, inserted_content);
-        assert_eq!(*content, notification.content);
+            .map_ok(|notification| ConfirmationInsertEntity::new(notification, user_id, now))
+            .try_collect::<Vec<_>>()
+            .await?;
+        if confirmations.is_empty() {
+            return Ok(());
+        }
 
-        destroy_test_database(database).await;
+        let ids = confirmations
+            .iter()
+            .map(|confirmation| confirmation.notification_id)
+            .collect::<Vec<_>>();
+
+        // Unordered insert doesn't stop on duplicates,
+        // so notifications that were already delivered are skipped
+        let insert_result = self
+            .database
+            .collection::<ConfirmationInsertEntity>(CONFIRMATIONS)
+            .insert_many(confirmations)
+            .ordered(false)
+            .await;
+
+        let inserted_ids = match insert_result {
+            Ok(_) => ids,
+            Err(err) => {
+                let duplicate_indexes = match *err.kind {
+                    ErrorKind::InsertMany(ref insert_many_error)
+                        if insert_many_error.write_concern_error.is_none() =>
+                    {
+                        insert_many_error
+                            .write_errors
+                            .iter()
+                            .flatten()
+                            .map(|write_error| {
+                                (write_error.code == DUPLICATE_KEY_CODE)
+                                    .then_some(write_error.index)
+                            })
+                            .collect::<Option<HashSet<_>>>()
+                    }
+                    _ => None,
+                };
+                let Some(duplicate_indexes) = duplicate_indexes else {
+                    return Err(Error::Mongo(err));
+                };
+
+                ids.into_iter()
+                    .enumerate()
+                    .filter(|(index, _)| !duplicate_indexes.contains(index))
+                    .map(|(_, id)| id)
+                    .collect()
+            }
+        };
+
+        // Confirmations are already inserted, so receipts that failed
+        // to be saved are not worth failing the delivery
+        let mut session = self.database.client().start_session().await?;
+        if let Err(err) = self
+            .insert_receipts(
+                &inserted_ids,
+                user_id,
+                ReceiptEvent::Delivered,
+                now,
+                &mut session,
+            )
+            .await
+        {
+            tracing::warn!(%err, "failed to save delivery receipts");
+        }
 
         Ok(())
     }
 
-    #[tokio::test]
-    async fn insert_correct_confirmations() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    async fn update_confirmation_seen(
+        &self,
+        id: ObjectId,
+        user_id: Uuid,
+        seen: bool,
+    ) -> Result<(), Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
 
-        let notification = repository
-            .insert(
-                vec![Uuid::from_u128(8129381)],
-                OffsetDateTime::now_utc(),
-                None,
-                Uuid::from_u128(1231203),
-                1,
-                "utf-8".to_string(),
-                b"data".to_vec(),
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
+
+        self.find_not_retracted(id, &mut session).await?;
+
+        let update_result = self
+            .database
+            .collection::<Document>(CONFIRMATIONS)
+            .update_one(
+                doc! {
+                    "notification_id": id,
+                    "user_id": user_id,
+                    "notification_deleted": false,
+                },
+                doc! {
+                    "$set": {
+                        "notification_seen": seen,
+                    }
+                },
             )
+            .session(&mut session)
             .await?;
 
-        let document = collection
-            .find_one(doc! { "_id": notification.id })
-            .await?
-            .unwrap();
-        let confirmations = document.get_array("confirmations")?;
+        // matched_count instead of modified_count becaouse replacing
+        // false with false doesn't count as modification
+        if update_result.matched_count != 1 {
+            return Err(Error::NoDocumentUpdated);
+        }
 
-        assert!(confirmations.is_empty());
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_one(OutboxMessageInsertEntity::updated_seen(
+                id, user_id, seen, now,
+            ))
+            .session(&mut session)
+            .await?;
 
-        destroy_test_database(database).await;
+        self.insert_receipts(&[id], user_id, Self::seen_event(seen), now, &mut session)
+            .await?;
+
+        session.commit_transaction().await?;
 
         Ok(())
     }
 
-    #[tokio::test]
-    async fn insert_duplicated_producer_notification() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let producer_id = Uuid::from_u128(7498127391);
-        let producer_notification_id = 672;
+    async fn update_many_confirmations_seen(
+        &self,
+        user_id: Uuid,
+        mut selection: input::NotificationsSelection,
+        seen: bool,
+    ) -> Result<u64, Error> {
+        let user_id = bson::Uuid::from(user_id);
 
-        // fixture
-        collection
-            .insert_many([doc! {
-                "producer_id": bson::Uuid::from(producer_id),
-                "producer_notification_id": producer_notification_id,
-            }])
-            .await?;
+        // Only confirmations with different seen value are updated
+        // so no UPDATED message is sent for unchanged notifications
+        // and updated ones are not selected by the next batch
+        let mut filters = selection.filters.take().unwrap_or_default();
+        if filters.seen == Some(seen) {
+            return Ok(0);
+        }
+        filters.seen = Some(!seen);
+        selection.filters = Some(filters);
 
-        let insert_result = repository
-            .insert(
-                vec![Uuid::from_u128(8129381)],
-                OffsetDateTime::now_utc(),
-                None,
-                producer_id,
-                producer_notification_id,
-                "utf-8".to_string(),
-                b"data".to_vec(),
-            )
-            .await;
+        let mut modified_count = 0;
+        loop {
+            let (selected_count, batch_modified_count) = self
+                .update_confirmations_seen_batch(user_id, &selection, seen)
+                .await?;
+            modified_count += batch_modified_count;
+            if selected_count < SELECTION_BATCH_SIZE as usize {
+                return Ok(modified_count);
+            }
+        }
+    }
 
-        assert!(matches!(insert_result, Err(Error::InsertUniqueViolation)));
+    async fn delete(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
 
-        destroy_test_database(database).await;
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
 
-        Ok(())
-    }
+        self.find_not_retracted(id, &mut session).await?;
 
-    #[tokio::test]
-    async fn update_invalidate_at_value_updated() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let update_result = self
+            .database
+            .collection::<Document>(CONFIRMATIONS)
+            .update_one(
+                doc! {
+                    "notification_id": id,
+                    "user_id": user_id,
+                },
+                doc! {
+                    "$set": {
+                        "notification_deleted": true,
+                    }
+                },
+            )
+            .session(&mut session)
+            .await?;
 
-        let id = ObjectId::new();
-        let producer_id = Uuid::from_u128(5791022193);
+        if update_result.modified_count != 1 {
+            return Err(Error::NoDocumentUpdated);
+        }
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "producer_id": bson::Uuid::from(producer_id),
-                "invalidate_at": None as Option<DateTime>,
-            }])
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_one(OutboxMessageInsertEntity::deleted(
+                id,
+                vec![user_id],
+                vec![],
+                None,
+                now,
+            ))
+            .session(&mut session)
             .await?;
 
-        let mut inserted_invalidate_at = OffsetDateTime::now_utc() + Duration::from_secs(300);
-        repository
-            .update_invalidate_at(id, producer_id, Some(inserted_invalidate_at))
+        self.insert_receipts(&[id], user_id, ReceiptEvent::Deleted, now, &mut session)
             .await?;
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let invalidate_at = document.get_datetime("invalidate_at")?;
-        let invalidate_at = OffsetDateTime::from(*invalidate_at);
-
-        inserted_invalidate_at = inserted_invalidate_at
-            .replace_millisecond(inserted_invalidate_at.millisecond())
-            .unwrap();
+        session.commit_transaction().await?;
 
-        assert_eq!(invalidate_at, inserted_invalidate_at);
+        Ok(())
+    }
 
-        destroy_test_database(database).await;
+    async fn delete_many(
+        &self,
+        user_id: Uuid,
+        selection: input::NotificationsSelection,
+    ) -> Result<u64, Error> {
+        let user_id = bson::Uuid::from(user_id);
 
-        Ok(())
+        // Deleted notifications are not selected by the next batch
+        let mut modified_count = 0;
+        loop {
+            let (selected_count, batch_modified_count) =
+                self.delete_batch(user_id, &selection).await?;
+            modified_count += batch_modified_count;
+            if selected_count < SELECTION_BATCH_SIZE as usize {
+                return Ok(modified_count);
+            }
+        }
     }
 
-    #[tokio::test]
-    async fn update_invalidate_at_the_same_value() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+    async fn retract(&self, id: ObjectId, producer_id: Uuid) -> Result<(), Error> {
+        let producer_id = bson::Uuid::from(producer_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
 
-        let id = ObjectId::new();
-        let producer_id = Uuid::from_u128(5791022193);
-        let invalidate_at = OffsetDateTime::now_utc();
+        let mut session = self.database.client().start_session().await?;
+        session.start_transaction().await?;
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "producer_id": bson::Uuid::from(producer_id),
-                "invalidate_at": DateTime::from(invalidate_at),
-            }])
-            .await?;
+        let notification = self
+            .database
+            .collection::<NotificationUserIdsFindEntity>(NOTIFICATIONS)
+            .find_one_and_update(
+                doc! {
+                    "_id": id,
+                    "producer_id": producer_id,
+                    "retracted_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "$set": {
+                        "retracted_at": now,
+                    }
+                },
+            )
+            .projection(doc! { "user_ids": 1, "groups": 1, "topic": 1 })
+            .session(&mut session)
+            .await?
+            .ok_or(Error::NoDocumentUpdated)?;
 
-        let update_result = repository
-            .update_invalidate_at(id, producer_id, Some(invalidate_at))
-            .await;
+        // Scheduled notification that was not published yet
+        // must never be published after retracting it
+        self.database
+            .collection::<Document>(OUTBOX)
+            .delete_many(Self::unpublished_new_message_filter(id))
+            .session(&mut session)
+            .await?;
 
-        assert!(update_result.is_ok());
+        // Empty user_ids, groups and topic make DELETED a broadcast message
+        // so every recipient of broadcast notification receives it
+        self.database
+            .collection::<OutboxMessageInsertEntity>(OUTBOX)
+            .insert_one(OutboxMessageInsertEntity::deleted(
+                id,
+                notification.user_ids,
+                notification.groups,
+                notification.topic,
+                now,
+            ))
+            .session(&mut session)
+            .await?;
 
-        destroy_test_database(database).await;
+        session.commit_transaction().await?;
 
         Ok(())
     }
 
-    #[tokio::test]
-    async fn update_invalidate_at_id_not_exist() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let other_id = ObjectId::new();
-        let producer_id = Uuid::from_u128(1);
-
-        collection
-            .insert_many([doc! {
-                "_id": other_id,
+    async fn find_delivery_report(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        pagination: input::DeliveryReportPagination,
+    ) -> Result<Option<DeliveryReport>, Error> {
+        let notification = self
+            .database
+            .collection::<NotificationUserIdsFindEntity>(NOTIFICATIONS)
+            .find_one(doc! {
+                "_id": id,
                 "producer_id": bson::Uuid::from(producer_id),
-                "invalidate_at": None as Option<DateTime>,
-            }])
+            })
+            .projection(doc! { "user_ids": 1, "groups": 1, "topic": 1 })
             .await?;
+        let Some(notification) = notification else {
+            return Ok(None);
+        };
 
-        let update_result = repository
-            .update_invalidate_at(id, producer_id, Some(OffsetDateTime::now_utc()))
-            .await;
+        let collection = self.database.collection::<Document>(CONFIRMATIONS);
+        let confirmed_count = collection
+            .count_documents(doc! { "notification_id": id })
+            .await?;
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+        let mut filter = doc! { "notification_id": id };
+        if let Some(cursor) = pagination.confirmations_cursor {
+            let delivered_at = DateTime::from(cursor.delivered_at);
+            filter.insert(
+                "$or",
+                vec![
+                    doc! { "notification_delivered_at": { "$gt": delivered_at } },
+                    doc! {
+                        "notification_delivered_at": delivered_at,
+                        "_id": { "$gt": cursor.id },
+                    },
+                ],
+            );
+        }
+        let confirmations = collection
+            .clone_with_type::<ConfirmationFindEntity>()
+            .find(filter)
+            .sort(doc! { "notification_delivered_at": 1, "_id": 1 })
+            .limit(pagination.page_size as i64)
+            .await?
+            .map_ok(RecipientConfirmation::from)
+            .try_collect::<Vec<_>>()
+            .await?;
 
-        destroy_test_database(database).await;
+        let pending_recipients = self
+            .find_pending_recipients(id, &notification, &pagination)
+            .await?;
 
-        Ok(())
+        Ok(Some(DeliveryReport {
+            confirmed_count,
+            confirmations,
+            pending_count: pending_recipients
+                .as_ref()
+                .map(|pending_recipients| pending_recipients.count as u64),
+            pending_user_ids: pending_recipients.map(|pending_recipients| {
+                pending_recipients
+                    .user_ids
+                    .into_iter()
+                    .map(Uuid::from)
+                    .collect()
+            }),
+        }))
     }
 
-    #[tokio::test]
-    async fn update_invalidate_at_wrong_producer() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
+    async fn find_many_produced(
+        &self,
+        producer_id: Uuid,
+        pagination: input::Pagination,
+        filters: input::ProducedNotificationFilters,
+    ) -> Result<Vec<ProducedNotification>, Error> {
+        let order = filters.order.unwrap_or_default();
+        let mut filter = Self::produced_filter(bson::Uuid::from(producer_id), &filters);
+
+        let (cursor_operator, sort_direction) = match order {
+            input::NotificationsOrder::Asc => ("$gt", 1),
+            input::NotificationsOrder::Desc => ("$lt", -1),
+        };
+        if let Some(cursor) = pagination.cursor {
+            let created_at = DateTime::from(cursor.created_at);
+            filter.insert(
+                "$or",
+                vec![
+                    doc! { "created_at": { cursor_operator: created_at } },
+                    doc! {
+                        "created_at": created_at,
+                        "_id": { cursor_operator: cursor.id },
+                    },
+                ],
+            );
+        }
 
-        let producer_id = Uuid::from_u128(1);
-        let other_producer_id = Uuid::from_u128(5871923123);
+        // Confirmations are counted only for notifications of the returned page
+        let mut pipeline = vec![
+            doc! { "$match": filter },
+            doc! {
+                "$sort": {
+                    "created_at": sort_direction,
+                    "_id": sort_direction,
+                }
+            },
+        ];
+        if let Some(page_idx) = pagination.page_idx {
+            pipeline.push(doc! { "$skip": (pagination.page_size * page_idx) as i64 });
+        }
+        pipeline.push(doc! { "$limit": pagination.page_size as i64 });
+        pipeline.extend(Self::produced_notification_stages());
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "producer_id": bson::Uuid::from(other_producer_id),
-                "invalidate_at": None as Option<DateTime>,
-            }])
+        let notifications = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<ProducedNotificationFindEntity>()
+            .await?
+            .map_ok(ProducedNotification::from)
+            .try_collect()
             .await?;
 
-        let update_result = repository
-            .update_invalidate_at(id, producer_id, Some(OffsetDateTime::now_utc()))
-            .await;
+        Ok(notifications)
+    }
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+    async fn find_produced(
+        &self,
+        producer_id: Uuid,
+        producer_notification_id: i64,
+    ) -> Result<Option<ProducedNotification>, Error> {
+        let mut pipeline = vec![doc! {
+            "$match": {
+                "producer_id": bson::Uuid::from(producer_id),
+                "producer_notification_id": producer_notification_id,
+            }
+        }];
+        pipeline.extend(Self::produced_notification_stages());
 
-        destroy_test_database(database).await;
+        let notification = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<ProducedNotificationFindEntity>()
+            .await?
+            .try_next()
+            .await?
+            .map(ProducedNotification::from);
 
-        Ok(())
+        Ok(notification)
     }
 
-    #[tokio::test]
-    async fn insert_confirmation_correct_user_id() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
-
-        let inserted_user_id = Uuid::from_u128(48120938012);
+    async fn find_delivered(
+        &self,
+        id: ObjectId,
+        user_id: Uuid,
+    ) -> Result<Option<Notification>, Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let audience = self.audience_filter(user_id).await?;
+        let unmuted = self.unmuted_filter(user_id).await?;
+
+        let mut filter = Self::subscribed_filter(audience);
+        filter.extend(unmuted);
+        filter.insert("_id", id);
+        filter.insert("retracted_at", None as Option<DateTime>);
+
+        let pipeline = [
+            doc! { "$match": filter },
+            Self::confirmations_lookup(doc! {
+                "user_id": user_id,
+                "notification_deleted": false,
+            }),
+            doc! {
+                "$match": {
+                    "confirmations": { "$ne": [] },
+                }
+            },
+            doc! {
+                "$project": {
+                    "_id": 1,
+                    "created_at": 1,
+                    "producer_id": 1,
+                    "priority": 1,
+                    "content_type": 1,
+                    "content": 1,
+                    "localized_contents": 1,
+                    "attachment_ids": 1,
+                    "confirmations": 1,
+                }
+            },
+        ];
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(inserted_user_id)],
-                "invalidate_at": None as Option<DateTime>,
-                "confirmations": [],
-            }])
+        let notification_entity = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<NotificationFindEntity>()
+            .await?
+            .try_next()
             .await?;
 
-        repository.insert_confirmation(id, inserted_user_id).await?;
+        let notification = notification_entity.map(Notification::from);
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
+        Ok(notification)
+    }
 
-        let user_id = confirmation.get("user_id").unwrap();
-        let Bson::Binary(binary) = user_id else {
-            panic!("Invalid user_id type");
+    async fn find_many_delivered(
+        &self,
+        user_id: Uuid,
+        pagination: input::Pagination,
+        filters: input::NotificationFilters,
+    ) -> Result<Vec<Notification>, Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let order = filters.order.unwrap_or_default();
+        let mut filter = Self::delivered_filter(user_id, &filters);
+        let audience = self.audience_filter(user_id).await?;
+        let unmuted = self.unmuted_filter(user_id).await?;
+
+        let (cursor_operator, sort_direction) = match order {
+            input::NotificationsOrder::Asc => ("$gt", 1),
+            input::NotificationsOrder::Desc => ("$lt", -1),
         };
-        let user_id = binary.to_uuid()?.to_uuid_1();
+        if let Some(cursor) = pagination.cursor {
+            let created_at = DateTime::from(cursor.created_at);
+            filter.insert(
+                "$or",
+                vec![
+                    doc! { "notification_created_at": { cursor_operator: created_at } },
+                    doc! {
+                        "notification_created_at": created_at,
+                        "notification_id": { cursor_operator: cursor.id },
+                    },
+                ],
+            );
+        }
 
-        assert_eq!(user_id, inserted_user_id);
+        // Confirmations are sorted before looking up notifications,
+        // so only notifications of the returned page are loaded.
+        // notification_id makes order deterministic when notifications
+        // were created at the same time
+        let mut pipeline = vec![
+            doc! { "$match": filter },
+            doc! {
+                "$sort": {
+                    "notification_created_at": sort_direction,
+                    "notification_id": sort_direction,
+                }
+            },
+        ];
+        pipeline.extend(Self::delivered_notification_lookup(
+            audience,
+            unmuted,
+            filters.content_type,
+            doc! {
+                "_id": 1,
+                "created_at": 1,
+                "producer_id": 1,
+                "priority": 1,
+                "content_type": 1,
+                "content": 1,
+                "localized_contents": 1,
+                "attachment_ids": 1,
+            },
+        ));
+        if let Some(page_idx) = pagination.page_idx {
+            pipeline.push(doc! { "$skip": (pagination.page_size * page_idx) as i64 });
+        }
+        pipeline.push(doc! { "$limit": pagination.page_size as i64 });
+        pipeline.push(doc! {
+            "$replaceWith": {
+                "_id": "$notification._id",
+                "created_at": "$notification.created_at",
+                "producer_id": "$notification.producer_id",
+                "priority": "$notification.priority",
+                "content_type": "$notification.content_type",
+                "content": "$notification.content",
+                "localized_contents": "$notification.localized_contents",
+                "attachment_ids": "$notification.attachment_ids",
+                "confirmations": [
+                    { "notification_seen": "$notification_seen" },
+                ],
+            }
+        });
 
-        destroy_test_database(database).await;
+        let notifications = self
+            .database
+            .collection::<Document>(CONFIRMATIONS)
+            .aggregate(pipeline)
+            .with_type::<NotificationFindEntity>()
+            .await?
+            .map_ok(Notification::from)
+            .try_collect()
+            .await?;
 
-        Ok(())
+        Ok(notifications)
     }
 
-    #[tokio::test]
-    async fn insert_confirmation_correct_notification_delivered_at() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
-
-        let user_id = Uuid::from_u128(41203810);
+    async fn find_many_undelivered(&self, user_id: Uuid) -> Result<Vec<Notification>, Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
+        let audience = self.audience_filter(user_id).await?;
+
+        // Muted notifications are hidden rather than confirmed,
+        // so they show up again when the user unmutes them
+        let mut filter = Self::deliverable_filter(audience, now);
+        filter.extend(self.unmuted_filter(user_id).await?);
+
+        let pipeline = [
+            doc! { "$match": filter },
+            // Notifications saved before priorities were introduced have normal priority
+            doc! {
+                "$set": {
+                    "priority": { "$ifNull": ["$priority", NotificationPriority::Normal.as_i32()] },
+                }
+            },
+            doc! { "$sort": { "priority": -1, "created_at": 1 } },
+            Self::confirmations_lookup(doc! { "user_id": user_id }),
+            doc! {
+                "$match": {
+                    "confirmations": [],
+                }
+            },
+            doc! {
+                "$project": {
+                    "_id": 1,
+                    "created_at": 1,
+                    "producer_id": 1,
+                    "priority": 1,
+                    "content_type": 1,
+                    "content": 1,
+                    "localized_contents": 1,
+                    "attachment_ids": 1,
+                }
+            },
+        ];
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "invalidate_at": None as Option<DateTime>,
-                "confirmations": [],
-            }])
+        let notifications = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<NotificationFindEntity>()
+            .await?
+            .map_ok(Notification::from)
+            .try_collect()
             .await?;
 
-        let beg = OffsetDateTime::now_utc();
-        repository.insert_confirmation(id, user_id).await?;
-        let end = OffsetDateTime::now_utc();
-
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
-        let notification_delivered_at = confirmation.get_datetime("notification_delivered_at")?;
-        let notification_delivered_at = OffsetDateTime::from(*notification_delivered_at);
-
-        // adjust precision to database's precision
-        let beg = beg.replace_millisecond(beg.millisecond()).unwrap();
-        let end = end.replace_millisecond(end.millisecond()).unwrap();
-
-        assert!(beg <= notification_delivered_at);
-        assert!(notification_delivered_at <= end);
-
-        destroy_test_database(database).await;
-
-        Ok(())
+        Ok(notifications)
     }
 
-    #[tokio::test]
-    async fn insert_confirmation_correct_notification_deleted() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
-
-        let user_id = Uuid::from_u128(41203810);
+    async fn count(&self, user_id: Uuid) -> Result<NotificationsCount, Error> {
+        let user_id = bson::Uuid::from(user_id);
+        let now = DateTime::from(OffsetDateTime::now_utc());
+        let audience = self.audience_filter(user_id).await?;
+        let unmuted = self.unmuted_filter(user_id).await?;
+
+        // Undelivered notifications are matched the same way as in find_many_undelivered
+        let mut undelivered_filter = Self::deliverable_filter(audience.clone(), now);
+        undelivered_filter.extend(unmuted.clone());
+        let undelivered_pipeline = [
+            doc! { "$match": undelivered_filter },
+            Self::confirmations_lookup(doc! { "user_id": user_id }),
+            doc! {
+                "$match": {
+                    "confirmations": [],
+                }
+            },
+            doc! { "$count": "undelivered" },
+        ];
+
+        // Delivered notifications are matched the same way as in find_many_delivered,
+        // so notifications of groups the user left are counted, while notifications
+        // of unsubscribed topics and muted notifications are not
+        let mut delivered_pipeline = vec![doc! {
+            "$match": Self::delivered_filter(user_id, &input::NotificationFilters::default())
+        }];
+        delivered_pipeline.extend(Self::delivered_notification_lookup(
+            audience,
+            unmuted,
+            None,
+            doc! { "_id": 1 },
+        ));
+        delivered_pipeline.push(doc! {
+            "$group": {
+                "_id": None as Option<i32>,
+                "delivered": { "$sum": 1 },
+                "delivered_unseen": {
+                    "$sum": { "$cond": [{ "$eq": ["$notification_seen", false] }, 1, 0] }
+                },
+            }
+        });
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "invalidate_at": None as Option<DateTime>,
-                "confirmations": [],
-            }])
-            .await?;
+        // $count and $group return no documents when no notification was matched
+        let undelivered = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(undelivered_pipeline)
+            .with_type::<NotificationsCountFindEntity>()
+            .await?
+            .try_next()
+            .await?
+            .unwrap_or_default();
+        let delivered = self
+            .database
+            .collection::<Document>(CONFIRMATIONS)
+            .aggregate(delivered_pipeline)
+            .with_type::<NotificationsCountFindEntity>()
+            .await?
+            .try_next()
+            .await?
+            .unwrap_or_default();
 
-        repository.insert_confirmation(id, user_id).await?;
+        Ok(NotificationsCount::from(NotificationsCountFindEntity {
+            undelivered: undelivered.undelivered,
+            ..delivered
+        }))
+    }
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
+    async fn purge_created_before(
+        &self,
+        created_before: OffsetDateTime,
+        producers: PurgeProducers,
+        limit: u32,
+    ) -> Result<u64, Error> {
+        let producer_id = match producers {
+            PurgeProducers::Only(producer_id) => Bson::from(bson::Uuid::from(producer_id)),
+            PurgeProducers::AllExcept(producer_ids) => Bson::from(doc! {
+                "$nin": producer_ids
+                    .into_iter()
+                    .map(bson::Uuid::from)
+                    .collect::<Vec<_>>(),
+            }),
         };
-        let deleted = confirmation.get_bool("notification_deleted")?;
 
-        assert_eq!(deleted, false);
+        let filter = doc! {
+            "created_at": { "$lt": DateTime::from(created_before) },
+            "producer_id": producer_id,
+        };
 
-        destroy_test_database(database).await;
+        let ids = self.find_purged_ids(filter, limit).await?;
 
-        Ok(())
+        self.purge(ids).await
     }
 
-    #[tokio::test]
-    async fn insert_confirmation_correct_notification_seen() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
-
-        let user_id = Uuid::from_u128(41203810);
+    async fn purge_invalidated_before(
+        &self,
+        invalidated_before: OffsetDateTime,
+        limit: u32,
+    ) -> Result<u64, Error> {
+        // Invalidated notification is still listed to users it was delivered to,
+        // so it is kept until they delete it or it reaches max age
+        let pipeline = [
+            doc! {
+                "$match": {
+                    "invalidate_at": { "$lt": DateTime::from(invalidated_before) },
+                }
+            },
+            doc! {
+                "$lookup": {
+                    "from": CONFIRMATIONS,
+                    "localField": "_id",
+                    "foreignField": "notification_id",
+                    "pipeline": [
+                        { "$match": { "notification_deleted": false } },
+                        { "$limit": 1 },
+                        { "$project": { "_id": 1 } },
+                    ],
+                    "as": "visible_confirmations",
+                }
+            },
+            doc! { "$match": { "visible_confirmations": { "$size": 0 } } },
+            doc! { "$limit": limit as i64 },
+            doc! { "$project": { "_id": 1 } },
+        ];
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "invalidate_at": None as Option<DateTime>,
-                "confirmations": [],
-            }])
+        let ids = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<NotificationIdFindEntity>()
+            .await?
+            .map_ok(|entity| entity.id)
+            .try_collect()
             .await?;
 
-        repository.insert_confirmation(id, user_id).await?;
-
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
-        let seen = confirmation.get_bool("notification_seen")?;
-
-        assert_eq!(seen, false);
-
-        destroy_test_database(database).await;
-
-        Ok(())
+        self.purge(ids).await
     }
 
-    #[tokio::test]
-    async fn insert_confirmation_already_exist() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
-
-        let user_id = Uuid::from_u128(41203810);
+    async fn purge_deleted_before(
+        &self,
+        deleted_before: OffsetDateTime,
+        limit: u32,
+    ) -> Result<u64, Error> {
+        let deleted_before = DateTime::from(deleted_before);
+        let retracted_filter = doc! {
+            "retracted_at": { "$lt": deleted_before },
+        };
 
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "invalidate_at": None as Option<DateTime>,
-                    "confirmations": [
+        // Every recipient has at most one confirmation, so when
+        // there are as many deleted confirmations as recipients
+        // then all recipients deleted notification.
+        // Members of groups and subscribers of topics may change, so notifications
+        // targeting groups or topics are never considered deleted by all recipients
+        let pipeline = [
+            doc! {
+                "$match": {
+                    "$or": [
+                        retracted_filter.clone(),
                         {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-22 12:34:31 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
+                            "created_at": { "$lt": deleted_before },
+                            "user_ids.0": { "$exists": true },
+                            "groups.0": { "$exists": false },
+                            "topic": None as Option<String>,
+                        },
+                    ]
+                }
+            },
+            doc! {
+                "$lookup": {
+                    "from": CONFIRMATIONS,
+                    "localField": "_id",
+                    "foreignField": "notification_id",
+                    "pipeline": [
+                        { "$match": { "notification_deleted": true } },
+                        { "$count": "count" },
                     ],
-                }],
-            )
+                    "as": "deleted_confirmations",
+                }
+            },
+            doc! {
+                "$match": {
+                    "$or": [
+                        retracted_filter,
+                        {
+                            "$expr": {
+                                "$eq": [
+                                    {
+                                        "$ifNull": [{ "$first": "$deleted_confirmations.count" }, 0]
+                                    },
+                                    { "$size": { "$ifNull": ["$user_ids", []] } },
+                                ]
+                            }
+                        },
+                    ]
+                }
+            },
+            doc! { "$limit": limit as i64 },
+            doc! { "$project": { "_id": 1 } },
+        ];
+
+        let ids = self
+            .database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .with_type::<NotificationIdFindEntity>()
+            .await?
+            .map_ok(|entity| entity.id)
+            .try_collect()
             .await?;
 
-        let result = repository.insert_confirmation(id, user_id).await;
+        self.purge(ids).await
+    }
 
-        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+    async fn purge_orphaned_attachments(
+        &self,
+        uploaded_before: OffsetDateTime,
+        limit: u32,
+    ) -> Result<u64, Error> {
+        let pipeline = [
+            doc! {
+                "$match": {
+                    "uploadDate": { "$lt": DateTime::from(uploaded_before) },
+                }
+            },
+            doc! {
+                "$lookup": {
+                    "from": NOTIFICATIONS,
+                    "localField": "_id",
+                    "foreignField": "attachment_ids",
+                    "pipeline": [
+                        { "$limit": 1 },
+                        { "$project": { "_id": 1 } },
+                    ],
+                    "as": "notifications",
+                }
+            },
+            doc! { "$match": { "notifications": { "$size": 0 } } },
+            doc! { "$limit": limit as i64 },
+            doc! { "$project": { "_id": 1 } },
+        ];
 
-        destroy_test_database(database).await;
+        let ids = self
+            .database
+            .collection::<Document>(ATTACHMENTS_FILES)
+            .aggregate(pipeline)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter_map(|document| document.get_object_id("_id").ok())
+            .collect::<Vec<_>>();
 
-        Ok(())
+        self.delete_attachments(ids).await
     }
+}
 
-    #[tokio::test]
-    async fn insert_confirmation_other_user_notification() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repository::entity::COMPRESSED_CONTENT_SUBTYPE;
+    use anyhow::anyhow;
+    use bson::{spec::BinarySubtype, Binary};
+    use mongodb::{options::ClientOptions, Client};
+    use std::{sync::Once, time::Duration};
+    use time::macros::datetime;
 
-        let user_id = Uuid::from_u128(41203810);
-        let other_user_id = Uuid::from_u128(809123123);
-        assert_ne!(user_id, other_user_id);
+    static BEFORE_ALL: Once = Once::new();
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(other_user_id)],
-                "invalidate_at": None as Option<DateTime>,
-                "confirmations": [],
-            }])
-            .await?;
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
 
-        let result = repository.insert_confirmation(id, user_id).await;
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
 
-        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
 
-        destroy_test_database(database).await;
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
 
-        Ok(())
-    }
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
 
-    #[tokio::test]
-    async fn insert_confirmation_multicast_notification() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let id = ObjectId::new();
+        Ok(db)
+    }
 
-        let user_1_id = Uuid::from_u128(1);
-        let user_2_id = Uuid::from_u128(2);
-        let other_user_id = Uuid::from_u128(48210380128310);
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
 
-        collection
-            .insert_many([doc! {
-                    "_id": id,
-                    "user_ids": [
-                        bson::Uuid::from(user_1_id),
-                        bson::Uuid::from(user_2_id),
-                    ],
-                    "invalidate_at": None as Option<DateTime>,
-                    "confirmations": [],
-            }])
+    ///
+    /// Inserts notifications and moves their embedded confirmations
+    /// to confirmations collection, so fixtures can keep confirmations
+    /// next to notification
+    ///
+    async fn insert_notifications(
+        database: &Database,
+        notifications: impl IntoIterator<Item = Document>,
+    ) -> anyhow::Result<()> {
+        database
+            .collection::<Document>(NOTIFICATIONS)
+            .insert_many(notifications)
             .await?;
+        NotificationsRepositoryImpl::migrate_embedded_confirmations(database).await?;
 
-        repository.insert_confirmation(id, user_1_id).await?;
-        repository.insert_confirmation(id, user_2_id).await?;
-
-        let other_user_insert_result = repository.insert_confirmation(id, other_user_id).await;
+        Ok(())
+    }
 
-        assert!(matches!(
-            other_user_insert_result,
-            Err(Error::NoDocumentUpdated)
-        ));
+    ///
+    /// Finds notification with its confirmations embedded
+    /// in the same shape as they are inserted by [insert_notifications]
+    ///
+    async fn find_notification(
+        database: &Database,
+        filter: Document,
+    ) -> anyhow::Result<Option<Document>> {
+        let pipeline = [
+            doc! { "$match": filter },
+            doc! {
+                "$lookup": {
+                    "from": CONFIRMATIONS,
+                    "localField": "_id",
+                    "foreignField": "notification_id",
+                    "pipeline": [
+                        {
+                            "$project": {
+                                "_id": 0,
+                                "user_id": 1,
+                                "notification_delivered_at": 1,
+                                "notification_seen": 1,
+                                "notification_deleted": 1,
+                            }
+                        },
+                    ],
+                    "as": "confirmations",
+                }
+            },
+        ];
 
-        destroy_test_database(database).await;
+        let document = database
+            .collection::<Document>(NOTIFICATIONS)
+            .aggregate(pipeline)
+            .await?
+            .try_next()
+            .await?;
 
-        Ok(())
+        Ok(document)
     }
 
     #[tokio::test]
-    async fn insert_confirmation_broadcast_notification_correct_user_id() -> anyhow::Result<()> {
+    async fn new_migrates_embedded_confirmations() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
         let collection = database.collection::<Document>(NOTIFICATIONS);
+        let confirmations_collection = database.collection::<Document>(CONFIRMATIONS);
 
         let id = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(841203981);
+        let user_id = bson::Uuid::from(Uuid::from_u128(1));
+        let producer_id = bson::Uuid::from(Uuid::from_u128(2));
+        let created_at = DateTime::from(datetime!(2024-01-01 00:00:00 UTC));
+        let delivered_at = DateTime::from(datetime!(2024-01-02 00:00:00 UTC));
 
         collection
-            .insert_many([doc! {
+            .insert_one(doc! {
                 "_id": id,
+                "created_at": created_at,
+                "producer_id": producer_id,
                 "user_ids": [],
-                "invalidate_at": None as Option<DateTime>,
-                "confirmations": []
-            }])
+                "confirmations": [
+                    {
+                        "user_id": user_id,
+                        "notification_delivered_at": delivered_at,
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    }
+                ]
+            })
             .await?;
 
-        repository.insert_confirmation(id, inserted_user_id).await?;
+        NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        // second start must not duplicate confirmations
+        NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
-
-        let user_id = confirmation.get("user_id").unwrap();
-        let Bson::Binary(binary) = user_id else {
-            panic!("Invalid user_id type");
-        };
-        let user_id = binary.to_uuid()?.to_uuid_1();
+        assert!(!document.contains_key("confirmations"));
 
-        assert_eq!(user_id, inserted_user_id);
+        let confirmations = confirmations_collection
+            .find(doc! {})
+            .projection(doc! { "_id": 0 })
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(
+            confirmations,
+            vec![doc! {
+                "notification_id": id,
+                "user_id": user_id,
+                "notification_created_at": created_at,
+                "notification_producer_id": producer_id,
+                "notification_delivered_at": delivered_at,
+                "notification_seen": true,
+                "notification_deleted": false,
+            }]
+        );
 
         destroy_test_database(database).await;
 
@@ -1386,34 +2863,50 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_confirmation_broadcast_notification_already_exist() -> anyhow::Result<()> {
+    async fn insert_correct_created_at() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(41203810);
+        let mut inserted_created_at = OffsetDateTime::now_utc();
 
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "user_ids": [],
-                    "invalidate_at": None as Option<DateTime>,
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-22 12:34:31 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    ],
-                }],
-            ).await?;
+        let mut notification = repository
+            .insert(
+                inserted_created_at,
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await?;
 
-        let result = repository.insert_confirmation(id, user_id).await;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let datetime = document.get_datetime("created_at")?;
+        let created_at = OffsetDateTime::from(*datetime);
 
-        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+        // Mongo keeps datetime in milliseconds so it's
+        // necessary to set micro and nanoseconds to 0
+        inserted_created_at =
+            inserted_created_at.replace_millisecond(inserted_created_at.millisecond())?;
+        notification.created_at = notification
+            .created_at
+            .replace_millisecond(notification.created_at.millisecond())?;
+
+        assert_eq!(created_at, inserted_created_at);
+        assert_eq!(created_at, notification.created_at);
 
         destroy_test_database(database).await;
 
@@ -1421,77 +2914,42 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_confirmation_broadcast_notification_other_users_confirmations_exist(
-    ) -> anyhow::Result<()> {
+    async fn insert_correct_invalidate_at() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(4);
-
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "user_ids": [],
-                    "invalidate_at": None as Option<DateTime>,
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(2)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:24:31 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(1)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:24:35 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": true,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(5)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:24:41 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(3)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:25:11 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": true,
-                        }
-                    ]
-                }]
-            ).await?;
-
-        repository.insert_confirmation(id, inserted_user_id).await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
+        let inserted_invalidate_at = datetime!(2024-01-21 19:06:25 UTC);
 
-        let contains_new_confirmation = confirmations
-            .into_iter()
-            .filter_map(|confirmation| match confirmation {
-                Bson::Document(confirmation) => Some(confirmation),
-                _ => None,
-            })
-            .any(|confirmation| {
-                let Some(user_id) = confirmation.get("user_id") else {
-                    return false;
-                };
-                let Bson::Binary(binary) = user_id else {
-                    return false;
-                };
-                let Ok(user_id) = binary.to_uuid() else {
-                    return false;
-                };
-                let user_id = Uuid::from(user_id);
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: Some(inserted_invalidate_at),
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await?;
 
-                user_id == inserted_user_id
-            });
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let datetime = document.get_datetime("invalidate_at")?;
+        let invalidate_at = OffsetDateTime::from(*datetime);
 
-        assert_eq!(contains_new_confirmation, true);
+        assert_eq!(invalidate_at, inserted_invalidate_at);
+        assert_eq!(invalidate_at, notification.invalidate_at.unwrap());
 
         destroy_test_database(database).await;
 
@@ -1499,26 +2957,41 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_confirmation_invalidate_at_passed() -> anyhow::Result<()> {
+    async fn insert_correct_invalidate_at_none() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(41203810);
+        let inserted_invalidate_at = None;
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [],
-                "invalidate_at": DateTime::from(datetime!(2024-01-22 13:49:00 UTC)),
-                "confirmations": [],
-            }])
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: inserted_invalidate_at,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let result = repository.insert_confirmation(id, user_id).await;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let invalidate_at = document.get("invalidate_at").unwrap().as_null();
 
-        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+        assert!(matches!(invalidate_at, Some(())));
+        assert!(notification.invalidate_at.is_none());
 
         destroy_test_database(database).await;
 
@@ -1526,30 +2999,49 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_confirmation_invalidate_at_not_passed() -> anyhow::Result<()> {
+    async fn insert_correct_user_ids_unicast() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(41203810);
-        let future_datetime = OffsetDateTime::now_utc() + Duration::from_secs(30 * 60);
+        let inserted_user_id = Uuid::from_u128(48190238120);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [],
-                "invalidate_at": DateTime::from(future_datetime),
-                "confirmations": [],
-            }])
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![inserted_user_id],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        repository.insert_confirmation(id, user_id).await?;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let user_ids = document.get_array("user_ids")?;
+        assert_eq!(user_ids.len(), 1);
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
+        let user_id = user_ids.first().unwrap();
+        let Bson::Binary(binary) = user_id else {
+            panic!("invalid user_id type");
+        };
+        let user_id = binary.to_uuid()?;
+        let user_id = Uuid::from(user_id);
 
-        assert_eq!(confirmations.is_empty(), false);
+        assert_eq!(user_id, inserted_user_id);
+        assert_eq!(user_id, *notification.user_ids.first().unwrap());
 
         destroy_test_database(database).await;
 
@@ -1557,67 +3049,62 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmations_correct_user_id() -> anyhow::Result<()> {
+    async fn insert_correct_user_ids_multicast() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let inserted_user_ids = vec![Uuid::from_u128(48190238120), Uuid::from_u128(11212039809)];
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 1,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 2,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 3,
-                    "confirmations": [],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: inserted_user_ids.clone(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
-            .await?;
-
-        let ids = [id_1, id_2, id_3];
-
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
+            )
             .await?;
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
-            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-                panic!("confirmations is not a document");
-            };
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let user_ids = document
+            .get_array("user_ids")?
+            .into_iter()
+            .filter_map(|bson| {
+                let Bson::Binary(binary) = bson else {
+                    return None;
+                };
+                let Ok(user_id) = binary.to_uuid() else {
+                    return None;
+                };
+                Some(user_id.into())
+            })
+            .collect::<Vec<Uuid>>();
+        assert_eq!(user_ids.len(), inserted_user_ids.len());
+        assert_eq!(user_ids.len(), notification.user_ids.len());
 
-            let user_id = confirmation.get("user_id").unwrap();
-            let Bson::Binary(binary) = user_id else {
-                panic!("Invalid user_id type");
-            };
-            let user_id = binary.to_uuid()?.to_uuid_1();
+        let database_ok = inserted_user_ids
+            .iter()
+            .all(|inserted_user_id| user_ids.contains(inserted_user_id));
+        let notification_ok = inserted_user_ids
+            .iter()
+            .all(|inserted_user_id| notification.user_ids.contains(inserted_user_id));
 
-            assert_eq!(user_id, inserted_user_id);
-        }
+        assert!(database_ok);
+        assert!(notification_ok);
 
         destroy_test_database(database).await;
 
@@ -1625,71 +3112,39 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmations_correct_notification_delivered_at() -> anyhow::Result<()> {
+    async fn insert_correct_user_ids_empty() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 1,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 2,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 3,
-                    "confirmations": [],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
-            .await?;
-
-        let ids = [id_1, id_2, id_3];
-
-        let mut beg = OffsetDateTime::now_utc();
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
+            )
             .await?;
-        let mut end = OffsetDateTime::now_utc();
-
-        // adjust to database's precision
-        beg = beg.replace_millisecond(beg.millisecond()).unwrap();
-        end = end.replace_millisecond(end.millisecond()).unwrap();
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
-            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-                panic!("confirmations is not a document");
-            };
-            let notification_delivered_at =
-                confirmation.get_datetime("notification_delivered_at")?;
-            let notification_delivered_at = OffsetDateTime::from(*notification_delivered_at);
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let user_ids = document.get_array("user_ids")?;
 
-            assert!(beg <= notification_delivered_at);
-            assert!(notification_delivered_at <= end);
-        }
+        assert!(user_ids.is_empty());
+        assert!(notification.user_ids.is_empty());
 
         destroy_test_database(database).await;
 
@@ -1697,62 +3152,46 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmations_correct_notification_seen() -> anyhow::Result<()> {
+    async fn insert_correct_producer_id() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let inserted_producer_id = Uuid::from_u128(4812038102);
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 1,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 2,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 3,
-                    "confirmations": [],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                inserted_producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
-            .await?;
-
-        let ids = [id_1, id_2, id_3];
-
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
+            )
             .await?;
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
-            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-                panic!("confirmations is not a document");
-            };
-            let notification_seen = confirmation.get_bool("notification_seen")?;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let producer_id = document.get("producer_id").unwrap();
+        let Bson::Binary(binary) = producer_id else {
+            panic!("invalid type");
+        };
+        let producer_id = binary.to_uuid()?;
+        let producer_id = Uuid::from(producer_id);
 
-            assert_eq!(notification_seen, false);
-        }
+        assert_eq!(producer_id, inserted_producer_id);
+        assert_eq!(producer_id, notification.producer_id);
 
         destroy_test_database(database).await;
 
@@ -1760,62 +3199,44 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmations_correct_notification_deleted() -> anyhow::Result<()> {
+    async fn insert_correct_producer_notification_id() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let inserted_producer_notification_id = 721;
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 1,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 2,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": None as Option<DateTime>,
-                    "producer_notification_id": 3,
-                    "confirmations": [],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(3214098123091),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: inserted_producer_notification_id,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
-            .await?;
-
-        let ids = [id_1, id_2, id_3];
-
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
+            )
             .await?;
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
-            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-                panic!("confirmations is not a document");
-            };
-            let notification_deleted = confirmation.get_bool("notification_deleted")?;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let producer_notification_id = document.get_i64("producer_notification_id")?;
 
-            assert_eq!(notification_deleted, false);
-        }
+        assert_eq!(producer_notification_id, inserted_producer_notification_id);
+        assert_eq!(
+            producer_notification_id,
+            notification.producer_notification_id
+        );
 
         destroy_test_database(database).await;
 
@@ -1823,58 +3244,41 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmations_invalidate_at_passed() -> anyhow::Result<()> {
+    async fn insert_correct_content_type() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let inserted_content_type = "json".to_string();
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
-                    "producer_notification_id": 1,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
-                    "producer_notification_id": 2,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
-                    "producer_notification_id": 3,
-                    "confirmations": [],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(1231203),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: inserted_content_type.clone(),
+                    content: br#"{"value":null}"#.to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
-            .await?;
-
-        let ids = [id_1, id_2, id_3];
-
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
+            )
             .await?;
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let content_type = document.get_str("content_type")?;
 
-            assert!(confirmations.is_empty());
-        }
+        assert_eq!(content_type, inserted_content_type);
+        assert_eq!(content_type, notification.content_type);
 
         destroy_test_database(database).await;
 
@@ -1882,58 +3286,41 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmations_invalidate_at_not_passed() -> anyhow::Result<()> {
+    async fn insert_correct_content() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let inserted_content = br#"{"value": null}"#.to_vec();
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
-                    "producer_notification_id": 1,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
-                    "producer_notification_id": 2,
-                    "confirmations": [],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
-                    "producer_notification_id": 3,
-                    "confirmations": [],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(1231203),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "json".to_string(),
+                    content: inserted_content.clone(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
-            .await?;
-
-        let ids = [id_1, id_2, id_3];
-
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
+            )
             .await?;
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let content = document.get_binary_generic("content")?;
 
-            assert_eq!(confirmations.is_empty(), false);
-        }
+        assert_eq!(*content, inserted_content);
+        assert_eq!(*content, notification.content);
 
         destroy_test_database(database).await;
 
@@ -1941,71 +3328,69 @@ mod test {
     }
 
     #[tokio::test]
-    async fn insert_many_confirmatons_skip_notifications_with_confirmations() -> anyhow::Result<()>
-    {
+    async fn insert_compressed_content() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), Some(256)).await?;
 
-        let id_1 = ObjectId::new();
-        let id_2 = ObjectId::new();
-        let id_3 = ObjectId::new();
-        let inserted_user_id = Uuid::from_u128(75917293871);
-        let other_user_id = Uuid::from_u128(12412908123);
+        let user_id = Uuid::from_u128(8129381);
+        let inserted_content = "compressible content ".repeat(64).into_bytes();
+        let localized_content = "komprimovatelný obsah ".repeat(64).into_bytes();
 
-        collection
-            .insert_many([
-                doc! {
-                    "_id": id_1,
-                    "user_ids": [],
-                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
-                    "producer_notification_id": 1,
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(inserted_user_id),
-                        }
-                    ],
-                },
-                doc! {
-                    "_id": id_2,
-                    "user_ids": [bson::Uuid::from(inserted_user_id)],
-                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
-                    "producer_notification_id": 2,
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(inserted_user_id),
-                        }
-                    ],
-                },
-                doc! {
-                    "_id": id_3,
-                    "user_ids": [
-                        bson::Uuid::from(inserted_user_id),
-                        bson::Uuid::from(other_user_id),
-                    ],
-                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
-                    "producer_notification_id": 3,
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(inserted_user_id),
-                        }
-                    ],
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(1231203),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![user_id],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: inserted_content.clone(),
+                    localized_contents: vec![input::LocalizedContent {
+                        locale: "cs-CZ".to_string(),
+                        content_type: "utf-8".to_string(),
+                        content: localized_content.clone(),
+                    }],
+                    attachment_ids: vec![],
+                    template: None,
                 },
-            ])
+            )
             .await?;
 
-        let ids = [id_1, id_2, id_3];
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let Some(Bson::Binary(content)) = document.get("content") else {
+            return Err(anyhow!("content not saved"));
+        };
+        let localized_contents = document.get_array("localized_contents")?;
+        let Some(Bson::Document(localized_content_document)) = localized_contents.first() else {
+            return Err(anyhow!("localized content not saved"));
+        };
+        let Some(Bson::Binary(stored_localized_content)) =
+            localized_content_document.get("content")
+        else {
+            return Err(anyhow!("localized content not saved"));
+        };
 
-        repository
-            .insert_many_confirmations(&ids, inserted_user_id)
-            .await?;
+        assert_eq!(content.subtype, COMPRESSED_CONTENT_SUBTYPE);
+        assert!(content.bytes.len() < inserted_content.len());
+        assert_eq!(stored_localized_content.subtype, COMPRESSED_CONTENT_SUBTYPE);
+        assert_eq!(notification.content, inserted_content);
 
-        for id in ids {
-            let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-            let confirmations = document.get_array("confirmations")?;
+        let notifications = repository.find_many_undelivered(user_id).await?;
 
-            assert_eq!(confirmations.len(), 1);
-        }
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].content, inserted_content);
+        assert_eq!(
+            notifications[0].localized_contents[0].content,
+            localized_content
+        );
 
         destroy_test_database(database).await;
 
@@ -2013,43 +3398,40 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_value_changed() -> anyhow::Result<()> {
+    async fn insert_content_below_compression_threshold() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), Some(256)).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
-        let seen = true;
-        let updated_seen = !seen;
+        let inserted_content = br#"{"value": null}"#.to_vec();
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_seen": seen,
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(1231203),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "json".to_string(),
+                    content: inserted_content.clone(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        repository
-            .update_confirmation_seen(id, user_id, updated_seen)
-            .await?;
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        let content = document.get_binary_generic("content")?;
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
-
-        let seen = confirmation.get_bool("notification_seen")?;
-
-        assert_eq!(seen, updated_seen);
+        assert_eq!(*content, inserted_content);
 
         destroy_test_database(database).await;
 
@@ -2057,43 +3439,38 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_the_same_value() -> anyhow::Result<()> {
+    async fn insert_correct_confirmations() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
-        let seen = true;
-        let updated_seen = seen;
-
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_seen": seen,
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
-            .await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        repository
-            .update_confirmation_seen(id, user_id, updated_seen)
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(1231203),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
+        let document = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
         let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
-
-        let seen = confirmation.get_bool("notification_seen")?;
 
-        assert_eq!(seen, updated_seen);
+        assert!(confirmations.is_empty());
 
         destroy_test_database(database).await;
 
@@ -2101,16 +3478,46 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_notification_not_exist() -> anyhow::Result<()> {
+    async fn insert_duplicated_producer_notification() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
+        let producer_id = Uuid::from_u128(7498127391);
+        let producer_notification_id = 672;
 
-        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+        // fixture
+        insert_notifications(
+            &database,
+            [doc! {
+                "producer_id": bson::Uuid::from(producer_id),
+                "producer_notification_id": producer_notification_id,
+            }],
+        )
+        .await?;
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+        let insert_result = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(insert_result, Err(Error::InsertUniqueViolation)));
 
         destroy_test_database(database).await;
 
@@ -2118,33 +3525,54 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_other_user_notification() -> anyhow::Result<()> {
+    async fn insert_saves_outbox_message() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let collection = database.collection::<Document>(OUTBOX);
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
-        let other_user_id = Uuid::from_u128(41820983102);
-        assert_ne!(user_id, other_user_id);
+        let user_id = Uuid::from_u128(812903810923);
+        let producer_id = Uuid::from_u128(37219837129);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(other_user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(other_user_id),
-                        "notification_seen": false,
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![user_id],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+        let document = collection
+            .find_one(doc! { "notification_id": notification.id })
+            .await?
+            .unwrap();
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+        assert_eq!(document.get_str("status")?, "NEW");
+        assert_eq!(
+            document.get_array("user_ids")?,
+            &vec![Bson::from(bson::Uuid::from(user_id))]
+        );
+        assert_eq!(
+            document.get("created_by"),
+            Some(&Bson::from(bson::Uuid::from(producer_id)))
+        );
+        assert!(!document.get_bool("seen")?);
+        assert_eq!(document.get_str("content_type")?, "utf-8");
+        assert_eq!(document.get_binary_generic("content")?, b"data");
+        assert_eq!(document.get("published_at"), Some(&Bson::Null));
 
         destroy_test_database(database).await;
 
@@ -2152,25 +3580,45 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_confirmation_does_not_exist() -> anyhow::Result<()> {
+    async fn insert_scheduled_outbox_message_publish_at() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let collection = database.collection::<Document>(OUTBOX);
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
+        let deliver_at = datetime!(9999-12-31 00:00:00 UTC);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": []
-            }])
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                Uuid::from_u128(37219837129),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: Some(deliver_at),
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+        let document = collection
+            .find_one(doc! { "notification_id": notification.id })
+            .await?
+            .unwrap();
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+        assert_eq!(notification.deliver_at, Some(deliver_at));
+        assert_eq!(
+            document.get("publish_at"),
+            Some(&Bson::from(DateTime::from(deliver_at)))
+        );
 
         destroy_test_database(database).await;
 
@@ -2178,31 +3626,50 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_notification_deleted() -> anyhow::Result<()> {
+    async fn insert_duplicated_producer_notification_no_outbox_message() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
+        let producer_id = Uuid::from_u128(7498127391);
+        let producer_notification_id = 672;
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_seen": false,
-                        "notification_deleted": true,
-                    }
-                ]
-            }])
-            .await?;
+        // fixture
+        insert_notifications(
+            &database,
+            [doc! {
+                "producer_id": bson::Uuid::from(producer_id),
+                "producer_notification_id": producer_notification_id,
+            }],
+        )
+        .await?;
 
-        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+        let insert_result = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![Uuid::from_u128(8129381)],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+        assert!(matches!(insert_result, Err(Error::InsertUniqueViolation)));
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+
+        assert_eq!(outbox_count, 0);
 
         destroy_test_database(database).await;
 
@@ -2210,50 +3677,67 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_multicast_notification() -> anyhow::Result<()> {
+    async fn insert_collapse_key_retracts_previous() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
 
         let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(7712093810);
         let user_1_id = Uuid::from_u128(1);
         let user_2_id = Uuid::from_u128(2);
-        let other_user_id = Uuid::from_u128(5810293810238);
 
-        collection
-            .insert_many([doc! {
+        // user_ids in different order and missing groups still mean the same audience
+        insert_notifications(
+            &database,
+            [doc! {
                 "_id": id,
-                "user_ids": [
-                    bson::Uuid::from(user_1_id),
-                    bson::Uuid::from(user_2_id),
-                ],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_1_id),
-                        "notification_seen": false,
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(user_2_id),
-                        "notification_seen": false,
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
-            .await?;
+                "producer_id": bson::Uuid::from(producer_id),
+                "producer_notification_id": 1_i64,
+                "collapse_key": "build-42",
+                "user_ids": [bson::Uuid::from(user_2_id), bson::Uuid::from(user_1_id)],
+            }],
+        )
+        .await?;
 
-        repository
-            .update_confirmation_seen(id, user_1_id, true)
-            .await?;
-        repository
-            .update_confirmation_seen(id, user_2_id, true)
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![user_1_id, user_2_id],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: Some("build-42".to_string()),
+                    producer_notification_id: 2,
+                    content_type: "utf-8".to_string(),
+                    content: b"done".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let update_result = repository
-            .update_confirmation_seen(id, other_user_id, true)
-            .await;
+        let superseded = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        assert!(superseded.get_datetime("retracted_at").is_ok());
 
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+        let inserted = find_notification(&database, doc! { "_id": notification.id })
+            .await?
+            .unwrap();
+        assert_eq!(inserted.get("retracted_at"), Some(&Bson::Null));
+
+        let message = outbox_collection
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
+        assert_eq!(message.get_str("status")?, "DELETED");
+        assert_eq!(message.get("published_at"), Some(&Bson::Null));
 
         destroy_test_database(database).await;
 
@@ -2261,64 +3745,107 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_broadcast_notification() -> anyhow::Result<()> {
+    async fn insert_collapse_key_other_notifications_not_retracted() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(3819028301);
-        let seen = true;
-        let updated_seen = !seen;
-
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_seen": seen,
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
-            .await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let producer_id = bson::Uuid::from(Uuid::from_u128(7712093810));
+        let other_producer_id = bson::Uuid::from(Uuid::from_u128(7712093811));
+        let user_1_id = bson::Uuid::from(Uuid::from_u128(1));
+        let user_2_id = bson::Uuid::from(Uuid::from_u128(2));
+        let ids = [
+            ObjectId::new(),
+            ObjectId::new(),
+            ObjectId::new(),
+            ObjectId::new(),
+            ObjectId::new(),
+            ObjectId::new(),
+        ];
+
+        insert_notifications(
+            &database,
+            [
+                // other producer
+                doc! {
+                    "_id": ids[0],
+                    "producer_id": other_producer_id,
+                    "producer_notification_id": 11_i64,
+                    "collapse_key": "build-42",
+                    "user_ids": [user_1_id],
+                },
+                // other collapse key
+                doc! {
+                    "_id": ids[1],
+                    "producer_id": producer_id,
+                    "producer_notification_id": 12_i64,
+                    "collapse_key": "build-43",
+                    "user_ids": [user_1_id],
+                },
+                // without collapse key
+                doc! {
+                    "_id": ids[2],
+                    "producer_id": producer_id,
+                    "producer_notification_id": 13_i64,
+                    "user_ids": [user_1_id],
+                },
+                // other user_ids
+                doc! {
+                    "_id": ids[3],
+                    "producer_id": producer_id,
+                    "producer_notification_id": 14_i64,
+                    "collapse_key": "build-42",
+                    "user_ids": [user_1_id, user_2_id],
+                },
+                // other groups
+                doc! {
+                    "_id": ids[4],
+                    "producer_id": producer_id,
+                    "producer_notification_id": 15_i64,
+                    "collapse_key": "build-42",
+                    "user_ids": [user_1_id],
+                    "groups": ["developers"],
+                },
+                // other topic
+                doc! {
+                    "_id": ids[5],
+                    "producer_id": producer_id,
+                    "producer_notification_id": 16_i64,
+                    "collapse_key": "build-42",
+                    "user_ids": [user_1_id],
+                    "topic": "builds",
+                },
+            ],
+        )
+        .await?;
 
         repository
-            .update_confirmation_seen(id, user_id, updated_seen)
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id.into(),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![user_1_id.into()],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: Some("build-42".to_string()),
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"done".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-
-        let confirmation = confirmations
-            .into_iter()
-            .filter_map(|confirmation| {
-                let Bson::Document(confirmation) = confirmation else {
-                    return None;
-                };
-                let Some(confirmation_user_id) = confirmation.get("user_id") else {
-                    return None;
-                };
-                let Bson::Binary(binary) = confirmation_user_id else {
-                    return None;
-                };
-                let Ok(confirmation_user_id) = binary.to_uuid() else {
-                    return None;
-                };
-                let confirmation_user_id = Uuid::from(confirmation_user_id);
-                match confirmation_user_id == user_id {
-                    true => Some(confirmation),
-                    false => None,
-                }
-            })
-            .next()
-            .unwrap();
-
-        let seen = confirmation.get_bool("notification_seen")?;
-
-        assert_eq!(seen, updated_seen);
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            assert!(document.get("retracted_at").is_none());
+        }
 
         destroy_test_database(database).await;
 
@@ -2326,47 +3853,71 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_broadcast_notification_confirmation_not_exist(
-    ) -> anyhow::Result<()> {
+    async fn insert_many_correct_results() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(1);
+        let producer_id = Uuid::from_u128(8819203819);
+        let user_id = Uuid::from_u128(8819203820);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(3120312093)),
-                        "notification_seen": false,
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(58102380192)),
-                        "notification_seen": false,
-                        "notification_deleted": true,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(48120312092)),
-                        "notification_seen": true,
-                        "notification_deleted": false,
+        let results = repository
+            .insert_many(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                vec![
+                    input::Notification {
+                        invalidate_at: None,
+                        deliver_at: None,
+                        user_ids: vec![user_id],
+                        groups: vec![],
+                        topic: None,
+                        priority: NotificationPriority::Normal,
+                        collapse_key: None,
+                        producer_notification_id: 1,
+                        content_type: "utf-8".to_string(),
+                        content: b"first".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                        template: None,
                     },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(81023812012)),
-                        "notification_seen": true,
-                        "notification_deleted": true,
+                    input::Notification {
+                        invalidate_at: None,
+                        deliver_at: None,
+                        user_ids: vec![],
+                        groups: vec![],
+                        topic: None,
+                        priority: NotificationPriority::Normal,
+                        collapse_key: None,
+                        producer_notification_id: 2,
+                        content_type: "utf-8".to_string(),
+                        content: b"second".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                        template: None,
                     },
-                ]
-            }])
+                ],
+            )
             .await?;
+        assert_eq!(results.len(), 2);
+
+        for (result, (producer_notification_id, content)) in
+            std::iter::zip(results, [(1, b"first".to_vec()), (2, b"second".to_vec())])
+        {
+            let notification = result?;
+            assert_eq!(
+                notification.producer_notification_id,
+                producer_notification_id
+            );
+            assert_eq!(notification.content, content);
 
-        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
-
-        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+            let document = find_notification(&database, doc! { "_id": notification.id })
+                .await?
+                .unwrap();
+            assert_eq!(
+                document.get_i64("producer_notification_id")?,
+                producer_notification_id
+            );
+        }
 
         destroy_test_database(database).await;
 
@@ -2374,80 +3925,70 @@ mod test {
     }
 
     #[tokio::test]
-    async fn update_confirmation_seen_broadcast_notification_has_multiple_confirmations(
-    ) -> anyhow::Result<()> {
+    async fn insert_many_duplicated_producer_notification() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
         let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(1);
-        let seen = true;
-        let updated_seen = !seen;
-
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(3120312093)),
-                        "notification_seen": false,
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(58102380192)),
-                        "notification_seen": false,
-                        "notification_deleted": true,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_seen": true,
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(81023812012)),
-                        "notification_seen": true,
-                        "notification_deleted": true,
-                    },
-                ]
-            }])
-            .await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let producer_id = Uuid::from_u128(8819203819);
+        let notification = |producer_notification_id| input::Notification {
+            invalidate_at: None,
+            deliver_at: None,
+            user_ids: vec![],
+            groups: vec![],
+            topic: None,
+            priority: NotificationPriority::Normal,
+            collapse_key: None,
+            producer_notification_id,
+            content_type: "utf-8".to_string(),
+            content: b"not important content".to_vec(),
+            localized_contents: vec![],
+            attachment_ids: vec![],
+            template: None,
+        };
 
         repository
-            .update_confirmation_seen(id, user_id, updated_seen)
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"not important content".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
             .await?;
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
+        let results = repository
+            .insert_many(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                vec![notification(1), notification(2), notification(2)],
+            )
+            .await?;
 
-        let confirmation = confirmations
-            .into_iter()
-            .filter_map(|confirmation| {
-                let Bson::Document(confirmation) = confirmation else {
-                    return None;
-                };
-                let Some(confirmation_user_id) = confirmation.get("user_id") else {
-                    return None;
-                };
-                let Bson::Binary(binary) = confirmation_user_id else {
-                    return None;
-                };
-                let Ok(confirmation_user_id) = binary.to_uuid() else {
-                    return None;
-                };
-                let confirmation_user_id = Uuid::from(confirmation_user_id);
-                match confirmation_user_id == user_id {
-                    true => Some(confirmation),
-                    false => None,
-                }
-            })
-            .next()
-            .unwrap();
+        assert!(matches!(results[0], Err(Error::InsertUniqueViolation)));
+        assert!(results[1].is_ok());
+        assert!(matches!(results[2], Err(Error::InsertUniqueViolation)));
 
-        let seen = confirmation.get_bool("notification_seen")?;
+        let count = collection.count_documents(doc! {}).await?;
+        assert_eq!(count, 2);
 
-        assert_eq!(seen, updated_seen);
+        let outbox_count = outbox_collection
+            .count_documents(doc! { "status": "NEW" })
+            .await?;
+        assert_eq!(outbox_count, 2);
 
         destroy_test_database(database).await;
 
@@ -2455,38 +3996,51 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_notification_deleted_set_to_true() -> anyhow::Result<()> {
+    async fn insert_many_saves_outbox_messages() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(48120381029);
+        let producer_id = Uuid::from_u128(8819203819);
+        let user_id = Uuid::from_u128(8819203820);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
+        let results = repository
+            .insert_many(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                (1..=3)
+                    .map(|producer_notification_id| input::Notification {
+                        invalidate_at: None,
+                        deliver_at: None,
+                        user_ids: vec![user_id],
+                        groups: vec![],
+                        topic: None,
+                        priority: NotificationPriority::Normal,
+                        collapse_key: None,
+                        producer_notification_id,
+                        content_type: "utf-8".to_string(),
+                        content: b"not important content".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                        template: None,
+                    })
+                    .collect(),
+            )
             .await?;
 
-        repository.delete(id, user_id).await?;
-
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document.get_array("confirmations")?;
-        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
-            panic!("confirmations is not a document");
-        };
-
-        let deleted = confirmation.get_bool("notification_deleted")?;
+        for result in results {
+            let notification = result?;
+            let document = outbox_collection
+                .find_one(doc! { "notification_id": notification.id })
+                .await?
+                .unwrap();
 
-        assert_eq!(deleted, true);
+            assert_eq!(document.get_str("status")?, "NEW");
+            assert_eq!(
+                document.get_array("user_ids")?,
+                &vec![Bson::from(bson::Uuid::from(user_id))]
+            );
+        }
 
         destroy_test_database(database).await;
 
@@ -2494,25 +4048,65 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_confirmation_not_exist() -> anyhow::Result<()> {
+    async fn insert_many_collapse_key_only_last_published() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(48120381029);
+        let producer_id = Uuid::from_u128(8819203819);
+        let user_id = Uuid::from_u128(8819203820);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": []
-            }])
+        let results = repository
+            .insert_many(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                (1..=3)
+                    .map(|producer_notification_id| input::Notification {
+                        invalidate_at: None,
+                        deliver_at: None,
+                        user_ids: vec![user_id],
+                        groups: vec![],
+                        topic: None,
+                        priority: NotificationPriority::Normal,
+                        collapse_key: Some("build-42".to_string()),
+                        producer_notification_id,
+                        content_type: "utf-8".to_string(),
+                        content: b"not important content".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                        template: None,
+                    })
+                    .collect(),
+            )
             .await?;
+        let ids = results
+            .into_iter()
+            .map(|result| result.map(|notification| notification.id))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids.len(), 3);
 
-        let delete_result = repository.delete(id, user_id).await;
+        for id in &ids[..2] {
+            let document = find_notification(&database, doc! { "_id": *id })
+                .await?
+                .unwrap();
+            assert!(document.get_datetime("retracted_at").is_ok());
 
-        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+            let message = outbox_collection
+                .find_one(doc! { "notification_id": *id })
+                .await?;
+            assert!(message.is_none());
+        }
+
+        let document = find_notification(&database, doc! { "_id": ids[2] })
+            .await?
+            .unwrap();
+        assert_eq!(document.get("retracted_at"), Some(&Bson::Null));
+
+        let message = outbox_collection
+            .find_one(doc! { "notification_id": ids[2] })
+            .await?
+            .unwrap();
+        assert_eq!(message.get_str("status")?, "NEW");
 
         destroy_test_database(database).await;
 
@@ -2520,30 +4114,39 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_already_deleted() -> anyhow::Result<()> {
+    async fn update_invalidate_at_value_updated() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let id = ObjectId::new();
-        let user_id = Uuid::from_u128(48120381029);
+        let producer_id = Uuid::from_u128(5791022193);
 
-        collection
-            .insert_many([doc! {
+        insert_notifications(
+            &database,
+            [doc! {
                 "_id": id,
-                "user_ids": [bson::Uuid::from(user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_deleted": true,
-                    }
-                ]
-            }])
+                "producer_id": bson::Uuid::from(producer_id),
+                "invalidate_at": None as Option<DateTime>,
+            }],
+        )
+        .await?;
+
+        let mut inserted_invalidate_at = OffsetDateTime::now_utc() + Duration::from_secs(300);
+        repository
+            .update_invalidate_at(id, producer_id, Some(inserted_invalidate_at))
             .await?;
 
-        let delete_result = repository.delete(id, user_id).await;
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let invalidate_at = document.get_datetime("invalidate_at")?;
+        let invalidate_at = OffsetDateTime::from(*invalidate_at);
 
-        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+        inserted_invalidate_at = inserted_invalidate_at
+            .replace_millisecond(inserted_invalidate_at.millisecond())
+            .unwrap();
+
+        assert_eq!(invalidate_at, inserted_invalidate_at);
 
         destroy_test_database(database).await;
 
@@ -2551,16 +4154,29 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_notification_not_exist() -> anyhow::Result<()> {
+    async fn update_invalidate_at_the_same_value() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let id = ObjectId::new();
-        let user_id = Uuid::from_u128(48120381029);
+        let producer_id = Uuid::from_u128(5791022193);
+        let invalidate_at = OffsetDateTime::now_utc();
 
-        let delete_result = repository.delete(id, user_id).await;
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "invalidate_at": DateTime::from(invalidate_at),
+            }],
+        )
+        .await?;
 
-        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+        let update_result = repository
+            .update_invalidate_at(id, producer_id, Some(invalidate_at))
+            .await;
+
+        assert!(update_result.is_ok());
 
         destroy_test_database(database).await;
 
@@ -2568,32 +4184,29 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_other_user_notification() -> anyhow::Result<()> {
+    async fn update_invalidate_at_id_not_exist() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let id = ObjectId::new();
-        let user_id = Uuid::from_u128(48120381029);
-        let other_user_id = Uuid::from_u128(4820193801283);
-        assert_ne!(user_id, other_user_id);
+        let other_id = ObjectId::new();
+        let producer_id = Uuid::from_u128(1);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [bson::Uuid::from(other_user_id)],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(other_user_id),
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
-            .await?;
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": other_id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "invalidate_at": None as Option<DateTime>,
+            }],
+        )
+        .await?;
 
-        let delete_result = repository.delete(id, user_id).await;
+        let update_result = repository
+            .update_invalidate_at(id, producer_id, Some(OffsetDateTime::now_utc()))
+            .await;
 
-        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
 
         destroy_test_database(database).await;
 
@@ -2601,44 +4214,74 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_multicast_notification() -> anyhow::Result<()> {
+    async fn update_invalidate_at_wrong_producer() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let producer_id = Uuid::from_u128(1);
+        let other_producer_id = Uuid::from_u128(5871923123);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(other_producer_id),
+                "invalidate_at": None as Option<DateTime>,
+            }],
+        )
+        .await?;
+
+        let update_result = repository
+            .update_invalidate_at(id, producer_id, Some(OffsetDateTime::now_utc()))
+            .await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_content_value_updated() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let id = ObjectId::new();
-        let user_1_id = Uuid::from_u128(1);
-        let user_2_id = Uuid::from_u128(2);
-        let other_user_id = Uuid::from_u128(50238210);
+        let producer_id = Uuid::from_u128(48190238012);
 
-        collection
-            .insert_many([doc! {
+        insert_notifications(
+            &database,
+            [doc! {
                 "_id": id,
-                "user_ids": [
-                    bson::Uuid::from(user_1_id),
-                    bson::Uuid::from(user_2_id),
-                ],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(user_1_id),
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(user_2_id),
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
+            }],
+        )
+        .await?;
+
+        repository
+            .update_content(
+                id,
+                producer_id,
+                "json".to_string(),
+                b"new content".to_vec(),
+                vec![],
+            )
             .await?;
 
-        let other_user_delete_result = repository.delete(id, other_user_id).await;
-        assert!(matches!(
-            other_user_delete_result,
-            Err(Error::NoDocumentUpdated)
-        ));
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
 
-        repository.delete(id, user_1_id).await?;
-        repository.delete(id, user_2_id).await?;
+        assert_eq!(document.get_str("content_type")?, "json");
+        assert_eq!(document.get_binary_generic("content")?, b"new content");
 
         destroy_test_database(database).await;
 
@@ -2646,86 +4289,249 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_broadcast_notification() -> anyhow::Result<()> {
+    async fn update_content_saves_outbox_message() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
 
         let id = ObjectId::new();
-        let user_id = Uuid::from_u128(1);
+        let producer_id = Uuid::from_u128(48190238012);
+        let user_id = Uuid::from_u128(1290381209);
 
-        collection
-            .insert_many([doc! {
+        insert_notifications(
+            &database,
+            [doc! {
                 "_id": id,
-                "user_ids": [],
-                "confirmations": [
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(48012983019283)),
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(user_id),
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(809841812308)),
-                        "notification_deleted": false,
-                    }
-                ]
-            }])
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
+            }],
+        )
+        .await?;
+
+        repository
+            .update_content(
+                id,
+                producer_id,
+                "json".to_string(),
+                b"new content".to_vec(),
+                vec![],
+            )
             .await?;
 
-        repository.delete(id, user_id).await?;
+        let document = outbox_collection
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
 
-        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
-        let confirmations = document
-            .get_array("confirmations")?
-            .into_iter()
-            .filter_map(|confirmation| match confirmation {
-                Bson::Document(confirmation) => {
-                    let Some(confirmation_user_id) = confirmation.get("user_id") else {
-                        return None;
-                    };
-                    let Bson::Binary(binary) = confirmation_user_id else {
-                        return None;
-                    };
-                    let Ok(confirmation_user_id) = binary.to_uuid() else {
-                        return None;
-                    };
-                    let confirmation_user_id = Uuid::from(confirmation_user_id);
-                    let Ok(deleted) = confirmation.get_bool("notification_deleted") else {
-                        return None;
-                    };
-                    Some((confirmation_user_id, deleted))
-                }
-                _ => None,
+        assert_eq!(document.get_str("status")?, "UPDATED");
+        assert_eq!(
+            document.get_array("user_ids")?,
+            &vec![Bson::from(bson::Uuid::from(user_id))]
+        );
+        assert_eq!(document.get_str("content_type")?, "json");
+        assert_eq!(document.get_binary_generic("content")?, b"new content");
+        assert_eq!(document.get("seen"), Some(&Bson::Null));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_content_of_pending_scheduled_notification_no_updated_message(
+    ) -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(48190238012);
+        let deliver_at = DateTime::from(OffsetDateTime::now_utc() + Duration::from_secs(3600));
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+                "deliver_at": deliver_at,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
+            }],
+        )
+        .await?;
+        outbox_collection
+            .insert_one(doc! {
+                "notification_id": id,
+                "status": "NEW",
+                "published_at": None as Option<DateTime>,
+                "publish_at": deliver_at,
+                "locked_until": None as Option<DateTime>,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
             })
-            .collect::<Vec<_>>();
-        assert_eq!(confirmations.len(), 3);
+            .await?;
 
-        let deleted = confirmations
-            .iter()
-            .filter_map(|(confirmation_user_id, deleted)| {
-                match Uuid::from(*confirmation_user_id) == user_id {
-                    true => Some(deleted),
-                    false => None,
-                }
+        repository
+            .update_content(
+                id,
+                producer_id,
+                "json".to_string(),
+                b"new content".to_vec(),
+                vec![],
+            )
+            .await?;
+
+        let outbox_documents = outbox_collection
+            .find(doc! { "notification_id": id })
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(outbox_documents.len(), 1);
+        assert_eq!(outbox_documents[0].get_str("status")?, "NEW");
+        assert_eq!(outbox_documents[0].get_str("content_type")?, "json");
+        assert_eq!(
+            outbox_documents[0].get_binary_generic("content")?,
+            b"new content"
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_content_of_locked_new_message_saves_updated_message() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(48190238012);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
+            }],
+        )
+        .await?;
+        outbox_collection
+            .insert_one(doc! {
+                "notification_id": id,
+                "status": "NEW",
+                "published_at": None as Option<DateTime>,
+                "publish_at": None as Option<DateTime>,
+                "locked_until": DateTime::from(
+                    OffsetDateTime::now_utc() + Duration::from_secs(60)
+                ),
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
             })
-            .next()
-            .expect("Could not find user_id in confirmations");
-        assert_eq!(*deleted, true);
+            .await?;
 
-        let rest_unchanged = confirmations
-            .iter()
-            .filter_map(|(confirmation_user_id, deleted)| {
-                match Uuid::from(*confirmation_user_id) != user_id {
-                    true => Some(deleted),
-                    false => None,
-                }
+        repository
+            .update_content(
+                id,
+                producer_id,
+                "json".to_string(),
+                b"new content".to_vec(),
+                vec![],
+            )
+            .await?;
+
+        let updated_count = outbox_collection
+            .count_documents(doc! {
+                "notification_id": id,
+                "status": "UPDATED",
+                "content_type": "json",
             })
-            .all(|deleted| *deleted == false);
+            .await?;
+        assert_eq!(updated_count, 1);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_content_localized_contents_updated() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(48190238012);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"old content".to_vec(),
+                },
+            }],
+        )
+        .await?;
+
+        repository
+            .update_content(
+                id,
+                producer_id,
+                "utf-8".to_string(),
+                b"new content".to_vec(),
+                vec![input::LocalizedContent {
+                    locale: "pl-PL".to_string(),
+                    content_type: "utf-8".to_string(),
+                    content: b"nowa tresc".to_vec(),
+                }],
+            )
+            .await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let outbox_document = outbox_collection
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
 
-        assert_eq!(rest_unchanged, true);
+        let localized_contents = document.get_array("localized_contents")?;
+        assert_eq!(localized_contents.len(), 1);
+        let localized_content = localized_contents[0]
+            .as_document()
+            .ok_or(anyhow!("localized content is not a document"))?;
+        assert_eq!(localized_content.get_str("locale")?, "pl-PL");
+        assert_eq!(
+            localized_content.get_binary_generic("content")?,
+            b"nowa tresc"
+        );
+        assert_eq!(outbox_document.get_array("localized_contents")?.len(), 1);
 
         destroy_test_database(database).await;
 
@@ -2733,38 +4539,5164 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_broadcast_notification_confirmation_not_exist() -> anyhow::Result<()> {
+    async fn update_content_wrong_producer() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(48190238012);
+        let other_producer_id = Uuid::from_u128(9012830912);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+            }],
+        )
+        .await?;
+
+        let update_result = repository
+            .update_content(
+                id,
+                other_producer_id,
+                "json".to_string(),
+                b"new content".to_vec(),
+                vec![],
+            )
+            .await;
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+
+        assert_eq!(outbox_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_correct_user_id() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let inserted_user_id = Uuid::from_u128(48120938012);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(inserted_user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, inserted_user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+
+        let user_id = confirmation.get("user_id").unwrap();
+        let Bson::Binary(binary) = user_id else {
+            panic!("Invalid user_id type");
+        };
+        let user_id = binary.to_uuid()?.to_uuid_1();
+
+        assert_eq!(user_id, inserted_user_id);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_correct_notification_delivered_at() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        let beg = OffsetDateTime::now_utc();
+        repository.insert_confirmation(id, user_id).await?;
+        let end = OffsetDateTime::now_utc();
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+        let notification_delivered_at = confirmation.get_datetime("notification_delivered_at")?;
+        let notification_delivered_at = OffsetDateTime::from(*notification_delivered_at);
+
+        // adjust precision to database's precision
+        let beg = beg.replace_millisecond(beg.millisecond()).unwrap();
+        let end = end.replace_millisecond(end.millisecond()).unwrap();
+
+        assert!(beg <= notification_delivered_at);
+        assert!(notification_delivered_at <= end);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_correct_notification_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+        let deleted = confirmation.get_bool("notification_deleted")?;
+
+        assert!(!deleted);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_correct_notification_seen() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+        let seen = confirmation.get_bool("notification_seen")?;
+
+        assert!(!seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_already_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-22 12:34:31 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ],
+            }],
+        )
+        .await?;
+
+        let result = repository.insert_confirmation(id, user_id).await;
+
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_other_user_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let user_id = Uuid::from_u128(41203810);
+        let other_user_id = Uuid::from_u128(809123123);
+        assert_ne!(user_id, other_user_id);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(other_user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        let result = repository.insert_confirmation(id, user_id).await;
+
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_multicast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let id = ObjectId::new();
+
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+        let other_user_id = Uuid::from_u128(48210380128310);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [
+                    bson::Uuid::from(user_1_id),
+                    bson::Uuid::from(user_2_id),
+                ],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, user_1_id).await?;
+        repository.insert_confirmation(id, user_2_id).await?;
+
+        let other_user_insert_result = repository.insert_confirmation(id, other_user_id).await;
+
+        assert!(matches!(
+            other_user_insert_result,
+            Err(Error::NoDocumentUpdated)
+        ));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_broadcast_notification_correct_user_id() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(841203981);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": []
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, inserted_user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+
+        let user_id = confirmation.get("user_id").unwrap();
+        let Bson::Binary(binary) = user_id else {
+            panic!("Invalid user_id type");
+        };
+        let user_id = binary.to_uuid()?.to_uuid_1();
+
+        assert_eq!(user_id, inserted_user_id);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_broadcast_notification_already_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-22 12:34:31 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ],
+            }],
+        )
+        .await?;
+
+        let result = repository.insert_confirmation(id, user_id).await;
+
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_broadcast_notification_other_users_confirmations_exist(
+    ) -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(4);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(2)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:24:31 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(1)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:24:35 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": true,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(5)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:24:41 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(3)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-22 13:25:11 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": true,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, inserted_user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+
+        let contains_new_confirmation = confirmations
+            .into_iter()
+            .filter_map(|confirmation| match confirmation {
+                Bson::Document(confirmation) => Some(confirmation),
+                _ => None,
+            })
+            .any(|confirmation| {
+                let Some(user_id) = confirmation.get("user_id") else {
+                    return false;
+                };
+                let Bson::Binary(binary) = user_id else {
+                    return false;
+                };
+                let Ok(user_id) = binary.to_uuid() else {
+                    return false;
+                };
+                let user_id = Uuid::from(user_id);
+
+                user_id == inserted_user_id
+            });
+
+        assert!(contains_new_confirmation);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_group_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let member_id = Uuid::from_u128(41203810);
+        let other_user_id = Uuid::from_u128(41203811);
+
+        database
+            .collection::<Document>(GROUP_MEMBERS)
+            .insert_one(doc! { "group": "admins", "user_id": bson::Uuid::from(member_id) })
+            .await?;
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "groups": ["admins"],
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        let member_result = repository.insert_confirmation(id, member_id).await;
+        let other_user_result = repository.insert_confirmation(id, other_user_id).await;
+
+        assert!(member_result.is_ok());
+        assert!(matches!(other_user_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_invalidate_at_passed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "invalidate_at": DateTime::from(datetime!(2024-01-22 13:49:00 UTC)),
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        let result = repository.insert_confirmation(id, user_id).await;
+
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_invalidate_at_not_passed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(41203810);
+        let future_datetime = OffsetDateTime::now_utc() + Duration::from_secs(30 * 60);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "invalidate_at": DateTime::from(future_datetime),
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        repository.insert_confirmation(id, user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+
+        assert!(!confirmations.is_empty());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_deliver_at_not_passed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(41203810);
+        let future_datetime = OffsetDateTime::now_utc() + Duration::from_secs(30 * 60);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [],
+                "deliver_at": DateTime::from(future_datetime),
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        let result = repository.insert_confirmation(id, user_id).await;
+
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_copies_notification_properties() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let confirmations_collection = database.collection::<Document>(CONFIRMATIONS);
+
+        let user_id = Uuid::from_u128(48120938012);
+        let producer_id = Uuid::from_u128(1823918);
+        let created_at = datetime!(2024-01-01 00:00:00 UTC);
+
+        let notification = repository
+            .insert(
+                created_at,
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![user_id],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await?;
+
+        repository
+            .insert_confirmation(notification.id, user_id)
+            .await?;
+
+        let confirmation = confirmations_collection
+            .find_one(doc! { "notification_id": notification.id })
+            .await?
+            .unwrap();
+
+        assert_eq!(
+            confirmation.get("user_id"),
+            Some(&Bson::from(bson::Uuid::from(user_id)))
+        );
+        assert_eq!(
+            confirmation.get_datetime("notification_created_at")?,
+            &DateTime::from(created_at)
+        );
+        assert_eq!(
+            confirmation.get("notification_producer_id"),
+            Some(&Bson::from(bson::Uuid::from(producer_id)))
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_correct_user_id() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 3,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+                panic!("confirmations is not a document");
+            };
+
+            let user_id = confirmation.get("user_id").unwrap();
+            let Bson::Binary(binary) = user_id else {
+                panic!("Invalid user_id type");
+            };
+            let user_id = binary.to_uuid()?.to_uuid_1();
+
+            assert_eq!(user_id, inserted_user_id);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_correct_notification_delivered_at() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 3,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        let mut beg = OffsetDateTime::now_utc();
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+        let mut end = OffsetDateTime::now_utc();
+
+        // adjust to database's precision
+        beg = beg.replace_millisecond(beg.millisecond()).unwrap();
+        end = end.replace_millisecond(end.millisecond()).unwrap();
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+                panic!("confirmations is not a document");
+            };
+            let notification_delivered_at =
+                confirmation.get_datetime("notification_delivered_at")?;
+            let notification_delivered_at = OffsetDateTime::from(*notification_delivered_at);
+
+            assert!(beg <= notification_delivered_at);
+            assert!(notification_delivered_at <= end);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_correct_notification_seen() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 3,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+                panic!("confirmations is not a document");
+            };
+            let notification_seen = confirmation.get_bool("notification_seen")?;
+
+            assert!(!notification_seen);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_correct_notification_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 3,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+                panic!("confirmations is not a document");
+            };
+            let notification_deleted = confirmation.get_bool("notification_deleted")?;
+
+            assert!(!notification_deleted);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_invalidate_at_passed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
+                    "producer_notification_id": 3,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+
+            assert!(confirmations.is_empty());
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_invalidate_at_not_passed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
+                    "producer_notification_id": 3,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+
+            assert!(!confirmations.is_empty());
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmatons_skip_notifications_with_confirmations() -> anyhow::Result<()>
+    {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+        let inserted_user_id = Uuid::from_u128(75917293871);
+        let other_user_id = Uuid::from_u128(12412908123);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [],
+                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
+                    "producer_notification_id": 1,
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(inserted_user_id),
+                        }
+                    ],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [bson::Uuid::from(inserted_user_id)],
+                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
+                    "producer_notification_id": 2,
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(inserted_user_id),
+                        }
+                    ],
+                },
+                doc! {
+                    "_id": id_3,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "user_ids": [
+                        bson::Uuid::from(inserted_user_id),
+                        bson::Uuid::from(other_user_id),
+                    ],
+                    "invalidate_at": OffsetDateTime::now_utc() + Duration::from_secs(300),
+                    "producer_notification_id": 3,
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(inserted_user_id),
+                        }
+                    ],
+                },
+            ],
+        )
+        .await?;
+
+        let ids = [id_1, id_2, id_3];
+
+        repository
+            .insert_many_confirmations(&ids, inserted_user_id)
+            .await?;
+
+        for id in ids {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+
+            assert_eq!(confirmations.len(), 1);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_value_changed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+        let seen = true;
+        let updated_seen = !seen;
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": seen,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository
+            .update_confirmation_seen(id, user_id, updated_seen)
+            .await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+
+        let seen = confirmation.get_bool("notification_seen")?;
+
+        assert_eq!(seen, updated_seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_the_same_value() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+        let seen = true;
+        let updated_seen = seen;
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": seen,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository
+            .update_confirmation_seen(id, user_id, updated_seen)
+            .await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+
+        let seen = confirmation.get_bool("notification_seen")?;
+
+        assert_eq!(seen, updated_seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_saves_outbox_message() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository
+            .update_confirmation_seen(id, user_id, true)
+            .await?;
+
+        let document = outbox_collection
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
+
+        assert_eq!(document.get_str("status")?, "UPDATED");
+        assert_eq!(
+            document.get_array("user_ids")?,
+            &vec![Bson::from(bson::Uuid::from(user_id))]
+        );
+        assert!(document.get_bool("seen")?);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_notification_not_exist_no_outbox_message(
+    ) -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let update_result = repository
+            .update_confirmation_seen(ObjectId::new(), Uuid::from_u128(3819028301), true)
+            .await;
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+
+        assert_eq!(outbox_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_notification_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+
+        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_other_user_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+        let other_user_id = Uuid::from_u128(41820983102);
+        assert_ne!(user_id, other_user_id);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(other_user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(other_user_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_confirmation_does_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": []
+            }],
+        )
+        .await?;
+
+        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_notification_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": false,
+                        "notification_deleted": true,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_multicast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+        let other_user_id = Uuid::from_u128(5810293810238);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [
+                    bson::Uuid::from(user_1_id),
+                    bson::Uuid::from(user_2_id),
+                ],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_1_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_2_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository
+            .update_confirmation_seen(id, user_1_id, true)
+            .await?;
+        repository
+            .update_confirmation_seen(id, user_2_id, true)
+            .await?;
+
+        let update_result = repository
+            .update_confirmation_seen(id, other_user_id, true)
+            .await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_broadcast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+        let seen = true;
+        let updated_seen = !seen;
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": seen,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository
+            .update_confirmation_seen(id, user_id, updated_seen)
+            .await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+
+        let confirmation = confirmations
+            .into_iter()
+            .filter_map(|confirmation| {
+                let Bson::Document(confirmation) = confirmation else {
+                    return None;
+                };
+                let Some(confirmation_user_id) = confirmation.get("user_id") else {
+                    return None;
+                };
+                let Bson::Binary(binary) = confirmation_user_id else {
+                    return None;
+                };
+                let Ok(confirmation_user_id) = binary.to_uuid() else {
+                    return None;
+                };
+                let confirmation_user_id = Uuid::from(confirmation_user_id);
+                match confirmation_user_id == user_id {
+                    true => Some(confirmation),
+                    false => None,
+                }
+            })
+            .next()
+            .unwrap();
+
+        let seen = confirmation.get_bool("notification_seen")?;
+
+        assert_eq!(seen, updated_seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_broadcast_notification_confirmation_not_exist(
+    ) -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(3120312093)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(58102380192)),
+                        "notification_seen": false,
+                        "notification_deleted": true,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(48120312092)),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(81023812012)),
+                        "notification_seen": true,
+                        "notification_deleted": true,
+                    },
+                ]
+            }],
+        )
+        .await?;
+
+        let update_result = repository.update_confirmation_seen(id, user_id, true).await;
+
+        assert!(matches!(update_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_broadcast_notification_has_multiple_confirmations(
+    ) -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+        let seen = true;
+        let updated_seen = !seen;
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(3120312093)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(58102380192)),
+                        "notification_seen": false,
+                        "notification_deleted": true,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(81023812012)),
+                        "notification_seen": true,
+                        "notification_deleted": true,
+                    },
+                ]
+            }],
+        )
+        .await?;
+
+        repository
+            .update_confirmation_seen(id, user_id, updated_seen)
+            .await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+
+        let confirmation = confirmations
+            .into_iter()
+            .filter_map(|confirmation| {
+                let Bson::Document(confirmation) = confirmation else {
+                    return None;
+                };
+                let Some(confirmation_user_id) = confirmation.get("user_id") else {
+                    return None;
+                };
+                let Bson::Binary(binary) = confirmation_user_id else {
+                    return None;
+                };
+                let Ok(confirmation_user_id) = binary.to_uuid() else {
+                    return None;
+                };
+                let confirmation_user_id = Uuid::from(confirmation_user_id);
+                match confirmation_user_id == user_id {
+                    true => Some(confirmation),
+                    false => None,
+                }
+            })
+            .next()
+            .unwrap();
+
+        let seen = confirmation.get_bool("notification_seen")?;
+
+        assert_eq!(seen, updated_seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_many_confirmations_seen_by_ids() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let ids = [ObjectId::new(), ObjectId::new(), ObjectId::new()];
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            ids.map(|id| {
+                doc! {
+                    "_id": id,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .update_many_confirmations_seen(
+                user_id,
+                input::NotificationsSelection {
+                    ids: Some(vec![ids[0], ids[1]]),
+                    filters: None,
+                },
+                true,
+            )
+            .await?;
+        assert_eq!(count, 2);
+
+        for (id, expected_seen) in std::iter::zip(ids, [true, true, false]) {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+                panic!("confirmations is not a document");
+            };
+            assert_eq!(confirmation.get_bool("notification_seen")?, expected_seen);
+        }
+
+        let outbox_messages = outbox_collection
+            .find(doc! {
+                "status": "UPDATED",
+                "seen": true,
+            })
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(outbox_messages.len(), 1);
+        let outbox_ids = outbox_messages[0]
+            .get_array("notification_ids")?
+            .iter()
+            .map(|id| id.as_object_id().unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(outbox_ids, HashSet::from([ids[0], ids[1]]));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_many_confirmations_seen_skips_unchanged() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let seen_id = ObjectId::new();
+        let unseen_id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [(seen_id, true), (unseen_id, false)].map(|(id, seen)| {
+                doc! {
+                    "_id": id,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": seen,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .update_many_confirmations_seen(
+                user_id,
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters::default()),
+                },
+                true,
+            )
+            .await?;
+        assert_eq!(count, 1);
+
+        let outbox_ids = outbox_collection
+            .distinct("notification_id", doc! {})
+            .await?;
+        assert_eq!(outbox_ids, vec![Bson::from(unseen_id)]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_many_confirmations_seen_by_filters() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let confirmations_collection = database.collection::<Document>(CONFIRMATIONS);
+
+        let old_id = ObjectId::new();
+        let new_id = ObjectId::new();
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [
+                (old_id, datetime!(2024-01-01 00:00 UTC)),
+                (new_id, datetime!(2024-02-01 00:00 UTC)),
+            ]
+            .map(|(id, created_at)| {
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(created_at),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .update_many_confirmations_seen(
+                user_id,
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters {
+                        created_at_to: Some(datetime!(2024-01-15 00:00 UTC)),
+                        ..Default::default()
+                    }),
+                },
+                true,
+            )
+            .await?;
+        assert_eq!(count, 1);
+
+        let seen_count = confirmations_collection
+            .count_documents(doc! {
+                "notification_seen": true,
+            })
+            .await?;
+        let old_seen_count = confirmations_collection
+            .count_documents(doc! {
+                "notification_id": old_id,
+                "notification_seen": true,
+            })
+            .await?;
+        assert_eq!(seen_count, 1);
+        assert_eq!(old_seen_count, 1);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_many_confirmations_seen_in_batches() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let user_id = Uuid::from_u128(3819028301);
+        let notifications_count = SELECTION_BATCH_SIZE as usize + 1;
+
+        insert_notifications(
+            &database,
+            (0..notifications_count).map(|_| {
+                doc! {
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .update_many_confirmations_seen(
+                user_id,
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters::default()),
+                },
+                true,
+            )
+            .await?;
+        assert_eq!(count, notifications_count as u64);
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+        assert_eq!(outbox_count, 2);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_many_confirmations_seen_skips_deleted_and_other_users() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let user_id = Uuid::from_u128(3819028301);
+        let other_user_id = Uuid::from_u128(3819028302);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": true,
+                        }
+                    ]
+                },
+                doc! {
+                    "user_ids": [bson::Uuid::from(other_user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(other_user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .update_many_confirmations_seen(
+                user_id,
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters::default()),
+                },
+                true,
+            )
+            .await?;
+        assert_eq!(count, 0);
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+        assert_eq!(outbox_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_notification_deleted_set_to_true() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository.delete(id, user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document.get_array("confirmations")?;
+        let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+            panic!("confirmations is not a document");
+        };
+
+        let deleted = confirmation.get_bool("notification_deleted")?;
+
+        assert!(deleted);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_saves_outbox_message() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository.delete(id, user_id).await?;
+
+        let document = outbox_collection
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
+
+        assert_eq!(document.get_str("status")?, "DELETED");
+        assert_eq!(
+            document.get_array("user_ids")?,
+            &vec![Bson::from(bson::Uuid::from(user_id))]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_already_deleted_no_outbox_message() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_deleted": true,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let delete_result = repository.delete(id, user_id).await;
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+
+        assert_eq!(outbox_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_confirmation_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": []
+            }],
+        )
+        .await?;
+
+        let delete_result = repository.delete(id, user_id).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_already_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_deleted": true,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let delete_result = repository.delete(id, user_id).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_notification_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+
+        let delete_result = repository.delete(id, user_id).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_other_user_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(48120381029);
+        let other_user_id = Uuid::from_u128(4820193801283);
+        assert_ne!(user_id, other_user_id);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [bson::Uuid::from(other_user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(other_user_id),
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let delete_result = repository.delete(id, user_id).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_multicast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+        let other_user_id = Uuid::from_u128(50238210);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [
+                    bson::Uuid::from(user_1_id),
+                    bson::Uuid::from(user_2_id),
+                ],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_1_id),
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_2_id),
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let other_user_delete_result = repository.delete(id, other_user_id).await;
+        assert!(matches!(
+            other_user_delete_result,
+            Err(Error::NoDocumentUpdated)
+        ));
+
+        repository.delete(id, user_1_id).await?;
+        repository.delete(id, user_2_id).await?;
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_broadcast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(48012983019283)),
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(809841812308)),
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        repository.delete(id, user_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+        let confirmations = document
+            .get_array("confirmations")?
+            .into_iter()
+            .filter_map(|confirmation| match confirmation {
+                Bson::Document(confirmation) => {
+                    let Some(confirmation_user_id) = confirmation.get("user_id") else {
+                        return None;
+                    };
+                    let Bson::Binary(binary) = confirmation_user_id else {
+                        return None;
+                    };
+                    let Ok(confirmation_user_id) = binary.to_uuid() else {
+                        return None;
+                    };
+                    let confirmation_user_id = Uuid::from(confirmation_user_id);
+                    let Ok(deleted) = confirmation.get_bool("notification_deleted") else {
+                        return None;
+                    };
+                    Some((confirmation_user_id, deleted))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(confirmations.len(), 3);
+
+        let deleted = confirmations
+            .iter()
+            .filter_map(|(confirmation_user_id, deleted)| {
+                match Uuid::from(*confirmation_user_id) == user_id {
+                    true => Some(deleted),
+                    false => None,
+                }
+            })
+            .next()
+            .expect("Could not find user_id in confirmations");
+        assert!(*deleted);
+
+        let rest_unchanged = confirmations
+            .iter()
+            .filter_map(|(confirmation_user_id, deleted)| {
+                match Uuid::from(*confirmation_user_id) != user_id {
+                    true => Some(deleted),
+                    false => None,
+                }
+            })
+            .all(|deleted| !*deleted);
+
+        assert!(rest_unchanged);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_broadcast_notification_confirmation_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(48012983019283)),
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(3841200192)),
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(809841812308)),
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let delete_result = repository.delete(id, user_id).await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_many_by_ids() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let ids = [ObjectId::new(), ObjectId::new(), ObjectId::new()];
+        let user_id = Uuid::from_u128(5810293810);
+
+        insert_notifications(
+            &database,
+            ids.map(|id| {
+                doc! {
+                    "_id": id,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .delete_many(
+                user_id,
+                input::NotificationsSelection {
+                    ids: Some(vec![ids[0], ids[1]]),
+                    filters: None,
+                },
+            )
+            .await?;
+        assert_eq!(count, 2);
+
+        for (id, expected_deleted) in std::iter::zip(ids, [true, true, false]) {
+            let document = find_notification(&database, doc! { "_id": id })
+                .await?
+                .unwrap();
+            let confirmations = document.get_array("confirmations")?;
+            let Bson::Document(confirmation) = confirmations.first().unwrap() else {
+                panic!("confirmations is not a document");
+            };
+            assert_eq!(
+                confirmation.get_bool("notification_deleted")?,
+                expected_deleted
+            );
+        }
+
+        let outbox_count = outbox_collection
+            .count_documents(doc! {
+                "notification_id": { "$in": [ids[0], ids[1]] },
+                "status": "DELETED",
+                "user_ids": [bson::Uuid::from(user_id)],
+            })
+            .await?;
+        assert_eq!(outbox_count, 2);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_many_by_filters() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let confirmations_collection = database.collection::<Document>(CONFIRMATIONS);
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let old_seen_id = ObjectId::new();
+        let old_unseen_id = ObjectId::new();
+        let new_seen_id = ObjectId::new();
+        let user_id = Uuid::from_u128(5810293810);
+
+        insert_notifications(
+            &database,
+            [
+                (old_seen_id, datetime!(2024-01-01 00:00 UTC), true),
+                (old_unseen_id, datetime!(2024-01-01 00:00 UTC), false),
+                (new_seen_id, datetime!(2024-02-01 00:00 UTC), true),
+            ]
+            .map(|(id, created_at, seen)| {
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(created_at),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": seen,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .delete_many(
+                user_id,
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters {
+                        seen: Some(true),
+                        created_at_to: Some(datetime!(2024-01-15 00:00 UTC)),
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await?;
+        assert_eq!(count, 1);
+
+        let deleted_ids = confirmations_collection
+            .distinct("notification_id", doc! { "notification_deleted": true })
+            .await?;
+        assert_eq!(deleted_ids, vec![Bson::from(old_seen_id)]);
+
+        let outbox_ids = outbox_collection
+            .distinct("notification_id", doc! {})
+            .await?;
+        assert_eq!(outbox_ids, vec![Bson::from(old_seen_id)]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_many_skips_deleted_retracted_and_other_users() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let user_id = Uuid::from_u128(5810293810);
+        let other_user_id = Uuid::from_u128(5810293811);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": true,
+                        }
+                    ]
+                },
+                doc! {
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "retracted_at": DateTime::from(datetime!(2024-01-01 00:00 UTC)),
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                doc! {
+                    "user_ids": [bson::Uuid::from(other_user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(other_user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .delete_many(
+                user_id,
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters::default()),
+                },
+            )
+            .await?;
+        assert_eq!(count, 0);
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+        assert_eq!(outbox_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_many_broadcast_notification_only_user_confirmation() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let confirmations_collection = database.collection::<Document>(CONFIRMATIONS);
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(5810293810);
+        let other_user_id = Uuid::from_u128(5810293811);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(other_user_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let count = repository
+            .delete_many(
+                user_id,
+                input::NotificationsSelection {
+                    ids: Some(vec![id]),
+                    filters: None,
+                },
+            )
+            .await?;
+        assert_eq!(count, 1);
+
+        let other_user_deleted_count = confirmations_collection
+            .count_documents(doc! {
+                "user_id": bson::Uuid::from(other_user_id),
+                "notification_deleted": true,
+            })
+            .await?;
+        let user_deleted_count = confirmations_collection
+            .count_documents(doc! {
+                "user_id": bson::Uuid::from(user_id),
+                "notification_deleted": true,
+            })
+            .await?;
+        assert_eq!(other_user_deleted_count, 0);
+        assert_eq!(user_deleted_count, 1);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retract_retracted_at_set() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+            }],
+        )
+        .await?;
+
+        repository.retract(id, producer_id).await?;
+
+        let document = find_notification(&database, doc! { "_id": id })
+            .await?
+            .unwrap();
+
+        assert!(document.get_datetime("retracted_at").is_ok());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retract_saves_outbox_message() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [
+                    bson::Uuid::from(user_1_id),
+                    bson::Uuid::from(user_2_id),
+                ],
+            }],
+        )
+        .await?;
+
+        repository.retract(id, producer_id).await?;
+
+        let document = outbox_collection
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
+
+        assert_eq!(document.get_str("status")?, "DELETED");
+        assert_eq!(
+            document.get_array("user_ids")?,
+            &vec![
+                Bson::from(bson::Uuid::from(user_1_id)),
+                Bson::from(bson::Uuid::from(user_2_id)),
+            ]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retract_removes_unpublished_new_outbox_message() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let producer_id = Uuid::from_u128(8129038123);
+
+        let notification = repository
+            .insert(
+                OffsetDateTime::now_utc(),
+                producer_id,
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: Some(datetime!(9999-12-31 00:00:00 UTC)),
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await?;
+
+        repository.retract(notification.id, producer_id).await?;
+
+        let statuses = outbox_collection
+            .find(doc! { "notification_id": notification.id })
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(|document| document.get_str("status").map(str::to_string))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(statuses, vec!["DELETED".to_string()]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retract_wrong_producer() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+        let other_producer_id = Uuid::from_u128(471982371);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+            }],
+        )
+        .await?;
+
+        let retract_result = repository.retract(id, other_producer_id).await;
+        assert!(matches!(retract_result, Err(Error::NoDocumentUpdated)));
+
+        let outbox_count = outbox_collection.count_documents(doc! {}).await?;
+
+        assert_eq!(outbox_count, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retract_already_retracted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+                "retracted_at": DateTime::now(),
+            }],
+        )
+        .await?;
+
+        let retract_result = repository.retract(id, producer_id).await;
+
+        assert!(matches!(retract_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retract_hides_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let delivered_id = ObjectId::new();
+        let undelivered_id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": delivered_id,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                doc! {
+                    "_id": undelivered_id,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content".to_vec(),
+                    },
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        repository.retract(delivered_id, producer_id).await?;
+        repository.retract(undelivered_id, producer_id).await?;
+
+        let delivered = repository
+            .find_many_delivered(
+                user_id,
+                input::Pagination {
+                    page_idx: Some(0),
+                    page_size: 10,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+            )
+            .await?;
+        assert!(delivered.is_empty());
+
+        let delivered = repository.find_delivered(delivered_id, user_id).await?;
+        assert!(delivered.is_none());
+
+        let undelivered = repository.find_many_undelivered(user_id).await?;
+        assert!(undelivered.is_empty());
+
+        let seen_result = repository
+            .update_confirmation_seen(delivered_id, user_id, true)
+            .await;
+        assert!(matches!(seen_result, Err(Error::NoDocumentUpdated)));
+
+        let delete_result = repository.delete(delivered_id, user_id).await;
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        let confirmation_result = repository
+            .insert_confirmation(undelivered_id, user_id)
+            .await;
+        assert!(matches!(confirmation_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_multicast() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+        let member_id = Uuid::from_u128(3);
+        let subscriber_id = Uuid::from_u128(4);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [bson::Uuid::from(user_1_id), bson::Uuid::from(user_2_id)],
+                "groups": ["staff"],
+                "topic": "orders",
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(member_id),
+                        "notification_delivered_at":
+                            DateTime::from(datetime!(2024-01-22 12:34:35 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": true,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_1_id),
+                        "notification_delivered_at":
+                            DateTime::from(datetime!(2024-01-22 12:34:31 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    },
+                ]
+            }],
+        )
+        .await?;
+        database
+            .collection::<Document>(GROUP_MEMBERS)
+            .insert_one(doc! { "group": "staff", "user_id": bson::Uuid::from(member_id) })
+            .await?;
+        database
+            .collection::<Document>(SUBSCRIPTIONS)
+            .insert_one(doc! {
+                "user_id": bson::Uuid::from(subscriber_id),
+                "topic": "orders",
+                "created_at": DateTime::now(),
+            })
+            .await?;
+
+        let report = repository
+            .find_delivery_report(id, producer_id, delivery_report_pagination(10, None, None))
+            .await?
+            .unwrap();
+
+        let confirmations = report
+            .confirmations
+            .iter()
+            .map(|confirmation| {
+                (
+                    confirmation.user_id,
+                    confirmation.delivered_at,
+                    confirmation.seen,
+                    confirmation.deleted,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            confirmations,
+            vec![
+                (user_1_id, datetime!(2024-01-22 12:34:31 UTC), true, false),
+                (member_id, datetime!(2024-01-22 12:34:35 UTC), false, true),
+            ]
+        );
+        assert_eq!(report.confirmed_count, 2);
+        assert_eq!(report.pending_count, Some(2));
+        assert_eq!(
+            report.pending_user_ids,
+            Some(vec![user_2_id, subscriber_id])
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_pages() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+        let delivered_at = datetime!(2024-01-22 12:34:31 UTC);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": (1..=5)
+                    .map(|user_id| bson::Uuid::from(Uuid::from_u128(user_id)))
+                    .collect::<Vec<_>>(),
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(2)),
+                        "notification_delivered_at": DateTime::from(delivered_at),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(4)),
+                        "notification_delivered_at": DateTime::from(delivered_at),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                ]
+            }],
+        )
+        .await?;
+
+        let first_page = repository
+            .find_delivery_report(id, producer_id, delivery_report_pagination(1, None, None))
+            .await?
+            .unwrap();
+        let last_confirmation = &first_page.confirmations[0];
+        let second_page = repository
+            .find_delivery_report(
+                id,
+                producer_id,
+                delivery_report_pagination(
+                    1,
+                    Some(input::ConfirmationsCursor {
+                        delivered_at: last_confirmation.delivered_at,
+                        id: last_confirmation.id,
+                    }),
+                    Some(Uuid::from_u128(1)),
+                ),
+            )
+            .await?
+            .unwrap();
+
+        assert_eq!(first_page.confirmed_count, 2);
+        assert_eq!(first_page.confirmations.len(), 1);
+        assert_eq!(first_page.pending_count, Some(3));
+        assert_eq!(first_page.pending_user_ids, Some(vec![Uuid::from_u128(1)]));
+        assert_eq!(second_page.confirmed_count, 2);
+        assert_eq!(second_page.confirmations.len(), 1);
+        assert_ne!(second_page.confirmations[0].id, last_confirmation.id);
+        assert_eq!(second_page.pending_count, Some(3));
+        assert_eq!(second_page.pending_user_ids, Some(vec![Uuid::from_u128(3)]));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_broadcast() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(8129038123);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(1)),
+                        "notification_delivered_at": DateTime::now(),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                ]
+            }],
+        )
+        .await?;
+
+        let report = repository
+            .find_delivery_report(id, producer_id, delivery_report_pagination(10, None, None))
+            .await?
+            .unwrap();
+
+        assert_eq!(report.confirmed_count, 1);
+        assert_eq!(report.confirmations.len(), 1);
+        assert!(report.pending_count.is_none());
+        assert!(report.pending_user_ids.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_wrong_producer() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(8129038123)),
+                "user_ids": [],
+                "confirmations": [],
+            }],
+        )
+        .await?;
+
+        let report = repository
+            .find_delivery_report(
+                id,
+                Uuid::from_u128(8129038124),
+                delivery_report_pagination(10, None, None),
+            )
+            .await?;
+
+        assert!(report.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    fn delivery_report_pagination(
+        page_size: u32,
+        confirmations_cursor: Option<input::ConfirmationsCursor>,
+        pending_cursor: Option<Uuid>,
+    ) -> input::DeliveryReportPagination {
+        input::DeliveryReportPagination {
+            page_size,
+            confirmations_cursor,
+            pending_cursor,
+        }
+    }
+
+    fn produced_notification_document(
+        id: ObjectId,
+        producer_id: Uuid,
+        producer_notification_id: i64,
+        created_at: OffsetDateTime,
+        content_type: &str,
+        confirmations: Vec<Document>,
+    ) -> Document {
+        doc! {
+            "_id": id,
+            "created_at": DateTime::from(created_at),
+            "retracted_at": None as Option<DateTime>,
+            "user_ids": [bson::Uuid::from(Uuid::from_u128(1))],
+            "producer_id": bson::Uuid::from(producer_id),
+            "producer_notification_id": producer_notification_id,
+            "content_type": content_type,
+            "content": Binary {
+                subtype: BinarySubtype::Generic,
+                bytes: b"notification".to_vec(),
+            },
+            "confirmations": confirmations,
+        }
+    }
+
+    fn produced_confirmation_document(user_id: u128, seen: bool, deleted: bool) -> Document {
+        doc! {
+            "user_id": bson::Uuid::from(Uuid::from_u128(user_id)),
+            "notification_delivered_at": DateTime::now(),
+            "notification_seen": seen,
+            "notification_deleted": deleted,
+        }
+    }
+
+    #[tokio::test]
+    async fn find_many_produced_with_delivery_counts() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let producer_id = Uuid::from_u128(8129038123);
+        let confirmed_id = ObjectId::new();
+        let retracted_id = ObjectId::new();
+        let mut retracted = produced_notification_document(
+            retracted_id,
+            producer_id,
+            2,
+            datetime!(2024-01-02 00:00 UTC),
+            "utf-8",
+            vec![],
+        );
+        retracted.insert("retracted_at", DateTime::now());
+
+        insert_notifications(
+            &database,
+            [
+                produced_notification_document(
+                    confirmed_id,
+                    producer_id,
+                    1,
+                    datetime!(2024-01-01 00:00 UTC),
+                    "utf-8",
+                    vec![
+                        produced_confirmation_document(1, true, false),
+                        produced_confirmation_document(2, true, true),
+                        produced_confirmation_document(3, false, false),
+                    ],
+                ),
+                retracted,
+                produced_notification_document(
+                    ObjectId::new(),
+                    Uuid::from_u128(8129038124),
+                    1,
+                    datetime!(2024-01-03 00:00 UTC),
+                    "utf-8",
+                    vec![],
+                ),
+            ],
+        )
+        .await?;
+
+        let notifications = repository
+            .find_many_produced(
+                producer_id,
+                input::Pagination {
+                    page_idx: None,
+                    page_size: u32::MAX,
+                    cursor: None,
+                },
+                input::ProducedNotificationFilters::default(),
+            )
+            .await?;
+
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0].id, retracted_id);
+        assert!(notifications[0].retracted_at.is_some());
+        assert_eq!(notifications[0].confirmed_count, 0);
+        assert_eq!(notifications[1].id, confirmed_id);
+        assert_eq!(notifications[1].producer_notification_id, 1);
+        assert_eq!(notifications[1].user_ids, vec![Uuid::from_u128(1)]);
+        assert_eq!(notifications[1].content, b"notification");
+        assert_eq!(notifications[1].confirmed_count, 3);
+        assert_eq!(notifications[1].seen_count, 2);
+        assert_eq!(notifications[1].deleted_count, 1);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    async fn find_many_produced_ids(
+        repository: &NotificationsRepositoryImpl,
+        producer_id: Uuid,
+        page_size: u32,
+        cursor: Option<input::NotificationsCursor>,
+        filters: input::ProducedNotificationFilters,
+    ) -> anyhow::Result<Vec<ObjectId>> {
+        let notifications = repository
+            .find_many_produced(
+                producer_id,
+                input::Pagination {
+                    page_idx: None,
+                    page_size,
+                    cursor,
+                },
+                filters,
+            )
+            .await?;
+
+        Ok(notifications
+            .into_iter()
+            .map(|notification| notification.id)
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn find_many_produced_cursor_pagination_and_filters() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let producer_id = Uuid::from_u128(8129038123);
+        let ids = [ObjectId::new(), ObjectId::new(), ObjectId::new()];
+        let created_at = [
+            datetime!(2024-01-01 00:00 UTC),
+            datetime!(2024-01-02 00:00 UTC),
+            datetime!(2024-01-03 00:00 UTC),
+        ];
+        let content_types = ["utf-8", "json", "json"];
+        let mut documents = (0..3)
+            .map(|i| {
+                produced_notification_document(
+                    ids[i],
+                    producer_id,
+                    i as i64,
+                    created_at[i],
+                    content_types[i],
+                    vec![],
+                )
+            })
+            .collect::<Vec<_>>();
+        documents[2].insert("retracted_at", DateTime::now());
+        insert_notifications(&database, documents).await?;
+
+        let page =
+            find_many_produced_ids(&repository, producer_id, 2, None, Default::default()).await?;
+        assert_eq!(page, vec![ids[2], ids[1]]);
+
+        let cursor = input::NotificationsCursor {
+            created_at: created_at[1],
+            id: ids[1],
+        };
+        let page = find_many_produced_ids(
+            &repository,
+            producer_id,
+            2,
+            Some(cursor),
+            Default::default(),
+        )
+        .await?;
+        assert_eq!(page, vec![ids[0]]);
+
+        let page = find_many_produced_ids(
+            &repository,
+            producer_id,
+            10,
+            None,
+            input::ProducedNotificationFilters {
+                content_type: Some("json".to_string()),
+                retracted: Some(false),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(page, vec![ids[1]]);
+
+        let page = find_many_produced_ids(
+            &repository,
+            producer_id,
+            10,
+            None,
+            input::ProducedNotificationFilters {
+                created_at_from: Some(created_at[1]),
+                order: Some(input::NotificationsOrder::Asc),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(page, vec![ids[1], ids[2]]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_produced_by_producer_notification_id() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let producer_id = Uuid::from_u128(8129038123);
+        let id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [
+                produced_notification_document(
+                    id,
+                    producer_id,
+                    7,
+                    datetime!(2024-01-01 00:00 UTC),
+                    "utf-8",
+                    vec![produced_confirmation_document(1, false, false)],
+                ),
+                produced_notification_document(
+                    ObjectId::new(),
+                    Uuid::from_u128(8129038124),
+                    8,
+                    datetime!(2024-01-01 00:00 UTC),
+                    "utf-8",
+                    vec![],
+                ),
+            ],
+        )
+        .await?;
+
+        let notification = repository.find_produced(producer_id, 7).await?.unwrap();
+        assert_eq!(notification.id, id);
+        assert_eq!(notification.confirmed_count, 1);
+        assert_eq!(notification.seen_count, 0);
+
+        let notification = repository.find_produced(producer_id, 8).await?;
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    async fn insert_webhook(database: &Database, producer_id: Uuid) -> anyhow::Result<()> {
+        database
+            .collection::<Document>(WEBHOOKS)
+            .insert_one(doc! {
+                "_id": bson::Uuid::from(producer_id),
+                "url": "http://localhost/receipts",
+                "secret": "0123456789abcdef",
+                "updated_at": DateTime::now(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_receipt_events(
+        database: &Database,
+        notification_id: ObjectId,
+    ) -> anyhow::Result<Vec<String>> {
+        let events = database
+            .collection::<Document>(RECEIPTS)
+            .find(doc! { "notification_id": notification_id })
+            .sort(doc! { "created_at": 1 })
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter_map(|document| document.get_str("event").ok().map(str::to_string))
+            .collect();
+
+        Ok(events)
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_saves_receipt_of_webhook_producer() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(1);
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+        insert_webhook(&database, producer_id).await?;
+
+        repository.insert_confirmation(id, user_id).await?;
+
+        let receipt = database
+            .collection::<Document>(RECEIPTS)
+            .find_one(doc! { "notification_id": id })
+            .await?
+            .unwrap();
+        assert_eq!(receipt.get_str("event")?, "DELIVERED");
+        assert_eq!(
+            receipt.get("producer_id"),
+            Some(&Bson::from(bson::Uuid::from(producer_id)))
+        );
+        assert_eq!(
+            receipt.get("user_id"),
+            Some(&Bson::from(bson::Uuid::from(user_id)))
+        );
+        assert_eq!(receipt.get_i64("attempts")?, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_confirmation_no_receipt_without_webhook() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(41203810);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::now(),
+                "producer_id": bson::Uuid::from(Uuid::from_u128(1)),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "invalidate_at": None as Option<DateTime>,
+                "confirmations": [],
+            }],
+        )
+        .await?;
+        insert_webhook(&database, Uuid::from_u128(2)).await?;
+
+        repository.insert_confirmation(id, user_id).await?;
+
+        assert!(find_receipt_events(&database, id).await?.is_empty());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_many_confirmations_saves_receipts_of_inserted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let producer_id = Uuid::from_u128(1);
+        let user_id = Uuid::from_u128(75917293871);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id_1,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 1,
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": id_2,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "invalidate_at": None as Option<DateTime>,
+                    "producer_notification_id": 2,
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+        insert_webhook(&database, producer_id).await?;
+
+        repository.insert_confirmation(id_1, user_id).await?;
+        repository
+            .insert_many_confirmations(&[id_1, id_2], user_id)
+            .await?;
+
+        assert_eq!(
+            find_receipt_events(&database, id_1).await?,
+            vec!["DELIVERED"]
+        );
+        assert_eq!(
+            find_receipt_events(&database, id_2).await?,
+            vec!["DELIVERED"]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_confirmation_seen_and_delete_save_receipts() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let producer_id = Uuid::from_u128(1);
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "producer_id": bson::Uuid::from(producer_id),
+                "user_ids": [bson::Uuid::from(user_id)],
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+        insert_webhook(&database, producer_id).await?;
+
+        repository
+            .update_confirmation_seen(id, user_id, true)
+            .await?;
+        repository
+            .update_confirmation_seen(id, user_id, false)
+            .await?;
+        repository.delete(id, user_id).await?;
+
+        assert_eq!(
+            find_receipt_events(&database, id).await?,
+            vec!["SEEN", "UNSEEN", "DELETED"]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_many_confirmations_seen_and_delete_many_save_receipts() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let producer_id = Uuid::from_u128(1);
+        let user_id = Uuid::from_u128(3819028301);
+
+        insert_notifications(
+            &database,
+            [id_1, id_2].map(|id| {
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "content_type": "utf-8",
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::now(),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                }
+            }),
+        )
+        .await?;
+        insert_webhook(&database, producer_id).await?;
+
+        repository
+            .update_many_confirmations_seen(
+                user_id,
+                input::NotificationsSelection {
+                    ids: Some(vec![id_1, id_2]),
+                    filters: None,
+                },
+                true,
+            )
+            .await?;
+        repository
+            .delete_many(
+                user_id,
+                input::NotificationsSelection {
+                    ids: Some(vec![id_1]),
+                    filters: None,
+                },
+            )
+            .await?;
+
+        assert_eq!(
+            find_receipt_events(&database, id_1).await?,
+            vec!["SEEN", "DELETED"]
+        );
+        assert_eq!(find_receipt_events(&database, id_2).await?, vec!["SEEN"]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_correct_id() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let other_id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+        assert_ne!(id, other_id);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                doc! {
+                    "_id": other_id,
+                    "created_at": DateTime::from(datetime!(2024-01-28 11:42:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:43:11 UTC)),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+
+        assert_eq!(notification.id, id);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_correct_created_at() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+        let created_at = datetime!(2024-02-10 08:46:12.123 UTC);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(created_at),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+
+        assert_eq!(notification.created_at, created_at);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_correct_seen() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+        let seen = true;
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_seen": seen,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+
+        assert_eq!(notification.seen, seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_mutlicast_notification_correct_seen() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_1_id = Uuid::from_u128(85190238019);
+        let user_2_id = Uuid::from_u128(50128301822);
+        let user_1_seen = true;
+        let user_2_seen = false;
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [
+                        bson::Uuid::from(user_1_id),
+                        bson::Uuid::from(user_2_id),
+                    ],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_1_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_seen": user_1_seen,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(user_2_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_seen": user_2_seen,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notification_1 = repository
+            .find_delivered(id, user_1_id)
+            .await?
+            .expect("Fixture notification not found");
+        let notification_2 = repository
+            .find_delivered(id, user_2_id)
+            .await?
+            .expect("Fixture notification not found");
+
+        assert_eq!(notification_1.seen, user_1_seen);
+        assert_eq!(notification_2.seen, user_2_seen);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_correct_content_type() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+        let content_type = "My-Custom-Content-Encoding".to_string();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": &content_type,
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+
+        assert_eq!(notification.content_type, content_type);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_correct_content() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+        let content = b"My-Custom-Content".to_vec();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": id,
+                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: content.clone(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+
+        assert_eq!(notification.content, content);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_multicast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+        let other_user_id = Uuid::from_u128(58120381209381);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [
+                    bson::Uuid::from(user_1_id),
+                    bson::Uuid::from(user_2_id),
+                ],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_1_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_2_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let notification_1 = repository
+            .find_delivered(id, user_1_id)
+            .await?
+            .expect("did not find notification");
+        let notification_2 = repository
+            .find_delivered(id, user_2_id)
+            .await?
+            .expect("did not find notification");
+        assert_eq!(notification_1.id, notification_2.id);
+
+        let notification_3 = repository.find_delivered(id, other_user_id).await?;
+        assert!(notification_3.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_broadcast_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?;
+
+        assert!(notification.is_some());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notification_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let notification = repository
+            .find_delivered(ObjectId::new(), Uuid::from_u128(8301923019))
+            .await?;
+
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_other_user_notification() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+        let other_user_id = Uuid::from_u128(48021830912);
+        assert_ne!(other_user_id, user_id);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [bson::Uuid::from(other_user_id)],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(other_user_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:38:42 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?;
+
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_confirmation_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(85190238019);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": []
+            }],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?;
+
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_broadcast_notification_confirmation_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:08:12 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:09:25 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?;
+
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:08:12 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": true,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?;
+
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_delivered_broadcast_notification_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let id = ObjectId::new();
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "_id": id,
+                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"content of a notification".to_vec(),
+                },
+                "confirmations": [
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:08:12 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": false,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:51:18 UTC)),
+                        "notification_seen": true,
+                        "notification_deleted": true,
+                    },
+                    {
+                        "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
+                        "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:09:25 UTC)),
+                        "notification_seen": false,
+                        "notification_deleted": false,
+                    }
+                ]
+            }],
+        )
+        .await?;
+
+        let notification = repository.find_delivered(id, user_id).await?;
+
+        assert!(notification.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_correct_user_id() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+        let pagination = input::Pagination {
+            page_idx: Some(0),
+            page_size: u32::MAX,
+            cursor: None,
+        };
+        let filters = input::NotificationFilters::default();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:12:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(Uuid::from_u128(4012983018923))],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"yet another notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(4012983018923)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:13:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notifications = repository
+            .find_many_delivered(user_id, pagination, filters)
+            .await?;
+        assert_eq!(notifications.len(), 2);
+
+        for notification in notifications {
+            let document = find_notification(&database, doc! { "_id": notification.id })
+                .await?
+                .unwrap();
+            let user_ids = document.get_array("user_ids")?;
+            let contains_user_id = user_ids
+                .iter()
+                .filter_map(|bson| {
+                    let Bson::Binary(binary) = bson else {
+                        return None;
+                    };
+                    let Ok(document_user_id) = binary.to_uuid() else {
+                        return None;
+                    };
+                    Some(Uuid::from(document_user_id))
+                })
+                .any(|document_user_id| document_user_id == user_id);
+
+            if !user_ids.is_empty() && !contains_user_id {
+                panic!("found notification that does not belong to the user");
+            }
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_skip_not_delivered() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+        let pagination = input::Pagination {
+            page_idx: Some(0),
+            page_size: u32::MAX,
+            cursor: None,
+        };
+        let filters = input::NotificationFilters::default();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:12:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"broadcast notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(123102893123)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 07:13:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-30 05:12:40 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 4,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"not delivered notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+            ],
+        )
+        .await?;
+
+        let notifications = repository
+            .find_many_delivered(user_id, pagination, filters)
+            .await?;
+        assert_eq!(notifications.len(), 2);
+
+        for notification in notifications {
+            let document = find_notification(&database, doc! { "_id": notification.id })
+                .await?
+                .expect("did not find id in database");
+            let confirmations = document.get_array("confirmations")?;
+            let contains_user_confirmation = confirmations
+                .into_iter()
+                .filter_map(|bson| {
+                    let Bson::Document(document) = bson else {
+                        return None;
+                    };
+                    let Some(document_user_id) = document.get("user_id") else {
+                        return None;
+                    };
+                    let Bson::Binary(binary) = document_user_id else {
+                        return None;
+                    };
+                    let Ok(document_user_id) = binary.to_uuid() else {
+                        return None;
+                    };
+
+                    Some(Uuid::from(document_user_id))
+                })
+                .any(|confirmation_user_id| confirmation_user_id == user_id);
+
+            assert!(contains_user_confirmation);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_skip_unsubscribed_topic() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+        let pagination = input::Pagination {
+            page_idx: Some(0),
+            page_size: u32::MAX,
+            cursor: None,
+        };
+        let filters = input::NotificationFilters::default();
+
+        database
+            .collection::<Document>(SUBSCRIPTIONS)
+            .insert_one(doc! { "topic": "deployments", "user_id": bson::Uuid::from(user_id) })
+            .await?;
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "topic": "billing",
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"unsubscribed topic notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:09:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "topic": "deployments",
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"subscribed topic notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:09:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notifications = repository
+            .find_many_delivered(user_id, pagination, filters)
+            .await?;
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].content, b"subscribed topic notification");
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_skip_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+        let pagination = input::Pagination {
+            page_idx: Some(0),
+            page_size: u32::MAX,
+            cursor: None,
+        };
+        let filters = input::NotificationFilters::default();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"content of a notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": true,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:12:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notifications = repository
+            .find_many_delivered(user_id, pagination, filters)
+            .await?;
+        assert_eq!(notifications.len(), 1);
+
+        for notification in notifications {
+            let document = find_notification(&database, doc! { "_id": notification.id })
+                .await?
+                .expect("did not find id in database");
+            let confirmations = document.get_array("confirmations")?;
+            let user_confirmation = confirmations
+                .into_iter()
+                .filter_map(|bson| {
+                    let Bson::Document(document) = bson else {
+                        return None;
+                    };
+                    let Some(confirmation_user_id) = document.get("user_id") else {
+                        return None;
+                    };
+                    let Bson::Binary(binary) = confirmation_user_id else {
+                        return None;
+                    };
+                    let Ok(confirmation_user_id) = binary.to_uuid() else {
+                        return None;
+                    };
+                    match Uuid::from(confirmation_user_id) == user_id {
+                        true => Some(document),
+                        false => None,
+                    }
+                })
+                .next()
+                .expect("foud notification without user confirmation");
+
+            let deleted = user_confirmation.get_bool("notification_deleted")?;
+
+            assert!(!deleted);
+        }
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_sorted_by_created_at_desc() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+        let pagination = input::Pagination {
+            page_idx: Some(0),
+            page_size: u32::MAX,
+            cursor: None,
+        };
+        let filters = input::NotificationFilters::default();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 00:12:45 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-30 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-30 16:08:12 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 16:12:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let notifications = repository
+            .find_many_delivered(user_id, pagination, filters)
+            .await?;
+        assert_eq!(notifications.len(), 3);
+
+        let mut zip = std::iter::zip(
+            &notifications[..notifications.len() - 1],
+            &notifications[1..],
+        );
+
+        let order_correct = zip.all(|(a, b)| a.created_at >= b.created_at);
+
+        assert!(order_correct);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_correct_pagination() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 00:12:45 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-30 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-30 16:08:12 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 16:08:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 16:12:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                    ]
+                },
+            ],
+        )
+        .await?;
+
+        let pagination_all = input::Pagination {
+            page_idx: Some(0),
+            page_size: u32::MAX,
+            cursor: None,
+        };
+        let notifications = repository
+            .find_many_delivered(
+                user_id,
+                pagination_all,
+                input::NotificationFilters::default(),
+            )
+            .await?;
+        assert_eq!(notifications.len(), 3);
+
+        let pagination_first_two = input::Pagination {
+            page_idx: Some(0),
+            page_size: 2,
+            cursor: None,
+        };
+        let notifications_first_two = repository
+            .find_many_delivered(
+                user_id,
+                pagination_first_two,
+                input::NotificationFilters::default(),
+            )
+            .await?;
+
+        let pagination_last_one = input::Pagination {
+            page_idx: Some(1),
+            page_size: 2,
+            cursor: None,
+        };
+        let notifications_last_one = repository
+            .find_many_delivered(
+                user_id,
+                pagination_last_one,
+                input::NotificationFilters::default(),
+            )
+            .await?;
+
+        let first_two_correct = std::iter::zip(&notifications[0..2], &notifications_first_two)
+            .all(|(a, b)| a.id == b.id);
+        assert!(first_two_correct);
+
+        let last_one_correct =
+            std::iter::zip(&notifications[2..], &notifications_last_one).all(|(a, b)| a.id == b.id);
+        assert!(last_one_correct);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_delivered_correct_cursor_pagination() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
         let user_id = Uuid::from_u128(1);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "user_ids": [],
+        // notifications 2 and 3 share created_at so _id has to break the tie
+        let created_ats = [
+            datetime!(2024-01-28 00:12:41 UTC),
+            datetime!(2024-01-29 16:08:00 UTC),
+            datetime!(2024-01-29 16:08:00 UTC),
+            datetime!(2024-01-30 16:08:00 UTC),
+        ];
+        let documents = created_ats.into_iter().enumerate().map(|(i, created_at)| {
+            doc! {
+                "created_at": DateTime::from(created_at),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [bson::Uuid::from(user_id)],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": i as i64,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"other notification".to_vec(),
+                },
                 "confirmations": [
                     {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(48012983019283)),
-                        "notification_deleted": false,
-                    },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(3841200192)),
+                        "user_id": bson::Uuid::from(user_id),
+                        "notification_delivered_at": DateTime::from(created_at),
+                        "notification_seen": true,
                         "notification_deleted": false,
                     },
-                    {
-                        "user_id": bson::Uuid::from(Uuid::from_u128(809841812308)),
-                        "notification_deleted": false,
-                    }
                 ]
-            }])
+            }
+        });
+        insert_notifications(&database, documents).await?;
+
+        let notifications = repository
+            .find_many_delivered(
+                user_id,
+                input::Pagination {
+                    page_idx: Some(0),
+                    page_size: u32::MAX,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+            )
             .await?;
+        assert_eq!(notifications.len(), 4);
 
-        let delete_result = repository.delete(id, user_id).await;
+        let mut notifications_by_cursor = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = repository
+                .find_many_delivered(
+                    user_id,
+                    input::Pagination {
+                        page_idx: None,
+                        page_size: 3,
+                        cursor,
+                    },
+                    input::NotificationFilters::default(),
+                )
+                .await?;
+            cursor = page.last().map(|notification| input::NotificationsCursor {
+                created_at: notification.created_at,
+                id: notification.id,
+            });
+            let page_len = page.len();
+            notifications_by_cursor.extend(page);
+            if page_len < 3 {
+                break;
+            }
+        }
 
-        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+        assert_eq!(notifications_by_cursor.len(), 4);
+        let all_correct =
+            std::iter::zip(&notifications, &notifications_by_cursor).all(|(a, b)| a.id == b.id);
+        assert!(all_correct);
 
         destroy_test_database(database).await;
 
@@ -2772,22 +9704,17 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_correct_id() -> anyhow::Result<()> {
+    async fn find_many_delivered_filters_seen() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let other_id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
-        assert_ne!(id, other_id);
+        let user_id = Uuid::from_u128(4819208301);
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
@@ -2795,20 +9722,19 @@ mod test {
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
-                            "notification_seen": false,
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:37:15 UTC)),
+                            "notification_seen": true,
                             "notification_deleted": false,
-                        }
+                        },
                     ]
                 },
                 doc! {
-                    "_id": other_id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:42:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
@@ -2816,69 +9742,205 @@ mod test {
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"other notification".to_vec(),
                     },
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:43:11 UTC)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:38:21 UTC)),
                             "notification_seen": false,
                             "notification_deleted": false,
-                        }
+                        },
                     ]
                 },
-            ])
-            .await?;
-
-        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+            ],
+        )
+        .await?;
 
-        assert_eq!(notification.id, id);
+        test(&repository, user_id, true).await?;
+        test(&repository, user_id, false).await?;
 
         destroy_test_database(database).await;
 
-        Ok(())
-    }
+        return Ok(());
 
-    #[tokio::test]
-    async fn find_delivered_correct_created_at() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        async fn test(
+            repository: &NotificationsRepositoryImpl,
+            user_id: Uuid,
+            seen: bool,
+        ) -> anyhow::Result<()> {
+            let notifications = repository
+                .find_many_delivered(
+                    user_id,
+                    input::Pagination {
+                        page_idx: Some(0),
+                        page_size: u32::MAX,
+                        cursor: None,
+                    },
+                    input::NotificationFilters {
+                        seen: Some(seen),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            if notifications.is_empty() {
+                panic!("notifications empty");
+            }
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
-        let created_at = datetime!(2024-02-10 08:46:12.123 UTC);
+            let all_correct_seen = notifications
+                .iter()
+                .all(|notification| notification.seen == seen);
 
-        collection
-            .insert_many(
-                [
+            match all_correct_seen {
+                true => Ok(()),
+                false => Err(anyhow!("filter seen: {seen} does not work")),
+            }
+        }
+    }
+
+    ///
+    /// Inserts 4 notifications delivered to user_id that differ
+    /// by producer_id, content_type, created_at and delivered_at.
+    ///
+    /// Returns their ids sorted ascending by created_at
+    ///
+    async fn insert_notifications_for_filtering(
+        database: &Database,
+        user_id: Uuid,
+    ) -> anyhow::Result<[ObjectId; 4]> {
+        let producer_a = bson::Uuid::from(Uuid::from_u128(1));
+        let producer_b = bson::Uuid::from(Uuid::from_u128(2));
+        let ids = [
+            ObjectId::new(),
+            ObjectId::new(),
+            ObjectId::new(),
+            ObjectId::new(),
+        ];
+        let notifications = [
+            (
+                producer_a,
+                "utf-8",
+                datetime!(2024-01-01 00:00 UTC),
+                datetime!(2024-01-05 00:00 UTC),
+            ),
+            (
+                producer_b,
+                "utf-8",
+                datetime!(2024-01-02 00:00 UTC),
+                datetime!(2024-01-03 00:00 UTC),
+            ),
+            (
+                producer_a,
+                "json",
+                datetime!(2024-01-03 00:00 UTC),
+                datetime!(2024-01-04 00:00 UTC),
+            ),
+            (
+                producer_b,
+                "json",
+                datetime!(2024-01-04 00:00 UTC),
+                datetime!(2024-01-06 00:00 UTC),
+            ),
+        ];
+
+        let documents = std::iter::zip(ids, notifications).enumerate().map(
+            |(i, (id, (producer_id, content_type, created_at, delivered_at)))| {
                 doc! {
                     "_id": id,
                     "created_at": DateTime::from(created_at),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
+                    "producer_id": producer_id,
+                    "producer_notification_id": i as i64,
+                    "content_type": content_type,
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_delivered_at": DateTime::from(delivered_at),
                             "notification_seen": false,
                             "notification_deleted": false,
-                        }
+                        },
                     ]
+                }
+            },
+        );
+        insert_notifications(database, documents).await?;
+
+        Ok(ids)
+    }
+
+    async fn find_many_delivered_ids(
+        repository: &NotificationsRepositoryImpl,
+        user_id: Uuid,
+        cursor: Option<input::NotificationsCursor>,
+        filters: input::NotificationFilters,
+    ) -> anyhow::Result<Vec<ObjectId>> {
+        let notifications = repository
+            .find_many_delivered(
+                user_id,
+                input::Pagination {
+                    page_idx: None,
+                    page_size: u32::MAX,
+                    cursor,
                 },
-            ])
+                filters,
+            )
             .await?;
 
-        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+        Ok(notifications
+            .into_iter()
+            .map(|notification| notification.id)
+            .collect())
+    }
 
-        assert_eq!(notification.created_at, created_at);
+    #[tokio::test]
+    async fn find_many_delivered_filters_created_by_and_content_type() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+        let [n0, n1, n2, n3] = insert_notifications_for_filtering(&database, user_id).await?;
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                created_by: Some(Uuid::from_u128(1)),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n2, n0]);
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                content_type: Some("json".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n3, n2]);
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                created_by: Some(Uuid::from_u128(2)),
+                content_type: Some("utf-8".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n1]);
 
         destroy_test_database(database).await;
 
@@ -2886,45 +9948,50 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_correct_seen() -> anyhow::Result<()> {
+    async fn find_many_delivered_filters_created_at_and_delivered_at_range() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
-        let seen = true;
-
-        collection
-            .insert_many(
-                [
-                doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
-                            "notification_seen": seen,
-                            "notification_deleted": false,
-                        }
-                    ]
-                },
-            ])
-            .await?;
-
-        let notification = repository.find_delivered(id, user_id).await?.unwrap();
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        assert_eq!(notification.seen, seen);
+        let user_id = Uuid::from_u128(1);
+        let [n0, n1, n2, n3] = insert_notifications_for_filtering(&database, user_id).await?;
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                created_at_from: Some(datetime!(2024-01-02 00:00 UTC)),
+                created_at_to: Some(datetime!(2024-01-04 00:00 UTC)),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n2, n1]);
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                created_at_from: Some(datetime!(2024-01-03 00:00 UTC)),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n3, n2]);
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                delivered_at_from: Some(datetime!(2024-01-04 00:00 UTC)),
+                delivered_at_to: Some(datetime!(2024-01-06 00:00 UTC)),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n2, n0]);
 
         destroy_test_database(database).await;
 
@@ -2932,64 +9999,39 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_mutlicast_notification_correct_seen() -> anyhow::Result<()> {
+    async fn find_many_delivered_sorted_by_created_at_asc() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_1_id = Uuid::from_u128(85190238019);
-        let user_2_id = Uuid::from_u128(50128301822);
-        let user_1_seen = true;
-        let user_2_seen = false;
-
-        collection
-            .insert_many(
-                [
-                doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [
-                        bson::Uuid::from(user_1_id),
-                        bson::Uuid::from(user_2_id),
-                    ],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_1_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
-                            "notification_seen": user_1_seen,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(user_2_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
-                            "notification_seen": user_2_seen,
-                            "notification_deleted": false,
-                        }
-                    ]
-                },
-            ])
-            .await?;
-
-        let notification_1 = repository
-            .find_delivered(id, user_1_id)
-            .await?
-            .expect("Fixture notification not found");
-        let notification_2 = repository
-            .find_delivered(id, user_2_id)
-            .await?
-            .expect("Fixture notification not found");
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        assert_eq!(notification_1.seen, user_1_seen);
-        assert_eq!(notification_2.seen, user_2_seen);
+        let user_id = Uuid::from_u128(1);
+        let [n0, n1, n2, n3] = insert_notifications_for_filtering(&database, user_id).await?;
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            None,
+            input::NotificationFilters {
+                order: Some(input::NotificationsOrder::Asc),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n0, n1, n2, n3]);
+
+        let ids = find_many_delivered_ids(
+            &repository,
+            user_id,
+            Some(input::NotificationsCursor {
+                created_at: datetime!(2024-01-02 00:00 UTC),
+                id: n1,
+            }),
+            input::NotificationFilters {
+                order: Some(input::NotificationsOrder::Asc),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(ids, vec![n2, n3]);
 
         destroy_test_database(database).await;
 
@@ -2997,154 +10039,96 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_correct_content_type() -> anyhow::Result<()> {
+    async fn find_many_delivered_contains_uni_multi_and_broadcast_notifications(
+    ) -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
-        let content_type = "My-Custom-Content-Encoding".to_string();
+        let user_id = Uuid::from_u128(123120831);
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
                     "producer_notification_id": 1,
-                    "content_type": &content_type,
+                    "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:37:15 UTC)),
                             "notification_seen": true,
                             "notification_deleted": false,
-                        }
+                        },
                     ]
                 },
-            ])
-            .await?;
-
-        let notification = repository.find_delivered(id, user_id).await?.unwrap();
-
-        assert_eq!(notification.content_type, content_type);
-
-        destroy_test_database(database).await;
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn find_delivered_correct_content() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
-        let content = b"My-Custom-Content".to_vec();
-
-        collection
-            .insert_many(
-                [
                 doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-02-10 08:46:12.123 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
+                    "user_ids": [],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
+                    "producer_notification_id": 2,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: content.clone(),
+                        bytes: b"other notification".to_vec(),
                     },
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-02-10 08:47:00 UTC)),
-                            "notification_seen": true,
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:38:21 UTC)),
+                            "notification_seen": false,
                             "notification_deleted": false,
-                        }
+                        },
                     ]
                 },
-            ])
-            .await?;
-
-        let notification = repository.find_delivered(id, user_id).await?.unwrap();
-
-        assert_eq!(notification.content, content);
-
-        destroy_test_database(database).await;
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn find_delivered_multicast_notification() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_1_id = Uuid::from_u128(1);
-        let user_2_id = Uuid::from_u128(2);
-        let other_user_id = Uuid::from_u128(58120381209381);
-
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [
-                        bson::Uuid::from(user_1_id),
-                        bson::Uuid::from(user_2_id),
+                        bson::Uuid::from(Uuid::from_u128(95123123912831)),
+                        bson::Uuid::from(user_id),
                     ],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
+                    "producer_notification_id": 3,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"other notification".to_vec(),
                     },
                     "confirmations": [
                         {
-                            "user_id": bson::Uuid::from(user_1_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:38:21 UTC)),
                             "notification_seen": false,
                             "notification_deleted": false,
                         },
-                        {
-                            "user_id": bson::Uuid::from(user_2_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
                     ]
-                }])
-            .await?;
+                },
+            ],
+        )
+        .await?;
 
-        let notification_1 = repository
-            .find_delivered(id, user_1_id)
-            .await?
-            .expect("did not find notification");
-        let notification_2 = repository
-            .find_delivered(id, user_2_id)
-            .await?
-            .expect("did not find notification");
-        assert_eq!(notification_1.id, notification_2.id);
+        let notifications = repository
+            .find_many_delivered(
+                user_id,
+                input::Pagination {
+                    page_idx: Some(0),
+                    page_size: u32::MAX,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+            )
+            .await?;
 
-        let notification_3 = repository.find_delivered(id, other_user_id).await?;
-        assert!(notification_3.is_none());
+        assert_eq!(notifications.len(), 3);
 
         destroy_test_database(database).await;
 
@@ -3152,58 +10136,62 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_broadcast_notification() -> anyhow::Result<()> {
+    async fn find_many_undelivered_multicast_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
+        let user_1_id = Uuid::from_u128(1);
+        let user_2_id = Uuid::from_u128(2);
+        let other_user_id = Uuid::from_u128(5981023810293);
 
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [],
+                    "user_ids": [
+                        bson::Uuid::from(user_1_id),
+                        bson::Uuid::from(user_2_id),
+                    ],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
                     "producer_notification_id": 1,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 11:11:11 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    ]
-                }])
-            .await?;
-
-        let notification = repository.find_delivered(id, user_id).await?;
-
-        assert!(notification.is_some());
-
-        destroy_test_database(database).await;
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [
+                        bson::Uuid::from(user_1_id),
+                        bson::Uuid::from(user_2_id),
+                    ],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+            ],
+        )
+        .await?;
 
-        Ok(())
-    }
+        let notifications_1 = repository.find_many_undelivered(user_1_id).await?;
+        let notifications_2 = repository.find_many_undelivered(user_2_id).await?;
 
-    #[tokio::test]
-    async fn find_delivered_notification_not_exist() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
+        let notifications_3 = repository.find_many_undelivered(other_user_id).await?;
 
-        let notification = repository
-            .find_delivered(ObjectId::new(), Uuid::from_u128(8301923019))
-            .await?;
+        assert_eq!(notifications_1.len(), 2);
+        assert_eq!(notifications_2.len(), 2);
 
-        assert!(notification.is_none());
+        assert!(notifications_3.is_empty());
 
         destroy_test_database(database).await;
 
@@ -3211,44 +10199,71 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_other_user_notification() -> anyhow::Result<()> {
+    async fn find_many_undelivered_broadcast_notifications_correct_user_ids() -> anyhow::Result<()>
+    {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
-        let other_user_id = Uuid::from_u128(48021830912);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let user_id = Uuid::from_u128(4120831209);
+        let other_user_id = Uuid::from_u128(580912830);
         assert_ne!(other_user_id, user_id);
 
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(other_user_id)],
+                    "user_ids": [],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
                     "producer_notification_id": 1,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(other_user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:38:42 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        }
-                    ]
-                }])
-            .await?;
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(other_user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 19:00:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+            ],
+        )
+        .await?;
 
-        let notification = repository.find_delivered(id, user_id).await?;
+        let notifications = repository.find_many_undelivered(user_id).await?;
+        assert_eq!(notifications.len(), 2);
 
-        assert!(notification.is_none());
+        for notification in notifications {
+            let document = find_notification(&database, doc! { "_id": notification.id })
+                .await?
+                .expect("did not find id in database");
+            let user_ids = document.get_array("user_ids")?;
+
+            assert!(user_ids.is_empty());
+        }
 
         destroy_test_database(database).await;
 
@@ -3256,34 +10271,47 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_confirmation_not_exist() -> anyhow::Result<()> {
+    async fn find_many_undelivered_group_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(85190238019);
+        let member_id = Uuid::from_u128(1);
+        let direct_user_id = Uuid::from_u128(2);
+        let other_user_id = Uuid::from_u128(3);
 
-        collection
-            .insert_many([doc! {
-                "_id": id,
-                "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
+        database
+            .collection::<Document>(GROUP_MEMBERS)
+            .insert_many([
+                doc! { "group": "admins", "user_id": bson::Uuid::from(member_id) },
+                doc! { "group": "others", "user_id": bson::Uuid::from(other_user_id) },
+            ])
+            .await?;
+        insert_notifications(
+            &database,
+            [doc! {
+                "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                 "invalidate_at": None as Option<DateTime>,
-                "user_ids": [bson::Uuid::from(user_id)],
+                "user_ids": [bson::Uuid::from(direct_user_id)],
+                "groups": ["admins"],
                 "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
                 "producer_notification_id": 1,
                 "content_type": "utf-8",
                 "content": Binary {
                     subtype: BinarySubtype::Generic,
-                    bytes: b"content of a notification".to_vec(),
+                    bytes: b"notification".to_vec(),
                 },
                 "confirmations": []
-            }])
-            .await?;
+            }],
+        )
+        .await?;
 
-        let notification = repository.find_delivered(id, user_id).await?;
+        let member_notifications = repository.find_many_undelivered(member_id).await?;
+        let direct_user_notifications = repository.find_many_undelivered(direct_user_id).await?;
+        let other_user_notifications = repository.find_many_undelivered(other_user_id).await?;
 
-        assert!(notification.is_none());
+        assert_eq!(member_notifications.len(), 1);
+        assert_eq!(direct_user_notifications.len(), 1);
+        assert!(other_user_notifications.is_empty());
 
         destroy_test_database(database).await;
 
@@ -3291,48 +10319,32 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_broadcast_notification_confirmation_not_exist() -> anyhow::Result<()> {
+    async fn find_many_undelivered_group_notification_is_not_broadcast() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(1);
-
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:08:12 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:09:25 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    ]
-                }])
-            .await?;
+        insert_notifications(
+            &database,
+            [doc! {
+                "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [],
+                "groups": ["admins"],
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"notification".to_vec(),
+                },
+                "confirmations": []
+            }],
+        )
+        .await?;
 
-        let notification = repository.find_delivered(id, user_id).await?;
+        let notifications = repository.find_many_undelivered(Uuid::from_u128(1)).await?;
 
-        assert!(notification.is_none());
+        assert!(notifications.is_empty());
 
         destroy_test_database(database).await;
 
@@ -3340,42 +10352,44 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_deleted() -> anyhow::Result<()> {
+    async fn find_many_undelivered_topic_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
-        let user_id = Uuid::from_u128(1);
+        let subscriber_id = Uuid::from_u128(1);
+        let other_user_id = Uuid::from_u128(2);
 
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:08:12 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": true,
-                        }
-                    ]
-                }])
+        database
+            .collection::<Document>(SUBSCRIPTIONS)
+            .insert_many([
+                doc! { "topic": "billing", "user_id": bson::Uuid::from(subscriber_id) },
+                doc! { "topic": "deployments", "user_id": bson::Uuid::from(other_user_id) },
+            ])
             .await?;
+        insert_notifications(
+            &database,
+            [doc! {
+                "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
+                "invalidate_at": None as Option<DateTime>,
+                "user_ids": [],
+                "topic": "billing",
+                "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: b"notification".to_vec(),
+                },
+                "confirmations": []
+            }],
+        )
+        .await?;
 
-        let notification = repository.find_delivered(id, user_id).await?;
+        let subscriber_notifications = repository.find_many_undelivered(subscriber_id).await?;
+        let other_user_notifications = repository.find_many_undelivered(other_user_id).await?;
 
-        assert!(notification.is_none());
+        assert_eq!(subscriber_notifications.len(), 1);
+        assert!(other_user_notifications.is_empty());
 
         destroy_test_database(database).await;
 
@@ -3383,179 +10397,76 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_delivered_broadcast_notification_deleted() -> anyhow::Result<()> {
+    async fn find_many_undelivered_skip_muted() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let id = ObjectId::new();
         let user_id = Uuid::from_u128(1);
-
-        collection
-            .insert_many(
-                [doc! {
-                    "_id": id,
-                    "created_at": DateTime::from(datetime!(2024-01-28 11:02:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:08:12 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:51:18 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": true,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 12:09:25 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    ]
-                }])
+        let muted_producer_id = Uuid::from_u128(2);
+        let producer_id = Uuid::from_u128(3);
+
+        database
+            .collection::<Document>(PREFERENCES)
+            .insert_one(doc! {
+                "_id": bson::Uuid::from(user_id),
+                "muted_producers": [bson::Uuid::from(muted_producer_id)],
+                "muted_content_types": ["marketing"],
+                "quiet_hours": None as Option<Document>,
+                "updated_at": DateTime::now(),
+            })
             .await?;
-
-        let notification = repository.find_delivered(id, user_id).await?;
-
-        assert!(notification.is_none());
-
-        destroy_test_database(database).await;
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn find_many_delivered_correct_user_id() -> anyhow::Result<()> {
-        let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-
-        let user_id = Uuid::from_u128(1);
-        let pagination = input::Pagination {
-            page_idx: 0,
-            page_size: u32::MAX,
-        };
-        let filters = input::NotificationFilters { seen: None };
-
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_id": bson::Uuid::from(muted_producer_id),
                     "producer_notification_id": 1,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"muted producer".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    ]
+                    "confirmations": []
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:42 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "producer_notification_id": 1,
+                    "content_type": "marketing",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
+                        bytes: b"muted content type".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:12:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:43 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(Uuid::from_u128(4012983018923))],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "producer_notification_id": 2,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"yet another notification".to_vec(),
+                        bytes: b"not muted".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(4012983018923)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:13:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
-            ])
-            .await?;
-
-        let notifications = repository
-            .find_many_delivered(user_id, pagination, filters)
-            .await?;
-        assert_eq!(notifications.len(), 2);
-
-        for notification in notifications {
-            let document = collection
-                .find_one(doc! { "_id": notification.id })
-                .await?
-                .unwrap();
-            let user_ids = document.get_array("user_ids")?;
-            let contains_user_id = user_ids
-                .iter()
-                .filter_map(|bson| {
-                    let Bson::Binary(binary) = bson else {
-                        return None;
-                    };
-                    let Ok(document_user_id) = binary.to_uuid() else {
-                        return None;
-                    };
-                    Some(Uuid::from(document_user_id))
-                })
-                .any(|document_user_id| document_user_id == user_id);
-
-            if !user_ids.is_empty() && !contains_user_id {
-                panic!("found notification that does not belong to the user");
-            }
-        }
+            ],
+        )
+        .await?;
+
+        let notifications = repository.find_many_undelivered(user_id).await?;
+        let other_user_notifications = repository.find_many_undelivered(Uuid::from_u128(4)).await?;
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].content, b"not muted");
+        assert_eq!(other_user_notifications.len(), 3);
 
         destroy_test_database(database).await;
 
@@ -3563,23 +10474,17 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_delivered_skip_not_delivered() -> anyhow::Result<()> {
+    async fn find_many_undelivered_skip_notifications_with_confirmations() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let user_id = Uuid::from_u128(1);
-        let pagination = input::Pagination {
-            page_idx: 0,
-            page_size: u32::MAX,
-        };
-        let filters = input::NotificationFilters { seen: None };
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
@@ -3587,35 +10492,62 @@ mod test {
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
                     "confirmations": [
                         {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "user_id": bson::Uuid::from(Uuid::from_u128(5801238012)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
                             "notification_seen": true,
                             "notification_deleted": false,
                         },
                         {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                            "user_id": bson::Uuid::from(Uuid::from_u128(48120381209)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
                             "notification_seen": true,
                             "notification_deleted": false,
-                        },
+                        }
+                    ]
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": [
                         {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": false,
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
+                            "notification_seen": true,
                             "notification_deleted": false,
                         }
                     ]
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 4,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
@@ -3624,56 +10556,73 @@ mod test {
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:12:00 UTC)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
                             "notification_seen": true,
                             "notification_deleted": false,
-                        },
+                        }
                     ]
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [],
+                    "user_ids": [
+                        bson::Uuid::from(Uuid::from_u128(57102380129831)),
+                        bson::Uuid::from(user_id)
+                    ],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
+                    "producer_notification_id": 5,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"broadcast notification".to_vec(),
+                        bytes: b"other notification".to_vec(),
                     },
                     "confirmations": [
                         {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(123102893123)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 07:13:00 UTC)),
+                            "user_id": bson::Uuid::from(Uuid::from_u128(57102380129831)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
                             "notification_seen": true,
                             "notification_deleted": false,
-                        },
+                        }
                     ]
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-30 05:12:40 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
+                    "user_ids": [
+                        bson::Uuid::from(Uuid::from_u128(57102380129831)),
+                        bson::Uuid::from(user_id)
+                    ],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 4,
+                    "producer_notification_id": 6,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"not delivered notification".to_vec(),
+                        bytes: b"other notification".to_vec(),
                     },
-                    "confirmations": []
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        },
+                        {
+                            "user_id": bson::Uuid::from(Uuid::from_u128(57102380129831)),
+                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
+                            "notification_seen": true,
+                            "notification_deleted": false,
+                        }
+                    ]
                 },
-            ])
-            .await?;
+            ],
+        )
+        .await?;
 
-        let notifications = repository
-            .find_many_delivered(user_id, pagination, filters)
-            .await?;
-        assert_eq!(notifications.len(), 2);
+        let notifications = repository.find_many_undelivered(user_id).await?;
+        assert_eq!(notifications.len(), 3);
 
         for notification in notifications {
-            let document = collection
-                .find_one(doc! { "_id": notification.id })
+            let document = find_notification(&database, doc! { "_id": notification.id })
                 .await?
                 .expect("did not find id in database");
             let confirmations = document.get_array("confirmations")?;
@@ -3697,7 +10646,7 @@ mod test {
                 })
                 .any(|confirmation_user_id| confirmation_user_id == user_id);
 
-            assert_eq!(contains_user_confirmation, true);
+            assert!(!contains_user_confirmation);
         }
 
         destroy_test_database(database).await;
@@ -3706,23 +10655,17 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_delivered_skip_deleted() -> anyhow::Result<()> {
+    async fn find_many_undelivered_skip_invalidated_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let user_id = Uuid::from_u128(1);
-        let pagination = input::Pagination {
-            page_idx: 0,
-            page_size: u32::MAX,
-        };
-        let filters = input::NotificationFilters { seen: None };
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
@@ -3730,89 +10673,96 @@ mod test {
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"content of a notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(481290381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": true,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(58102381029)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        }
-                    ]
+                    "confirmations": []
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": DateTime::from(datetime!(9999-12-31 00:00:00 UTC)),
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": DateTime::from(OffsetDateTime::now_utc() - Duration::from_secs(6000)),
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 3,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
+                    "producer_notification_id": 4,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": DateTime::from(datetime!(9999-12-31 00:00:00 UTC)),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 5,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
                         bytes: b"other notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 16:12:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
-            ])
-            .await?;
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "invalidate_at": DateTime::from(OffsetDateTime::now_utc() - Duration::from_secs(6000)),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 6,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+            ],
+        )
+        .await?;
 
-        let notifications = repository
-            .find_many_delivered(user_id, pagination, filters)
-            .await?;
-        assert_eq!(notifications.len(), 1);
+        let notifications = repository.find_many_undelivered(user_id).await?;
+        assert_eq!(notifications.len(), 4);
+
+        let now = OffsetDateTime::now_utc();
 
         for notification in notifications {
-            let document = collection
-                .find_one(doc! { "_id": notification.id })
+            let document = find_notification(&database, doc! { "_id": notification.id })
                 .await?
                 .expect("did not find id in database");
-            let confirmations = document.get_array("confirmations")?;
-            let user_confirmation = confirmations
-                .into_iter()
-                .filter_map(|bson| {
-                    let Bson::Document(document) = bson else {
-                        return None;
-                    };
-                    let Some(confirmation_user_id) = document.get("user_id") else {
-                        return None;
-                    };
-                    let Bson::Binary(binary) = confirmation_user_id else {
-                        return None;
-                    };
-                    let Ok(confirmation_user_id) = binary.to_uuid() else {
-                        return None;
-                    };
-                    match Uuid::from(confirmation_user_id) == user_id {
-                        true => Some(document),
-                        false => None,
-                    }
-                })
-                .next()
-                .expect("foud notification without user confirmation");
-
-            let deleted = user_confirmation.get_bool("notification_deleted")?;
+            let invalidate_at = document
+                .get("invalidate_at")
+                .expect("document has no property invalidate_at");
 
-            assert_eq!(deleted, false);
+            if let Some(datetime) = invalidate_at.as_datetime() {
+                let datetime = OffsetDateTime::from(*datetime);
+                assert!(datetime >= now);
+            }
         }
 
         destroy_test_database(database).await;
@@ -3821,44 +10771,35 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_delivered_sorted_by_created_at_desc() -> anyhow::Result<()> {
+    async fn find_many_undelivered_skip_scheduled_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let user_id = Uuid::from_u128(1);
-        let pagination = input::Pagination {
-            page_idx: 0,
-            page_size: u32::MAX,
-        };
-        let filters = input::NotificationFilters { seen: None };
+        let due_id = ObjectId::new();
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
+                    "_id": due_id,
+                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
                     "invalidate_at": None as Option<DateTime>,
+                    "deliver_at": DateTime::from(OffsetDateTime::now_utc() - Duration::from_secs(6000)),
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
                     "producer_notification_id": 1,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 00:12:45 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-30 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
                     "invalidate_at": None as Option<DateTime>,
+                    "deliver_at": DateTime::from(datetime!(9999-12-31 00:00:00 UTC)),
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
                     "producer_notification_id": 2,
@@ -3867,51 +10808,98 @@ mod test {
                         subtype: BinarySubtype::Generic,
                         bytes: b"other notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-30 16:08:12 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
+            ],
+        )
+        .await?;
+
+        let notifications = repository.find_many_undelivered(user_id).await?;
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].id, due_id);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_undelivered_sorted_by_created_at_asc() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(1);
+
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 16:08:00 UTC)),
+                    "created_at": DateTime::from(datetime!(2024-01-29 19:57:22 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 1,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 18:22:11 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 2,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"other notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 17:15:00 UTC)),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
+                    "producer_notification_id": 4,
+                    "content_type": "utf-8",
+                    "content": Binary {
+                        subtype: BinarySubtype::Generic,
+                        bytes: b"notification".to_vec(),
+                    },
+                    "confirmations": []
+                },
+                doc! {
+                    "created_at": DateTime::from(datetime!(2024-01-29 20:00:00 UTC)),
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
+                    "producer_notification_id": 5,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
                         bytes: b"other notification".to_vec(),
                     },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 16:12:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
-            ])
-            .await?;
+            ],
+        )
+        .await?;
 
-        let notifications = repository
-            .find_many_delivered(user_id, pagination, filters)
-            .await?;
-        assert_eq!(notifications.len(), 3);
+        let notifications = repository.find_many_undelivered(user_id).await?;
+        assert_eq!(notifications.len(), 4);
 
-        let mut zip = std::iter::zip(
+        let correct_order = std::iter::zip(
             &notifications[..notifications.len() - 1],
             &notifications[1..],
-        );
-
-        let order_correct = zip.all(|(a, b)| a.created_at >= b.created_at);
+        )
+        .all(|(a, b)| a.created_at <= b.created_at);
 
-        assert!(order_correct);
+        assert!(correct_order);
 
         destroy_test_database(database).await;
 
@@ -3919,123 +10907,185 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_delivered_correct_pagination() -> anyhow::Result<()> {
+    async fn find_many_undelivered_sorted_by_priority_desc() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
         let user_id = Uuid::from_u128(1);
-
-        collection
-            .insert_many(
-                [
-                doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
+        let notification =
+            |producer_notification_id: i64, created_at: OffsetDateTime, priority: Option<i32>| {
+                let mut notification = doc! {
+                    "created_at": DateTime::from(created_at),
                     "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
                     "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
+                    "producer_notification_id": producer_notification_id,
                     "content_type": "utf-8",
                     "content": Binary {
                         subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
+                        bytes: b"notification".to_vec(),
                     },
+                    "confirmations": []
+                };
+                if let Some(priority) = priority {
+                    notification.insert("priority", priority);
+                }
+                notification
+            };
+
+        insert_notifications(
+            &database,
+            [
+                notification(
+                    1,
+                    datetime!(2024-01-29 17:00:00 UTC),
+                    Some(NotificationPriority::Low.as_i32()),
+                ),
+                // notification saved before priorities were introduced
+                notification(2, datetime!(2024-01-29 18:00:00 UTC), None),
+                notification(
+                    3,
+                    datetime!(2024-01-29 19:00:00 UTC),
+                    Some(NotificationPriority::Urgent.as_i32()),
+                ),
+                notification(
+                    4,
+                    datetime!(2024-01-29 17:30:00 UTC),
+                    Some(NotificationPriority::Normal.as_i32()),
+                ),
+            ],
+        )
+        .await?;
+
+        let notifications = repository.find_many_undelivered(user_id).await?;
+
+        let priorities = notifications
+            .iter()
+            .map(|notification| (notification.priority, notification.created_at))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            priorities,
+            vec![
+                (
+                    NotificationPriority::Urgent,
+                    datetime!(2024-01-29 19:00:00 UTC)
+                ),
+                (
+                    NotificationPriority::Normal,
+                    datetime!(2024-01-29 17:30:00 UTC)
+                ),
+                (
+                    NotificationPriority::Normal,
+                    datetime!(2024-01-29 18:00:00 UTC)
+                ),
+                (
+                    NotificationPriority::Low,
+                    datetime!(2024-01-29 17:00:00 UTC)
+                ),
+            ]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn count_no_notifications() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let count = repository.count(Uuid::from_u128(6619203810)).await?;
+
+        assert_eq!(count.undelivered, 0);
+        assert_eq!(count.delivered, 0);
+        assert_eq!(count.delivered_unseen, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn count_unicast_multicast_and_broadcast_notifications() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(6619203810);
+        let other_user_id = Uuid::from_u128(6619203811);
+
+        insert_notifications(
+            &database,
+            [
+                // undelivered unicast
+                doc! {
+                    "invalidate_at": None as Option<DateTime>,
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": []
+                },
+                // undelivered broadcast delivered to other user
+                doc! {
+                    "user_ids": [],
                     "confirmations": [
                         {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 00:12:45 UTC)),
+                            "user_id": bson::Uuid::from(other_user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
+                    ]
+                },
+                // delivered unseen multicast
+                doc! {
+                    "user_ids": [bson::Uuid::from(other_user_id), bson::Uuid::from(user_id)],
+                    "confirmations": [
+                        {
+                            "user_id": bson::Uuid::from(other_user_id),
                             "notification_seen": true,
                             "notification_deleted": false,
                         },
+                        {
+                            "user_id": bson::Uuid::from(user_id),
+                            "notification_seen": false,
+                            "notification_deleted": false,
+                        }
                     ]
                 },
+                // delivered seen broadcast
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-30 16:08:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
+                    "user_ids": [],
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-30 16:08:12 UTC)),
                             "notification_seen": true,
                             "notification_deleted": false,
-                        },
+                        }
                     ]
                 },
+                // deleted unicast
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 16:08:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
                     "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 16:12:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
+                            "notification_seen": false,
+                            "notification_deleted": true,
+                        }
                     ]
                 },
-            ])
-            .await?;
-
-        let pagination_all = input::Pagination {
-            page_idx: 0,
-            page_size: u32::MAX,
-        };
-        let notifications = repository
-            .find_many_delivered(
-                user_id,
-                pagination_all,
-                input::NotificationFilters { seen: None },
-            )
-            .await?;
-        assert_eq!(notifications.len(), 3);
-
-        let pagination_first_two = input::Pagination {
-            page_idx: 0,
-            page_size: 2,
-        };
-        let notifications_first_two = repository
-            .find_many_delivered(
-                user_id,
-                pagination_first_two,
-                input::NotificationFilters { seen: None },
-            )
-            .await?;
-
-        let pagination_last_one = input::Pagination {
-            page_idx: 1,
-            page_size: 2,
-        };
-        let notifications_last_one = repository
-            .find_many_delivered(
-                user_id,
-                pagination_last_one,
-                input::NotificationFilters { seen: None },
-            )
-            .await?;
+                // other user notification
+                doc! {
+                    "user_ids": [bson::Uuid::from(other_user_id)],
+                    "confirmations": []
+                },
+            ],
+        )
+        .await?;
 
-        let first_two_correct = std::iter::zip(&notifications[0..2], &notifications_first_two)
-            .all(|(a, b)| a.id == b.id);
-        assert!(first_two_correct);
+        let count = repository.count(user_id).await?;
 
-        let last_one_correct =
-            std::iter::zip(&notifications[2..], &notifications_last_one).all(|(a, b)| a.id == b.id);
-        assert!(last_one_correct);
+        assert_eq!(count.undelivered, 2);
+        assert_eq!(count.delivered, 2);
+        assert_eq!(count.delivered_unseen, 1);
 
         destroy_test_database(database).await;
 
@@ -4043,248 +11093,354 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_delivered_filters_seen() -> anyhow::Result<()> {
+    async fn count_group_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let user_id = Uuid::from_u128(4819208301);
+        let member_id = Uuid::from_u128(6619203810);
+        let other_user_id = Uuid::from_u128(6619203811);
 
-        collection
-            .insert_many(
-                [
+        database
+            .collection::<Document>(GROUP_MEMBERS)
+            .insert_one(doc! { "group": "admins", "user_id": bson::Uuid::from(member_id) })
+            .await?;
+        insert_notifications(
+            &database,
+            [
+                // undelivered group notification
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:37:15 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "user_ids": [],
+                    "groups": ["admins"],
+                    "confirmations": []
                 },
+                // delivered unseen group notification
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
+                    "user_ids": [],
+                    "groups": ["admins"],
                     "confirmations": [
                         {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:38:21 UTC)),
+                            "user_id": bson::Uuid::from(member_id),
                             "notification_seen": false,
                             "notification_deleted": false,
-                        },
+                        }
                     ]
                 },
-            ])
-            .await?;
-
-        test(&repository, user_id, true).await?;
-        test(&repository, user_id, false).await?;
-
-        destroy_test_database(database).await;
+                // other group notification
+                doc! {
+                    "user_ids": [],
+                    "groups": ["others"],
+                    "confirmations": []
+                },
+            ],
+        )
+        .await?;
 
-        return Ok(());
+        let member_count = repository.count(member_id).await?;
+        let other_user_count = repository.count(other_user_id).await?;
 
-        async fn test(
-            repository: &NotificationsRepositoryImpl,
-            user_id: Uuid,
-            seen: bool,
-        ) -> anyhow::Result<()> {
-            let notifications = repository
-                .find_many_delivered(
-                    user_id,
-                    input::Pagination {
-                        page_idx: 0,
-                        page_size: u32::MAX,
-                    },
-                    input::NotificationFilters { seen: Some(seen) },
-                )
-                .await?;
-            if notifications.is_empty() {
-                panic!("notifications empty");
-            }
+        assert_eq!(member_count.undelivered, 1);
+        assert_eq!(member_count.delivered, 1);
+        assert_eq!(member_count.delivered_unseen, 1);
+        assert_eq!(other_user_count.undelivered, 0);
+        assert_eq!(other_user_count.delivered, 0);
 
-            let all_correct_seen = notifications
-                .iter()
-                .all(|notification| notification.seen == seen);
+        destroy_test_database(database).await;
 
-            match all_correct_seen {
-                true => Ok(()),
-                false => Err(anyhow!("filter seen: {seen} does not work")),
-            }
-        }
+        Ok(())
     }
 
     #[tokio::test]
-    async fn find_many_delivered_contains_uni_multi_and_broadcast_notifications(
-    ) -> anyhow::Result<()> {
+    async fn count_skip_invalidated_and_retracted_notifications() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let user_id = Uuid::from_u128(123120831);
+        let user_id = Uuid::from_u128(6619203810);
+        let now = OffsetDateTime::now_utc();
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "invalidate_at": DateTime::from(now - Duration::from_secs(60)),
                     "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:37:15 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "confirmations": []
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:38:21 UTC)),
-                            "notification_seen": false,
-                            "notification_deleted": false,
-                        },
-                    ]
+                    "invalidate_at": DateTime::from(now + Duration::from_secs(600)),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": []
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-28 00:12:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [
-                        bson::Uuid::from(Uuid::from_u128(95123123912831)),
-                        bson::Uuid::from(user_id),
-                    ],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
+                    "retracted_at": DateTime::from(now),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": []
+                },
+                doc! {
+                    "retracted_at": DateTime::from(now),
+                    "user_ids": [bson::Uuid::from(user_id)],
                     "confirmations": [
                         {
                             "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-28 17:38:21 UTC)),
                             "notification_seen": false,
                             "notification_deleted": false,
-                        },
+                        }
                     ]
                 },
-            ])
+            ],
+        )
+        .await?;
+
+        let count = repository.count(user_id).await?;
+
+        assert_eq!(count.undelivered, 1);
+        assert_eq!(count.delivered, 0);
+        assert_eq!(count.delivered_unseen, 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn count_equals_number_of_listed_delivered_notifications() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_id = Uuid::from_u128(6619203810);
+        let muted_producer_id = Uuid::from_u128(8129038123);
+        let delivered = |seen: bool| {
+            doc! {
+                "user_id": bson::Uuid::from(user_id),
+                "notification_seen": seen,
+                "notification_deleted": false,
+            }
+        };
+
+        database
+            .collection::<Document>(PREFERENCES)
+            .insert_one(doc! {
+                "_id": bson::Uuid::from(user_id),
+                "muted_producers": [bson::Uuid::from(muted_producer_id)],
+                "muted_content_types": [],
+            })
             .await?;
+        insert_notifications(
+            &database,
+            [
+                // delivered unicast
+                doc! {
+                    "created_at": DateTime::now(),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [delivered(false)],
+                },
+                // delivered notification of group the user is no longer member of
+                doc! {
+                    "created_at": DateTime::now(),
+                    "user_ids": [],
+                    "groups": ["admins"],
+                    "confirmations": [delivered(true)],
+                },
+                // delivered notification of topic the user unsubscribed from
+                doc! {
+                    "created_at": DateTime::now(),
+                    "user_ids": [],
+                    "topic": "billing",
+                    "confirmations": [delivered(false)],
+                },
+                // delivered notification of muted producer
+                doc! {
+                    "created_at": DateTime::now(),
+                    "producer_id": bson::Uuid::from(muted_producer_id),
+                    "user_ids": [bson::Uuid::from(user_id)],
+                    "confirmations": [delivered(false)],
+                },
+            ],
+        )
+        .await?;
 
+        let count = repository.count(user_id).await?;
         let notifications = repository
             .find_many_delivered(
                 user_id,
                 input::Pagination {
-                    page_idx: 0,
-                    page_size: u32::MAX,
+                    page_idx: None,
+                    page_size: 10,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+            )
+            .await?;
+        let unseen_notifications = repository
+            .find_many_delivered(
+                user_id,
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 10,
+                    cursor: None,
+                },
+                input::NotificationFilters {
+                    seen: Some(false),
+                    ..Default::default()
                 },
-                input::NotificationFilters { seen: None },
             )
             .await?;
 
-        assert_eq!(notifications.len(), 3);
+        assert_eq!(count.delivered, 2);
+        assert_eq!(count.delivered, notifications.len() as u64);
+        assert_eq!(count.delivered_unseen, unseen_notifications.len() as u64);
 
         destroy_test_database(database).await;
 
         Ok(())
     }
 
+    async fn remaining_ids(database: &Database) -> anyhow::Result<Vec<ObjectId>> {
+        let mut ids = database
+            .collection::<NotificationIdFindEntity>(NOTIFICATIONS)
+            .find(doc! {})
+            .await?
+            .map_ok(|entity| entity.id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        ids.sort();
+
+        Ok(ids)
+    }
+
     #[tokio::test]
-    async fn find_many_undelivered_multicast_notifications() -> anyhow::Result<()> {
+    async fn purge_created_before_only_producer() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let user_1_id = Uuid::from_u128(1);
-        let user_2_id = Uuid::from_u128(2);
-        let other_user_id = Uuid::from_u128(5981023810293);
+        let producer_id = Uuid::from_u128(8129038123);
+        let other_producer_id = Uuid::from_u128(8129038124);
+        let new_id = ObjectId::new();
+        let other_producer_notification_id = ObjectId::new();
 
-        collection
-            .insert_many([
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [
-                        bson::Uuid::from(user_1_id),
-                        bson::Uuid::from(user_2_id),
-                    ],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [],
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [
-                        bson::Uuid::from(user_1_id),
-                        bson::Uuid::from(user_2_id),
-                    ],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "_id": new_id,
+                    "created_at": DateTime::from(datetime!(2024-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [],
+                    "confirmations": [],
+                },
+                doc! {
+                    "_id": other_producer_notification_id,
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(other_producer_id),
+                    "user_ids": [],
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_created_before(
+                datetime!(2022-01-01 00:00:00 UTC),
+                PurgeProducers::Only(producer_id),
+                10,
+            )
+            .await?;
+
+        let mut expected_ids = vec![new_id, other_producer_notification_id];
+        expected_ids.sort();
+
+        assert_eq!(count, 1);
+        assert_eq!(remaining_ids(&database).await?, expected_ids);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn purge_created_before_all_except_producers() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let producer_id = Uuid::from_u128(8129038123);
+        let excluded_producer_id = Uuid::from_u128(8129038124);
+        let excluded_producer_notification_id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [],
+                    "confirmations": [],
                 },
-            ])
+                doc! {
+                    "_id": excluded_producer_notification_id,
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(excluded_producer_id),
+                    "user_ids": [],
+                    "confirmations": [],
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_created_before(
+                datetime!(2022-01-01 00:00:00 UTC),
+                PurgeProducers::AllExcept(vec![excluded_producer_id]),
+                10,
+            )
             .await?;
 
-        let notifications_1 = repository.find_many_undelivered(user_1_id).await?;
-        let notifications_2 = repository.find_many_undelivered(user_2_id).await?;
+        assert_eq!(count, 1);
+        assert_eq!(
+            remaining_ids(&database).await?,
+            vec![excluded_producer_notification_id]
+        );
 
-        let notifications_3 = repository.find_many_undelivered(other_user_id).await?;
+        destroy_test_database(database).await;
 
-        assert_eq!(notifications_1.len(), 2);
-        assert_eq!(notifications_2.len(), 2);
+        Ok(())
+    }
 
-        assert!(notifications_3.is_empty());
+    #[tokio::test]
+    async fn purge_created_before_limit() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let producer_id = Uuid::from_u128(8129038123);
+
+        insert_notifications(
+            &database,
+            (0..5).map(|_| {
+                doc! {
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(producer_id),
+                    "user_ids": [],
+                    "confirmations": [],
+                }
+            }),
+        )
+        .await?;
+
+        let count = repository
+            .purge_created_before(
+                datetime!(2022-01-01 00:00:00 UTC),
+                PurgeProducers::AllExcept(vec![]),
+                3,
+            )
+            .await?;
+
+        assert_eq!(count, 3);
+        assert_eq!(remaining_ids(&database).await?.len(), 2);
 
         destroy_test_database(database).await;
 
@@ -4292,253 +11448,231 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_undelivered_broadcast_notifications_correct_user_ids() -> anyhow::Result<()>
-    {
+    async fn purge_created_before_deletes_unreferenced_attachments() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
-        let user_id = Uuid::from_u128(4120831209);
-        let other_user_id = Uuid::from_u128(580912830);
-        assert_ne!(other_user_id, user_id);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        collection
-            .insert_many([
+        let producer_id = Uuid::from_u128(8129038123);
+        let purged_attachment_id = ObjectId::new();
+        let shared_attachment_id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(producer_id),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
-                },
-                doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(other_user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
+                    "attachment_ids": [purged_attachment_id, shared_attachment_id],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 19:00:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "created_at": DateTime::from(datetime!(2024-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(producer_id),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
+                    "attachment_ids": [shared_attachment_id],
                 },
-            ])
+            ],
+        )
+        .await?;
+        for attachment_id in [purged_attachment_id, shared_attachment_id] {
+            database
+                .collection::<Document>(ATTACHMENTS_FILES)
+                .insert_one(doc! { "_id": attachment_id })
+                .await?;
+            database
+                .collection::<Document>(ATTACHMENTS_CHUNKS)
+                .insert_one(doc! { "files_id": attachment_id, "n": 0 })
+                .await?;
+        }
+
+        let count = repository
+            .purge_created_before(
+                datetime!(2022-01-01 00:00:00 UTC),
+                PurgeProducers::Only(producer_id),
+                10,
+            )
             .await?;
 
-        let notifications = repository.find_many_undelivered(user_id).await?;
-        assert_eq!(notifications.len(), 2);
+        let files_ids = database
+            .collection::<Document>(ATTACHMENTS_FILES)
+            .distinct("_id", doc! {})
+            .await?;
+        let chunks_files_ids = database
+            .collection::<Document>(ATTACHMENTS_CHUNKS)
+            .distinct("files_id", doc! {})
+            .await?;
 
-        for notification in notifications {
-            let document = collection
-                .find_one(doc! { "_id": notification.id })
-                .await?
-                .expect("did not find id in database");
-            let user_ids = document.get_array("user_ids")?;
+        assert_eq!(count, 1);
+        assert_eq!(files_ids, vec![Bson::from(shared_attachment_id)]);
+        assert_eq!(chunks_files_ids, vec![Bson::from(shared_attachment_id)]);
 
-            assert!(user_ids.is_empty());
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn purge_orphaned_attachments_deletes_old_unreferenced() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let orphaned_attachment_id = ObjectId::new();
+        let referenced_attachment_id = ObjectId::new();
+        let recent_attachment_id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [doc! {
+                "created_at": DateTime::from(datetime!(2024-01-01 00:00:00 UTC)),
+                "user_ids": [],
+                "confirmations": [],
+                "attachment_ids": [referenced_attachment_id],
+            }],
+        )
+        .await?;
+        for (attachment_id, upload_date) in [
+            (orphaned_attachment_id, datetime!(2024-01-01 00:00:00 UTC)),
+            (referenced_attachment_id, datetime!(2024-01-01 00:00:00 UTC)),
+            (recent_attachment_id, datetime!(2024-01-03 00:00:00 UTC)),
+        ] {
+            database
+                .collection::<Document>(ATTACHMENTS_FILES)
+                .insert_one(doc! {
+                    "_id": attachment_id,
+                    "uploadDate": DateTime::from(upload_date),
+                })
+                .await?;
+            database
+                .collection::<Document>(ATTACHMENTS_CHUNKS)
+                .insert_one(doc! { "files_id": attachment_id, "n": 0 })
+                .await?;
         }
 
+        let count = repository
+            .purge_orphaned_attachments(datetime!(2024-01-02 00:00:00 UTC), 10)
+            .await?;
+
+        let files_ids = database
+            .collection::<Document>(ATTACHMENTS_FILES)
+            .distinct("_id", doc! {})
+            .await?
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect::<HashSet<_>>();
+        let chunks_files_ids = database
+            .collection::<Document>(ATTACHMENTS_CHUNKS)
+            .distinct("files_id", doc! {})
+            .await?
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect::<HashSet<_>>();
+
+        let expected_ids = HashSet::from([referenced_attachment_id, recent_attachment_id]);
+        assert_eq!(count, 1);
+        assert_eq!(files_ids, expected_ids);
+        assert_eq!(chunks_files_ids, expected_ids);
+
         destroy_test_database(database).await;
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn find_many_undelivered_skip_notifications_with_confirmations() -> anyhow::Result<()> {
+    async fn purge_invalidated_before_deletes_invalidated() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let user_id = Uuid::from_u128(1);
+        let recently_invalidated_id = ObjectId::new();
+        let not_invalidated_id = ObjectId::new();
+        let never_invalidated_id = ObjectId::new();
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "invalidate_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(5801238012)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(48120381209)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        }
-                    ]
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "_id": recently_invalidated_id,
+                    "invalidate_at": DateTime::from(datetime!(2022-06-01 00:00:00 UTC)),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        }
-                    ]
-                },
-                doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
-                },
-                doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 4,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        }
-                    ]
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [
-                        bson::Uuid::from(Uuid::from_u128(57102380129831)),
-                        bson::Uuid::from(user_id)
-                    ],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 5,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(57102380129831)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        }
-                    ]
+                    "_id": not_invalidated_id,
+                    "invalidate_at": DateTime::from(datetime!(9999-12-31 00:00:00 UTC)),
+                    "user_ids": [],
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
+                    "_id": never_invalidated_id,
                     "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [
-                        bson::Uuid::from(Uuid::from_u128(57102380129831)),
-                        bson::Uuid::from(user_id)
-                    ],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 6,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": [
-                        {
-                            "user_id": bson::Uuid::from(user_id),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        },
-                        {
-                            "user_id": bson::Uuid::from(Uuid::from_u128(57102380129831)),
-                            "notification_delivered_at": DateTime::from(datetime!(2024-01-29 19:17:00 UTC)),
-                            "notification_seen": true,
-                            "notification_deleted": false,
-                        }
-                    ]
+                    "user_ids": [],
+                    "confirmations": [],
                 },
-            ])
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_invalidated_before(datetime!(2022-01-01 00:00:00 UTC), 10)
             .await?;
 
-        let notifications = repository.find_many_undelivered(user_id).await?;
-        assert_eq!(notifications.len(), 3);
+        let mut expected_ids = vec![
+            recently_invalidated_id,
+            not_invalidated_id,
+            never_invalidated_id,
+        ];
+        expected_ids.sort();
+
+        assert_eq!(count, 1);
+        assert_eq!(remaining_ids(&database).await?, expected_ids);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
 
-        for notification in notifications {
-            let document = collection
-                .find_one(doc! { "_id": notification.id })
-                .await?
-                .expect("did not find id in database");
-            let confirmations = document.get_array("confirmations")?;
-            let contains_user_confirmation = confirmations
-                .into_iter()
-                .filter_map(|bson| {
-                    let Bson::Document(document) = bson else {
-                        return None;
-                    };
-                    let Some(document_user_id) = document.get("user_id") else {
-                        return None;
-                    };
-                    let Bson::Binary(binary) = document_user_id else {
-                        return None;
-                    };
-                    let Ok(document_user_id) = binary.to_uuid() else {
-                        return None;
-                    };
+    #[tokio::test]
+    async fn purge_invalidated_before_keeps_delivered_not_deleted() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-                    Some(Uuid::from(document_user_id))
-                })
-                .any(|confirmation_user_id| confirmation_user_id == user_id);
+        let user_id = bson::Uuid::from(Uuid::from_u128(1));
+        let delivered_id = ObjectId::new();
 
-            assert_eq!(contains_user_confirmation, false);
-        }
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "_id": delivered_id,
+                    "invalidate_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "user_ids": [],
+                    "confirmations": [
+                        { "user_id": user_id, "notification_deleted": false },
+                    ],
+                },
+                doc! {
+                    "invalidate_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "user_ids": [],
+                    "confirmations": [
+                        { "user_id": user_id, "notification_deleted": true },
+                    ],
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_invalidated_before(datetime!(2022-01-01 00:00:00 UTC), 10)
+            .await?;
+
+        assert_eq!(count, 1);
+        assert_eq!(remaining_ids(&database).await?, vec![delivered_id]);
 
         destroy_test_database(database).await;
 
@@ -4546,116 +11680,168 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_undelivered_skip_invalidated_notifications() -> anyhow::Result<()> {
+    async fn purge_deleted_before_retracted() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
 
-        let user_id = Uuid::from_u128(1);
+        let recently_retracted_id = ObjectId::new();
+        let not_retracted_id = ObjectId::new();
 
-        collection
-            .insert_many(
-                [
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "retracted_at": DateTime::from(datetime!(2021-01-01 00:00:00 UTC)),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": DateTime::from(datetime!(9999-12-31 00:00:00 UTC)),
+                    "_id": recently_retracted_id,
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "retracted_at": DateTime::from(datetime!(2022-06-01 00:00:00 UTC)),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": DateTime::from(OffsetDateTime::now_utc() - Duration::from_secs(6000)),
+                    "_id": not_retracted_id,
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "retracted_at": None as Option<DateTime>,
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 3,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
                 },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_deleted_before(datetime!(2022-01-01 00:00:00 UTC), 10)
+            .await?;
+
+        let mut expected_ids = vec![recently_retracted_id, not_retracted_id];
+        expected_ids.sort();
+
+        assert_eq!(count, 1);
+        assert_eq!(remaining_ids(&database).await?, expected_ids);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn purge_deleted_before_deleted_by_all_recipients() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+
+        let user_1_id = bson::Uuid::from(Uuid::from_u128(1));
+        let user_2_id = bson::Uuid::from(Uuid::from_u128(2));
+        let created_at = DateTime::from(datetime!(2020-01-01 00:00:00 UTC));
+        let not_deleted_by_all_id = ObjectId::new();
+        let not_received_by_all_id = ObjectId::new();
+        let broadcast_id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 13:56:41 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 4,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "created_at": created_at,
+                    "user_ids": [user_1_id, user_2_id],
+                    "confirmations": [
+                        { "user_id": user_1_id, "notification_deleted": true },
+                        { "user_id": user_2_id, "notification_deleted": true },
+                    ],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": DateTime::from(datetime!(9999-12-31 00:00:00 UTC)),
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 5,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "_id": not_deleted_by_all_id,
+                    "created_at": created_at,
+                    "user_ids": [user_1_id, user_2_id],
+                    "confirmations": [
+                        { "user_id": user_1_id, "notification_deleted": true },
+                        { "user_id": user_2_id, "notification_deleted": false },
+                    ],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:52:42 UTC)),
-                    "invalidate_at": DateTime::from(OffsetDateTime::now_utc() - Duration::from_secs(6000)),
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 6,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "_id": not_received_by_all_id,
+                    "created_at": created_at,
+                    "user_ids": [user_1_id, user_2_id],
+                    "confirmations": [
+                        { "user_id": user_1_id, "notification_deleted": true },
+                    ],
                 },
-            ])
+                doc! {
+                    "_id": broadcast_id,
+                    "created_at": created_at,
+                    "user_ids": [],
+                    "confirmations": [
+                        { "user_id": user_1_id, "notification_deleted": true },
+                    ],
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_deleted_before(datetime!(2022-01-01 00:00:00 UTC), 10)
             .await?;
 
-        let notifications = repository.find_many_undelivered(user_id).await?;
-        assert_eq!(notifications.len(), 4);
+        let mut expected_ids = vec![not_deleted_by_all_id, not_received_by_all_id, broadcast_id];
+        expected_ids.sort();
 
-        let now = OffsetDateTime::now_utc();
+        assert_eq!(count, 1);
+        assert_eq!(remaining_ids(&database).await?, expected_ids);
 
-        for notification in notifications {
-            let document = collection
-                .find_one(doc! { "_id": notification.id })
-                .await?
-                .expect("did not find id in database");
-            let invalidate_at = document
-                .get("invalidate_at")
-                .expect("document has no property invalidate_at");
+        destroy_test_database(database).await;
 
-            if let Some(datetime) = invalidate_at.as_datetime() {
-                let datetime = OffsetDateTime::from(*datetime);
-                assert!(datetime >= now);
-            }
-        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn purge_deletes_confirmations() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let confirmations_collection = database.collection::<Document>(CONFIRMATIONS);
+
+        let user_id = bson::Uuid::from(Uuid::from_u128(1));
+        let kept_id = ObjectId::new();
+
+        insert_notifications(
+            &database,
+            [
+                doc! {
+                    "created_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(2)),
+                    "user_ids": [],
+                    "confirmations": [
+                        { "user_id": user_id, "notification_deleted": false },
+                    ],
+                },
+                doc! {
+                    "_id": kept_id,
+                    "created_at": DateTime::from(datetime!(2023-01-01 00:00:00 UTC)),
+                    "producer_id": bson::Uuid::from(Uuid::from_u128(2)),
+                    "user_ids": [],
+                    "confirmations": [
+                        { "user_id": user_id, "notification_deleted": false },
+                    ],
+                },
+            ],
+        )
+        .await?;
+
+        let count = repository
+            .purge_created_before(
+                datetime!(2022-01-01 00:00:00 UTC),
+                PurgeProducers::AllExcept(vec![]),
+                10,
+            )
+            .await?;
+        assert_eq!(count, 1);
+
+        let confirmation_ids = confirmations_collection
+            .distinct("notification_id", doc! {})
+            .await?;
+        assert_eq!(confirmation_ids, vec![Bson::from(kept_id)]);
 
         destroy_test_database(database).await;
 
@@ -4663,80 +11849,114 @@ mod test {
     }
 
     #[tokio::test]
-    async fn find_many_undelivered_sorted_by_created_at_asc() -> anyhow::Result<()> {
+    async fn purge_deletes_unpublished_messages_and_unsent_receipts() -> anyhow::Result<()> {
         let database = create_test_database().await?;
-        let repository = NotificationsRepositoryImpl::new(database.clone()).await?;
-        let collection = database.collection::<Document>(NOTIFICATIONS);
+        let repository = NotificationsRepositoryImpl::new(database.clone(), None).await?;
+        let outbox_collection = database.collection::<Document>(OUTBOX);
+        let receipts_collection = database.collection::<Document>(RECEIPTS);
 
-        let user_id = Uuid::from_u128(1);
+        let purged_id = ObjectId::new();
+        let kept_id = ObjectId::new();
 
-        collection
-            .insert_many([
+        insert_notifications(
+            &database,
+            [
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 19:57:22 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "_id": purged_id,
+                    "retracted_at": DateTime::from(datetime!(2020-01-01 00:00:00 UTC)),
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 1,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 18:22:11 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
+                    "_id": kept_id,
                     "user_ids": [],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 2,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "confirmations": [],
                 },
+            ],
+        )
+        .await?;
+
+        let published_message_id = ObjectId::new();
+        let kept_message_id = ObjectId::new();
+        let many_seen_message_id = ObjectId::new();
+        outbox_collection
+            .insert_many([
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 17:15:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 4,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "notification_id": purged_id,
+                    "notification_ids": [],
+                    "published_at": None as Option<DateTime>,
                 },
                 doc! {
-                    "created_at": DateTime::from(datetime!(2024-01-29 20:00:00 UTC)),
-                    "invalidate_at": None as Option<DateTime>,
-                    "user_ids": [bson::Uuid::from(user_id)],
-                    "producer_id": bson::Uuid::from(Uuid::from_u128(48129038210)),
-                    "producer_notification_id": 5,
-                    "content_type": "utf-8",
-                    "content": Binary {
-                        subtype: BinarySubtype::Generic,
-                        bytes: b"other notification".to_vec(),
-                    },
-                    "confirmations": []
+                    "_id": published_message_id,
+                    "notification_id": purged_id,
+                    "notification_ids": [],
+                    "published_at": DateTime::now(),
+                },
+                doc! {
+                    "_id": kept_message_id,
+                    "notification_id": kept_id,
+                    "notification_ids": [],
+                    "published_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "notification_id": purged_id,
+                    "notification_ids": [purged_id],
+                    "published_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "_id": many_seen_message_id,
+                    "notification_id": purged_id,
+                    "notification_ids": [purged_id, kept_id],
+                    "published_at": None as Option<DateTime>,
                 },
             ])
             .await?;
 
-        let notifications = repository.find_many_undelivered(user_id).await?;
-        assert_eq!(notifications.len(), 4);
-
-        let correct_order = std::iter::zip(
-            &notifications[..notifications.len() - 1],
-            &notifications[1..],
-        )
-        .all(|(a, b)| a.created_at <= b.created_at);
+        let dead_receipt_id = ObjectId::new();
+        let kept_receipt_id = ObjectId::new();
+        receipts_collection
+            .insert_many([
+                doc! {
+                    "notification_id": purged_id,
+                    "delivered_at": None as Option<DateTime>,
+                    "dead_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "_id": dead_receipt_id,
+                    "notification_id": purged_id,
+                    "delivered_at": None as Option<DateTime>,
+                    "dead_at": DateTime::now(),
+                },
+                doc! {
+                    "_id": kept_receipt_id,
+                    "notification_id": kept_id,
+                    "delivered_at": None as Option<DateTime>,
+                    "dead_at": None as Option<DateTime>,
+                },
+            ])
+            .await?;
 
-        assert_eq!(correct_order, true);
+        let count = repository
+            .purge_deleted_before(datetime!(2022-01-01 00:00:00 UTC), 10)
+            .await?;
+        assert_eq!(count, 1);
+
+        let mut message_ids = outbox_collection.distinct("_id", doc! {}).await?;
+        message_ids.sort_by_key(|id| id.as_object_id());
+        let mut expected_message_ids = vec![
+            Bson::from(published_message_id),
+            Bson::from(kept_message_id),
+            Bson::from(many_seen_message_id),
+        ];
+        expected_message_ids.sort_by_key(|id| id.as_object_id());
+        assert_eq!(message_ids, expected_message_ids);
+
+        let mut receipt_ids = receipts_collection.distinct("_id", doc! {}).await?;
+        receipt_ids.sort_by_key(|id| id.as_object_id());
+        let mut expected_receipt_ids =
+            vec![Bson::from(dead_receipt_id), Bson::from(kept_receipt_id)];
+        expected_receipt_ids.sort_by_key(|id| id.as_object_id());
+        assert_eq!(receipt_ids, expected_receipt_ids);
 
         destroy_test_database(database).await;
 
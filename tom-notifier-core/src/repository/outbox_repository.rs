@@ -0,0 +1,42 @@
+use super::{dto::OutboxMessage, Error};
+use axum::async_trait;
+use bson::oid::ObjectId;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    ///
+    /// Finds at most `limit` messages that have not been published yet
+    /// and locks them for `lock_duration`, so other instances of the application
+    /// won't publish them at the same time.
+    /// Scheduled messages are skipped until their publish_at passes.
+    /// Messages are sorted ascending by creation date.
+    /// Current members of message groups and current subscribers
+    /// of message topic are added to message user_ids.
+    /// Recipients of NEW messages that muted the notification
+    /// or are in their quiet hours are added to message excluded_user_ids.
+    ///
+    async fn lock_pending(
+        &self,
+        limit: u32,
+        lock_duration: Duration,
+    ) -> Result<Vec<OutboxMessage>, Error>;
+
+    ///
+    /// Marks message as published
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when
+    ///     - message does not exist
+    ///     - message has already been published
+    ///
+    async fn mark_published(&self, id: ObjectId) -> Result<(), Error>;
+
+    ///
+    /// Finds the earliest publish_at of scheduled messages
+    /// that have not been published yet and are not due yet
+    ///
+    async fn find_next_publish_at(&self) -> Result<Option<OffsetDateTime>, Error>;
+}
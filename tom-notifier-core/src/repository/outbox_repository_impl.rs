@@ -0,0 +1,977 @@
+use super::{
+    dto::{OutboxMessage, OutboxMessageStatus, QuietHours},
+    entity::{GroupMemberEntity, OutboxMessageFindEntity, PreferencesEntity, SubscriptionEntity},
+    groups_repository_impl::GROUP_MEMBERS,
+    preferences_repository_impl::PREFERENCES,
+    subscriptions_repository_impl::SUBSCRIPTIONS,
+    Error, OutboxRepository,
+};
+use axum::async_trait;
+use bson::{doc, oid::ObjectId, DateTime, Document};
+use futures_util::TryStreamExt;
+use mongodb::{options::IndexOptions, Collection, Database, IndexModel};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub(super) const OUTBOX: &str = "outbox";
+const INDEX_NAME_PENDING: &str = "index_published_at_created_at";
+const INDEX_NAME_TTL_PUBLISHED_AT: &str = "ttl_index_published_at";
+const INDEX_NAME_SCHEDULED: &str = "index_published_at_publish_at";
+const INDEX_NAME_NOTIFICATION_ID: &str = "index_notification_id_published_at";
+
+///
+/// How long published messages are kept before Mongo removes them
+///
+const PUBLISHED_MESSAGE_LIFESPAN: Duration = Duration::from_secs(3600);
+
+///
+/// Max number of recipients excluded from a single NEW message, so the message
+/// stays small. Recipients above the limit are pushed the notification
+///
+const MAX_EXCLUDED_RECIPIENTS: usize = 10_000;
+
+pub struct OutboxRepositoryImpl {
+    database: Database,
+}
+
+impl OutboxRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(OUTBOX).await?;
+
+        let collection = database.collection(OUTBOX);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_PENDING.to_string()) {
+            Self::create_pending_index(&collection).await?;
+            tracing::debug!("created index {OUTBOX}.{INDEX_NAME_PENDING}");
+        }
+        if !index_names.contains(&INDEX_NAME_TTL_PUBLISHED_AT.to_string()) {
+            Self::create_ttl_published_at_index(&collection).await?;
+            tracing::debug!("created index {OUTBOX}.{INDEX_NAME_TTL_PUBLISHED_AT}");
+        }
+        if !index_names.contains(&INDEX_NAME_SCHEDULED.to_string()) {
+            Self::create_scheduled_index(&collection).await?;
+            tracing::debug!("created index {OUTBOX}.{INDEX_NAME_SCHEDULED}");
+        }
+        if !index_names.contains(&INDEX_NAME_NOTIFICATION_ID.to_string()) {
+            Self::create_notification_id_index(&collection).await?;
+            tracing::debug!("created index {OUTBOX}.{INDEX_NAME_NOTIFICATION_ID}");
+        }
+
+        Ok(Self { database })
+    }
+
+    async fn create_pending_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "published_at": 1,
+                "created_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_PENDING.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_scheduled_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "published_at": 1,
+                "publish_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_SCHEDULED.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_notification_id_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "notification_id": 1,
+                "published_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_NOTIFICATION_ID.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_ttl_published_at_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "published_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_TTL_PUBLISHED_AT.to_string())
+                    .expire_after(PUBLISHED_MESSAGE_LIFESPAN)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    ///
+    /// Finds current members of groups of the messages
+    ///
+    async fn find_group_members(
+        &self,
+        messages: &[OutboxMessage],
+    ) -> Result<HashMap<String, Vec<Uuid>>, mongodb::error::Error> {
+        let groups = messages
+            .iter()
+            .flat_map(|message| message.groups.iter().cloned())
+            .collect::<HashSet<_>>();
+        if groups.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut members = HashMap::<String, Vec<Uuid>>::new();
+        let mut cursor = self
+            .database
+            .collection::<GroupMemberEntity>(GROUP_MEMBERS)
+            .find(doc! { "group": { "$in": groups.into_iter().collect::<Vec<_>>() } })
+            .await?;
+        while let Some(member) = cursor.try_next().await? {
+            members
+                .entry(member.group)
+                .or_default()
+                .push(Uuid::from(member.user_id));
+        }
+
+        Ok(members)
+    }
+
+    ///
+    /// Finds current subscribers of topics of the messages
+    ///
+    async fn find_topic_subscribers(
+        &self,
+        messages: &[OutboxMessage],
+    ) -> Result<HashMap<String, Vec<Uuid>>, mongodb::error::Error> {
+        let topics = messages
+            .iter()
+            .filter_map(|message| message.topic.clone())
+            .collect::<HashSet<_>>();
+        if topics.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut subscribers = HashMap::<String, Vec<Uuid>>::new();
+        let mut cursor = self
+            .database
+            .collection::<SubscriptionEntity>(SUBSCRIPTIONS)
+            .find(doc! { "topic": { "$in": topics.into_iter().collect::<Vec<_>>() } })
+            .await?;
+        while let Some(subscription) = cursor.try_next().await? {
+            subscribers
+                .entry(subscription.topic)
+                .or_default()
+                .push(Uuid::from(subscription.user_id));
+        }
+
+        Ok(subscribers)
+    }
+
+    ///
+    /// Adds current members of message groups and current subscribers
+    /// of message topic to message user_ids, so consumers of published
+    /// messages don't have to know about groups and topics
+    ///
+    async fn resolve_recipients(
+        &self,
+        messages: &mut [OutboxMessage],
+    ) -> Result<(), mongodb::error::Error> {
+        let members = self.find_group_members(messages).await?;
+        let subscribers = self.find_topic_subscribers(messages).await?;
+
+        for message in messages.iter_mut() {
+            let mut user_ids = message.user_ids.iter().copied().collect::<HashSet<_>>();
+            let group_members = message
+                .groups
+                .iter()
+                .filter_map(|group| members.get(group))
+                .flatten();
+            let topic_subscribers = message
+                .topic
+                .iter()
+                .filter_map(|topic| subscribers.get(topic))
+                .flatten();
+            for user_id in group_members.chain(topic_subscribers) {
+                if user_ids.insert(*user_id) {
+                    message.user_ids.push(*user_id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Finds recipients of NEW messages that muted the notification
+    /// or are in their quiet hours, so the notification is not pushed to them.
+    /// Recipients of broadcast messages are looked up among all users with preferences.
+    /// At most [MAX_EXCLUDED_RECIPIENTS] recipients are excluded from a message
+    ///
+    async fn resolve_excluded_recipients(
+        &self,
+        messages: &mut [OutboxMessage],
+    ) -> Result<(), mongodb::error::Error> {
+        let now = OffsetDateTime::now_utc();
+        let new_messages = messages
+            .iter_mut()
+            .filter(|message| message.status == OutboxMessageStatus::New);
+
+        for message in new_messages {
+            let mut conditions = Self::quiet_hours_conditions(now);
+            if let Some(created_by) = message.created_by {
+                conditions.push(doc! { "muted_producers": bson::Uuid::from(created_by) });
+            }
+            if let Some(content_type) = &message.content_type {
+                conditions.push(doc! { "muted_content_types": content_type });
+            }
+            let mut filter = doc! { "$or": conditions };
+            if !message.user_ids.is_empty() {
+                let user_ids = message
+                    .user_ids
+                    .iter()
+                    .map(|user_id| bson::Uuid::from(*user_id))
+                    .collect::<Vec<_>>();
+                filter.insert("_id", doc! { "$in": user_ids });
+            }
+
+            let mut cursor = self
+                .database
+                .collection::<PreferencesEntity>(PREFERENCES)
+                .find(filter)
+                .limit(MAX_EXCLUDED_RECIPIENTS as i64)
+                .await?;
+            while let Some(preferences) = cursor.try_next().await? {
+                let is_producer_muted = message.created_by.is_some_and(|created_by| {
+                    preferences
+                        .muted_producers
+                        .contains(&bson::Uuid::from(created_by))
+                });
+                let is_content_type_muted =
+                    message.content_type.as_ref().is_some_and(|content_type| {
+                        preferences.muted_content_types.contains(content_type)
+                    });
+                let is_quiet = preferences
+                    .quiet_hours
+                    .map(QuietHours::from)
+                    .is_some_and(|quiet_hours| quiet_hours.contains(now));
+
+                if is_producer_muted || is_content_type_muted || is_quiet {
+                    message.excluded_user_ids.push(Uuid::from(preferences._id));
+                }
+            }
+
+            if message.excluded_user_ids.len() == MAX_EXCLUDED_RECIPIENTS {
+                tracing::warn!(
+                    id = message.id.to_hex(),
+                    "too many excluded recipients, some of them will be pushed the notification"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Creates conditions matching preferences with quiet hours active at the time
+    ///
+    fn quiet_hours_conditions(at: OffsetDateTime) -> Vec<Document> {
+        let at = at.to_offset(time::UtcOffset::UTC);
+        let minute = at.hour() as u32 * 60 + at.minute() as u32;
+
+        vec![
+            doc! {
+                "quiet_hours.utc_wraps": false,
+                "quiet_hours.utc_from_minute": { "$lte": minute },
+                "quiet_hours.utc_to_minute": { "$gt": minute },
+            },
+            doc! {
+                "quiet_hours.utc_wraps": true,
+                "quiet_hours.utc_from_minute": { "$lte": minute },
+            },
+            doc! {
+                "quiet_hours.utc_wraps": true,
+                "quiet_hours.utc_to_minute": { "$gt": minute },
+            },
+        ]
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxRepositoryImpl {
+    async fn lock_pending(
+        &self,
+        limit: u32,
+        lock_duration: Duration,
+    ) -> Result<Vec<OutboxMessage>, Error> {
+        let now = OffsetDateTime::now_utc();
+        let locked_until = DateTime::from(now + lock_duration);
+        let now = DateTime::from(now);
+        let lock_id = ObjectId::new();
+        let collection = self.database.collection::<Document>(OUTBOX);
+
+        let pending_filter = doc! {
+            "published_at": None as Option<DateTime>,
+            "$and": [
+                {
+                    "$or": [
+                        { "locked_until": None as Option<DateTime> },
+                        { "locked_until": { "$lte": now } },
+                    ]
+                },
+                {
+                    "$or": [
+                        { "publish_at": None as Option<DateTime> },
+                        { "publish_at": { "$lte": now } },
+                    ]
+                },
+            ],
+        };
+
+        let ids = collection
+            .find(pending_filter.clone())
+            .projection(doc! { "_id": 1 })
+            .sort(doc! { "created_at": 1 })
+            .limit(limit as i64)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter_map(|document| document.get_object_id("_id").ok())
+            .collect::<Vec<_>>();
+
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Other instance could lock some of the messages in the meantime
+        // so pending filter has to be applied once again
+        let mut lock_filter = pending_filter;
+        lock_filter.insert("_id", doc! { "$in": ids });
+        collection
+            .update_many(
+                lock_filter,
+                doc! {
+                    "$set": {
+                        "locked_until": locked_until,
+                        "lock_id": lock_id,
+                    }
+                },
+            )
+            .await?;
+
+        let mut messages = self
+            .database
+            .collection::<OutboxMessageFindEntity>(OUTBOX)
+            .find(doc! { "lock_id": lock_id })
+            .sort(doc! { "created_at": 1 })
+            .await?
+            .map_ok(OutboxMessage::from)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        self.resolve_recipients(&mut messages).await?;
+        self.resolve_excluded_recipients(&mut messages).await?;
+
+        Ok(messages)
+    }
+
+    async fn mark_published(&self, id: ObjectId) -> Result<(), Error> {
+        let now = DateTime::from(OffsetDateTime::now_utc());
+
+        let update_result = self
+            .database
+            .collection::<Document>(OUTBOX)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    "published_at": None as Option<DateTime>,
+                },
+                doc! {
+                    "$set": {
+                        "published_at": now,
+                    }
+                },
+            )
+            .await?;
+
+        match update_result.modified_count == 1 {
+            true => Ok(()),
+            false => Err(Error::NoDocumentUpdated),
+        }
+    }
+
+    async fn find_next_publish_at(&self) -> Result<Option<OffsetDateTime>, Error> {
+        let now = DateTime::from(OffsetDateTime::now_utc());
+
+        let document = self
+            .database
+            .collection::<Document>(OUTBOX)
+            .find_one(doc! {
+                "published_at": None as Option<DateTime>,
+                "publish_at": { "$gt": now },
+            })
+            .projection(doc! { "_id": 0, "publish_at": 1 })
+            .sort(doc! { "publish_at": 1 })
+            .await?;
+
+        let publish_at = document
+            .and_then(|document| document.get_datetime("publish_at").ok().copied())
+            .map(OffsetDateTime::from);
+
+        Ok(publish_at)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repository::entity::{content_binary, QuietHoursEntity};
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    fn outbox_message(id: ObjectId, created_at: DateTime) -> Document {
+        doc! {
+            "_id": id,
+            "created_at": created_at,
+            "locked_until": None as Option<DateTime>,
+            "lock_id": None as Option<ObjectId>,
+            "published_at": None as Option<DateTime>,
+            "status": "DELETED",
+            "user_ids": [bson::Uuid::from(Uuid::from_u128(1))],
+            "notification_id": ObjectId::new(),
+            "timestamp": created_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn lock_pending_sorted_by_created_at_asc() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let id_1 = ObjectId::new();
+        let id_2 = ObjectId::new();
+        let id_3 = ObjectId::new();
+
+        collection
+            .insert_many([
+                outbox_message(id_2, DateTime::from_millis(2000)),
+                outbox_message(id_3, DateTime::from_millis(3000)),
+                outbox_message(id_1, DateTime::from_millis(1000)),
+            ])
+            .await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        let ids = messages
+            .into_iter()
+            .map(|message| message.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![id_1, id_2, id_3]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_correct_limit() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        collection
+            .insert_many([
+                outbox_message(ObjectId::new(), DateTime::from_millis(1000)),
+                outbox_message(ObjectId::new(), DateTime::from_millis(2000)),
+                outbox_message(ObjectId::new(), DateTime::from_millis(3000)),
+            ])
+            .await?;
+
+        let messages = repository.lock_pending(2, Duration::from_secs(10)).await?;
+
+        assert_eq!(messages.len(), 2);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_skip_locked() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        collection
+            .insert_many([outbox_message(ObjectId::new(), DateTime::from_millis(1000))])
+            .await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(messages.len(), 1);
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(messages.len(), 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_lock_expired() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+        let mut message = outbox_message(id, DateTime::from_millis(1000));
+        message.insert("locked_until", DateTime::from_millis(2000));
+        message.insert("lock_id", ObjectId::new());
+
+        collection.insert_many([message]).await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        let ids = messages
+            .into_iter()
+            .map(|message| message.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![id]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_skip_published() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let mut message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        message.insert("published_at", DateTime::now());
+
+        collection.insert_many([message]).await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        assert_eq!(messages.len(), 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_skip_scheduled() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let due_id = ObjectId::new();
+        let mut due_message = outbox_message(due_id, DateTime::from_millis(1000));
+        due_message.insert("publish_at", DateTime::from_millis(2000));
+        let mut scheduled_message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        scheduled_message.insert(
+            "publish_at",
+            DateTime::from(OffsetDateTime::now_utc() + Duration::from_secs(3600)),
+        );
+
+        collection
+            .insert_many([due_message, scheduled_message])
+            .await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        let ids = messages
+            .into_iter()
+            .map(|message| message.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![due_id]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_resolves_group_members() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        database
+            .collection::<Document>(GROUP_MEMBERS)
+            .insert_many([
+                doc! { "group": "admins", "user_id": bson::Uuid::from(Uuid::from_u128(1)) },
+                doc! { "group": "admins", "user_id": bson::Uuid::from(Uuid::from_u128(2)) },
+                doc! { "group": "others", "user_id": bson::Uuid::from(Uuid::from_u128(3)) },
+            ])
+            .await?;
+
+        let mut message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        message.insert("groups", vec!["admins", "missing"]);
+
+        collection.insert_many([message]).await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        assert_eq!(messages.len(), 1);
+
+        let mut user_ids = messages[0].user_ids.clone();
+        user_ids.sort();
+
+        assert_eq!(user_ids, vec![Uuid::from_u128(1), Uuid::from_u128(2)]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_resolves_topic_subscribers() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        database
+            .collection::<Document>(SUBSCRIPTIONS)
+            .insert_many([
+                doc! { "topic": "billing", "user_id": bson::Uuid::from(Uuid::from_u128(1)) },
+                doc! { "topic": "billing", "user_id": bson::Uuid::from(Uuid::from_u128(2)) },
+                doc! { "topic": "deployments", "user_id": bson::Uuid::from(Uuid::from_u128(3)) },
+            ])
+            .await?;
+
+        let mut message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        message.insert("user_ids", vec![bson::Uuid::from(Uuid::from_u128(2))]);
+        message.insert("topic", "billing");
+
+        collection.insert_many([message]).await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        assert_eq!(messages.len(), 1);
+
+        let mut user_ids = messages[0].user_ids.clone();
+        user_ids.sort();
+
+        assert_eq!(user_ids, vec![Uuid::from_u128(1), Uuid::from_u128(2)]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    fn quiet_hours_document(from_minute: i32, to_minute: i32) -> Document {
+        let quiet_hours = QuietHours {
+            from_minute: (from_minute % (24 * 60)) as u32,
+            to_minute: (to_minute % (24 * 60)) as u32,
+            utc_offset_minutes: 0,
+        };
+
+        bson::to_document(&QuietHoursEntity::from(quiet_hours)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn lock_pending_resolves_excluded_recipients() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let producer_id = bson::Uuid::from(Uuid::from_u128(10));
+        let now = OffsetDateTime::now_utc();
+        let now_minute = now.hour() as i32 * 60 + now.minute() as i32;
+
+        database
+            .collection::<Document>(PREFERENCES)
+            .insert_many([
+                doc! {
+                    "_id": bson::Uuid::from(Uuid::from_u128(1)),
+                    "muted_producers": [producer_id],
+                    "muted_content_types": [],
+                    "quiet_hours": None as Option<Document>,
+                    "updated_at": DateTime::now(),
+                },
+                doc! {
+                    "_id": bson::Uuid::from(Uuid::from_u128(2)),
+                    "muted_producers": [],
+                    "muted_content_types": ["marketing"],
+                    "quiet_hours": None as Option<Document>,
+                    "updated_at": DateTime::now(),
+                },
+                doc! {
+                    "_id": bson::Uuid::from(Uuid::from_u128(3)),
+                    "muted_producers": [],
+                    "muted_content_types": [],
+                    "quiet_hours": quiet_hours_document(now_minute, now_minute + 10),
+                    "updated_at": DateTime::now(),
+                },
+                doc! {
+                    "_id": bson::Uuid::from(Uuid::from_u128(5)),
+                    "muted_producers": [],
+                    "muted_content_types": [],
+                    "quiet_hours": quiet_hours_document(now_minute + 10, now_minute + 20),
+                    "updated_at": DateTime::now(),
+                },
+                doc! {
+                    "_id": bson::Uuid::from(Uuid::from_u128(4)),
+                    "muted_producers": [],
+                    "muted_content_types": ["utf-8"],
+                    "quiet_hours": None as Option<Document>,
+                    "updated_at": DateTime::now(),
+                },
+            ])
+            .await?;
+
+        let mut message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        message.insert("status", "NEW");
+        message.insert(
+            "user_ids",
+            [1, 2, 3, 5]
+                .map(|id| bson::Uuid::from(Uuid::from_u128(id)))
+                .into_iter()
+                .collect::<Vec<_>>(),
+        );
+        message.insert("created_by", producer_id);
+        message.insert("content_type", "marketing");
+
+        collection.insert_many([message]).await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        assert_eq!(messages.len(), 1);
+
+        let mut excluded_user_ids = messages[0].excluded_user_ids.clone();
+        excluded_user_ids.sort();
+
+        assert_eq!(
+            excluded_user_ids,
+            vec![Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3)]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_decompresses_content() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let content = "compressible content ".repeat(64).into_bytes();
+
+        let mut message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        message.insert("status", "NEW");
+        message.insert("content_type", "utf-8");
+        message.insert("content", content_binary(&content, Some(0)));
+        message.insert(
+            "localized_contents",
+            vec![doc! {
+                "locale": "en-US",
+                "content_type": "utf-8",
+                "content": content_binary(&content, Some(0)),
+            }],
+        );
+
+        collection.insert_many([message]).await?;
+
+        let messages = repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, Some(content.clone()));
+        assert_eq!(messages[0].localized_contents[0].content, content);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_next_publish_at_earliest_scheduled() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let now = OffsetDateTime::now_utc();
+        let earliest_publish_at = DateTime::from(now + Duration::from_secs(60));
+
+        let mut due_message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        due_message.insert("publish_at", DateTime::from(now - Duration::from_secs(60)));
+        let mut published_message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        published_message.insert("publish_at", DateTime::from(now + Duration::from_secs(30)));
+        published_message.insert("published_at", DateTime::from(now));
+        let mut earliest_message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        earliest_message.insert("publish_at", earliest_publish_at);
+        let mut later_message = outbox_message(ObjectId::new(), DateTime::from_millis(1000));
+        later_message.insert("publish_at", DateTime::from(now + Duration::from_secs(120)));
+
+        collection
+            .insert_many([
+                due_message,
+                published_message,
+                later_message,
+                earliest_message,
+            ])
+            .await?;
+
+        let publish_at = repository.find_next_publish_at().await?;
+
+        assert_eq!(publish_at, Some(OffsetDateTime::from(earliest_publish_at)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_next_publish_at_nothing_scheduled() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        collection
+            .insert_many([outbox_message(ObjectId::new(), DateTime::from_millis(1000))])
+            .await?;
+
+        let publish_at = repository.find_next_publish_at().await?;
+
+        assert_eq!(publish_at, None);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mark_published_published_at_set() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+
+        collection
+            .insert_many([outbox_message(id, DateTime::from_millis(1000))])
+            .await?;
+
+        repository.mark_published(id).await?;
+
+        let document = collection.find_one(doc! { "_id": id }).await?.unwrap();
+
+        assert!(document.get_datetime("published_at").is_ok());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mark_published_already_published() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+        let collection = database.collection::<Document>(OUTBOX);
+
+        let id = ObjectId::new();
+
+        collection
+            .insert_many([outbox_message(id, DateTime::from_millis(1000))])
+            .await?;
+
+        repository.mark_published(id).await?;
+        let mark_result = repository.mark_published(id).await;
+
+        assert!(matches!(mark_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mark_published_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = OutboxRepositoryImpl::new(database.clone()).await?;
+
+        let mark_result = repository.mark_published(ObjectId::new()).await;
+
+        assert!(matches!(mark_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
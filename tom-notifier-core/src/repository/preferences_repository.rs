@@ -0,0 +1,20 @@
+use super::{dto::Preferences, Error};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PreferencesRepository: Send + Sync {
+    ///
+    /// Finds preferences of the user
+    ///
+    /// ### Returns
+    /// None when user has never saved preferences
+    ///
+    async fn find(&self, user_id: Uuid) -> Result<Option<Preferences>, Error>;
+
+    ///
+    /// Replaces preferences of the user, inserts them if they don't exist
+    ///
+    async fn upsert(&self, user_id: Uuid, preferences: Preferences) -> Result<(), Error>;
+}
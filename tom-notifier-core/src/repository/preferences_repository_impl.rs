@@ -0,0 +1,347 @@
+use super::{
+    dto::Preferences,
+    entity::{PreferencesEntity, QuietHoursEntity},
+    Error, PreferencesRepository,
+};
+use axum::async_trait;
+use bson::{doc, DateTime, Document};
+use mongodb::{options::IndexOptions, Collection, Database, IndexModel};
+use uuid::Uuid;
+
+pub(super) const PREFERENCES: &str = "preferences";
+const INDEX_NAME_MUTED_PRODUCERS: &str = "index_muted_producers";
+const INDEX_NAME_MUTED_CONTENT_TYPES: &str = "index_muted_content_types";
+const INDEX_NAME_QUIET_HOURS_FROM: &str = "index_quiet_hours_utc_wraps_utc_from_minute";
+const INDEX_NAME_QUIET_HOURS_TO: &str = "index_quiet_hours_utc_wraps_utc_to_minute";
+
+pub struct PreferencesRepositoryImpl {
+    collection: Collection<PreferencesEntity>,
+}
+
+impl PreferencesRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(PREFERENCES).await?;
+
+        let collection = database.collection(PREFERENCES);
+        let index_names = collection.list_index_names().await?;
+
+        // Indexes support finding recipients excluded from NEW messages
+        let indexes = [
+            (INDEX_NAME_MUTED_PRODUCERS, doc! { "muted_producers": 1 }),
+            (
+                INDEX_NAME_MUTED_CONTENT_TYPES,
+                doc! { "muted_content_types": 1 },
+            ),
+            (
+                INDEX_NAME_QUIET_HOURS_FROM,
+                doc! { "quiet_hours.utc_wraps": 1, "quiet_hours.utc_from_minute": 1 },
+            ),
+            (
+                INDEX_NAME_QUIET_HOURS_TO,
+                doc! { "quiet_hours.utc_wraps": 1, "quiet_hours.utc_to_minute": 1 },
+            ),
+        ];
+        for (index_name, keys) in indexes {
+            if !index_names.contains(&index_name.to_string()) {
+                Self::create_index(&collection, index_name, keys).await?;
+                tracing::debug!("created index {PREFERENCES}.{index_name}");
+            }
+        }
+
+        Self::migrate_utc_quiet_hours(&collection).await?;
+
+        Ok(Self {
+            collection: collection.clone_with_type(),
+        })
+    }
+
+    async fn create_index(
+        collection: &Collection<Document>,
+        name: &str,
+        keys: Document,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(keys)
+            .options(IndexOptions::builder().name(name.to_string()).build())
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    ///
+    /// Adds quiet hours in UTC to preferences saved before they were stored
+    ///
+    async fn migrate_utc_quiet_hours(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        // (minute - offset) mod 1440, kept positive for negative differences
+        let to_utc = |field: &str| {
+            doc! {
+                "$mod": [
+                    { "$add": [
+                        { "$mod": [
+                            { "$subtract": [field, "$quiet_hours.utc_offset_minutes"] },
+                            24 * 60,
+                        ] },
+                        24 * 60,
+                    ] },
+                    24 * 60,
+                ]
+            }
+        };
+
+        let update_result = collection
+            .update_many(
+                doc! {
+                    "quiet_hours": { "$ne": None as Option<Document> },
+                    "quiet_hours.utc_wraps": { "$exists": false },
+                },
+                vec![
+                    doc! {
+                        "$set": {
+                            "quiet_hours.utc_from_minute": to_utc("$quiet_hours.from_minute"),
+                            "quiet_hours.utc_to_minute": to_utc("$quiet_hours.to_minute"),
+                        }
+                    },
+                    doc! {
+                        "$set": {
+                            "quiet_hours.utc_wraps": {
+                                "$gt": ["$quiet_hours.utc_from_minute", "$quiet_hours.utc_to_minute"]
+                            },
+                        }
+                    },
+                ],
+            )
+            .await?;
+        if update_result.modified_count > 0 {
+            tracing::info!(
+                count = update_result.modified_count,
+                "migrated quiet hours of preferences"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PreferencesRepository for PreferencesRepositoryImpl {
+    async fn find(&self, user_id: Uuid) -> Result<Option<Preferences>, Error> {
+        let preferences = self
+            .collection
+            .find_one(doc! { "_id": bson::Uuid::from(user_id) })
+            .await?
+            .map(Preferences::from);
+
+        Ok(preferences)
+    }
+
+    async fn upsert(&self, user_id: Uuid, preferences: Preferences) -> Result<(), Error> {
+        let user_id = bson::Uuid::from(user_id);
+
+        self.collection
+            .replace_one(
+                doc! { "_id": user_id },
+                PreferencesEntity {
+                    _id: user_id,
+                    muted_producers: preferences
+                        .muted_producers
+                        .into_iter()
+                        .map(bson::Uuid::from)
+                        .collect(),
+                    muted_content_types: preferences.muted_content_types,
+                    quiet_hours: preferences.quiet_hours.map(QuietHoursEntity::from),
+                    updated_at: DateTime::from(preferences.updated_at),
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Ok(())
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repository::QuietHours;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use time::macros::datetime;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn new_migrates_utc_quiet_hours() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let user_id = bson::Uuid::from(Uuid::new_v4());
+        database
+            .collection::<Document>(PREFERENCES)
+            .insert_one(doc! {
+                "_id": user_id,
+                "muted_producers": [],
+                "muted_content_types": [],
+                "quiet_hours": {
+                    "from_minute": 60,
+                    "to_minute": 23 * 60,
+                    "utc_offset_minutes": 120,
+                },
+                "updated_at": DateTime::now(),
+            })
+            .await?;
+
+        PreferencesRepositoryImpl::new(database.clone()).await?;
+
+        let preferences = database
+            .collection::<PreferencesEntity>(PREFERENCES)
+            .find_one(doc! { "_id": user_id })
+            .await?
+            .ok_or(anyhow!("preferences not found"))?;
+        let quiet_hours = preferences
+            .quiet_hours
+            .ok_or(anyhow!("quiet hours not found"))?;
+
+        assert_eq!(quiet_hours.utc_from_minute, 23 * 60);
+        assert_eq!(quiet_hours.utc_to_minute, 21 * 60);
+        assert!(quiet_hours.utc_wraps);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = PreferencesRepositoryImpl::new(database.clone()).await?;
+
+        let preferences = repository.find(Uuid::new_v4()).await?;
+
+        assert!(preferences.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upsert_inserts_preferences() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = PreferencesRepositoryImpl::new(database.clone()).await?;
+
+        let user_id = Uuid::new_v4();
+        let producer_id = Uuid::new_v4();
+        let quiet_hours = QuietHours {
+            from_minute: 22 * 60,
+            to_minute: 7 * 60,
+            utc_offset_minutes: 60,
+        };
+        let updated_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository
+            .upsert(
+                user_id,
+                Preferences {
+                    muted_producers: vec![producer_id],
+                    muted_content_types: vec!["marketing".to_string()],
+                    quiet_hours: Some(quiet_hours),
+                    updated_at,
+                },
+            )
+            .await?;
+
+        let preferences = repository
+            .find(user_id)
+            .await?
+            .expect("preferences not saved");
+
+        assert_eq!(preferences.muted_producers, vec![producer_id]);
+        assert_eq!(preferences.muted_content_types, vec!["marketing"]);
+        assert_eq!(preferences.quiet_hours, Some(quiet_hours));
+        assert_eq!(preferences.updated_at, updated_at);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_preferences() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = PreferencesRepositoryImpl::new(database.clone()).await?;
+
+        let user_id = Uuid::new_v4();
+
+        repository
+            .upsert(
+                user_id,
+                Preferences {
+                    muted_producers: vec![Uuid::new_v4()],
+                    muted_content_types: vec!["marketing".to_string()],
+                    quiet_hours: Some(QuietHours {
+                        from_minute: 22 * 60,
+                        to_minute: 7 * 60,
+                        utc_offset_minutes: 0,
+                    }),
+                    updated_at: datetime!(2024-01-01 0:00 UTC),
+                },
+            )
+            .await?;
+        repository
+            .upsert(
+                user_id,
+                Preferences {
+                    muted_producers: vec![],
+                    muted_content_types: vec![],
+                    quiet_hours: None,
+                    updated_at: datetime!(2024-01-02 0:00 UTC),
+                },
+            )
+            .await?;
+
+        let preferences = repository
+            .find(user_id)
+            .await?
+            .expect("preferences not saved");
+
+        assert!(preferences.muted_producers.is_empty());
+        assert!(preferences.muted_content_types.is_empty());
+        assert!(preferences.quiet_hours.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use super::{dto::Receipt, Error};
+use axum::async_trait;
+use bson::oid::ObjectId;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReceiptsRepository: Send + Sync {
+    ///
+    /// Finds at most `limit` receipts that have not been sent yet
+    /// and locks them for `lock_duration`, so other instances of the application
+    /// won't send them at the same time.
+    /// Receipts are skipped until their next attempt is due
+    /// and receipts moved to dead letters are never returned.
+    /// Receipts are sorted ascending by creation date.
+    ///
+    async fn lock_pending(
+        &self,
+        limit: u32,
+        lock_duration: Duration,
+    ) -> Result<Vec<Receipt>, Error>;
+
+    ///
+    /// Marks receipt as sent
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when
+    ///     - receipt does not exist
+    ///     - receipt has already been sent or moved to dead letters
+    ///
+    async fn mark_delivered(&self, id: ObjectId) -> Result<(), Error>;
+
+    ///
+    /// Records failed attempt of sending the receipt
+    /// and unlocks it, so it's sent again at `next_attempt_at`
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when
+    ///     - receipt does not exist
+    ///     - receipt has already been sent or moved to dead letters
+    ///
+    async fn mark_failed(
+        &self,
+        id: ObjectId,
+        next_attempt_at: OffsetDateTime,
+        error: String,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Records the last failed attempt of sending the receipt and moves it to
+    /// dead letters. Dead letters are kept, but they are never sent again
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when
+    ///     - receipt does not exist
+    ///     - receipt has already been sent or moved to dead letters
+    ///
+    async fn mark_dead(&self, id: ObjectId, error: String) -> Result<(), Error>;
+
+    ///
+    /// Finds the earliest next attempt of receipts
+    /// that have not been sent yet and are not due yet
+    ///
+    async fn find_next_attempt_at(&self) -> Result<Option<OffsetDateTime>, Error>;
+}
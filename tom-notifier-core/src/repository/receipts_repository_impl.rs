@@ -0,0 +1,463 @@
+use super::{dto::Receipt, entity::ReceiptFindEntity, Error, ReceiptsRepository};
+use axum::async_trait;
+use bson::{doc, oid::ObjectId, DateTime, Document};
+use futures_util::TryStreamExt;
+use mongodb::{options::IndexOptions, Collection, Database, IndexModel};
+use std::time::Duration;
+use time::OffsetDateTime;
+
+pub(super) const RECEIPTS: &str = "receipts";
+const INDEX_NAME_PENDING: &str = "index_delivered_at_dead_at_next_attempt_at";
+const INDEX_NAME_TTL_DELIVERED_AT: &str = "ttl_index_delivered_at";
+const INDEX_NAME_NOTIFICATION_ID: &str = "index_notification_id_delivered_at";
+
+///
+/// How long sent receipts are kept before Mongo removes them
+///
+const DELIVERED_RECEIPT_LIFESPAN: Duration = Duration::from_secs(3600);
+
+pub struct ReceiptsRepositoryImpl {
+    database: Database,
+}
+
+impl ReceiptsRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(RECEIPTS).await?;
+
+        let collection = database.collection(RECEIPTS);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_PENDING.to_string()) {
+            Self::create_pending_index(&collection).await?;
+            tracing::debug!("created index {RECEIPTS}.{INDEX_NAME_PENDING}");
+        }
+        if !index_names.contains(&INDEX_NAME_TTL_DELIVERED_AT.to_string()) {
+            Self::create_ttl_delivered_at_index(&collection).await?;
+            tracing::debug!("created index {RECEIPTS}.{INDEX_NAME_TTL_DELIVERED_AT}");
+        }
+        if !index_names.contains(&INDEX_NAME_NOTIFICATION_ID.to_string()) {
+            Self::create_notification_id_index(&collection).await?;
+            tracing::debug!("created index {RECEIPTS}.{INDEX_NAME_NOTIFICATION_ID}");
+        }
+
+        Ok(Self { database })
+    }
+
+    async fn create_pending_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "delivered_at": 1,
+                "dead_at": 1,
+                "next_attempt_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_PENDING.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_notification_id_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "notification_id": 1,
+                "delivered_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_NOTIFICATION_ID.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_ttl_delivered_at_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "delivered_at": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_TTL_DELIVERED_AT.to_string())
+                    .expire_after(DELIVERED_RECEIPT_LIFESPAN)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    ///
+    /// Updates receipt that has not been sent yet
+    /// nor moved to dead letters and unlocks it
+    ///
+    async fn update_unsent(&self, id: ObjectId, mut update: Document) -> Result<(), Error> {
+        update.insert(
+            "$unset",
+            doc! {
+                "locked_until": "",
+                "lock_id": "",
+            },
+        );
+
+        let update_result = self
+            .database
+            .collection::<Document>(RECEIPTS)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    "delivered_at": None as Option<DateTime>,
+                    "dead_at": None as Option<DateTime>,
+                },
+                update,
+            )
+            .await?;
+
+        match update_result.modified_count == 1 {
+            true => Ok(()),
+            false => Err(Error::NoDocumentUpdated),
+        }
+    }
+}
+
+#[async_trait]
+impl ReceiptsRepository for ReceiptsRepositoryImpl {
+    async fn lock_pending(
+        &self,
+        limit: u32,
+        lock_duration: Duration,
+    ) -> Result<Vec<Receipt>, Error> {
+        let now = OffsetDateTime::now_utc();
+        let locked_until = DateTime::from(now + lock_duration);
+        let now = DateTime::from(now);
+        let lock_id = ObjectId::new();
+        let collection = self.database.collection::<Document>(RECEIPTS);
+
+        let pending_filter = doc! {
+            "delivered_at": None as Option<DateTime>,
+            "dead_at": None as Option<DateTime>,
+            "next_attempt_at": { "$lte": now },
+            "$or": [
+                { "locked_until": None as Option<DateTime> },
+                { "locked_until": { "$lte": now } },
+            ],
+        };
+
+        let ids = collection
+            .find(pending_filter.clone())
+            .projection(doc! { "_id": 1 })
+            .sort(doc! { "created_at": 1 })
+            .limit(limit as i64)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter_map(|document| document.get_object_id("_id").ok())
+            .collect::<Vec<_>>();
+
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Other instance could lock some of the receipts in the meantime
+        // so pending filter has to be applied once again
+        let mut lock_filter = pending_filter;
+        lock_filter.insert("_id", doc! { "$in": ids });
+        collection
+            .update_many(
+                lock_filter,
+                doc! {
+                    "$set": {
+                        "locked_until": locked_until,
+                        "lock_id": lock_id,
+                    }
+                },
+            )
+            .await?;
+
+        let receipts = self
+            .database
+            .collection::<ReceiptFindEntity>(RECEIPTS)
+            .find(doc! { "lock_id": lock_id })
+            .sort(doc! { "created_at": 1 })
+            .await?
+            .map_ok(Receipt::from)
+            .try_collect()
+            .await?;
+
+        Ok(receipts)
+    }
+
+    async fn mark_delivered(&self, id: ObjectId) -> Result<(), Error> {
+        let now = DateTime::from(OffsetDateTime::now_utc());
+
+        self.update_unsent(
+            id,
+            doc! {
+                "$set": {
+                    "delivered_at": now,
+                }
+            },
+        )
+        .await
+    }
+
+    async fn mark_failed(
+        &self,
+        id: ObjectId,
+        next_attempt_at: OffsetDateTime,
+        error: String,
+    ) -> Result<(), Error> {
+        self.update_unsent(
+            id,
+            doc! {
+                "$set": {
+                    "next_attempt_at": DateTime::from(next_attempt_at),
+                    "last_error": error,
+                },
+                "$inc": {
+                    "attempts": 1,
+                },
+            },
+        )
+        .await
+    }
+
+    async fn mark_dead(&self, id: ObjectId, error: String) -> Result<(), Error> {
+        let now = DateTime::from(OffsetDateTime::now_utc());
+
+        self.update_unsent(
+            id,
+            doc! {
+                "$set": {
+                    "dead_at": now,
+                    "last_error": error,
+                },
+                "$inc": {
+                    "attempts": 1,
+                },
+            },
+        )
+        .await
+    }
+
+    async fn find_next_attempt_at(&self) -> Result<Option<OffsetDateTime>, Error> {
+        let now = DateTime::from(OffsetDateTime::now_utc());
+
+        let document = self
+            .database
+            .collection::<Document>(RECEIPTS)
+            .find_one(doc! {
+                "delivered_at": None as Option<DateTime>,
+                "dead_at": None as Option<DateTime>,
+                "next_attempt_at": { "$gt": now },
+            })
+            .projection(doc! { "_id": 0, "next_attempt_at": 1 })
+            .sort(doc! { "next_attempt_at": 1 })
+            .await?;
+
+        let next_attempt_at = document
+            .and_then(|document| document.get_datetime("next_attempt_at").ok().copied())
+            .map(OffsetDateTime::from);
+
+        Ok(next_attempt_at)
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repository::{entity::ReceiptInsertEntity, ReceiptEvent};
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use uuid::Uuid;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    async fn insert_receipt(
+        database: &Database,
+        next_attempt_at: OffsetDateTime,
+    ) -> anyhow::Result<ObjectId> {
+        let insert_result = database
+            .collection::<ReceiptInsertEntity>(RECEIPTS)
+            .insert_one(ReceiptInsertEntity::new(
+                bson::Uuid::from(Uuid::from_u128(1)),
+                ReceiptEvent::Delivered,
+                ObjectId::new(),
+                bson::Uuid::from(Uuid::from_u128(2)),
+                DateTime::from(next_attempt_at),
+            ))
+            .await?;
+
+        insert_result
+            .inserted_id
+            .as_object_id()
+            .ok_or(anyhow!("invalid type of inserted '_id'"))
+    }
+
+    #[tokio::test]
+    async fn lock_pending_skip_locked() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ReceiptsRepositoryImpl::new(database.clone()).await?;
+
+        let id = insert_receipt(&database, OffsetDateTime::now_utc()).await?;
+
+        let receipts = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].id, id);
+        assert_eq!(receipts[0].attempts, 0);
+        assert_eq!(receipts[0].event, ReceiptEvent::Delivered);
+
+        let receipts = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(receipts.len(), 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_pending_skip_not_due() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ReceiptsRepositoryImpl::new(database.clone()).await?;
+
+        let next_attempt_at = OffsetDateTime::now_utc() + Duration::from_secs(60);
+        insert_receipt(&database, next_attempt_at).await?;
+
+        let receipts = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(receipts.len(), 0);
+
+        let found_next_attempt_at = repository.find_next_attempt_at().await?.unwrap();
+        assert_eq!(
+            found_next_attempt_at.unix_timestamp(),
+            next_attempt_at.unix_timestamp()
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mark_failed_reschedules_receipt() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ReceiptsRepositoryImpl::new(database.clone()).await?;
+
+        let id = insert_receipt(&database, OffsetDateTime::now_utc()).await?;
+        repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        repository
+            .mark_failed(id, OffsetDateTime::now_utc(), "status 500".to_string())
+            .await?;
+
+        let receipts = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].attempts, 1);
+
+        let document = database
+            .collection::<Document>(RECEIPTS)
+            .find_one(doc! { "_id": id })
+            .await?
+            .unwrap();
+        assert_eq!(document.get_str("last_error")?, "status 500");
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mark_dead_keeps_dead_letter() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ReceiptsRepositoryImpl::new(database.clone()).await?;
+
+        let id = insert_receipt(&database, OffsetDateTime::now_utc()).await?;
+        repository.lock_pending(10, Duration::from_secs(10)).await?;
+
+        repository.mark_dead(id, "status 500".to_string()).await?;
+
+        let receipts = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(receipts.len(), 0);
+
+        let document = database
+            .collection::<Document>(RECEIPTS)
+            .find_one(doc! { "_id": id })
+            .await?
+            .unwrap();
+        assert!(document.get_datetime("dead_at").is_ok());
+        assert_eq!(document.get_str("last_error")?, "status 500");
+
+        let result = repository.mark_delivered(id).await;
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mark_delivered_twice() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = ReceiptsRepositoryImpl::new(database.clone()).await?;
+
+        let id = insert_receipt(&database, OffsetDateTime::now_utc()).await?;
+
+        repository.mark_delivered(id).await?;
+        let result = repository.mark_delivered(id).await;
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        let receipts = repository.lock_pending(10, Duration::from_secs(10)).await?;
+        assert_eq!(receipts.len(), 0);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
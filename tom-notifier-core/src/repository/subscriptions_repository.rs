@@ -0,0 +1,34 @@
+use super::{dto::Subscription, Error};
+use axum::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SubscriptionsRepository: Send + Sync {
+    ///
+    /// Subscribes user to the topic
+    ///
+    /// ### Errors
+    /// - [Error::InsertUniqueViolation] when user is already subscribed to the topic
+    ///
+    async fn insert(
+        &self,
+        user_id: Uuid,
+        topic: String,
+        created_at: OffsetDateTime,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Finds user subscriptions sorted ascending by topic
+    ///
+    async fn find_many(&self, user_id: Uuid) -> Result<Vec<Subscription>, Error>;
+
+    ///
+    /// Unsubscribes user from the topic
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when user is not subscribed to the topic
+    ///
+    async fn delete(&self, user_id: Uuid, topic: String) -> Result<(), Error>;
+}
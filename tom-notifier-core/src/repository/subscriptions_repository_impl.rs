@@ -0,0 +1,307 @@
+use super::{
+    dto::Subscription, entity::SubscriptionEntity,
+    notifications_repository_impl::DUPLICATE_KEY_CODE, Error, SubscriptionsRepository,
+};
+use axum::async_trait;
+use bson::{doc, DateTime, Document};
+use futures_util::TryStreamExt;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::IndexOptions,
+    Collection, Database, IndexModel,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub(super) const SUBSCRIPTIONS: &str = "subscriptions";
+const INDEX_NAME_UNIQUE_USER_TOPIC: &str = "unique_index_user_id_topic";
+const INDEX_NAME_TOPIC: &str = "index_topic";
+
+pub struct SubscriptionsRepositoryImpl {
+    collection: Collection<SubscriptionEntity>,
+}
+
+impl SubscriptionsRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(SUBSCRIPTIONS).await?;
+
+        let collection = database.collection(SUBSCRIPTIONS);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_UNIQUE_USER_TOPIC.to_string()) {
+            Self::create_unique_user_topic_index(&collection).await?;
+            tracing::debug!("created index {SUBSCRIPTIONS}.{INDEX_NAME_UNIQUE_USER_TOPIC}");
+        }
+        if !index_names.contains(&INDEX_NAME_TOPIC.to_string()) {
+            Self::create_topic_index(&collection).await?;
+            tracing::debug!("created index {SUBSCRIPTIONS}.{INDEX_NAME_TOPIC}");
+        }
+
+        Ok(Self {
+            collection: database.collection(SUBSCRIPTIONS),
+        })
+    }
+
+    async fn create_unique_user_topic_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "user_id": 1,
+                "topic": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_UNIQUE_USER_TOPIC.to_string())
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn create_topic_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "topic": 1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_TOPIC.to_string())
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubscriptionsRepository for SubscriptionsRepositoryImpl {
+    async fn insert(
+        &self,
+        user_id: Uuid,
+        topic: String,
+        created_at: OffsetDateTime,
+    ) -> Result<(), Error> {
+        let insert_result = self
+            .collection
+            .insert_one(SubscriptionEntity {
+                user_id: bson::Uuid::from(user_id),
+                topic,
+                created_at: DateTime::from(created_at),
+            })
+            .await;
+
+        let Err(err) = insert_result else {
+            return Ok(());
+        };
+        let is_duplicate_key = matches!(
+            *err.kind,
+            ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                if write_error.code == DUPLICATE_KEY_CODE
+        );
+
+        match is_duplicate_key {
+            true => Err(Error::InsertUniqueViolation),
+            false => Err(Error::Mongo(err)),
+        }
+    }
+
+    async fn find_many(&self, user_id: Uuid) -> Result<Vec<Subscription>, Error> {
+        let subscriptions = self
+            .collection
+            .find(doc! { "user_id": bson::Uuid::from(user_id) })
+            .sort(doc! { "topic": 1 })
+            .await?
+            .map_ok(Subscription::from)
+            .try_collect()
+            .await?;
+
+        Ok(subscriptions)
+    }
+
+    async fn delete(&self, user_id: Uuid, topic: String) -> Result<(), Error> {
+        let delete_result = self
+            .collection
+            .delete_one(doc! {
+                "user_id": bson::Uuid::from(user_id),
+                "topic": topic,
+            })
+            .await?;
+
+        match delete_result.deleted_count {
+            1 => Ok(()),
+            _ => Err(Error::NoDocumentUpdated),
+        }
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use time::macros::datetime;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn insert_correct_created_at() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = SubscriptionsRepositoryImpl::new(database.clone()).await?;
+
+        let user_id = Uuid::new_v4();
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository
+            .insert(user_id, "billing".to_string(), created_at)
+            .await?;
+
+        let subscriptions = repository.find_many(user_id).await?;
+
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].topic, "billing");
+        assert_eq!(subscriptions[0].created_at, created_at);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_already_subscribed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = SubscriptionsRepositoryImpl::new(database.clone()).await?;
+
+        let user_id = Uuid::new_v4();
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository
+            .insert(user_id, "billing".to_string(), created_at)
+            .await?;
+        let insert_result = repository
+            .insert(user_id, "billing".to_string(), created_at)
+            .await;
+
+        assert!(matches!(insert_result, Err(Error::InsertUniqueViolation)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_only_user_subscriptions_sorted_by_topic_asc() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = SubscriptionsRepositoryImpl::new(database.clone()).await?;
+
+        let user_id = Uuid::new_v4();
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository
+            .insert(user_id, "deployments".to_string(), created_at)
+            .await?;
+        repository
+            .insert(user_id, "billing".to_string(), created_at)
+            .await?;
+        repository
+            .insert(Uuid::new_v4(), "alerts".to_string(), created_at)
+            .await?;
+
+        let topics = repository
+            .find_many(user_id)
+            .await?
+            .into_iter()
+            .map(|subscription| subscription.topic)
+            .collect::<Vec<_>>();
+
+        assert_eq!(topics, vec!["billing", "deployments"]);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_only_selected_removed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = SubscriptionsRepositoryImpl::new(database.clone()).await?;
+
+        let user_id = Uuid::new_v4();
+        let other_user_id = Uuid::new_v4();
+        let created_at = datetime!(2024-01-01 0:00 UTC);
+
+        repository
+            .insert(user_id, "billing".to_string(), created_at)
+            .await?;
+        repository
+            .insert(other_user_id, "billing".to_string(), created_at)
+            .await?;
+
+        repository.delete(user_id, "billing".to_string()).await?;
+
+        let subscriptions = repository.find_many(user_id).await?;
+        let other_subscriptions = repository.find_many(other_user_id).await?;
+
+        assert!(subscriptions.is_empty());
+        assert_eq!(other_subscriptions.len(), 1);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_not_subscribed() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = SubscriptionsRepositoryImpl::new(database.clone()).await?;
+
+        let delete_result = repository
+            .delete(Uuid::new_v4(), "billing".to_string())
+            .await;
+
+        assert!(matches!(delete_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
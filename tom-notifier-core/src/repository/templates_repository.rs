@@ -0,0 +1,62 @@
+use super::{dto::Template, Error};
+use axum::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TemplatesRepository: Send + Sync {
+    ///
+    /// Inserts the first version of the producer template
+    ///
+    /// ### Errors
+    /// - [Error::InsertUniqueViolation] when producer already has template with the id
+    ///
+    async fn insert(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        created_at: OffsetDateTime,
+        content_type: String,
+        body: String,
+        parameters: Vec<String>,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Inserts the next version of existing producer template
+    ///
+    /// ### Returns
+    /// version of inserted template
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when template does not exist
+    /// - [Error::InsertUniqueViolation] when the same version was inserted concurrently
+    ///
+    async fn insert_next_version(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        created_at: OffsetDateTime,
+        content_type: String,
+        body: String,
+        parameters: Vec<String>,
+    ) -> Result<u32, Error>;
+
+    ///
+    /// Finds version of the producer template, the latest one when version is None
+    ///
+    /// ### Returns
+    /// None when template or its version does not exist
+    ///
+    async fn find(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        version: Option<u32>,
+    ) -> Result<Option<Template>, Error>;
+
+    ///
+    /// Finds the latest versions of all producer templates sorted ascending by id
+    ///
+    async fn find_many(&self, producer_id: Uuid) -> Result<Vec<Template>, Error>;
+}
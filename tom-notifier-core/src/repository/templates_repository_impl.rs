@@ -0,0 +1,447 @@
+use super::{
+    dto::Template, entity::TemplateEntity, notifications_repository_impl::DUPLICATE_KEY_CODE,
+    Error, TemplatesRepository,
+};
+use axum::async_trait;
+use bson::{doc, DateTime, Document};
+use futures_util::TryStreamExt;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::IndexOptions,
+    Collection, Database, IndexModel,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub(super) const TEMPLATES: &str = "templates";
+const INDEX_NAME_UNIQUE_PRODUCER_TEMPLATE_VERSION: &str =
+    "unique_index_producer_id_template_id_version";
+
+pub struct TemplatesRepositoryImpl {
+    collection: Collection<TemplateEntity>,
+}
+
+impl TemplatesRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(TEMPLATES).await?;
+
+        let collection = database.collection(TEMPLATES);
+        let index_names = collection.list_index_names().await?;
+
+        if !index_names.contains(&INDEX_NAME_UNIQUE_PRODUCER_TEMPLATE_VERSION.to_string()) {
+            Self::create_unique_producer_template_version_index(&collection).await?;
+            tracing::debug!(
+                "created index {TEMPLATES}.{INDEX_NAME_UNIQUE_PRODUCER_TEMPLATE_VERSION}"
+            );
+        }
+
+        Ok(Self {
+            collection: database.collection(TEMPLATES),
+        })
+    }
+
+    async fn create_unique_producer_template_version_index(
+        collection: &Collection<Document>,
+    ) -> Result<(), mongodb::error::Error> {
+        let index = IndexModel::builder()
+            .keys(doc! {
+                "producer_id": 1,
+                "template_id": 1,
+                "version": -1,
+            })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME_UNIQUE_PRODUCER_TEMPLATE_VERSION.to_string())
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+
+        Ok(())
+    }
+
+    async fn insert_version(&self, entity: TemplateEntity) -> Result<(), Error> {
+        let insert_result = self.collection.insert_one(entity).await;
+
+        let Err(err) = insert_result else {
+            return Ok(());
+        };
+        let is_duplicate_key = matches!(
+            *err.kind,
+            ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                if write_error.code == DUPLICATE_KEY_CODE
+        );
+
+        match is_duplicate_key {
+            true => Err(Error::InsertUniqueViolation),
+            false => Err(Error::Mongo(err)),
+        }
+    }
+}
+
+#[async_trait]
+impl TemplatesRepository for TemplatesRepositoryImpl {
+    async fn insert(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        created_at: OffsetDateTime,
+        content_type: String,
+        body: String,
+        parameters: Vec<String>,
+    ) -> Result<(), Error> {
+        // The first version is unique as well, so the unique index
+        // prevents creating template with the same id twice
+        self.insert_version(TemplateEntity {
+            producer_id: bson::Uuid::from(producer_id),
+            template_id: id,
+            version: 1,
+            created_at: DateTime::from(created_at),
+            content_type,
+            body,
+            parameters,
+        })
+        .await
+    }
+
+    async fn insert_next_version(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        created_at: OffsetDateTime,
+        content_type: String,
+        body: String,
+        parameters: Vec<String>,
+    ) -> Result<u32, Error> {
+        let producer_id = bson::Uuid::from(producer_id);
+
+        let latest_version = self
+            .collection
+            .find_one(doc! {
+                "producer_id": producer_id,
+                "template_id": &id,
+            })
+            .sort(doc! { "version": -1 })
+            .await?
+            .ok_or(Error::NoDocumentUpdated)?
+            .version;
+
+        let version = latest_version + 1;
+        self.insert_version(TemplateEntity {
+            producer_id,
+            template_id: id,
+            version,
+            created_at: DateTime::from(created_at),
+            content_type,
+            body,
+            parameters,
+        })
+        .await?;
+
+        Ok(version as u32)
+    }
+
+    async fn find(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        version: Option<u32>,
+    ) -> Result<Option<Template>, Error> {
+        let mut filter = doc! {
+            "producer_id": bson::Uuid::from(producer_id),
+            "template_id": id,
+        };
+        if let Some(version) = version {
+            filter.insert("version", version as i32);
+        }
+
+        let template = self
+            .collection
+            .find_one(filter)
+            .sort(doc! { "version": -1 })
+            .await?
+            .map(Template::from);
+
+        Ok(template)
+    }
+
+    async fn find_many(&self, producer_id: Uuid) -> Result<Vec<Template>, Error> {
+        let pipeline = [
+            doc! {
+                "$match": {
+                    "producer_id": bson::Uuid::from(producer_id),
+                }
+            },
+            doc! {
+                "$sort": {
+                    "template_id": 1,
+                    "version": -1,
+                }
+            },
+            doc! {
+                "$group": {
+                    "_id": "$template_id",
+                    "template": { "$first": "$$ROOT" },
+                }
+            },
+            doc! {
+                "$replaceWith": "$template",
+            },
+            doc! {
+                "$sort": {
+                    "template_id": 1,
+                }
+            },
+        ];
+
+        let templates = self
+            .collection
+            .aggregate(pipeline)
+            .with_type::<TemplateEntity>()
+            .await?
+            .map_ok(Template::from)
+            .try_collect()
+            .await?;
+
+        Ok(templates)
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use time::macros::datetime;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    async fn insert_template(
+        repository: &TemplatesRepositoryImpl,
+        producer_id: Uuid,
+        id: &str,
+        body: &str,
+    ) -> Result<(), Error> {
+        repository
+            .insert(
+                producer_id,
+                id.to_string(),
+                datetime!(2024-01-01 0:00 UTC),
+                "utf-8".to_string(),
+                body.to_string(),
+                vec!["name".to_string()],
+            )
+            .await
+    }
+
+    async fn insert_template_next_version(
+        repository: &TemplatesRepositoryImpl,
+        producer_id: Uuid,
+        id: &str,
+        body: &str,
+    ) -> Result<u32, Error> {
+        repository
+            .insert_next_version(
+                producer_id,
+                id.to_string(),
+                datetime!(2024-01-02 0:00 UTC),
+                "utf-8".to_string(),
+                body.to_string(),
+                vec!["name".to_string()],
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn insert_first_version() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        insert_template(&repository, producer_id, "welcome", "Hello {{name}}").await?;
+
+        let template = repository
+            .find(producer_id, "welcome".to_string(), None)
+            .await?
+            .ok_or(anyhow!("template not found"))?;
+
+        assert_eq!(template.version, 1);
+        assert_eq!(template.body, "Hello {{name}}");
+        assert_eq!(template.parameters, vec!["name"]);
+        assert_eq!(template.created_at, datetime!(2024-01-01 0:00 UTC));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_already_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        insert_template(&repository, producer_id, "welcome", "Hello {{name}}").await?;
+        let insert_result =
+            insert_template(&repository, producer_id, "welcome", "Hi {{name}}").await;
+
+        assert!(matches!(insert_result, Err(Error::InsertUniqueViolation)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_next_version_incremented() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        insert_template(&repository, producer_id, "welcome", "Hello {{name}}").await?;
+        let version_2 =
+            insert_template_next_version(&repository, producer_id, "welcome", "Hi {{name}}")
+                .await?;
+        let version_3 =
+            insert_template_next_version(&repository, producer_id, "welcome", "Hey {{name}}")
+                .await?;
+
+        assert_eq!(version_2, 2);
+        assert_eq!(version_3, 3);
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_next_version_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        let insert_result =
+            insert_template_next_version(&repository, Uuid::new_v4(), "welcome", "Hi {{name}}")
+                .await;
+
+        assert!(matches!(insert_result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_latest_or_selected_version() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        insert_template(&repository, producer_id, "welcome", "Hello {{name}}").await?;
+        insert_template_next_version(&repository, producer_id, "welcome", "Hi {{name}}").await?;
+
+        let latest = repository
+            .find(producer_id, "welcome".to_string(), None)
+            .await?
+            .ok_or(anyhow!("template not found"))?;
+        let first = repository
+            .find(producer_id, "welcome".to_string(), Some(1))
+            .await?
+            .ok_or(anyhow!("template not found"))?;
+        let not_existing = repository
+            .find(producer_id, "welcome".to_string(), Some(3))
+            .await?;
+
+        assert_eq!(latest.version, 2);
+        assert_eq!(latest.body, "Hi {{name}}");
+        assert_eq!(first.version, 1);
+        assert_eq!(first.body, "Hello {{name}}");
+        assert!(not_existing.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_other_producer_template() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        insert_template(&repository, Uuid::new_v4(), "welcome", "Hello {{name}}").await?;
+
+        let template = repository
+            .find(Uuid::new_v4(), "welcome".to_string(), None)
+            .await?;
+
+        assert!(template.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_many_latest_versions_sorted_by_id_asc() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = TemplatesRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        insert_template(&repository, producer_id, "welcome", "Hello {{name}}").await?;
+        insert_template(&repository, producer_id, "goodbye", "Bye {{name}}").await?;
+        insert_template_next_version(&repository, producer_id, "welcome", "Hi {{name}}").await?;
+        insert_template(&repository, Uuid::new_v4(), "alert", "Alert {{name}}").await?;
+
+        let templates = repository
+            .find_many(producer_id)
+            .await?
+            .into_iter()
+            .map(|template| (template.id, template.version))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            templates,
+            vec![("goodbye".to_string(), 1), ("welcome".to_string(), 2)]
+        );
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use super::{dto::Webhook, Error};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait WebhooksRepository: Send + Sync {
+    ///
+    /// Finds webhook of the producer
+    ///
+    /// ### Returns
+    /// None when producer has not registered webhook
+    ///
+    async fn find(&self, producer_id: Uuid) -> Result<Option<Webhook>, Error>;
+
+    ///
+    /// Replaces webhook of the producer, inserts it if it doesn't exist
+    ///
+    async fn upsert(&self, producer_id: Uuid, webhook: Webhook) -> Result<(), Error>;
+
+    ///
+    /// Deletes webhook of the producer
+    ///
+    /// ### Errors
+    /// - [Error::NoDocumentUpdated] when producer has not registered webhook
+    ///
+    async fn delete(&self, producer_id: Uuid) -> Result<(), Error>;
+}
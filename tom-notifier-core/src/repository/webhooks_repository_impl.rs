@@ -0,0 +1,173 @@
+use super::{dto::Webhook, entity::WebhookEntity, Error, WebhooksRepository};
+use axum::async_trait;
+use bson::{doc, DateTime};
+use mongodb::{Collection, Database};
+use uuid::Uuid;
+
+pub(super) const WEBHOOKS: &str = "webhooks";
+
+pub struct WebhooksRepositoryImpl {
+    collection: Collection<WebhookEntity>,
+}
+
+impl WebhooksRepositoryImpl {
+    pub async fn new(database: Database) -> Result<Self, mongodb::error::Error> {
+        database.create_collection(WEBHOOKS).await?;
+
+        Ok(Self {
+            collection: database.collection(WEBHOOKS),
+        })
+    }
+}
+
+#[async_trait]
+impl WebhooksRepository for WebhooksRepositoryImpl {
+    async fn find(&self, producer_id: Uuid) -> Result<Option<Webhook>, Error> {
+        let webhook = self
+            .collection
+            .find_one(doc! { "_id": bson::Uuid::from(producer_id) })
+            .await?
+            .map(Webhook::from);
+
+        Ok(webhook)
+    }
+
+    async fn upsert(&self, producer_id: Uuid, webhook: Webhook) -> Result<(), Error> {
+        let producer_id = bson::Uuid::from(producer_id);
+
+        self.collection
+            .replace_one(
+                doc! { "_id": producer_id },
+                WebhookEntity {
+                    _id: producer_id,
+                    url: webhook.url,
+                    secret: webhook.secret,
+                    updated_at: DateTime::from(webhook.updated_at),
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, producer_id: Uuid) -> Result<(), Error> {
+        let delete_result = self
+            .collection
+            .delete_one(doc! { "_id": bson::Uuid::from(producer_id) })
+            .await?;
+
+        match delete_result.deleted_count == 1 {
+            true => Ok(()),
+            false => Err(Error::NoDocumentUpdated),
+        }
+    }
+}
+
+///
+/// Tests require env variables to be set and database to be running
+///
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use mongodb::{options::ClientOptions, Client};
+    use std::sync::Once;
+    use time::macros::datetime;
+
+    static BEFORE_ALL: Once = Once::new();
+
+    fn init_env() {
+        let _ = dotenvy::dotenv();
+    }
+
+    async fn create_test_database() -> anyhow::Result<Database> {
+        BEFORE_ALL.call_once(init_env);
+
+        let db_connection_string = std::env::var("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_CONNECTION_STRING not set"))?;
+        let db_name = std::env::var("TOM_NOTIFIER_CORE_DB_NAME")
+            .map_err(|_| anyhow!("TOM_NOTIFIER_CORE_DB_NAME not set"))?;
+
+        let db_name = format!("test_{}_{}", db_name, Uuid::new_v4());
+
+        let db_client_options = ClientOptions::parse(&db_connection_string).await?;
+        let db_client = Client::with_options(db_client_options)?;
+        let db = db_client.database(&db_name);
+
+        Ok(db)
+    }
+
+    async fn destroy_test_database(database: Database) {
+        let _ = database.drop().await;
+        database.client().clone().shutdown().await;
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            url: url.to_string(),
+            secret: "0123456789abcdef".to_string(),
+            updated_at: datetime!(2024-01-01 0:00 UTC),
+        }
+    }
+
+    #[tokio::test]
+    async fn find_not_exist() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = WebhooksRepositoryImpl::new(database.clone()).await?;
+
+        let webhook = repository.find(Uuid::new_v4()).await?;
+
+        assert!(webhook.is_none());
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_webhook() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = WebhooksRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        repository
+            .upsert(producer_id, webhook("https://example.com/old"))
+            .await?;
+        repository
+            .upsert(producer_id, webhook("https://example.com/new"))
+            .await?;
+
+        let webhook = repository.find(producer_id).await?.unwrap();
+        assert_eq!(webhook.url, "https://example.com/new");
+        assert_eq!(webhook.secret, "0123456789abcdef");
+        assert_eq!(webhook.updated_at, datetime!(2024-01-01 0:00 UTC));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_webhook() -> anyhow::Result<()> {
+        let database = create_test_database().await?;
+        let repository = WebhooksRepositoryImpl::new(database.clone()).await?;
+
+        let producer_id = Uuid::new_v4();
+
+        repository
+            .upsert(producer_id, webhook("https://example.com"))
+            .await?;
+
+        repository.delete(producer_id).await?;
+        assert!(repository.find(producer_id).await?.is_none());
+
+        let result = repository.delete(producer_id).await;
+        assert!(matches!(result, Err(Error::NoDocumentUpdated)));
+
+        destroy_test_database(database).await;
+
+        Ok(())
+    }
+}
@@ -3,12 +3,22 @@ use crate::{
     auth::Role,
     dto::{input, output},
     error::Error,
-    service::notifications_service::NotificationsService,
+    service::{
+        attachments_service::AttachmentsService, content_types_service::ContentTypesService,
+        groups_service::GroupsService, notifications_service::NotificationsService,
+        preferences_service::PreferencesService, subscriptions_service::SubscriptionsService,
+        templates_service::TemplatesService, webhooks_service::WebhooksService,
+    },
 };
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    routing::{get, post, put},
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{
+        header::{ACCEPT_LANGUAGE, CONTENT_DISPOSITION, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
     Extension, Json, Router,
 };
 use bson::oid::ObjectId;
@@ -21,13 +31,42 @@ pub fn routing() -> Router<ApplicationState> {
             "/api/v1/notifications/undelivered",
             post(post_notifications_undelivered).get(get_notifications_undelivered),
         )
+        .route(
+            "/api/v1/notifications/undelivered/batch",
+            post(post_notifications_undelivered_batch),
+        )
+        .route(
+            "/api/v1/notifications/undelivered/:notification_id",
+            delete(delete_notification_undelivered),
+        )
         .route(
             "/api/v1/notifications/undelivered/:notification_id/invalidate_at",
             put(put_notifications_undelivered_invalidate_at),
         )
+        .route(
+            "/api/v1/notifications/undelivered/:notification_id/content",
+            put(put_notifications_undelivered_content),
+        )
+        .route(
+            "/api/v1/notifications/undelivered/:notification_id/delivery_report",
+            get(get_notification_undelivered_delivery_report),
+        )
+        .route(
+            "/api/v1/notifications/produced",
+            get(get_notifications_produced),
+        )
+        .route(
+            "/api/v1/notifications/produced/:producer_notification_id",
+            get(get_notification_produced),
+        )
+        .route("/api/v1/notifications/count", get(get_notifications_count))
         .route(
             "/api/v1/notifications/delivered",
-            get(get_notifications_delivered),
+            get(get_notifications_delivered).delete(delete_notifications_delivered),
+        )
+        .route(
+            "/api/v1/notifications/delivered/seen",
+            put(put_notifications_delivered_seen),
         )
         .route(
             "/api/v1/notifications/delivered/:notification_id",
@@ -37,10 +76,57 @@ pub fn routing() -> Router<ApplicationState> {
             "/api/v1/notifications/delivered/:notification_id/seen",
             put(put_notification_delivered_seen),
         )
+        .route(
+            "/api/v1/notifications/delivered/:notification_id/attachments/:attachment_id",
+            get(get_notification_delivered_attachment),
+        )
+        .route("/api/v1/groups", post(post_groups).get(get_groups))
+        .route("/api/v1/groups/:name", delete(delete_group))
+        .route(
+            "/api/v1/groups/:name/members",
+            get(get_group_members)
+                .put(put_group_members)
+                .delete(delete_group_members),
+        )
+        .route("/api/v1/subscriptions", get(get_subscriptions))
+        .route(
+            "/api/v1/subscriptions/:topic",
+            put(put_subscription).delete(delete_subscription),
+        )
+        .route(
+            "/api/v1/preferences",
+            get(get_preferences).put(put_preferences),
+        )
+        .route("/api/v1/templates", post(post_templates).get(get_templates))
+        .route(
+            "/api/v1/templates/:template_id",
+            get(get_template).put(put_template),
+        )
+        .route(
+            "/api/v1/content_types",
+            get(get_content_types)
+                .put(put_content_type)
+                .delete(delete_content_type),
+        )
+        .route(
+            "/api/v1/webhook",
+            get(get_webhook).put(put_webhook).delete(delete_webhook),
+        )
+}
+
+///
+/// Routing of attachment uploads, separate from [routing]
+/// because attachments are limited by their own body limit
+///
+pub fn attachments_routing() -> Router<ApplicationState> {
+    Router::new()
+        .route("/api/v1/attachments", post(post_attachments))
+        .layer(DefaultBodyLimit::disable())
 }
 
 ///
-/// Create new notification
+/// Create new notification.
+/// Content is rendered from the producer template when template is set
 ///
 /// ### Returns
 /// 200 on success
@@ -50,15 +136,24 @@ pub fn routing() -> Router<ApplicationState> {
 /// - 403 when user lacks role [Role::ProduceNotifications]
 /// - 409 notification with producer_notification_id was already created by the user
 /// - 413 when content is too large
-/// - 422 when invalidate_at is set to past date
+/// - 422 when
+///     - invalidate_at is set to past date
+///     - there are too many groups or any group is empty
+///     - template does not exist, its parameters do not match or content is set as well
+///     - content_type is empty and template is not set
+///     - content does not match JSON Schema of its content type
 ///
 async fn post_notifications_undelivered(
     State(notifications_service): State<Arc<dyn NotificationsService>>,
+    State(templates_service): State<Arc<dyn TemplatesService>>,
     Extension(user): Extension<User>,
     Json(notification): Json<input::Notification>,
 ) -> Result<(StatusCode, Json<output::NotificationId>), Error> {
     require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
 
+    let notification = templates_service
+        .render_notification(user.id, notification)
+        .await?;
     let notification_id = notifications_service
         .save_notification(user.id, notification.into())
         .await?;
@@ -66,6 +161,46 @@ async fn post_notifications_undelivered(
     Ok((StatusCode::OK, Json(notification_id)))
 }
 
+///
+/// Create many notifications at once.
+/// Notifications whose producer_notification_id was already used
+/// by the user do not fail the whole batch.
+/// Content is rendered from the producer template when template is set
+///
+/// ### Returns
+/// 200 on success with result of every notification
+///
+/// ### Errors
+/// - 400 payload is invalid when content of any notification is not valid base64
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 409 when conflicting notification was created concurrently
+/// - 413 when content of any notification is too large
+/// - 422 when
+///     - notifications are empty or there are too many of them
+///     - invalidate_at of any notification is set to past date
+///     - template of any notification does not exist, its parameters do not match
+///       or content is set as well
+///     - content_type of any notification is empty and its template is not set
+///     - content of any notification does not match JSON Schema of its content type
+///
+async fn post_notifications_undelivered_batch(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    State(templates_service): State<Arc<dyn TemplatesService>>,
+    Extension(user): Extension<User>,
+    Json(notifications): Json<Vec<input::Notification>>,
+) -> Result<(StatusCode, Json<Vec<output::NotificationSaveResult>>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let notifications = templates_service
+        .render_notifications(user.id, notifications)
+        .await?;
+    let results = notifications_service
+        .save_notifications(user.id, notifications)
+        .await?;
+
+    Ok((StatusCode::OK, Json(results)))
+}
+
 ///
 /// Find notifications that have not yet been delivered.
 /// Skip notifications that have been invalided.
@@ -82,14 +217,149 @@ async fn post_notifications_undelivered(
 async fn get_notifications_undelivered(
     State(notifications_service): State<Arc<dyn NotificationsService>>,
     Extension(user): Extension<User>,
+    headers: HeaderMap,
 ) -> Result<(StatusCode, Json<Vec<output::Notification>>), Error> {
+    let locales = preferred_locales(&user, &headers);
+
     let notifications = notifications_service
-        .find_undelivered_notifications(user.id)
+        .find_undelivered_notifications(user.id, locales)
         .await?;
 
     Ok((StatusCode::OK, Json(notifications)))
 }
 
+///
+/// Count undelivered notifications and delivered notifications
+/// (all of them and only unseen ones).
+///
+/// Unlike fetching undelivered notifications, counting
+/// does not mark them as delivered
+///
+/// ### Returns
+/// 200 on success
+///
+async fn get_notifications_count(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<output::NotificationsCount>), Error> {
+    let count = notifications_service.count_notifications(user.id).await?;
+
+    Ok((StatusCode::OK, Json(count)))
+}
+
+///
+/// Retract notification from all of its recipients.
+/// Retracted notification is no longer delivered nor
+/// returned to the users that already received it
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 403 when user does not have role [Role::ProduceNotifications]
+/// - 404 when
+///     - notification with id does not exist
+///     - notification was not produced by the producer
+///     - notification have already been retracted
+///
+async fn delete_notification_undelivered(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<ObjectId>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    notifications_service
+        .retract_notification(id, user.id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Get delivery status report of the notification produced by the producer,
+/// counting and listing pages of recipients that confirmed the notification
+/// and recipients that did not confirm it yet
+///
+/// ### Returns
+/// 200 with [output::DeliveryReport]
+///
+/// ### Errors
+/// - 400 when any of the cursors is invalid
+/// - 403 when user does not have role [Role::ProduceNotifications]
+/// - 404 when
+///     - notification with id does not exist
+///     - notification was not produced by the producer
+///
+async fn get_notification_undelivered_delivery_report(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<ObjectId>,
+    Query(pagination): Query<input::DeliveryReportPagination>,
+) -> Result<Json<output::DeliveryReport>, Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let report = notifications_service
+        .find_delivery_report(id, user.id, pagination)
+        .await?;
+
+    Ok(Json(report))
+}
+
+///
+/// Find notifications produced by the producer, including retracted ones,
+/// with numbers of recipients that confirmed, have seen and deleted them
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 400 when cursor or any of the filters is invalid
+/// - 403 when user does not have role [Role::ProduceNotifications]
+/// - 422 when
+///     - both page_idx and cursor are set
+///     - created_at range is empty
+///
+async fn get_notifications_produced(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Query(pagination): Query<input::Pagination>,
+    Query(filters): Query<input::ProducedNotificationFilters>,
+) -> Result<(StatusCode, Json<output::ProducedNotificationsPage>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let page = notifications_service
+        .find_produced_notifications(user.id, pagination, filters)
+        .await?;
+
+    Ok((StatusCode::OK, Json(page)))
+}
+
+///
+/// Find notification produced by the producer by producer_notification_id
+/// with numbers of recipients that confirmed, have seen and deleted it
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user does not have role [Role::ProduceNotifications]
+/// - 404 when producer has not created notification with producer_notification_id
+///
+async fn get_notification_produced(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Path(producer_notification_id): Path<i64>,
+) -> Result<(StatusCode, Json<output::ProducedNotification>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let notification = notifications_service
+        .find_produced_notification(user.id, producer_notification_id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(notification)))
+}
+
 ///
 /// Update invalidate_at property of the notification
 ///
@@ -118,23 +388,74 @@ async fn put_notifications_undelivered_invalidate_at(
     Ok(StatusCode::NO_CONTENT)
 }
 
+///
+/// Replace content_type and content of the notification
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 400 payload is invalid when content is not valid base64
+/// - 403 when user does not have role [Role::ProduceNotifications]
+/// - 404 when
+///     - notification with id does not exist
+///     - notification was not produced by the producer
+/// - 413 when content or any localized content is too large
+/// - 422 when
+///     - content_type is empty
+///     - localized contents are invalid
+///     - content or any localized content does not match JSON Schema of its content type
+///
+async fn put_notifications_undelivered_content(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<ObjectId>,
+    Json(content): Json<input::NotificationContent>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    notifications_service
+        .update_notification_content(id, user.id, content)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 ///
 /// Find notifications that have already been delivered
 ///
+/// When page_idx is set response contains only notifications
+/// (for compatibility with clients that don't use cursor)
+///
 /// ### Returns
 /// 200 on success
 ///
+/// ### Errors
+/// - 400 when cursor or any of the filters is invalid
+/// - 422 when
+///     - both page_idx and cursor are set
+///     - created_at or delivered_at range is empty
+///
 async fn get_notifications_delivered(
     State(notifications_service): State<Arc<dyn NotificationsService>>,
     Extension(user): Extension<User>,
     Query(pagination): Query<input::Pagination>,
     Query(filters): Query<input::NotificationFilters>,
-) -> Result<(StatusCode, Json<Vec<output::Notification>>), Error> {
-    let notifications = notifications_service
-        .find_delivered_notifications(user.id, pagination, filters)
+    headers: HeaderMap,
+) -> Result<Response, Error> {
+    let paginated_by_page_idx = pagination.page_idx.is_some();
+    let locales = preferred_locales(&user, &headers);
+
+    let page = notifications_service
+        .find_delivered_notifications(user.id, pagination, filters, locales)
         .await?;
 
-    Ok((StatusCode::OK, Json(notifications)))
+    let response = match paginated_by_page_idx {
+        true => (StatusCode::OK, Json(page.notifications)).into_response(),
+        false => (StatusCode::OK, Json(page)).into_response(),
+    };
+
+    Ok(response)
 }
 
 ///
@@ -154,14 +475,49 @@ async fn get_notification_delivered(
     State(notifications_service): State<Arc<dyn NotificationsService>>,
     Extension(user): Extension<User>,
     Path(id): Path<ObjectId>,
+    headers: HeaderMap,
 ) -> Result<(StatusCode, Json<output::Notification>), Error> {
+    let locales = preferred_locales(&user, &headers);
+
     let notification = notifications_service
-        .find_delivered_notification(id, user.id)
+        .find_delivered_notification(id, user.id, locales)
         .await?;
 
     Ok((StatusCode::OK, Json(notification)))
 }
 
+///
+/// Download attachment of the notification
+///
+/// ### Returns
+/// 200 on success with attachment content
+///
+/// ### Errors
+/// - 404 when
+///     - notification does not exist
+///     - user does not belong to notification recipients
+///     - notification have not been delivered yet
+///     - notification is deleted
+///     - notification does not reference the attachment
+///
+async fn get_notification_delivered_attachment(
+    State(attachments_service): State<Arc<dyn AttachmentsService>>,
+    Extension(user): Extension<User>,
+    Path((notification_id, attachment_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<Response, Error> {
+    let attachment = attachments_service
+        .find_attachment(notification_id, attachment_id, user.id)
+        .await?;
+
+    let content_disposition = format!("attachment; filename=\"{}\"", attachment.filename);
+    let headers = [
+        (CONTENT_TYPE, attachment.content_type),
+        (CONTENT_DISPOSITION, content_disposition),
+    ];
+
+    Ok((StatusCode::OK, headers, attachment.content).into_response())
+}
+
 ///
 /// Delete notification
 ///
@@ -187,6 +543,31 @@ async fn delete_notification_delivered(
     Ok(StatusCode::NO_CONTENT)
 }
 
+///
+/// Delete many notifications selected by ids and/or filters
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 422 when
+///     - neither ids nor filters are set
+///     - ids are empty or there are too many of them
+///     - created_at or delivered_at range is empty
+///
+async fn delete_notifications_delivered(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Json(selection): Json<input::NotificationsSelection>,
+) -> Result<(StatusCode, Json<output::NotificationsAffected>), Error> {
+    let affected = notifications_service
+        .delete_notifications(user.id, selection)
+        .await?;
+
+    Ok((StatusCode::OK, Json(affected)))
+}
+
 ///
 /// Update seen property of notification
 ///
@@ -213,68 +594,3013 @@ async fn put_notification_delivered_seen(
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::{
-        error::Error, repository, service::notifications_service::MockNotificationsService,
-    };
-    use axum::{
-        body::Body,
-        http::{header::CONTENT_TYPE, Method, Request},
-    };
-    use serde_json::json;
-    use std::time::Duration;
-    use time::{macros::datetime, OffsetDateTime};
-    use tower::ServiceExt;
-    use uuid::Uuid;
+///
+/// Update seen property of many notifications selected by ids and/or filters
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 422 when
+///     - neither ids nor filters are set
+///     - ids are empty or there are too many of them
+///     - created_at or delivered_at range is empty
+///
+async fn put_notifications_delivered_seen(
+    State(notifications_service): State<Arc<dyn NotificationsService>>,
+    Extension(user): Extension<User>,
+    Json(seen): Json<input::NotificationsSeen>,
+) -> Result<(StatusCode, Json<output::NotificationsAffected>), Error> {
+    let affected = notifications_service
+        .update_notifications_seen(user.id, seen)
+        .await?;
+
+    Ok((StatusCode::OK, Json(affected)))
+}
+
+///
+/// Create new group without members
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ManageGroups]
+/// - 409 when group with the name already exists
+/// - 422 when name is empty, too long or contains invalid characters
+///
+async fn post_groups(
+    State(groups_service): State<Arc<dyn GroupsService>>,
+    Extension(user): Extension<User>,
+    Json(group): Json<input::Group>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ManageGroups.as_ref()])?;
+
+    groups_service.create_group(group).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Find all groups sorted by name
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ManageGroups]
+///
+async fn get_groups(
+    State(groups_service): State<Arc<dyn GroupsService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<Vec<output::Group>>), Error> {
+    require_all_roles(&user, &[Role::ManageGroups.as_ref()])?;
+
+    let groups = groups_service.find_groups().await?;
+
+    Ok((StatusCode::OK, Json(groups)))
+}
+
+///
+/// Delete group together with its members
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ManageGroups]
+/// - 404 when group does not exist
+///
+async fn delete_group(
+    State(groups_service): State<Arc<dyn GroupsService>>,
+    Extension(user): Extension<User>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ManageGroups.as_ref()])?;
+
+    groups_service.delete_group(name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Find ids of the group members
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ManageGroups]
+/// - 404 when group does not exist
+///
+async fn get_group_members(
+    State(groups_service): State<Arc<dyn GroupsService>>,
+    Extension(user): Extension<User>,
+    Path(name): Path<String>,
+) -> Result<(StatusCode, Json<output::GroupMembers>), Error> {
+    require_all_roles(&user, &[Role::ManageGroups.as_ref()])?;
+
+    let members = groups_service.find_group_members(name).await?;
+
+    Ok((StatusCode::OK, Json(members)))
+}
+
+///
+/// Add users to the group
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ManageGroups]
+/// - 404 when group does not exist
+/// - 422 when user_ids are empty or there are too many of them
+///
+async fn put_group_members(
+    State(groups_service): State<Arc<dyn GroupsService>>,
+    Extension(user): Extension<User>,
+    Path(name): Path<String>,
+    Json(members): Json<input::GroupMembers>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ManageGroups.as_ref()])?;
+
+    groups_service.add_group_members(name, members).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Remove users from the group
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ManageGroups]
+/// - 404 when group does not exist
+/// - 422 when user_ids are empty or there are too many of them
+///
+async fn delete_group_members(
+    State(groups_service): State<Arc<dyn GroupsService>>,
+    Extension(user): Extension<User>,
+    Path(name): Path<String>,
+    Json(members): Json<input::GroupMembers>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ManageGroups.as_ref()])?;
+
+    groups_service.remove_group_members(name, members).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Find topics the user is subscribed to sorted by topic
+///
+/// ### Returns
+/// 200 on success
+///
+async fn get_subscriptions(
+    State(subscriptions_service): State<Arc<dyn SubscriptionsService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<Vec<output::Subscription>>), Error> {
+    let subscriptions = subscriptions_service.find_subscriptions(user.id).await?;
+
+    Ok((StatusCode::OK, Json(subscriptions)))
+}
+
+///
+/// Subscribe user to the topic
+///
+/// ### Returns
+/// 204 on success, also when user is already subscribed
+///
+/// ### Errors
+/// - 422 when topic is too long or contains invalid characters
+///
+async fn put_subscription(
+    State(subscriptions_service): State<Arc<dyn SubscriptionsService>>,
+    Extension(user): Extension<User>,
+    Path(topic): Path<String>,
+) -> Result<StatusCode, Error> {
+    subscriptions_service.subscribe(user.id, topic).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Unsubscribe user from the topic
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 404 when user is not subscribed to the topic
+///
+async fn delete_subscription(
+    State(subscriptions_service): State<Arc<dyn SubscriptionsService>>,
+    Extension(user): Extension<User>,
+    Path(topic): Path<String>,
+) -> Result<StatusCode, Error> {
+    subscriptions_service.unsubscribe(user.id, topic).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Find notification preferences of the user
+///
+/// ### Returns
+/// 200 on success, default preferences when user has never saved them
+///
+async fn get_preferences(
+    State(preferences_service): State<Arc<dyn PreferencesService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<output::Preferences>), Error> {
+    let preferences = preferences_service.find_preferences(user.id).await?;
+
+    Ok((StatusCode::OK, Json(preferences)))
+}
+
+///
+/// Replace notification preferences of the user
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 422 when
+///     - there are too many muted producers or content types
+///     - muted content type is empty
+///     - quiet hours are not in "HH:MM" format or are empty
+///     - quiet hours utc_offset is out of range
+///
+async fn put_preferences(
+    State(preferences_service): State<Arc<dyn PreferencesService>>,
+    Extension(user): Extension<User>,
+    Json(preferences): Json<input::Preferences>,
+) -> Result<StatusCode, Error> {
+    preferences_service
+        .update_preferences(user.id, preferences)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Create the first version of the template
+///
+/// ### Returns
+/// 200 on success with version of created template
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 409 when user already has template with the id
+/// - 422 when
+///     - id is empty, too long or contains invalid characters
+///     - body is too long or has invalid placeholders
+///     - parameters are invalid, duplicated or do not cover placeholders of the body
+///
+async fn post_templates(
+    State(templates_service): State<Arc<dyn TemplatesService>>,
+    Extension(user): Extension<User>,
+    Json(template): Json<input::Template>,
+) -> Result<(StatusCode, Json<output::TemplateVersion>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let version = templates_service.create_template(user.id, template).await?;
+
+    Ok((StatusCode::OK, Json(version)))
+}
+
+///
+/// Find the latest versions of the user templates sorted by id
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ProduceNotifications]
+///
+async fn get_templates(
+    State(templates_service): State<Arc<dyn TemplatesService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<Vec<output::Template>>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let templates = templates_service.find_templates(user.id).await?;
+
+    Ok((StatusCode::OK, Json(templates)))
+}
+
+///
+/// Find version of the user template, the latest one when version is not passed
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 404 when template or its version does not exist
+///
+async fn get_template(
+    State(templates_service): State<Arc<dyn TemplatesService>>,
+    Extension(user): Extension<User>,
+    Path(template_id): Path<String>,
+    Query(version): Query<input::TemplateVersion>,
+) -> Result<(StatusCode, Json<output::Template>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let template = templates_service
+        .find_template(user.id, template_id, version)
+        .await?;
+
+    Ok((StatusCode::OK, Json(template)))
+}
+
+///
+/// Create the next version of the template.
+/// Notifications referencing previous versions are not affected
+///
+/// ### Returns
+/// 200 on success with version of created template
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 404 when template does not exist
+/// - 409 when the next version was created concurrently
+/// - 422 when
+///     - body is too long or has invalid placeholders
+///     - parameters are invalid, duplicated or do not cover placeholders of the body
+///
+async fn put_template(
+    State(templates_service): State<Arc<dyn TemplatesService>>,
+    Extension(user): Extension<User>,
+    Path(template_id): Path<String>,
+    Json(content): Json<input::TemplateContent>,
+) -> Result<(StatusCode, Json<output::TemplateVersion>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let version = templates_service
+        .update_template(user.id, template_id, content)
+        .await?;
+
+    Ok((StatusCode::OK, Json(version)))
+}
+
+///
+/// Attach JSON Schema to the content type, content of notifications
+/// with the content type created later has to match the schema
+///
+/// ### Returns
+/// 204 on success, also when schema replaces the previous one
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 422 when
+///     - content type is empty or too long
+///     - schema is too large or is not valid JSON Schema
+///
+async fn put_content_type(
+    State(content_types_service): State<Arc<dyn ContentTypesService>>,
+    Extension(user): Extension<User>,
+    Json(content_type): Json<input::ContentType>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    content_types_service
+        .put_content_type(user.id, content_type)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Find content types of the user with their schemas sorted by content type
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ProduceNotifications]
+///
+async fn get_content_types(
+    State(content_types_service): State<Arc<dyn ContentTypesService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<Vec<output::ContentType>>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let content_types = content_types_service.find_content_types(user.id).await?;
+
+    Ok((StatusCode::OK, Json(content_types)))
+}
+
+///
+/// Detach JSON Schema from the content type
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 404 when content type has no schema attached
+///
+async fn delete_content_type(
+    State(content_types_service): State<Arc<dyn ContentTypesService>>,
+    Extension(user): Extension<User>,
+    Json(content_type): Json<input::ContentTypeName>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    content_types_service
+        .delete_content_type(user.id, content_type)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Find webhook of the user
+///
+/// ### Returns
+/// 200 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 404 when user has not registered webhook
+///
+async fn get_webhook(
+    State(webhooks_service): State<Arc<dyn WebhooksService>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<output::Webhook>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let webhook = webhooks_service.find_webhook(user.id).await?;
+
+    Ok((StatusCode::OK, Json(webhook)))
+}
+
+///
+/// Register webhook receipts of notifications of the user are posted to,
+/// replacing the previous one
+///
+/// ### Returns
+/// 200 on success with new secret of receipt signatures
+///
+/// ### Errors
+/// - 400 when payload is invalid
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 422 when url is not absolute http(s) URL, is too long
+///   or its host is not public and not allowed
+///
+async fn put_webhook(
+    State(webhooks_service): State<Arc<dyn WebhooksService>>,
+    Extension(user): Extension<User>,
+    Json(webhook): Json<input::Webhook>,
+) -> Result<(StatusCode, Json<output::WebhookSecret>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let secret = webhooks_service.register_webhook(user.id, webhook).await?;
+
+    Ok((StatusCode::OK, Json(secret)))
+}
+
+///
+/// Delete webhook of the user, receipts are no longer sent
+///
+/// ### Returns
+/// 204 on success
+///
+/// ### Errors
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 404 when user has not registered webhook
+///
+async fn delete_webhook(
+    State(webhooks_service): State<Arc<dyn WebhooksService>>,
+    Extension(user): Extension<User>,
+) -> Result<StatusCode, Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    webhooks_service.delete_webhook(user.id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+///
+/// Upload attachment that notifications of the user can reference,
+/// content type of the attachment is taken from Content-Type header
+///
+/// ### Returns
+/// 201 on success
+///
+/// ### Errors
+/// - 400 when filename is missing
+/// - 403 when user lacks role [Role::ProduceNotifications]
+/// - 413 when attachment is too large
+/// - 422 when
+///     - filename is empty, too long or contains invalid characters
+///     - Content-Type header is missing or too long
+///     - attachment is empty
+///
+async fn post_attachments(
+    State(attachments_service): State<Arc<dyn AttachmentsService>>,
+    Extension(user): Extension<User>,
+    Query(filename): Query<input::AttachmentFilename>,
+    headers: HeaderMap,
+    content: Bytes,
+) -> Result<(StatusCode, Json<output::AttachmentId>), Error> {
+    require_all_roles(&user, &[Role::ProduceNotifications.as_ref()])?;
+
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let attachment_id = attachments_service
+        .save_attachment(user.id, filename, content_type, content.to_vec())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(attachment_id)))
+}
+
+///
+/// Locales preferred by the user, the locale claim of the token
+/// takes precedence over the Accept-Language header
+///
+fn preferred_locales(user: &User, headers: &HeaderMap) -> input::Locales {
+    let accept_language = headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok());
+
+    input::Locales::new(user.locale.as_deref(), accept_language)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        error::Error,
+        repository,
+        service::{
+            attachments_service::MockAttachmentsService,
+            content_types_service::MockContentTypesService, groups_service::MockGroupsService,
+            notifications_service::MockNotificationsService,
+            preferences_service::MockPreferencesService,
+            subscriptions_service::MockSubscriptionsService,
+            templates_service::MockTemplatesService, webhooks_service::MockWebhooksService,
+        },
+    };
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+    };
+    use serde_json::json;
+    use std::time::Duration;
+    use time::{macros::datetime, OffsetDateTime};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn create_consumer() -> User {
+        User::new(Uuid::new_v4(), vec![], None)
+    }
+
+    fn create_producer() -> User {
+        User::new(
+            Uuid::new_v4(),
+            vec![Role::ProduceNotifications.as_ref().to_string()],
+            None,
+        )
+    }
+
+    fn create_groups_manager() -> User {
+        User::new(
+            Uuid::new_v4(),
+            vec![Role::ManageGroups.as_ref().to_string()],
+            None,
+        )
+    }
+
+    fn mock_application_state() -> ApplicationState {
+        ApplicationState {
+            notifications_service: Arc::new(MockNotificationsService::new()),
+            groups_service: Arc::new(MockGroupsService::new()),
+            subscriptions_service: Arc::new(MockSubscriptionsService::new()),
+            preferences_service: Arc::new(MockPreferencesService::new()),
+            templates_service: Arc::new(mock_templates_service()),
+            content_types_service: Arc::new(MockContentTypesService::new()),
+            attachments_service: Arc::new(MockAttachmentsService::new()),
+            webhooks_service: Arc::new(MockWebhooksService::new()),
+        }
+    }
+
+    fn mock_templates_service() -> MockTemplatesService {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_render_notification()
+            .returning(|_, notification| Ok(notification));
+        templates_service
+            .expect_render_notifications()
+            .returning(|_, notifications| Ok(notifications));
+        templates_service
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notification()
+            .returning(|_, _| {
+                Ok(output::NotificationId {
+                    id: "some id".to_string(),
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_validation_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notification()
+            .returning(|_, _| Err(Error::Validation("invalidate_at passed")));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_validation_notification_too_large_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notification()
+            .returning(|_, _| {
+                Err(Error::ValidationNotificationTooLarge {
+                    size: 4096,
+                    max_size: 1024,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_already_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notification()
+            .returning(|_, _| Err(Error::NotificationAlreadySaved));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notification()
+            .returning(|_, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("unexpected database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notification()
+            .returning(|_, _| {
+                Ok(output::NotificationId {
+                    id: "some id".to_string(),
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_batch_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notifications()
+            .returning(|_, _| Ok(vec![output::NotificationSaveResult::Conflict]));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!([{
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        }])
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_batch_validation_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notifications()
+            .returning(|_, _| Err(Error::Validation("any validation error")));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!([{
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        }])
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_batch_already_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notifications()
+            .returning(|_, _| Err(Error::NotificationAlreadySaved));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!([{
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        }])
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn post_notifications_undelivered_batch_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_save_notifications()
+            .returning(|_, _| {
+                Ok(vec![output::NotificationSaveResult::Created {
+                    id: "some id".to_string(),
+                }])
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/notifications/undelivered/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!([{
+                            "invalidate_at": None as Option<OffsetDateTime>,
+                            "user_ids": Vec::<Uuid>::new(),
+                            "producer_notification_id": 1,
+                            "content_type": "utf-8",
+                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                        }])
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_count_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_count_notifications()
+            .returning(|_| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/count")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_count_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_count_notifications()
+            .returning(|_| {
+                Ok(output::NotificationsCount {
+                    undelivered: 1,
+                    delivered: 2,
+                    delivered_unseen: 0,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/count")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_undelivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_undelivered_notifications()
+            .returning(|_, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/undelivered")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_undelivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_undelivered_notifications()
+            .returning(|_, _| Ok(vec![]));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/undelivered")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_undelivered_accept_language_passed() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_undelivered_notifications()
+            .withf(|_, locales| *locales == input::Locales::new(None, Some("pl-PL, en;q=0.5")))
+            .returning(|_, _| Ok(vec![]));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(ACCEPT_LANGUAGE, "pl-PL, en;q=0.5")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_undelivered_locale_claim_preferred() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_undelivered_notifications()
+            .withf(|_, locales| *locales == input::Locales::new(Some("de"), Some("pl-PL")))
+            .returning(|_, _| Ok(vec![]));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/undelivered")
+                    .header(ACCEPT_LANGUAGE, "pl-PL")
+                    .extension(User::new(Uuid::new_v4(), vec![], Some("de".to_string())))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_undelivered_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_retract_notification()
+            .returning(|_, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_undelivered_notification_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_retract_notification()
+            .returning(|_, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_undelivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_retract_notification()
+            .returning(|_, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_undelivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_retract_notification()
+            .returning(|_, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn get_notification_undelivered_delivery_report_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivery_report()
+            .returning(|_, _, _| {
+                Ok(output::DeliveryReport {
+                    confirmed_count: 0,
+                    confirmations: vec![],
+                    next_confirmations_cursor: None,
+                    pending_count: None,
+                    pending_user_ids: None,
+                    next_pending_cursor: None,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/delivery_report?page_size=10",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn get_notification_undelivered_delivery_report_notification_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivery_report()
+            .returning(|_, _, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/delivery_report?page_size=10",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn get_notification_undelivered_delivery_report_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivery_report()
+            .returning(|_, _, _| {
+                Ok(output::DeliveryReport {
+                    confirmed_count: 0,
+                    confirmations: vec![],
+                    next_confirmations_cursor: None,
+                    pending_count: None,
+                    pending_user_ids: None,
+                    next_pending_cursor: None,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/delivery_report?page_size=10",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn produced_notification() -> output::ProducedNotification {
+        output::ProducedNotification {
+            id: ObjectId::new().to_hex(),
+            producer_notification_id: 1,
+            created_at: OffsetDateTime::now_utc(),
+            invalidate_at: None,
+            deliver_at: None,
+            retracted_at: None,
+            user_ids: vec![Uuid::new_v4()],
+            groups: vec![],
+            topic: None,
+            priority: output::NotificationPriority::Normal,
+            collapse_key: None,
+            content_type: "utf-8".to_string(),
+            content: b"abc".to_vec(),
+            localized_contents: vec![],
+            attachment_ids: vec![],
+            confirmed_count: 1,
+            seen_count: 0,
+            deleted_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn get_notifications_produced_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_produced_notifications()
+            .never();
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/produced?page_size=10")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_produced_filters_parsed() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_produced_notifications()
+            .withf(|_, pagination, filters| {
+                pagination.page_size == 10
+                    && filters.content_type.as_deref() == Some("json")
+                    && filters.retracted == Some(false)
+                    && filters.created_at_from == Some(datetime!(2024-01-01 00:00 UTC))
+                    && filters.order == Some(input::NotificationsOrder::Asc)
+            })
+            .once()
+            .returning(|_, _, _| {
+                Ok(output::ProducedNotificationsPage {
+                    notifications: vec![produced_notification()],
+                    next_cursor: None,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(
+                        "/api/v1/notifications/produced?page_size=10&content_type=json\
+                        &retracted=false&created_at_from=2024-01-01T00:00:00Z&order=asc",
+                    )
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notification_produced_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_produced_notification()
+            .returning(|_, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/produced/1")
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn get_notification_produced_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_produced_notification()
+            .withf(|_, producer_notification_id| *producer_notification_id == 1)
+            .once()
+            .returning(|_, _| Ok(produced_notification()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/produced/1")
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_invalidate_at_validation_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_invalidate_at()
+            .returning(|_, _, _| Err(Error::Validation("invalidate at passed")));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/invalidate_at",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_invalidate_at_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_invalidate_at()
+            .returning(|_, _, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/invalidate_at",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_invalidate_at_notification_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_invalidate_at()
+            .returning(|_, _, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/invalidate_at",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_invalidate_at_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_invalidate_at()
+            .returning(|_, _, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/invalidate_at",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_invalidate_at_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_invalidate_at()
+            .returning(|_, _, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/invalidate_at",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "invalidate_at": None as Option<OffsetDateTime>
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_content_missing_role() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_content()
+            .returning(|_, _, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/content",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "content_type": "utf-8",
+                            "content": "MTIzNA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_content_notification_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_content()
+            .returning(|_, _, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/content",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "content_type": "utf-8",
+                            "content": "MTIzNA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_content_too_large() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_content()
+            .returning(|_, _, _| {
+                Err(Error::ValidationNotificationTooLarge {
+                    size: 4,
+                    max_size: 1,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/content",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "content_type": "utf-8",
+                            "content": "MTIzNA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_undelivered_content_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_content()
+            .returning(|_, _, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/undelivered/{}/content",
+                        ObjectId::new()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "content_type": "utf-8",
+                            "content": "MTIzNA==",
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .returning(|_, _, _, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/delivered?page_idx=5&page_size=10")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .returning(|_, _, _, _| {
+                Ok(output::NotificationsPage {
+                    notifications: vec![],
+                    next_cursor: None,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/delivered?page_idx=5&page_size=10&seen=true")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_invalid_cursor() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .never();
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/delivered?page_size=10&cursor=invalid")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_validation_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .returning(|_, _, _, _| Err(Error::Validation("any validation error")));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let cursor = input::NotificationsCursor {
+            created_at: OffsetDateTime::now_utc(),
+            id: ObjectId::new(),
+        };
+        let cursor = serde_json::to_value(cursor).unwrap();
+        let cursor = cursor.as_str().unwrap();
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered?page_idx=5&page_size=10&cursor={cursor}"
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_by_cursor_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .returning(|_, _, _, _| {
+                Ok(output::NotificationsPage {
+                    notifications: vec![],
+                    next_cursor: None,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/delivered?page_size=10")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert!(body.get("notifications").unwrap().is_array());
+        assert!(body.get("next_cursor").unwrap().is_null());
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_filters_parsed() {
+        let created_by = Uuid::new_v4();
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .withf(move |_, _, filters, _| {
+                filters.seen == Some(false)
+                    && filters.created_by == Some(created_by)
+                    && filters.content_type.as_deref() == Some("utf-8")
+                    && filters.created_at_from == Some(datetime!(2024-01-01 00:00 UTC))
+                    && filters.created_at_to == Some(datetime!(2024-02-01 00:00 UTC))
+                    && filters.delivered_at_from == Some(datetime!(2024-01-15 00:00 UTC))
+                    && filters.delivered_at_to.is_none()
+                    && filters.order == Some(input::NotificationsOrder::Asc)
+            })
+            .returning(|_, _, _, _| {
+                Ok(output::NotificationsPage {
+                    notifications: vec![],
+                    next_cursor: None,
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered?page_size=10&seen=false\
+                        &created_by={created_by}&content_type=utf-8\
+                        &created_at_from=2024-01-01T00:00:00Z&created_at_to=2024-02-01T00:00:00Z\
+                        &delivered_at_from=2024-01-15T00:00:00Z&order=asc"
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_notifications_delivered_invalid_filter() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notifications()
+            .never();
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/notifications/delivered?page_size=10&created_at_from=yesterday")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn get_notification_delivered_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notification()
+            .returning(|_, _, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn get_notification_delivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notification()
+            .returning(|_, _, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn get_notification_delivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_find_delivered_notification()
+            .returning(|_, _, _| {
+                Ok(output::Notification {
+                    id: ObjectId::new().to_hex(),
+                    created_at: datetime!(2024-02-13 19:09:35 UTC),
+                    created_by: Uuid::new_v4(),
+                    seen: true,
+                    priority: output::NotificationPriority::Normal,
+                    locale: None,
+                    content_type: "utf-8".to_string(),
+                    content: b"some content".to_vec(),
+                    attachment_ids: vec![],
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_delivered_notification_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_delete_notification()
+            .returning(|_, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_delivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_delete_notification()
+            .returning(|_, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn delete_notification_delivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_delete_notification()
+            .returning(|_, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}",
+                        ObjectId::new().to_hex()
+                    ))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn delete_notifications_delivered_validation_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_delete_notifications()
+            .returning(|_, _| Err(Error::Validation("any validation error")));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/api/v1/notifications/delivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(json!({}).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn delete_notifications_delivered_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_delete_notifications()
+            .returning(|_, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/api/v1/notifications/delivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(json!({ "ids": [ObjectId::new().to_hex()] }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn delete_notifications_delivered_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_delete_notifications()
+            .withf(|_, selection| {
+                selection.ids.is_none()
+                    && selection.filters.as_ref().is_some_and(|filters| {
+                        filters.seen == Some(true)
+                            && filters.created_at_to == Some(datetime!(2024-01-01 00:00 UTC))
+                    })
+            })
+            .returning(|_, _| Ok(output::NotificationsAffected { count: 4 }));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/api/v1/notifications/delivered")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "filters": {
+                                "seen": true,
+                                "created_at_to": "2024-01-01T00:00:00Z",
+                            },
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn put_notification_delivered_seen_notification_not_exist() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_seen()
+            .returning(|_, _, _| Err(Error::NotificationNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}/seen",
+                        ObjectId::new().to_hex()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "seen": false,
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn put_notification_delivered_seen_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_seen()
+            .returning(|_, _, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}/seen",
+                        ObjectId::new().to_hex()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "seen": false
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn put_notification_delivered_seen_success_code() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notification_seen()
+            .returning(|_, _, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!(
+                        "/api/v1/notifications/delivered/{}/seen",
+                        ObjectId::new().to_hex()
+                    ))
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "seen": false,
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_delivered_seen_validation_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notifications_seen()
+            .returning(|_, _| Err(Error::Validation("any validation error")));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri("/api/v1/notifications/delivered/seen")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(json!({ "seen": true }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_delivered_seen_database_error() {
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notifications_seen()
+            .returning(|_, _| {
+                Err(Error::Database(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                )))
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri("/api/v1/notifications/delivered/seen")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "seen": true,
+                            "ids": [ObjectId::new().to_hex()],
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn put_notifications_delivered_seen_success_code() {
+        let id = ObjectId::new();
+        let mut notifications_service = MockNotificationsService::new();
+        notifications_service
+            .expect_update_notifications_seen()
+            .withf(move |_, seen| {
+                seen.seen
+                    && seen.selection.ids == Some(vec![id])
+                    && seen.selection.filters.as_ref().is_some_and(|filters| {
+                        filters.created_at_to == Some(datetime!(2024-01-01 00:00 UTC))
+                    })
+            })
+            .returning(|_, _| Ok(output::NotificationsAffected { count: 1 }));
+
+        let mut application_state = mock_application_state();
+        application_state.notifications_service = Arc::new(notifications_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri("/api/v1/notifications/delivered/seen")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_consumer())
+                    .body(
+                        json!({
+                            "seen": true,
+                            "ids": [id.to_hex()],
+                            "filters": {
+                                "created_at_to": "2024-01-01T00:00:00Z",
+                            },
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn post_groups_missing_role() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service.expect_create_group().never();
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/groups")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(json!({ "name": "admins" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn post_groups_ok() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service
+            .expect_create_group()
+            .withf(|group| group.name == "admins")
+            .once()
+            .returning(|_| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/groups")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_groups_manager())
+                    .body(json!({ "name": "admins" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn post_groups_already_exist() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service
+            .expect_create_group()
+            .returning(|_| Err(Error::GroupAlreadyExist));
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/groups")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_groups_manager())
+                    .body(json!({ "name": "admins" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn get_groups_ok() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service.expect_find_groups().returning(|| {
+            Ok(vec![output::Group {
+                name: "admins".to_string(),
+                created_at: datetime!(2024-01-01 0:00 UTC),
+            }])
+        });
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/groups")
+                    .extension(create_groups_manager())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn delete_group_not_exist() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service
+            .expect_delete_group()
+            .withf(|name| name == "admins")
+            .returning(|_| Err(Error::GroupNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/api/v1/groups/admins")
+                    .extension(create_groups_manager())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn get_group_members_ok() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service
+            .expect_find_group_members()
+            .withf(|name| name == "admins")
+            .returning(|_| {
+                Ok(output::GroupMembers {
+                    user_ids: vec![Uuid::new_v4()],
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/groups/admins/members")
+                    .extension(create_groups_manager())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn put_group_members_ok() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service
+            .expect_add_group_members()
+            .withf(|name, members| name == "admins" && members.user_ids.len() == 1)
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri("/api/v1/groups/admins/members")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_groups_manager())
+                    .body(json!({ "user_ids": [Uuid::new_v4()] }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn delete_group_members_validation_error() {
+        let mut groups_service = MockGroupsService::new();
+        groups_service
+            .expect_remove_group_members()
+            .returning(|_, _| Err(Error::Validation("user_ids cannot be empty")));
+
+        let mut application_state = mock_application_state();
+        application_state.groups_service = Arc::new(groups_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/api/v1/groups/admins/members")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_groups_manager())
+                    .body(json!({ "user_ids": Vec::<Uuid>::new() }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn get_subscriptions_ok() {
+        let mut subscriptions_service = MockSubscriptionsService::new();
+        subscriptions_service
+            .expect_find_subscriptions()
+            .returning(|_| {
+                Ok(vec![output::Subscription {
+                    topic: "billing".to_string(),
+                    created_at: datetime!(2024-01-01 0:00 UTC),
+                }])
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.subscriptions_service = Arc::new(subscriptions_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/subscriptions")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn put_subscription_ok() {
+        let user = create_consumer();
+        let user_id = user.id;
+
+        let mut subscriptions_service = MockSubscriptionsService::new();
+        subscriptions_service
+            .expect_subscribe()
+            .withf(move |id, topic| *id == user_id && topic == "billing")
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mut application_state = mock_application_state();
+        application_state.subscriptions_service = Arc::new(subscriptions_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri("/api/v1/subscriptions/billing")
+                    .extension(user)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn put_subscription_validation_error() {
+        let mut subscriptions_service = MockSubscriptionsService::new();
+        subscriptions_service
+            .expect_subscribe()
+            .returning(|_, _| Err(Error::Validation("topic too long")));
+
+        let mut application_state = mock_application_state();
+        application_state.subscriptions_service = Arc::new(subscriptions_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!("/api/v1/subscriptions/{}", "a".repeat(100)))
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn delete_subscription_not_exist() {
+        let mut subscriptions_service = MockSubscriptionsService::new();
+        subscriptions_service
+            .expect_unsubscribe()
+            .returning(|_, _| Err(Error::SubscriptionNotExist));
+
+        let mut application_state = mock_application_state();
+        application_state.subscriptions_service = Arc::new(subscriptions_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/api/v1/subscriptions/billing")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
 
-    fn create_consumer() -> User {
-        User::new(Uuid::new_v4(), vec![])
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
-    fn create_producer() -> User {
-        User::new(
-            Uuid::new_v4(),
-            vec![Role::ProduceNotifications.as_ref().to_string()],
-        )
-    }
+    #[tokio::test]
+    async fn get_preferences_ok() {
+        let mut preferences_service = MockPreferencesService::new();
+        preferences_service
+            .expect_find_preferences()
+            .returning(|_| Ok(output::Preferences::default()));
 
-    fn mock_application_state() -> ApplicationState {
-        ApplicationState {
-            notifications_service: Arc::new(MockNotificationsService::new()),
-        }
+        let mut application_state = mock_application_state();
+        application_state.preferences_service = Arc::new(preferences_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/preferences")
+                    .extension(create_consumer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn post_notifications_undelivered_missing_role() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_save_notification()
-            .returning(|_, _| {
-                Ok(output::NotificationId {
-                    id: "some id".to_string(),
-                })
-            });
+    async fn put_preferences_ok() {
+        let user = create_consumer();
+        let user_id = user.id;
+
+        let mut preferences_service = MockPreferencesService::new();
+        preferences_service
+            .expect_update_preferences()
+            .withf(move |id, preferences| {
+                *id == user_id && preferences.muted_content_types == vec!["marketing"]
+            })
+            .once()
+            .returning(|_, _| Ok(()));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.preferences_service = Arc::new(preferences_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::POST)
-                    .uri("/api/v1/notifications/undelivered")
+                    .method(Method::PUT)
+                    .uri("/api/v1/preferences")
                     .header(CONTENT_TYPE, "application/json")
-                    .extension(create_consumer())
+                    .extension(user)
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>,
-                            "user_ids": Vec::<Uuid>::new(),
-                            "producer_notification_id": 1,
-                            "content_type": "utf-8",
-                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                            "muted_content_types": ["marketing"],
+                            "quiet_hours": { "from": "22:00", "to": "07:00" },
                         })
                         .to_string(),
                     )
@@ -283,37 +3609,28 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
     }
 
     #[tokio::test]
-    async fn post_notifications_undelivered_validation_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_save_notification()
-            .returning(|_, _| Err(Error::Validation("invalidate_at passed")));
+    async fn put_preferences_validation_error() {
+        let mut preferences_service = MockPreferencesService::new();
+        preferences_service
+            .expect_update_preferences()
+            .returning(|_, _| Err(Error::Validation("quiet hours cannot be empty")));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.preferences_service = Arc::new(preferences_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::POST)
-                    .uri("/api/v1/notifications/undelivered")
+                    .method(Method::PUT)
+                    .uri("/api/v1/preferences")
                     .header(CONTENT_TYPE, "application/json")
-                    .extension(create_producer())
-                    .body(
-                        json!({
-                            "invalidate_at": None as Option<OffsetDateTime>,
-                            "user_ids": Vec::<Uuid>::new(),
-                            "producer_notification_id": 1,
-                            "content_type": "utf-8",
-                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
-                        })
-                        .to_string(),
-                    )
+                    .extension(create_consumer())
+                    .body(json!({ "quiet_hours": { "from": "22:00", "to": "22:00" } }).to_string())
                     .unwrap(),
             )
             .await
@@ -323,19 +3640,24 @@ mod test {
     }
 
     #[tokio::test]
-    async fn post_notifications_undelivered_validation_notification_too_large_error() {
+    async fn post_notifications_undelivered_template_validation_error() {
         let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_save_notification()
-            .returning(|_, _| {
-                Err(Error::ValidationNotificationTooLarge {
-                    size: 4096,
-                    max_size: 1024,
-                })
-            });
+        notifications_service.expect_save_notification().never();
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_render_notification()
+            .withf(|_, notification| {
+                notification
+                    .template
+                    .as_ref()
+                    .is_some_and(|template| template.id == "welcome")
+            })
+            .once()
+            .returning(|_, _| Err(Error::Validation("missing template parameter")));
 
         let mut application_state = mock_application_state();
         application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
@@ -347,11 +3669,9 @@ mod test {
                     .extension(create_producer())
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>,
-                            "user_ids": Vec::<Uuid>::new(),
+                            "user_ids": [Uuid::new_v4()],
                             "producer_notification_id": 1,
-                            "content_type": "utf-8",
-                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                            "template": { "id": "welcome", "parameters": {} },
                         })
                         .to_string(),
                     )
@@ -360,34 +3680,31 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
     #[tokio::test]
-    async fn post_notifications_undelivered_already_exist() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_save_notification()
-            .returning(|_, _| Err(Error::NotificationAlreadySaved));
+    async fn post_templates_missing_role() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service.expect_create_template().never();
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::POST)
-                    .uri("/api/v1/notifications/undelivered")
+                    .uri("/api/v1/templates")
                     .header(CONTENT_TYPE, "application/json")
-                    .extension(create_producer())
+                    .extension(create_consumer())
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>,
-                            "user_ids": Vec::<Uuid>::new(),
-                            "producer_notification_id": 1,
-                            "content_type": "utf-8",
-                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                            "id": "welcome",
+                            "content_type": "text/plain",
+                            "body": "Hello {{name}}",
+                            "parameters": ["name"],
                         })
                         .to_string(),
                     )
@@ -396,38 +3713,40 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
 
     #[tokio::test]
-    async fn post_notifications_undelivered_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_save_notification()
-            .returning(|_, _| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("unexpected database error")).into(),
-                )))
+    async fn post_templates_ok() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_create_template()
+            .withf(|_, template| template.id == "welcome" && template.parameters == ["name"])
+            .once()
+            .returning(|_, template| {
+                Ok(output::TemplateVersion {
+                    id: template.id,
+                    version: 1,
+                })
             });
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::POST)
-                    .uri("/api/v1/notifications/undelivered")
+                    .uri("/api/v1/templates")
                     .header(CONTENT_TYPE, "application/json")
                     .extension(create_producer())
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>,
-                            "user_ids": Vec::<Uuid>::new(),
-                            "producer_notification_id": 1,
-                            "content_type": "utf-8",
-                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                            "id": "welcome",
+                            "content_type": "text/plain",
+                            "body": "Hello {{name}}",
+                            "parameters": ["name"],
                         })
                         .to_string(),
                     )
@@ -436,38 +3755,32 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn post_notifications_undelivered_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_save_notification()
-            .returning(|_, _| {
-                Ok(output::NotificationId {
-                    id: "some id".to_string(),
-                })
-            });
+    async fn post_templates_already_exist() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_create_template()
+            .returning(|_, _| Err(Error::TemplateAlreadyExist));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::POST)
-                    .uri("/api/v1/notifications/undelivered")
+                    .uri("/api/v1/templates")
                     .header(CONTENT_TYPE, "application/json")
                     .extension(create_producer())
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>,
-                            "user_ids": Vec::<Uuid>::new(),
-                            "producer_notification_id": 1,
-                            "content_type": "utf-8",
-                            "content": "VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==",
+                            "id": "welcome",
+                            "content_type": "text/plain",
+                            "body": "Hello",
                         })
                         .to_string(),
                     )
@@ -476,89 +3789,123 @@ mod test {
             .await
             .unwrap();
 
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn get_templates_ok() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_find_templates()
+            .returning(|_| Ok(vec![]));
+
+        let mut application_state = mock_application_state();
+        application_state.templates_service = Arc::new(templates_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/templates")
+                    .extension(create_producer())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
         assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn get_notifications_undelivered_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_undelivered_notifications()
-            .returning(|_| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                )))
+    async fn get_template_version_passed() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_find_template()
+            .withf(|_, id, version| id == "welcome" && version.version == Some(2))
+            .once()
+            .returning(|_, id, version| {
+                Ok(output::Template {
+                    id,
+                    version: version.version.unwrap(),
+                    created_at: OffsetDateTime::now_utc(),
+                    content_type: "text/plain".to_string(),
+                    body: "Hello".to_string(),
+                    parameters: vec![],
+                })
             });
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
-                    .uri("/api/v1/notifications/undelivered")
-                    .extension(create_consumer())
+                    .uri("/api/v1/templates/welcome?version=2")
+                    .extension(create_producer())
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn get_notifications_undelivered_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_undelivered_notifications()
-            .returning(|_| Ok(vec![]));
+    async fn get_template_not_exist() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_find_template()
+            .returning(|_, _, _| Err(Error::TemplateNotExist));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
-                    .uri("/api/v1/notifications/undelivered")
-                    .extension(create_consumer())
+                    .uri("/api/v1/templates/welcome")
+                    .extension(create_producer())
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn put_notifications_undelivered_invalidate_at_validation_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_invalidate_at()
-            .returning(|_, _, _| Err(Error::Validation("invalidate at passed")));
+    async fn put_template_ok() {
+        let mut templates_service = MockTemplatesService::new();
+        templates_service
+            .expect_update_template()
+            .withf(|_, id, content| id == "welcome" && content.body == "Hi {{name}}")
+            .once()
+            .returning(|_, id, _| Ok(output::TemplateVersion { id, version: 2 }));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.templates_service = Arc::new(templates_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/undelivered/{}/invalidate_at",
-                        ObjectId::new()
-                    ))
+                    .uri("/api/v1/templates/welcome")
                     .header(CONTENT_TYPE, "application/json")
                     .extension(create_producer())
                     .body(
                         json!({
-                            "invalidate_at": OffsetDateTime::now_utc() - Duration::from_secs(300),
+                            "content_type": "text/plain",
+                            "body": "Hi {{name}}",
+                            "parameters": ["name"],
                         })
                         .to_string(),
                     )
@@ -567,33 +3914,29 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn put_notifications_undelivered_invalidate_at_missing_role() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_invalidate_at()
-            .returning(|_, _, _| Ok(()));
+    async fn put_content_type_missing_role() {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service.expect_put_content_type().never();
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.content_types_service = Arc::new(content_types_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/undelivered/{}/invalidate_at",
-                        ObjectId::new()
-                    ))
+                    .uri("/api/v1/content_types")
                     .header(CONTENT_TYPE, "application/json")
                     .extension(create_consumer())
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>
+                            "content_type": "order",
+                            "schema": { "type": "object" },
                         })
                         .to_string(),
                     )
@@ -606,29 +3949,32 @@ mod test {
     }
 
     #[tokio::test]
-    async fn put_notifications_undelivered_invalidate_at_notification_not_exist() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_invalidate_at()
-            .returning(|_, _, _| Err(Error::NotificationNotExist));
+    async fn put_content_type_ok() {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service
+            .expect_put_content_type()
+            .withf(|_, content_type| {
+                content_type.content_type == "order"
+                    && content_type.schema == json!({ "type": "object" })
+            })
+            .once()
+            .returning(|_, _| Ok(()));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.content_types_service = Arc::new(content_types_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/undelivered/{}/invalidate_at",
-                        ObjectId::new()
-                    ))
+                    .uri("/api/v1/content_types")
                     .header(CONTENT_TYPE, "application/json")
                     .extension(create_producer())
                     .body(
                         json!({
-                            "invalidate_at": None as Option<OffsetDateTime>
+                            "content_type": "order",
+                            "schema": { "type": "object" },
                         })
                         .to_string(),
                     )
@@ -637,131 +3983,145 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
     }
 
     #[tokio::test]
-    async fn put_notifications_undelivered_invalidate_at_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_invalidate_at()
-            .returning(|_, _, _| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                )))
-            });
+    async fn put_content_type_validation_error() {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service
+            .expect_put_content_type()
+            .returning(|_, _| Err(Error::Validation("schema is not valid JSON Schema")));
+
+        let mut application_state = mock_application_state();
+        application_state.content_types_service = Arc::new(content_types_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PUT)
+                    .uri("/api/v1/content_types")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(
+                        json!({
+                            "content_type": "order",
+                            "schema": { "type": 5 },
+                        })
+                        .to_string(),
+                    )
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn get_content_types_ok() {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service
+            .expect_find_content_types()
+            .returning(|_| Ok(vec![]));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.content_types_service = Arc::new(content_types_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/undelivered/{}/invalidate_at",
-                        ObjectId::new()
-                    ))
-                    .header(CONTENT_TYPE, "application/json")
+                    .method(Method::GET)
+                    .uri("/api/v1/content_types")
                     .extension(create_producer())
-                    .body(
-                        json!({
-                            "invalidate_at": None as Option<OffsetDateTime>
-                        })
-                        .to_string(),
-                    )
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn put_notifications_undelivered_invalidate_at_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_invalidate_at()
-            .returning(|_, _, _| Ok(()));
+    async fn delete_content_type_not_exist() {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service
+            .expect_delete_content_type()
+            .returning(|_, _| Err(Error::ContentTypeNotExist));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.content_types_service = Arc::new(content_types_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/undelivered/{}/invalidate_at",
-                        ObjectId::new()
-                    ))
+                    .method(Method::DELETE)
+                    .uri("/api/v1/content_types")
                     .header(CONTENT_TYPE, "application/json")
                     .extension(create_producer())
-                    .body(
-                        json!({
-                            "invalidate_at": None as Option<OffsetDateTime>
-                        })
-                        .to_string(),
-                    )
+                    .body(json!({ "content_type": "order" }).to_string())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn get_notifications_delivered_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_delivered_notifications()
-            .returning(|_, _, _| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                )))
-            });
+    async fn put_webhook_missing_role() {
+        let mut webhooks_service = MockWebhooksService::new();
+        webhooks_service.expect_register_webhook().never();
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.webhooks_service = Arc::new(webhooks_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::GET)
-                    .uri("/api/v1/notifications/delivered?page_idx=5&page_size=10")
+                    .method(Method::PUT)
+                    .uri("/api/v1/webhook")
+                    .header(CONTENT_TYPE, "application/json")
                     .extension(create_consumer())
-                    .body(Body::empty())
+                    .body(json!({ "url": "https://example.com/receipts" }).to_string())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
 
     #[tokio::test]
-    async fn get_notifications_delivered_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_delivered_notifications()
-            .returning(|_, _, _| Ok(vec![]));
+    async fn put_webhook_ok() {
+        let mut webhooks_service = MockWebhooksService::new();
+        webhooks_service
+            .expect_register_webhook()
+            .withf(|_, webhook| webhook.url == "https://example.com/receipts")
+            .once()
+            .returning(|_, _| {
+                Ok(output::WebhookSecret {
+                    secret: "secret".to_string(),
+                })
+            });
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.webhooks_service = Arc::new(webhooks_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::GET)
-                    .uri("/api/v1/notifications/delivered?page_idx=5&page_size=10&seen=true")
-                    .extension(create_consumer())
-                    .body(Body::empty())
+                    .method(Method::PUT)
+                    .uri("/api/v1/webhook")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(json!({ "url": "https://example.com/receipts" }).to_string())
                     .unwrap(),
             )
             .await
@@ -771,122 +4131,103 @@ mod test {
     }
 
     #[tokio::test]
-    async fn get_notification_delivered_not_exist() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_delivered_notification()
-            .returning(|_, _| Err(Error::NotificationNotExist));
+    async fn put_webhook_validation_error() {
+        let mut webhooks_service = MockWebhooksService::new();
+        webhooks_service
+            .expect_register_webhook()
+            .returning(|_, _| Err(Error::Validation("url must be absolute http(s) URL")));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.webhooks_service = Arc::new(webhooks_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::GET)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}",
-                        ObjectId::new().to_hex()
-                    ))
-                    .extension(create_consumer())
-                    .body(Body::empty())
+                    .method(Method::PUT)
+                    .uri("/api/v1/webhook")
+                    .header(CONTENT_TYPE, "application/json")
+                    .extension(create_producer())
+                    .body(json!({ "url": "ftp://example.com" }).to_string())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
     #[tokio::test]
-    async fn get_notification_delivered_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_delivered_notification()
-            .returning(|_, _| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                )))
-            });
+    async fn get_webhook_not_exist() {
+        let mut webhooks_service = MockWebhooksService::new();
+        webhooks_service
+            .expect_find_webhook()
+            .returning(|_| Err(Error::WebhookNotExist));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.webhooks_service = Arc::new(webhooks_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}",
-                        ObjectId::new().to_hex()
-                    ))
-                    .extension(create_consumer())
+                    .uri("/api/v1/webhook")
+                    .extension(create_producer())
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn get_notification_delivered_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_find_delivered_notification()
-            .returning(|_, _| {
-                Ok(output::Notification {
-                    id: ObjectId::new().to_hex(),
-                    created_at: datetime!(2024-02-13 19:09:35 UTC),
-                    created_by: Uuid::new_v4(),
-                    seen: true,
-                    content_type: "utf-8".to_string(),
-                    content: b"some content".to_vec(),
-                })
-            });
+    async fn delete_webhook_ok() {
+        let mut webhooks_service = MockWebhooksService::new();
+        webhooks_service
+            .expect_delete_webhook()
+            .once()
+            .returning(|_| Ok(()));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.webhooks_service = Arc::new(webhooks_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::GET)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}",
-                        ObjectId::new().to_hex()
-                    ))
-                    .extension(create_consumer())
+                    .method(Method::DELETE)
+                    .uri("/api/v1/webhook")
+                    .extension(create_producer())
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
     }
 
     #[tokio::test]
-    async fn delete_notification_delivered_notification_not_exist() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_delete_notification()
-            .returning(|_, _| Err(Error::NotificationNotExist));
+    async fn get_notification_delivered_attachment_not_exist() {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service
+            .expect_find_attachment()
+            .returning(|_, _, _| Err(Error::AttachmentNotExist));
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.attachments_service = Arc::new(attachments_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::DELETE)
+                    .method(Method::GET)
                     .uri(format!(
-                        "/api/v1/notifications/delivered/{}",
+                        "/api/v1/notifications/delivered/{}/attachments/{}",
+                        ObjectId::new().to_hex(),
                         ObjectId::new().to_hex()
                     ))
                     .extension(create_consumer())
@@ -900,26 +4241,30 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_notification_delivered_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_delete_notification()
-            .returning(|_, _| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                )))
+    async fn get_notification_delivered_attachment_ok() {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service
+            .expect_find_attachment()
+            .once()
+            .returning(|_, _, _| {
+                Ok(output::Attachment {
+                    filename: "report.txt".to_string(),
+                    content_type: "text/plain".to_string(),
+                    content: b"report".to_vec(),
+                })
             });
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.attachments_service = Arc::new(attachments_service);
 
         let response = routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::DELETE)
+                    .method(Method::GET)
                     .uri(format!(
-                        "/api/v1/notifications/delivered/{}",
+                        "/api/v1/notifications/delivered/{}/attachments/{}",
+                        ObjectId::new().to_hex(),
                         ObjectId::new().to_hex()
                     ))
                     .extension(create_consumer())
@@ -929,144 +4274,134 @@ mod test {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/plain");
+        assert_eq!(
+            response.headers()[CONTENT_DISPOSITION],
+            "attachment; filename=\"report.txt\""
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"report");
     }
 
     #[tokio::test]
-    async fn delete_notification_delivered_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_delete_notification()
-            .returning(|_, _| Ok(()));
+    async fn post_attachments_missing_role() {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service.expect_save_attachment().never();
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.attachments_service = Arc::new(attachments_service);
 
-        let response = routing()
+        let response = attachments_routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::DELETE)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}",
-                        ObjectId::new().to_hex()
-                    ))
+                    .method(Method::POST)
+                    .uri("/api/v1/attachments?filename=report.txt")
+                    .header(CONTENT_TYPE, "text/plain")
                     .extension(create_consumer())
-                    .body(Body::empty())
+                    .body(Body::from("report"))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
 
     #[tokio::test]
-    async fn put_notification_delivered_seen_notification_not_exist() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_seen()
-            .returning(|_, _, _| Err(Error::NotificationNotExist));
+    async fn post_attachments_missing_filename() {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service.expect_save_attachment().never();
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.attachments_service = Arc::new(attachments_service);
 
-        let response = routing()
+        let response = attachments_routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}/seen",
-                        ObjectId::new().to_hex()
-                    ))
-                    .header(CONTENT_TYPE, "application/json")
-                    .extension(create_consumer())
-                    .body(
-                        json!({
-                            "seen": false,
-                        })
-                        .to_string(),
-                    )
+                    .method(Method::POST)
+                    .uri("/api/v1/attachments")
+                    .header(CONTENT_TYPE, "text/plain")
+                    .extension(create_producer())
+                    .body(Body::from("report"))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn put_notification_delivered_seen_database_error() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_seen()
-            .returning(|_, _, _| {
-                Err(Error::Database(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                )))
+    async fn post_attachments_success_code() {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service
+            .expect_save_attachment()
+            .withf(|_, filename, content_type, content| {
+                filename.filename == "report.txt"
+                    && content_type == "text/plain"
+                    && content == b"report"
+            })
+            .once()
+            .returning(|_, _, _, _| {
+                Ok(output::AttachmentId {
+                    id: ObjectId::new().to_hex(),
+                })
             });
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.attachments_service = Arc::new(attachments_service);
 
-        let response = routing()
+        let response = attachments_routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}/seen",
-                        ObjectId::new().to_hex()
-                    ))
-                    .header(CONTENT_TYPE, "application/json")
-                    .extension(create_consumer())
-                    .body(
-                        json!({
-                            "seen": false
-                        })
-                        .to_string(),
-                    )
+                    .method(Method::POST)
+                    .uri("/api/v1/attachments?filename=report.txt")
+                    .header(CONTENT_TYPE, "text/plain")
+                    .extension(create_producer())
+                    .body(Body::from("report"))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::CREATED);
     }
 
     #[tokio::test]
-    async fn put_notification_delivered_seen_success_code() {
-        let mut notifications_service = MockNotificationsService::new();
-        notifications_service
-            .expect_update_notification_seen()
-            .returning(|_, _, _| Ok(()));
+    async fn post_attachments_too_large() {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service
+            .expect_save_attachment()
+            .returning(|_, _, _, content| {
+                Err(Error::ValidationAttachmentTooLarge {
+                    size: content.len(),
+                    max_size: 1,
+                })
+            });
 
         let mut application_state = mock_application_state();
-        application_state.notifications_service = Arc::new(notifications_service);
+        application_state.attachments_service = Arc::new(attachments_service);
 
-        let response = routing()
+        let response = attachments_routing()
             .with_state(application_state)
             .oneshot(
                 Request::builder()
-                    .method(Method::PUT)
-                    .uri(format!(
-                        "/api/v1/notifications/delivered/{}/seen",
-                        ObjectId::new().to_hex()
-                    ))
-                    .header(CONTENT_TYPE, "application/json")
-                    .extension(create_consumer())
-                    .body(
-                        json!({
-                            "seen": false,
-                        })
-                        .to_string(),
-                    )
+                    .method(Method::POST)
+                    .uri("/api/v1/attachments?filename=report.txt")
+                    .header(CONTENT_TYPE, "text/plain")
+                    .extension(create_producer())
+                    .body(Body::from("report"))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
     }
 }
@@ -0,0 +1,58 @@
+use crate::{
+    dto::{input, output},
+    error::Error,
+};
+use axum::async_trait;
+use bson::oid::ObjectId;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AttachmentsService: Send + Sync {
+    ///
+    /// Uploads attachment of the producer, notifications
+    /// of the producer can reference it after upload
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - filename is empty, too long or contains invalid characters
+    ///     - content type is empty or too long
+    ///     - content is empty
+    /// - [Error::ValidationAttachmentTooLarge] when content is too large
+    ///
+    async fn save_attachment(
+        &self,
+        producer_id: Uuid,
+        filename: input::AttachmentFilename,
+        content_type: String,
+        content: Vec<u8>,
+    ) -> Result<output::AttachmentId, Error>;
+
+    ///
+    /// Validates attachments referenced by the notification of the producer
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - there are too many attachments or some are duplicated
+    ///     - some attachment of the producer does not exist
+    ///
+    async fn validate_attachments(
+        &self,
+        producer_id: Uuid,
+        attachment_ids: &[ObjectId],
+    ) -> Result<(), Error>;
+
+    ///
+    /// Finds attachment of the notification delivered to the user
+    ///
+    /// ### Errors
+    /// - [Error::NotificationNotExist] when notification is not delivered to the user
+    /// - [Error::AttachmentNotExist] when notification does not reference the attachment
+    ///
+    async fn find_attachment(
+        &self,
+        notification_id: ObjectId,
+        attachment_id: ObjectId,
+        user_id: Uuid,
+    ) -> Result<output::Attachment, Error>;
+}
@@ -0,0 +1,451 @@
+use super::{AttachmentsService, AttachmentsServiceConfig};
+use crate::{
+    dto::{input, output},
+    error::Error,
+    repository::{AttachmentsRepository, NotificationsRepository},
+};
+use axum::async_trait;
+use bson::oid::ObjectId;
+use std::sync::Arc;
+use uuid::Uuid;
+
+///
+/// Maximum number of attachments a single notification can reference
+///
+const MAX_NOTIFICATION_ATTACHMENTS: usize = 16;
+
+///
+/// Maximum length of the attachment filename
+///
+const MAX_FILENAME_LEN: usize = 255;
+
+///
+/// Maximum length of the attachment content type
+///
+const MAX_CONTENT_TYPE_LEN: usize = 128;
+
+pub struct AttachmentsServiceImpl {
+    config: AttachmentsServiceConfig,
+    repository: Arc<dyn AttachmentsRepository>,
+    notifications_repository: Arc<dyn NotificationsRepository>,
+}
+
+impl AttachmentsServiceImpl {
+    pub fn new(
+        config: AttachmentsServiceConfig,
+        repository: Arc<dyn AttachmentsRepository>,
+        notifications_repository: Arc<dyn NotificationsRepository>,
+    ) -> Self {
+        Self {
+            config,
+            repository,
+            notifications_repository,
+        }
+    }
+
+    fn validate_filename(filename: &str) -> Result<(), Error> {
+        if filename.is_empty() {
+            return Err(Error::Validation("filename cannot be empty"));
+        }
+        if filename.len() > MAX_FILENAME_LEN {
+            return Err(Error::Validation("filename too long"));
+        }
+        // Filename is sent back in Content-Disposition header
+        if !filename
+            .chars()
+            .all(|c| (c.is_ascii_graphic() || c == ' ') && !matches!(c, '"' | '\\' | '/'))
+        {
+            return Err(Error::Validation("filename contains invalid characters"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_content_type(content_type: &str) -> Result<(), Error> {
+        if content_type.is_empty() {
+            return Err(Error::Validation("content_type cannot be empty"));
+        }
+        if content_type.len() > MAX_CONTENT_TYPE_LEN {
+            return Err(Error::Validation("content_type too long"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_content(&self, content: &[u8]) -> Result<(), Error> {
+        if content.is_empty() {
+            return Err(Error::Validation("attachment cannot be empty"));
+        }
+        if content.len() > self.config.max_attachment_len {
+            return Err(Error::ValidationAttachmentTooLarge {
+                size: content.len(),
+                max_size: self.config.max_attachment_len,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AttachmentsService for AttachmentsServiceImpl {
+    async fn save_attachment(
+        &self,
+        producer_id: Uuid,
+        filename: input::AttachmentFilename,
+        content_type: String,
+        content: Vec<u8>,
+    ) -> Result<output::AttachmentId, Error> {
+        tracing::info!(
+            filename = filename.filename,
+            content_type,
+            len = content.len(),
+            "saving attachment"
+        );
+
+        Self::validate_filename(&filename.filename)?;
+        Self::validate_content_type(&content_type)?;
+        self.validate_content(&content)?;
+
+        let id = self
+            .repository
+            .insert(producer_id, filename.filename, content_type, content)
+            .await?;
+
+        let id = id.to_hex();
+        tracing::info!(id, "saved attachment");
+
+        Ok(output::AttachmentId { id })
+    }
+
+    async fn validate_attachments(
+        &self,
+        producer_id: Uuid,
+        attachment_ids: &[ObjectId],
+    ) -> Result<(), Error> {
+        if attachment_ids.is_empty() {
+            return Ok(());
+        }
+        if attachment_ids.len() > MAX_NOTIFICATION_ATTACHMENTS {
+            return Err(Error::Validation("too many attachments"));
+        }
+        for (idx, attachment_id) in attachment_ids.iter().enumerate() {
+            if attachment_ids[..idx].contains(attachment_id) {
+                return Err(Error::Validation("duplicated attachment"));
+            }
+        }
+
+        let existing_ids = self
+            .repository
+            .find_many_ids(producer_id, attachment_ids.to_vec())
+            .await?;
+        if existing_ids.len() != attachment_ids.len() {
+            return Err(Error::Validation("attachment does not exist"));
+        }
+
+        Ok(())
+    }
+
+    async fn find_attachment(
+        &self,
+        notification_id: ObjectId,
+        attachment_id: ObjectId,
+        user_id: Uuid,
+    ) -> Result<output::Attachment, Error> {
+        tracing::info!("finding attachment");
+
+        let notification = self
+            .notifications_repository
+            .find_delivered(notification_id, user_id)
+            .await?
+            .ok_or(Error::NotificationNotExist)?;
+        if !notification.attachment_ids.contains(&attachment_id) {
+            return Err(Error::AttachmentNotExist);
+        }
+
+        let attachment = self
+            .repository
+            .find(attachment_id)
+            .await?
+            .ok_or(Error::AttachmentNotExist)?;
+
+        tracing::info!(len = attachment.content.len(), "found attachment");
+
+        Ok(attachment.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        dto::input::NotificationPriority,
+        repository::{self, MockAttachmentsRepository, MockNotificationsRepository},
+    };
+    use time::OffsetDateTime;
+
+    fn config() -> AttachmentsServiceConfig {
+        AttachmentsServiceConfig {
+            max_attachment_len: 16,
+        }
+    }
+
+    fn delivered_notification(
+        id: ObjectId,
+        attachment_ids: Vec<ObjectId>,
+    ) -> repository::Notification {
+        repository::Notification {
+            id,
+            created_at: OffsetDateTime::now_utc(),
+            producer_id: Uuid::new_v4().into(),
+            seen: false,
+            priority: NotificationPriority::Normal,
+            content_type: "utf-8".to_string(),
+            content: b"content".to_vec(),
+            localized_contents: vec![],
+            attachment_ids,
+        }
+    }
+
+    #[tokio::test]
+    async fn save_attachment_ok() {
+        let id = ObjectId::new();
+        let mut repository = MockAttachmentsRepository::new();
+        repository
+            .expect_insert()
+            .withf(|_, filename, content_type, content| {
+                filename == "report.txt" && content_type == "text/plain" && content == b"report"
+            })
+            .once()
+            .returning(move |_, _, _, _| Ok(id));
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+
+        let attachment_id = service
+            .save_attachment(
+                Uuid::new_v4(),
+                input::AttachmentFilename {
+                    filename: "report.txt".to_string(),
+                },
+                "text/plain".to_string(),
+                b"report".to_vec(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(attachment_id.id, id.to_hex());
+    }
+
+    #[tokio::test]
+    async fn save_attachment_validation_filename() {
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_insert().never();
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+
+        for filename in ["", "report\".txt", "../report.txt", "raport\u{f3}w.txt"] {
+            let save_result = service
+                .save_attachment(
+                    Uuid::new_v4(),
+                    input::AttachmentFilename {
+                        filename: filename.to_string(),
+                    },
+                    "text/plain".to_string(),
+                    b"report".to_vec(),
+                )
+                .await;
+
+            assert!(matches!(save_result, Err(Error::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn save_attachment_validation_too_large() {
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_insert().never();
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+
+        let save_result = service
+            .save_attachment(
+                Uuid::new_v4(),
+                input::AttachmentFilename {
+                    filename: "report.txt".to_string(),
+                },
+                "text/plain".to_string(),
+                vec![0; 17],
+            )
+            .await;
+
+        assert!(matches!(
+            save_result,
+            Err(Error::ValidationAttachmentTooLarge {
+                size: 17,
+                max_size: 16
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn validate_attachments_ok() {
+        let attachment_ids = vec![ObjectId::new(), ObjectId::new()];
+        let existing_ids = attachment_ids.clone();
+        let mut repository = MockAttachmentsRepository::new();
+        repository
+            .expect_find_many_ids()
+            .once()
+            .returning(move |_, _| Ok(existing_ids.clone()));
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+
+        let validate_result = service
+            .validate_attachments(Uuid::new_v4(), &attachment_ids)
+            .await;
+
+        assert!(validate_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn validate_attachments_empty_skips_repository() {
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_find_many_ids().never();
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+
+        let validate_result = service.validate_attachments(Uuid::new_v4(), &[]).await;
+
+        assert!(validate_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn validate_attachments_duplicated() {
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_find_many_ids().never();
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+        let attachment_id = ObjectId::new();
+
+        let validate_result = service
+            .validate_attachments(Uuid::new_v4(), &[attachment_id, attachment_id])
+            .await;
+
+        assert!(matches!(validate_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn validate_attachments_not_existing() {
+        let existing_id = ObjectId::new();
+        let mut repository = MockAttachmentsRepository::new();
+        repository
+            .expect_find_many_ids()
+            .once()
+            .returning(move |_, _| Ok(vec![existing_id]));
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(MockNotificationsRepository::new()),
+        );
+
+        let validate_result = service
+            .validate_attachments(Uuid::new_v4(), &[existing_id, ObjectId::new()])
+            .await;
+
+        assert!(matches!(validate_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_attachment_ok() {
+        let notification_id = ObjectId::new();
+        let attachment_id = ObjectId::new();
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_find_delivered()
+            .once()
+            .returning(move |id, _| Ok(Some(delivered_notification(id, vec![attachment_id]))));
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_find().once().returning(|id| {
+            Ok(Some(repository::Attachment {
+                id,
+                filename: "report.txt".to_string(),
+                content_type: "text/plain".to_string(),
+                content: b"report".to_vec(),
+            }))
+        });
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(notifications_repository),
+        );
+
+        let attachment = service
+            .find_attachment(notification_id, attachment_id, Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert_eq!(attachment.filename, "report.txt");
+        assert_eq!(attachment.content_type, "text/plain");
+        assert_eq!(attachment.content, b"report");
+    }
+
+    #[tokio::test]
+    async fn find_attachment_notification_not_delivered() {
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_find_delivered()
+            .once()
+            .returning(|_, _| Ok(None));
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_find().never();
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(notifications_repository),
+        );
+
+        let find_result = service
+            .find_attachment(ObjectId::new(), ObjectId::new(), Uuid::new_v4())
+            .await;
+
+        assert!(matches!(find_result, Err(Error::NotificationNotExist)));
+    }
+
+    #[tokio::test]
+    async fn find_attachment_not_referenced_by_notification() {
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_find_delivered()
+            .once()
+            .returning(|id, _| Ok(Some(delivered_notification(id, vec![ObjectId::new()]))));
+        let mut repository = MockAttachmentsRepository::new();
+        repository.expect_find().never();
+        let service = AttachmentsServiceImpl::new(
+            config(),
+            Arc::new(repository),
+            Arc::new(notifications_repository),
+        );
+
+        let find_result = service
+            .find_attachment(ObjectId::new(), ObjectId::new(), Uuid::new_v4())
+            .await;
+
+        assert!(matches!(find_result, Err(Error::AttachmentNotExist)));
+    }
+}
@@ -0,0 +1,6 @@
+pub struct AttachmentsServiceConfig {
+    ///
+    /// Maximum length of attachment content (in bytes)
+    ///
+    pub max_attachment_len: usize,
+}
@@ -0,0 +1,3 @@
+mod attachments_service_config;
+
+pub use attachments_service_config::*;
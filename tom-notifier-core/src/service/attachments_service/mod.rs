@@ -0,0 +1,7 @@
+mod attachments_service;
+mod attachments_service_impl;
+mod dto;
+
+pub use attachments_service::*;
+pub use attachments_service_impl::*;
+pub use dto::*;
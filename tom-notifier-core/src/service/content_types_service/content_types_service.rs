@@ -0,0 +1,58 @@
+use super::ContentValidator;
+use crate::{
+    dto::{input, output},
+    error::Error,
+};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ContentTypesService: Send + Sync {
+    ///
+    /// Attaches JSON Schema to the producer content type,
+    /// replaces schema attached before
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - content type is empty or too long
+    ///     - schema is too large or is not valid JSON Schema
+    ///
+    async fn put_content_type(
+        &self,
+        producer_id: Uuid,
+        content_type: input::ContentType,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Finds content types of the producer with their schemas sorted by content type
+    ///
+    async fn find_content_types(
+        &self,
+        producer_id: Uuid,
+    ) -> Result<Vec<output::ContentType>, Error>;
+
+    ///
+    /// Detaches JSON Schema from the producer content type
+    ///
+    /// ### Errors
+    /// - [Error::ContentTypeNotExist] when content type has no schema attached
+    ///
+    async fn delete_content_type(
+        &self,
+        producer_id: Uuid,
+        content_type: input::ContentTypeName,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Creates validator of the producer content types
+    ///
+    /// ### Returns
+    /// validator with schemas of content types that have schema attached
+    ///
+    async fn content_validator(
+        &self,
+        producer_id: Uuid,
+        content_types: Vec<String>,
+    ) -> Result<ContentValidator, Error>;
+}
@@ -0,0 +1,252 @@
+use super::{compile_schema, ContentTypesService, ContentValidator};
+use crate::{
+    dto::{input, output},
+    error::Error,
+    repository::{self, ContentTypesRepository},
+};
+use axum::async_trait;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Maximum length of the content type
+///
+const MAX_CONTENT_TYPE_LEN: usize = 128;
+
+///
+/// Maximum length of the serialized JSON Schema (in bytes)
+///
+const MAX_SCHEMA_LEN: usize = 64 * 1024;
+
+pub struct ContentTypesServiceImpl {
+    repository: Arc<dyn ContentTypesRepository>,
+}
+
+impl ContentTypesServiceImpl {
+    pub fn new(repository: Arc<dyn ContentTypesRepository>) -> Self {
+        Self { repository }
+    }
+
+    fn validate_content_type(content_type: &str) -> Result<(), Error> {
+        if content_type.is_empty() {
+            return Err(Error::Validation("content_type cannot be empty"));
+        }
+        if content_type.len() > MAX_CONTENT_TYPE_LEN {
+            return Err(Error::Validation("content_type too long"));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ContentTypesService for ContentTypesServiceImpl {
+    async fn put_content_type(
+        &self,
+        producer_id: Uuid,
+        content_type: input::ContentType,
+    ) -> Result<(), Error> {
+        tracing::info!(
+            content_type = content_type.content_type,
+            "saving content type"
+        );
+        tracing::trace!(?content_type);
+
+        Self::validate_content_type(&content_type.content_type)?;
+        let schema = content_type.schema.to_string();
+        if schema.len() > MAX_SCHEMA_LEN {
+            return Err(Error::Validation("schema too large"));
+        }
+        compile_schema(&content_type.schema)?;
+
+        self.repository
+            .upsert(
+                producer_id,
+                content_type.content_type,
+                schema,
+                OffsetDateTime::now_utc(),
+            )
+            .await?;
+
+        tracing::info!("saved content type");
+
+        Ok(())
+    }
+
+    async fn find_content_types(
+        &self,
+        producer_id: Uuid,
+    ) -> Result<Vec<output::ContentType>, Error> {
+        tracing::info!("finding content types");
+
+        let content_types = self.repository.find_many(producer_id).await?;
+
+        tracing::info!(count = content_types.len(), "found content types");
+
+        Ok(content_types
+            .into_iter()
+            .map(output::ContentType::from)
+            .collect())
+    }
+
+    async fn delete_content_type(
+        &self,
+        producer_id: Uuid,
+        content_type: input::ContentTypeName,
+    ) -> Result<(), Error> {
+        tracing::info!(
+            content_type = content_type.content_type,
+            "deleting content type"
+        );
+
+        self.repository
+            .delete(producer_id, content_type.content_type)
+            .await
+            .map_err(|err| match err {
+                repository::Error::NoDocumentUpdated => Error::ContentTypeNotExist,
+                err => Error::Database(err),
+            })?;
+
+        tracing::info!("deleted content type");
+
+        Ok(())
+    }
+
+    async fn content_validator(
+        &self,
+        producer_id: Uuid,
+        content_types: Vec<String>,
+    ) -> Result<ContentValidator, Error> {
+        if content_types.is_empty() {
+            return Ok(ContentValidator::default());
+        }
+
+        let content_types = self
+            .repository
+            .find_many_by_content_types(producer_id, content_types)
+            .await?;
+
+        ContentValidator::new(content_types)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use repository::MockContentTypesRepository;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn put_content_type_ok() {
+        let mut repository = MockContentTypesRepository::new();
+        repository
+            .expect_upsert()
+            .withf(|_, content_type, schema, _| {
+                content_type == "order" && schema == r#"{"type":"object"}"#
+            })
+            .once()
+            .returning(|_, _, _, _| Ok(()));
+        let service = ContentTypesServiceImpl::new(Arc::new(repository));
+
+        let put_result = service
+            .put_content_type(
+                Uuid::new_v4(),
+                input::ContentType {
+                    content_type: "order".to_string(),
+                    schema: json!({ "type": "object" }),
+                },
+            )
+            .await;
+
+        assert!(put_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn put_content_type_validation_content_type_empty() {
+        let mut repository = MockContentTypesRepository::new();
+        repository.expect_upsert().never();
+        let service = ContentTypesServiceImpl::new(Arc::new(repository));
+
+        let put_result = service
+            .put_content_type(
+                Uuid::new_v4(),
+                input::ContentType {
+                    content_type: "".to_string(),
+                    schema: json!({ "type": "object" }),
+                },
+            )
+            .await;
+
+        assert!(matches!(put_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn put_content_type_validation_schema_invalid() {
+        let mut repository = MockContentTypesRepository::new();
+        repository.expect_upsert().never();
+        let service = ContentTypesServiceImpl::new(Arc::new(repository));
+
+        let put_result = service
+            .put_content_type(
+                Uuid::new_v4(),
+                input::ContentType {
+                    content_type: "order".to_string(),
+                    schema: json!({ "type": 5 }),
+                },
+            )
+            .await;
+
+        assert!(matches!(put_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn put_content_type_validation_schema_too_large() {
+        let mut repository = MockContentTypesRepository::new();
+        repository.expect_upsert().never();
+        let service = ContentTypesServiceImpl::new(Arc::new(repository));
+
+        let put_result = service
+            .put_content_type(
+                Uuid::new_v4(),
+                input::ContentType {
+                    content_type: "order".to_string(),
+                    schema: json!({ "description": "a".repeat(MAX_SCHEMA_LEN) }),
+                },
+            )
+            .await;
+
+        assert!(matches!(put_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn delete_content_type_not_exist() {
+        let mut repository = MockContentTypesRepository::new();
+        repository
+            .expect_delete()
+            .returning(|_, _| Err(repository::Error::NoDocumentUpdated));
+        let service = ContentTypesServiceImpl::new(Arc::new(repository));
+
+        let delete_result = service
+            .delete_content_type(
+                Uuid::new_v4(),
+                input::ContentTypeName {
+                    content_type: "order".to_string(),
+                },
+            )
+            .await;
+
+        assert!(matches!(delete_result, Err(Error::ContentTypeNotExist)));
+    }
+
+    #[tokio::test]
+    async fn content_validator_without_content_types() {
+        let mut repository = MockContentTypesRepository::new();
+        repository.expect_find_many_by_content_types().never();
+        let service = ContentTypesServiceImpl::new(Arc::new(repository));
+
+        let content_validator = service.content_validator(Uuid::new_v4(), vec![]).await;
+
+        assert!(content_validator.is_ok());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{error::Error, repository};
+use jsonschema::JSONSchema;
+use std::collections::HashMap;
+
+///
+/// Validates content of notifications against JSON Schemas registered for their content types
+///
+#[derive(Default)]
+pub struct ContentValidator {
+    schemas: HashMap<String, JSONSchema>,
+}
+
+impl ContentValidator {
+    ///
+    /// Compiles schemas of the content types
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when any schema is not valid JSON Schema
+    ///
+    pub fn new(content_types: Vec<repository::ContentType>) -> Result<Self, Error> {
+        let schemas = content_types
+            .into_iter()
+            .map(|content_type| {
+                let schema = serde_json::from_str(&content_type.schema)
+                    .map_err(|_| Error::Validation("schema is not valid JSON"))?;
+
+                Ok((content_type.content_type, compile_schema(&schema)?))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self { schemas })
+    }
+
+    ///
+    /// Validates content against schema of the content type.
+    /// Content of content types without schema is not validated
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when content is not valid JSON or does not match the schema
+    ///
+    pub fn validate(&self, content_type: &str, content: &[u8]) -> Result<(), Error> {
+        let Some(schema) = self.schemas.get(content_type) else {
+            return Ok(());
+        };
+
+        let content = serde_json::from_slice::<serde_json::Value>(content)
+            .map_err(|_| Error::Validation("content is not valid JSON"))?;
+        if !schema.is_valid(&content) {
+            return Err(Error::Validation(
+                "content does not match content type schema",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// ### Errors
+/// - [Error::Validation] when schema is not valid JSON Schema
+///
+pub fn compile_schema(schema: &serde_json::Value) -> Result<JSONSchema, Error> {
+    JSONSchema::compile(schema).map_err(|_| Error::Validation("schema is not valid JSON Schema"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use time::OffsetDateTime;
+
+    fn content_validator() -> ContentValidator {
+        ContentValidator::new(vec![repository::ContentType {
+            content_type: "order".to_string(),
+            schema: r#"{
+                "type": "object",
+                "properties": { "id": { "type": "integer" } },
+                "required": ["id"]
+            }"#
+            .to_string(),
+            updated_at: OffsetDateTime::now_utc(),
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn validate_content_matches_schema() {
+        let validation_result = content_validator().validate("order", br#"{"id":1}"#);
+
+        assert!(validation_result.is_ok());
+    }
+
+    #[test]
+    fn validate_content_does_not_match_schema() {
+        let validation_result = content_validator().validate("order", br#"{"id":"1"}"#);
+
+        assert!(matches!(validation_result, Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn validate_content_not_json() {
+        let validation_result = content_validator().validate("order", b"id=1");
+
+        assert!(matches!(validation_result, Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn validate_content_type_without_schema() {
+        let validation_result = content_validator().validate("utf-8", b"id=1");
+
+        assert!(validation_result.is_ok());
+    }
+
+    #[test]
+    fn compile_schema_invalid() {
+        let compile_result = compile_schema(&serde_json::json!({ "type": "not a type" }));
+
+        assert!(matches!(compile_result, Err(Error::Validation(_))));
+    }
+}
@@ -0,0 +1,7 @@
+mod content_types_service;
+mod content_types_service_impl;
+mod content_validator;
+
+pub use content_types_service::*;
+pub use content_types_service_impl::*;
+pub use content_validator::*;
@@ -0,0 +1,75 @@
+use crate::{
+    dto::{input, output},
+    error::Error,
+};
+use axum::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GroupsService: Send + Sync {
+    ///
+    /// Creates new group without members
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - name is empty, too long or contains invalid characters
+    /// - [Error::GroupAlreadyExist] when group with the name already exists
+    ///
+    async fn create_group(&self, group: input::Group) -> Result<(), Error>;
+
+    ///
+    /// Finds all groups
+    ///
+    /// ### Returns
+    /// Vec of groups sorted ascending by name
+    ///
+    async fn find_groups(&self) -> Result<Vec<output::Group>, Error>;
+
+    ///
+    /// Finds members of the group
+    ///
+    /// ### Errors
+    /// - [Error::GroupNotExist] when group does not exist
+    ///
+    async fn find_group_members(&self, name: String) -> Result<output::GroupMembers, Error>;
+
+    ///
+    /// Deletes group together with its members.
+    /// Notifications targeting the group are no longer delivered
+    /// to its former members unless they are targeted otherwise
+    ///
+    /// ### Errors
+    /// - [Error::GroupNotExist] when group does not exist
+    ///
+    async fn delete_group(&self, name: String) -> Result<(), Error>;
+
+    ///
+    /// Adds users to the group.
+    /// Users that already are members of the group are skipped
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - user_ids are empty or there are too many of them
+    /// - [Error::GroupNotExist] when group does not exist
+    ///
+    async fn add_group_members(
+        &self,
+        name: String,
+        members: input::GroupMembers,
+    ) -> Result<(), Error>;
+
+    ///
+    /// Removes users from the group.
+    /// Users that are not members of the group are skipped
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - user_ids are empty or there are too many of them
+    /// - [Error::GroupNotExist] when group does not exist
+    ///
+    async fn remove_group_members(
+        &self,
+        name: String,
+        members: input::GroupMembers,
+    ) -> Result<(), Error>;
+}
@@ -0,0 +1,351 @@
+use super::GroupsService;
+use crate::{
+    dto::{input, output},
+    error::Error,
+    repository::{self, GroupsRepository},
+};
+use axum::async_trait;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+///
+/// Maximum length of the group name
+///
+const MAX_GROUP_NAME_LEN: usize = 64;
+
+///
+/// Maximum number of users that can be added or removed in a single request
+///
+const MAX_MEMBERS_USER_IDS: usize = 1000;
+
+pub struct GroupsServiceImpl {
+    repository: Arc<dyn GroupsRepository>,
+}
+
+impl GroupsServiceImpl {
+    pub fn new(repository: Arc<dyn GroupsRepository>) -> Self {
+        Self { repository }
+    }
+
+    fn validate_group(group: &input::Group) -> Result<(), Error> {
+        if group.name.is_empty() {
+            return Err(Error::Validation("name cannot be empty"));
+        }
+        if group.name.len() > MAX_GROUP_NAME_LEN {
+            return Err(Error::Validation("name too long"));
+        }
+        // Name is used as path segment, so it's limited to URL safe characters
+        let is_valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+        if !group.name.chars().all(is_valid_char) {
+            return Err(Error::Validation("name contains invalid characters"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_members(members: &input::GroupMembers) -> Result<(), Error> {
+        if members.user_ids.is_empty() {
+            return Err(Error::Validation("user_ids cannot be empty"));
+        }
+        if members.user_ids.len() > MAX_MEMBERS_USER_IDS {
+            return Err(Error::Validation("too many user_ids"));
+        }
+
+        Ok(())
+    }
+
+    fn map_group_not_exist(err: repository::Error) -> Error {
+        match err {
+            repository::Error::NoDocumentUpdated => Error::GroupNotExist,
+            err => Error::Database(err),
+        }
+    }
+}
+
+#[async_trait]
+impl GroupsService for GroupsServiceImpl {
+    async fn create_group(&self, group: input::Group) -> Result<(), Error> {
+        tracing::info!(name = group.name, "creating group");
+
+        Self::validate_group(&group)?;
+
+        self.repository
+            .insert(group.name, OffsetDateTime::now_utc())
+            .await
+            .map_err(|err| match err {
+                repository::Error::InsertUniqueViolation => Error::GroupAlreadyExist,
+                err => Error::Database(err),
+            })?;
+
+        tracing::info!("created group");
+
+        Ok(())
+    }
+
+    async fn find_groups(&self) -> Result<Vec<output::Group>, Error> {
+        tracing::info!("finding groups");
+
+        let groups = self.repository.find_many().await?;
+
+        tracing::info!(count = groups.len(), "found groups");
+
+        Ok(groups.into_iter().map(output::Group::from).collect())
+    }
+
+    async fn find_group_members(&self, name: String) -> Result<output::GroupMembers, Error> {
+        tracing::info!(name, "finding group members");
+
+        let user_ids = self
+            .repository
+            .find_members(name)
+            .await?
+            .ok_or(Error::GroupNotExist)?;
+
+        tracing::info!(count = user_ids.len(), "found group members");
+
+        Ok(output::GroupMembers { user_ids })
+    }
+
+    async fn delete_group(&self, name: String) -> Result<(), Error> {
+        tracing::info!(name, "deleting group");
+
+        self.repository
+            .delete(name)
+            .await
+            .map_err(Self::map_group_not_exist)?;
+
+        tracing::info!("deleted group");
+
+        Ok(())
+    }
+
+    async fn add_group_members(
+        &self,
+        name: String,
+        members: input::GroupMembers,
+    ) -> Result<(), Error> {
+        tracing::info!(name, "adding group members");
+        tracing::trace!(?members);
+
+        Self::validate_members(&members)?;
+
+        let count = self
+            .repository
+            .insert_members(name, members.user_ids)
+            .await
+            .map_err(Self::map_group_not_exist)?;
+
+        tracing::info!(count, "added group members");
+
+        Ok(())
+    }
+
+    async fn remove_group_members(
+        &self,
+        name: String,
+        members: input::GroupMembers,
+    ) -> Result<(), Error> {
+        tracing::info!(name, "removing group members");
+        tracing::trace!(?members);
+
+        Self::validate_members(&members)?;
+
+        let count = self
+            .repository
+            .delete_members(name, members.user_ids)
+            .await
+            .map_err(Self::map_group_not_exist)?;
+
+        tracing::info!(count, "removed group members");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use repository::MockGroupsRepository;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn create_group_ok() {
+        let mut repository = MockGroupsRepository::new();
+        repository
+            .expect_insert()
+            .withf(|name, _| name == "admins")
+            .once()
+            .returning(|_, _| Ok(()));
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_group(input::Group {
+                name: "admins".to_string(),
+            })
+            .await;
+
+        assert!(create_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn create_group_validation_name_empty() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_insert().never();
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_group(input::Group {
+                name: "".to_string(),
+            })
+            .await;
+
+        assert!(matches!(create_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_group_validation_name_too_long() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_insert().never();
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_group(input::Group {
+                name: "a".repeat(MAX_GROUP_NAME_LEN + 1),
+            })
+            .await;
+
+        assert!(matches!(create_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_group_validation_name_invalid_characters() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_insert().never();
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_group(input::Group {
+                name: "admins/all".to_string(),
+            })
+            .await;
+
+        assert!(matches!(create_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_group_already_exist() {
+        let mut repository = MockGroupsRepository::new();
+        repository
+            .expect_insert()
+            .returning(|_, _| Err(repository::Error::InsertUniqueViolation));
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_group(input::Group {
+                name: "admins".to_string(),
+            })
+            .await;
+
+        assert!(matches!(create_result, Err(Error::GroupAlreadyExist)));
+    }
+
+    #[tokio::test]
+    async fn find_group_members_not_exist() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_find_members().returning(|_| Ok(None));
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let find_result = service.find_group_members("admins".to_string()).await;
+
+        assert!(matches!(find_result, Err(Error::GroupNotExist)));
+    }
+
+    #[tokio::test]
+    async fn delete_group_not_exist() {
+        let mut repository = MockGroupsRepository::new();
+        repository
+            .expect_delete()
+            .returning(|_| Err(repository::Error::NoDocumentUpdated));
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let delete_result = service.delete_group("admins".to_string()).await;
+
+        assert!(matches!(delete_result, Err(Error::GroupNotExist)));
+    }
+
+    #[tokio::test]
+    async fn add_group_members_validation_user_ids_empty() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_insert_members().never();
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let add_result = service
+            .add_group_members(
+                "admins".to_string(),
+                input::GroupMembers { user_ids: vec![] },
+            )
+            .await;
+
+        assert!(matches!(add_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn add_group_members_validation_too_many_user_ids() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_insert_members().never();
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let add_result = service
+            .add_group_members(
+                "admins".to_string(),
+                input::GroupMembers {
+                    user_ids: vec![Uuid::from_u128(1); MAX_MEMBERS_USER_IDS + 1],
+                },
+            )
+            .await;
+
+        assert!(matches!(add_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn add_group_members_group_not_exist() {
+        let mut repository = MockGroupsRepository::new();
+        repository
+            .expect_insert_members()
+            .returning(|_, _| Err(repository::Error::NoDocumentUpdated));
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let add_result = service
+            .add_group_members(
+                "admins".to_string(),
+                input::GroupMembers {
+                    user_ids: vec![Uuid::from_u128(1)],
+                },
+            )
+            .await;
+
+        assert!(matches!(add_result, Err(Error::GroupNotExist)));
+    }
+
+    #[tokio::test]
+    async fn remove_group_members_database_error() {
+        let mut repository = MockGroupsRepository::new();
+        repository.expect_delete_members().returning(|_, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let service = GroupsServiceImpl::new(Arc::new(repository));
+
+        let remove_result = service
+            .remove_group_members(
+                "admins".to_string(),
+                input::GroupMembers {
+                    user_ids: vec![Uuid::from_u128(1)],
+                },
+            )
+            .await;
+
+        assert!(matches!(remove_result, Err(Error::Database(_))));
+    }
+}
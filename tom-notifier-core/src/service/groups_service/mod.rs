@@ -0,0 +1,5 @@
+mod groups_service;
+mod groups_service_impl;
+
+pub use groups_service::*;
+pub use groups_service_impl::*;
@@ -1,3 +1,13 @@
+pub mod attachments_service;
 pub mod confirmations_consumer_service;
+pub mod content_types_service;
+pub mod groups_service;
 pub mod notifications_producer_service;
 pub mod notifications_service;
+pub mod outbox_relay_service;
+pub mod preferences_service;
+pub mod retention_service;
+pub mod subscriptions_service;
+pub mod templates_service;
+pub mod webhook_relay_service;
+pub mod webhooks_service;
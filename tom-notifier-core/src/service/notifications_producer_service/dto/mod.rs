@@ -1,3 +1,7 @@
+mod new_notification;
 mod notifications_producer_service_config;
+mod updated_notification;
 
+pub use new_notification::*;
 pub use notifications_producer_service_config::*;
+pub use updated_notification::*;
@@ -0,0 +1,26 @@
+use crate::dto::{input, output};
+use bson::oid::ObjectId;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Created notification pushed to its recipients
+///
+#[derive(Debug)]
+pub struct NewNotification {
+    pub user_ids: Vec<Uuid>,
+    pub topic: Option<String>,
+
+    ///
+    /// Recipients that are not pushed the notification
+    ///
+    pub excluded_user_ids: Vec<Uuid>,
+    pub id: ObjectId,
+    pub timestamp: OffsetDateTime,
+    pub created_by: Uuid,
+    pub seen: bool,
+    pub content_type: String,
+    pub content: Vec<u8>,
+    pub priority: output::NotificationPriority,
+    pub localized_contents: Vec<input::LocalizedContent>,
+}
@@ -1,3 +1,4 @@
 pub struct NotificationsProducerServiceConfig {
     pub exchange_name: String,
+    pub compression_threshold: Option<usize>,
 }
@@ -0,0 +1,20 @@
+use crate::dto::input;
+use bson::oid::ObjectId;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Changed properties of the notification, so either seen or content_type
+/// with content and localized_contents
+///
+#[derive(Debug)]
+pub struct UpdatedNotification {
+    pub user_ids: Vec<Uuid>,
+    pub topic: Option<String>,
+    pub id: ObjectId,
+    pub seen: Option<bool>,
+    pub content_type: Option<String>,
+    pub content: Option<Vec<u8>>,
+    pub localized_contents: Vec<input::LocalizedContent>,
+    pub timestamp: OffsetDateTime,
+}
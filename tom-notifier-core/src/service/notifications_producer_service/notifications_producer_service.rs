@@ -1,32 +1,46 @@
+use super::{NewNotification, UpdatedNotification};
 use axum::async_trait;
 use bson::oid::ObjectId;
 use time::OffsetDateTime;
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
 ///
-/// Service used to propagate notification state change to any intrested party
+/// Service used to propagate notification state change to any intrested party.
+///
+/// Every function returns receiver that completes when message
+/// is confirmed by the broker
 ///
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait NotificationsProducerService: Send + Sync {
-    async fn send_new(
-        &self,
-        user_ids: Vec<Uuid>,
-        id: ObjectId,
-        timestamp: OffsetDateTime,
-        created_by: Uuid,
-        seen: bool,
-        content_type: String,
-        content: Vec<u8>,
-    );
+    ///
+    /// Users in excluded_user_ids are not pushed the notification
+    ///
+    async fn send_new(&self, notification: NewNotification) -> oneshot::Receiver<()>;
 
-    async fn send_updated(
+    ///
+    /// Sends only changed properties of the notification
+    ///
+    async fn send_updated(&self, notification: UpdatedNotification) -> oneshot::Receiver<()>;
+
+    ///
+    /// Sends a single message of the same seen change of all notifications
+    /// in ids, made by the user
+    ///
+    async fn send_updated_many_seen(
         &self,
         user_id: Uuid,
-        id: ObjectId,
+        ids: Vec<ObjectId>,
         seen: bool,
         timestamp: OffsetDateTime,
-    );
+    ) -> oneshot::Receiver<()>;
 
-    async fn send_deleted(&self, user_id: Uuid, id: ObjectId, timestamp: OffsetDateTime);
+    async fn send_deleted(
+        &self,
+        user_ids: Vec<Uuid>,
+        topic: Option<String>,
+        id: ObjectId,
+        timestamp: OffsetDateTime,
+    ) -> oneshot::Receiver<()>;
 }
@@ -1,4 +1,7 @@
-use super::{NotificationsProducerService, NotificationsProducerServiceConfig};
+use super::{
+    NewNotification, NotificationsProducerService, NotificationsProducerServiceConfig,
+    UpdatedNotification,
+};
 use crate::dto::output;
 use amqprs::{
     channel::{ExchangeDeclareArguments, ExchangeType},
@@ -10,10 +13,14 @@ use prost::Message;
 use prost_types::Timestamp;
 use rabbitmq_client::{connection::RabbitmqConnection, producer::RabbitmqProducer};
 use time::OffsetDateTime;
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
+const COMPRESSION_LEVEL: i32 = 3;
+
 pub struct NotificationsProducerServiceImpl {
     producer: RabbitmqProducer,
+    compression_threshold: Option<usize>,
 }
 
 impl NotificationsProducerServiceImpl {
@@ -25,71 +32,197 @@ impl NotificationsProducerServiceImpl {
             ExchangeDeclareArguments::of_type(&config.exchange_name, ExchangeType::Direct);
         let producer = RabbitmqProducer::new(rabbitmq_connection, exchange_declare_args).await?;
 
-        Ok(Self { producer })
+        Ok(Self {
+            producer,
+            compression_threshold: config.compression_threshold,
+        })
     }
 
     pub async fn close(self) {
         self.producer.close().await;
     }
 
-    fn send(&self, routing_key: &'static str, encoded_message: Vec<u8>) {
+    fn send(&self, routing_key: &'static str, encoded_message: Vec<u8>) -> oneshot::Receiver<()> {
         let basic_properties = BasicProperties::default().with_persistence(true).finish();
         self.producer
-            .send(routing_key.to_string(), basic_properties, encoded_message);
+            .send_confirmed(routing_key.to_string(), basic_properties, encoded_message)
+    }
+
+    ///
+    /// Compresses content and localized contents of the notification
+    /// when their total length reaches compression threshold
+    ///
+    /// ### Returns
+    ///
+    /// whether the contents were compressed
+    ///
+    fn compress_contents(&self, notification: &mut output::NotificationProtobuf) -> bool {
+        let Some(compression_threshold) = self.compression_threshold else {
+            return false;
+        };
+
+        let contents_len = notification.content.as_ref().map_or(0, Vec::len)
+            + notification
+                .localized_contents
+                .iter()
+                .map(|localized_content| localized_content.content.len())
+                .sum::<usize>();
+        if contents_len == 0 || contents_len < compression_threshold {
+            return false;
+        }
+
+        let compress = |content: &Vec<u8>| zstd::bulk::compress(content, COMPRESSION_LEVEL);
+        let compressed = notification
+            .content
+            .as_ref()
+            .map(compress)
+            .transpose()
+            .and_then(|content| {
+                let localized_contents = notification
+                    .localized_contents
+                    .iter()
+                    .map(|localized_content| compress(&localized_content.content))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((content, localized_contents))
+            });
+
+        match compressed {
+            Ok((content, localized_contents)) => {
+                notification.content = content;
+                for (localized_content, compressed) in
+                    std::iter::zip(&mut notification.localized_contents, localized_contents)
+                {
+                    localized_content.content = compressed;
+                }
+                true
+            }
+            Err(err) => {
+                tracing::warn!(%err, "failed to compress notification content");
+                false
+            }
+        }
     }
 }
 
 #[async_trait]
 impl NotificationsProducerService for NotificationsProducerServiceImpl {
-    async fn send_new(
-        &self,
-        user_ids: Vec<Uuid>,
-        id: ObjectId,
-        timestamp: OffsetDateTime,
-        created_by: Uuid,
-        seen: bool,
-        content_type: String,
-        content: Vec<u8>,
-    ) {
+    async fn send_new(&self, notification: NewNotification) -> oneshot::Receiver<()> {
+        let NewNotification {
+            user_ids,
+            topic,
+            excluded_user_ids,
+            id,
+            timestamp,
+            created_by,
+            seen,
+            content_type,
+            content,
+            priority,
+            localized_contents,
+        } = notification;
         let id_str = id.to_hex();
 
         tracing::info!(id = id_str, %timestamp, "producing NEW notification");
 
+        let mut notification = output::NotificationProtobuf {
+            id: id_str,
+            status: output::NotificationStatusProtobuf::New.into(),
+            timestamp: Some(Timestamp {
+                seconds: timestamp.unix_timestamp(),
+                nanos: timestamp.nanosecond() as i32,
+            }),
+            created_by: Some(created_by.to_string()),
+            seen: Some(seen),
+            content_type: Some(content_type),
+            content: Some(content),
+            priority: Some(output::NotificationPriorityProtobuf::from(priority).into()),
+            localized_contents: localized_contents
+                .into_iter()
+                .map(output::LocalizedContentProtobuf::from)
+                .collect(),
+            ids: vec![],
+        };
+        let content_compressed = self.compress_contents(&mut notification);
+
         let message = output::RabbitmqNotificationProtobuf {
             user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
-            notification: Some(output::NotificationProtobuf {
-                id: id_str,
-                status: output::NotificationStatusProtobuf::New.into(),
-                timestamp: Some(Timestamp {
-                    seconds: timestamp.unix_timestamp(),
-                    nanos: timestamp.nanosecond() as i32,
-                }),
-                created_by: Some(created_by.to_string()),
-                seen: Some(seen),
-                content_type: Some(content_type),
-                content: Some(content),
+            topic,
+            excluded_user_ids: excluded_user_ids
+                .into_iter()
+                .map(|uuid| uuid.to_string())
+                .collect(),
+            notification: Some(notification),
+            content_compressed,
+        };
+        let encoded_message = message.encode_to_vec();
+
+        self.send("NEW", encoded_message)
+    }
+
+    async fn send_updated(&self, notification: UpdatedNotification) -> oneshot::Receiver<()> {
+        let UpdatedNotification {
+            user_ids,
+            topic,
+            id,
+            seen,
+            content_type,
+            content,
+            localized_contents,
+            timestamp,
+        } = notification;
+        let id_str = id.to_hex();
+
+        tracing::info!(id = id_str, %timestamp, "producing UPDATED notification");
+
+        let mut notification = output::NotificationProtobuf {
+            id: id_str,
+            status: output::NotificationStatusProtobuf::Updated.into(),
+            timestamp: Some(Timestamp {
+                seconds: timestamp.unix_timestamp(),
+                nanos: timestamp.nanosecond() as i32,
             }),
+            created_by: None,
+            seen,
+            content_type,
+            content,
+            priority: None,
+            localized_contents: localized_contents
+                .into_iter()
+                .map(output::LocalizedContentProtobuf::from)
+                .collect(),
+            ids: vec![],
+        };
+        let content_compressed = self.compress_contents(&mut notification);
+
+        let message = output::RabbitmqNotificationProtobuf {
+            user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+            topic,
+            excluded_user_ids: vec![],
+            notification: Some(notification),
+            content_compressed,
         };
         let encoded_message = message.encode_to_vec();
 
-        self.send("NEW", encoded_message);
+        self.send("UPDATED", encoded_message)
     }
 
-    async fn send_updated(
+    async fn send_updated_many_seen(
         &self,
         user_id: Uuid,
-        id: ObjectId,
+        ids: Vec<ObjectId>,
         seen: bool,
         timestamp: OffsetDateTime,
-    ) {
-        let id_str = id.to_hex();
+    ) -> oneshot::Receiver<()> {
+        let count = ids.len();
 
-        tracing::info!(id = id_str, %timestamp, "producing UPDATED notification");
+        tracing::info!(count, %timestamp, "producing UPDATED notifications");
 
         let message = output::RabbitmqNotificationProtobuf {
             user_ids: vec![user_id.to_string()],
+            topic: None,
+            excluded_user_ids: vec![],
             notification: Some(output::NotificationProtobuf {
-                id: id_str,
+                id: String::new(),
                 status: output::NotificationStatusProtobuf::Updated.into(),
                 timestamp: Some(Timestamp {
                     seconds: timestamp.unix_timestamp(),
@@ -99,20 +232,32 @@ impl NotificationsProducerService for NotificationsProducerServiceImpl {
                 seen: Some(seen),
                 content_type: None,
                 content: None,
+                priority: None,
+                localized_contents: vec![],
+                ids: ids.into_iter().map(ObjectId::to_hex).collect(),
             }),
+            content_compressed: false,
         };
         let encoded_message = message.encode_to_vec();
 
-        self.send("UPDATED", encoded_message);
+        self.send("UPDATED", encoded_message)
     }
 
-    async fn send_deleted(&self, user_id: Uuid, id: ObjectId, timestamp: OffsetDateTime) {
+    async fn send_deleted(
+        &self,
+        user_ids: Vec<Uuid>,
+        topic: Option<String>,
+        id: ObjectId,
+        timestamp: OffsetDateTime,
+    ) -> oneshot::Receiver<()> {
         let id_str = id.to_hex();
 
         tracing::info!(id = id_str, %timestamp, "producing DELETED notification");
 
         let message = output::RabbitmqNotificationProtobuf {
-            user_ids: vec![user_id.to_string()],
+            user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+            topic,
+            excluded_user_ids: vec![],
             notification: Some(output::NotificationProtobuf {
                 id: id_str,
                 status: output::NotificationStatusProtobuf::Deleted.into(),
@@ -124,10 +269,14 @@ impl NotificationsProducerService for NotificationsProducerServiceImpl {
                 seen: None,
                 content_type: None,
                 content: None,
+                priority: None,
+                localized_contents: vec![],
+                ids: vec![],
             }),
+            content_compressed: false,
         };
         let encoded_message = message.encode_to_vec();
 
-        self.send("DELETED", encoded_message);
+        self.send("DELETED", encoded_message)
     }
 }
@@ -18,8 +18,12 @@ pub trait NotificationsService: Send + Sync {
     /// ### Errors
     /// - [Error::Validation] when
     ///     - invalidate_at already passed
+    ///     - there are too many groups or any group is empty
+    ///     - topic is empty or too long
+    ///     - there are too many localized contents or any locale is invalid or duplicated
+    ///     - content or any localized content does not match JSON Schema of its content type
     /// - [Error::ValidationNotificationTooLarge] when
-    ///     - notification content is too long
+    ///     - notification content or any localized content is too long
     /// - [Error::NotificationAlreadySaved] when producer
     ///    already created notification with producer_notification_id
     ///
@@ -29,37 +33,75 @@ pub trait NotificationsService: Send + Sync {
         notification: input::Notification,
     ) -> Result<output::NotificationId, Error>;
 
+    ///
+    /// Save many notifications of the producer at once
+    ///
+    /// ### Returns
+    /// result of every notification in the same order as notifications
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - notifications are empty or there are too many of them
+    ///     - invalidate_at of any notification already passed
+    ///     - content of any notification does not match JSON Schema of its content type
+    /// - [Error::ValidationNotificationTooLarge] when
+    ///     - content of any notification is too long
+    /// - [Error::NotificationAlreadySaved] when
+    ///     - conflicting notification was saved concurrently
+    ///
+    async fn save_notifications(
+        &self,
+        producer_id: Uuid,
+        notifications: Vec<input::Notification>,
+    ) -> Result<Vec<output::NotificationSaveResult>, Error>;
+
     ///
     /// Find all undelivered notifications that belong to the user
     /// and mark them as delivered.
     ///
     /// ### Returns
-    /// Vec of undelivered notifications
+    /// Vec of undelivered notifications with content best matching locales
     ///
     async fn find_undelivered_notifications(
         &self,
         user_id: Uuid,
+        locales: input::Locales,
     ) -> Result<Vec<output::Notification>, Error>;
 
+    ///
+    /// Count undelivered notifications of the user and delivered
+    /// notifications of the user (all of them and only unseen ones).
+    /// Notifications are not marked as delivered
+    ///
+    async fn count_notifications(&self, user_id: Uuid)
+        -> Result<output::NotificationsCount, Error>;
+
     ///
     /// Find all delivered notifications that belong to the user
     /// and match filters
     ///
     /// ### Returns
-    /// Vec of delivered notifications
+    /// page of delivered notifications with content best matching locales
+    /// and cursor pointing at the next page
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - both page_idx and cursor are set
+    ///     - created_at or delivered_at range is empty
     ///
     async fn find_delivered_notifications(
         &self,
         user_id: Uuid,
         pagination: input::Pagination,
         filters: input::NotificationFilters,
-    ) -> Result<Vec<output::Notification>, Error>;
+        locales: input::Locales,
+    ) -> Result<output::NotificationsPage, Error>;
 
     ///
     /// Find delivered notification
     ///
     /// ### Returns
-    /// notification
+    /// notification with content best matching locales
     ///
     /// ### Errors
     /// - [Error::NotificationNotExist] when
@@ -72,6 +114,7 @@ pub trait NotificationsService: Send + Sync {
         &self,
         id: ObjectId,
         user_id: Uuid,
+        locales: input::Locales,
     ) -> Result<output::Notification, Error>;
 
     ///
@@ -86,6 +129,90 @@ pub trait NotificationsService: Send + Sync {
     ///
     async fn delete_notification(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error>;
 
+    ///
+    /// Delete many delivered notifications selected by ids and/or filters
+    ///
+    /// ### Returns
+    /// number of deleted notifications
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - neither ids nor filters are set
+    ///     - ids are empty or there are too many of them
+    ///     - created_at or delivered_at range is empty
+    ///
+    async fn delete_notifications(
+        &self,
+        user_id: Uuid,
+        selection: input::NotificationsSelection,
+    ) -> Result<output::NotificationsAffected, Error>;
+
+    ///
+    /// Retract notification from all of its recipients
+    ///
+    /// ### Errors
+    /// - [Error::NotificationNotExist] when
+    ///     - notification with id does not exist
+    ///     - notification was not produced by the producer
+    ///     - notification have already been retracted
+    ///
+    async fn retract_notification(&self, id: ObjectId, producer_id: Uuid) -> Result<(), Error>;
+
+    ///
+    /// Find delivery report of the notification: numbers of recipients that
+    /// confirmed it and that are still pending, with a page of confirmations
+    /// (when and whether they have seen or deleted it) and a page of pending
+    /// recipients of (uni/multi)cast notification
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when page_size is 0
+    /// - [Error::NotificationNotExist] when
+    ///     - notification with id does not exist
+    ///     - notification was not produced by the producer
+    ///
+    async fn find_delivery_report(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        pagination: input::DeliveryReportPagination,
+    ) -> Result<output::DeliveryReport, Error>;
+
+    ///
+    /// Find notifications of the producer that match filters,
+    /// including retracted ones
+    ///
+    /// ### Returns
+    /// page of notifications with numbers of recipients that confirmed,
+    /// have seen and deleted them and cursor pointing at the next page
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - both page_idx and cursor are set
+    ///     - created_at range is empty
+    ///
+    async fn find_produced_notifications(
+        &self,
+        producer_id: Uuid,
+        pagination: input::Pagination,
+        filters: input::ProducedNotificationFilters,
+    ) -> Result<output::ProducedNotificationsPage, Error>;
+
+    ///
+    /// Find notification of the producer by producer_notification_id
+    ///
+    /// ### Returns
+    /// notification with numbers of recipients that confirmed, have seen and deleted it
+    ///
+    /// ### Errors
+    /// - [Error::NotificationNotExist] when producer has not created
+    /// notification with producer_notification_id
+    ///
+    async fn find_produced_notification(
+        &self,
+        producer_id: Uuid,
+        producer_notification_id: i64,
+    ) -> Result<output::ProducedNotification, Error>;
+
     ///
     /// Update field invalidate_at of the notification
     ///
@@ -103,6 +230,26 @@ pub trait NotificationsService: Send + Sync {
         invalidate_at: input::NotificationInvalidateAt,
     ) -> Result<(), Error>;
 
+    ///
+    /// Replace content_type, content and localized contents of the notification
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - there are too many localized contents or any locale is invalid or duplicated
+    ///     - content or any localized content does not match JSON Schema of its content type
+    /// - [Error::ValidationNotificationTooLarge] when
+    ///     - notification content or any localized content is too long
+    /// - [Error::NotificationNotExist] when
+    ///     - notification with id does not exist
+    ///     - notification was not produced by the producer
+    ///
+    async fn update_notification_content(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        content: input::NotificationContent,
+    ) -> Result<(), Error>;
+
     ///
     /// Update field seen of the notification
     ///
@@ -119,4 +266,23 @@ pub trait NotificationsService: Send + Sync {
         user_id: Uuid,
         seen: input::NotificationSeen,
     ) -> Result<(), Error>;
+
+    ///
+    /// Update field seen of many delivered notifications
+    /// selected by ids and/or filters
+    ///
+    /// ### Returns
+    /// number of notifications whose seen value changed
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - neither ids nor filters are set
+    ///     - ids are empty or there are too many of them
+    ///     - created_at or delivered_at range is empty
+    ///
+    async fn update_notifications_seen(
+        &self,
+        user_id: Uuid,
+        seen: input::NotificationsSeen,
+    ) -> Result<output::NotificationsAffected, Error>;
 }
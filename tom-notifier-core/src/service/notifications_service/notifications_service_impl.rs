@@ -3,7 +3,12 @@ use crate::{
     dto::{input, output},
     error::Error,
     repository::{self, NotificationsRepository},
-    service::notifications_producer_service::NotificationsProducerService,
+    service::{
+        attachments_service::AttachmentsService,
+        content_types_service::{ContentTypesService, ContentValidator},
+        outbox_relay_service::OutboxRelayService,
+        subscriptions_service::MAX_TOPIC_LEN,
+    },
 };
 use axum::async_trait;
 use bson::oid::ObjectId;
@@ -11,28 +16,124 @@ use std::sync::Arc;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+///
+/// Maximum number of ids user can select in a single bulk request
+///
+const MAX_SELECTION_IDS: usize = 1000;
+
+///
+/// Maximum number of notifications producer can save in a single batch
+///
+const MAX_BATCH_NOTIFICATIONS: usize = 1000;
+
+///
+/// Maximum number of groups a single notification can target
+///
+const MAX_NOTIFICATION_GROUPS: usize = 100;
+
+///
+/// Maximum length of the notification collapse key
+///
+const MAX_COLLAPSE_KEY_LEN: usize = 128;
+
+///
+/// Maximum number of localized variants of the notification content
+///
+const MAX_LOCALIZED_CONTENTS: usize = 32;
+
+///
+/// Maximum length of the locale, long enough for any practical language tag
+///
+const MAX_LOCALE_LEN: usize = 35;
+
 pub struct NotificationsServiceImpl {
     config: NotificationsServiceConfig,
     repository: Arc<dyn NotificationsRepository>,
-    notifications_producer_service: Arc<dyn NotificationsProducerService>,
+    outbox_relay_service: Arc<dyn OutboxRelayService>,
+    content_types_service: Arc<dyn ContentTypesService>,
+    attachments_service: Arc<dyn AttachmentsService>,
 }
 
 impl NotificationsServiceImpl {
     pub fn new(
         config: NotificationsServiceConfig,
         repository: Arc<dyn NotificationsRepository>,
-        notifications_producer_service: Arc<dyn NotificationsProducerService>,
+        outbox_relay_service: Arc<dyn OutboxRelayService>,
+        content_types_service: Arc<dyn ContentTypesService>,
+        attachments_service: Arc<dyn AttachmentsService>,
     ) -> Self {
         Self {
             config,
             repository,
-            notifications_producer_service,
+            outbox_relay_service,
+            content_types_service,
+            attachments_service,
         }
     }
 
-    fn validate_save_notification(&self, notification: &input::Notification) -> Result<(), Error> {
+    ///
+    /// Creates validator of content types used by content and localized contents
+    ///
+    async fn content_validator<'a>(
+        &self,
+        producer_id: Uuid,
+        content_types: impl IntoIterator<Item = &'a String>,
+    ) -> Result<ContentValidator, Error> {
+        let mut content_types = content_types.into_iter().cloned().collect::<Vec<_>>();
+        content_types.sort_unstable();
+        content_types.dedup();
+
+        self.content_types_service
+            .content_validator(producer_id, content_types)
+            .await
+    }
+
+    ///
+    /// Content types of the content and of its localized variants
+    ///
+    fn content_types<'a>(
+        content_type: &'a String,
+        localized_contents: &'a [input::LocalizedContent],
+    ) -> impl Iterator<Item = &'a String> {
+        std::iter::once(content_type).chain(
+            localized_contents
+                .iter()
+                .map(|localized_content| &localized_content.content_type),
+        )
+    }
+
+    fn validate_save_notification(
+        &self,
+        content_validator: &ContentValidator,
+        notification: &input::Notification,
+    ) -> Result<(), Error> {
         Self::validate_invalidate_at_not_passed(&notification.invalidate_at)?;
+        Self::validate_deliver_at(&notification.deliver_at, &notification.invalidate_at)?;
+        Self::validate_groups(&notification.groups)?;
+        Self::validate_topic(&notification.topic)?;
+        Self::validate_collapse_key(&notification.collapse_key)?;
+        Self::validate_content_type(&notification.content_type)?;
         self.validate_content_not_too_long(&notification.content)?;
+        content_validator.validate(&notification.content_type, &notification.content)?;
+        self.validate_localized_contents(content_validator, &notification.localized_contents)?;
+
+        Ok(())
+    }
+
+    fn validate_save_notifications(
+        &self,
+        content_validator: &ContentValidator,
+        notifications: &[input::Notification],
+    ) -> Result<(), Error> {
+        if notifications.is_empty() {
+            return Err(Error::Validation("notifications cannot be empty"));
+        }
+        if notifications.len() > MAX_BATCH_NOTIFICATIONS {
+            return Err(Error::Validation("too many notifications"));
+        }
+        for notification in notifications {
+            self.validate_save_notification(content_validator, notification)?;
+        }
 
         Ok(())
     }
@@ -45,6 +146,71 @@ impl NotificationsServiceImpl {
         Ok(())
     }
 
+    fn validate_pagination(pagination: &input::Pagination) -> Result<(), Error> {
+        if pagination.page_size == 0 {
+            return Err(Error::Validation("page_size must be greater than 0"));
+        }
+        if pagination.page_idx.is_some() && pagination.cursor.is_some() {
+            return Err(Error::Validation(
+                "page_idx cannot be used together with cursor",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_filters(filters: &input::NotificationFilters) -> Result<(), Error> {
+        if let (Some(from), Some(to)) = (filters.created_at_from, filters.created_at_to) {
+            if from >= to {
+                return Err(Error::Validation(
+                    "created_at_from must be earlier than created_at_to",
+                ));
+            }
+        }
+        if let (Some(from), Some(to)) = (filters.delivered_at_from, filters.delivered_at_to) {
+            if from >= to {
+                return Err(Error::Validation(
+                    "delivered_at_from must be earlier than delivered_at_to",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_produced_filters(
+        filters: &input::ProducedNotificationFilters,
+    ) -> Result<(), Error> {
+        if let (Some(from), Some(to)) = (filters.created_at_from, filters.created_at_to) {
+            if from >= to {
+                return Err(Error::Validation(
+                    "created_at_from must be earlier than created_at_to",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_selection(selection: &input::NotificationsSelection) -> Result<(), Error> {
+        if selection.ids.is_none() && selection.filters.is_none() {
+            return Err(Error::Validation("either ids or filters must be set"));
+        }
+        if let Some(ids) = &selection.ids {
+            if ids.is_empty() {
+                return Err(Error::Validation("ids cannot be empty"));
+            }
+            if ids.len() > MAX_SELECTION_IDS {
+                return Err(Error::Validation("too many ids"));
+            }
+        }
+        if let Some(filters) = &selection.filters {
+            Self::validate_filters(filters)?;
+        }
+
+        Ok(())
+    }
+
     fn validate_invalidate_at_not_passed(
         invalidate_at: &Option<OffsetDateTime>,
     ) -> Result<(), Error> {
@@ -57,6 +223,110 @@ impl NotificationsServiceImpl {
         Ok(())
     }
 
+    fn validate_deliver_at(
+        deliver_at: &Option<OffsetDateTime>,
+        invalidate_at: &Option<OffsetDateTime>,
+    ) -> Result<(), Error> {
+        let Some(deliver_at) = deliver_at else {
+            return Ok(());
+        };
+
+        if *deliver_at <= OffsetDateTime::now_utc() {
+            return Err(Error::Validation("deliver_at already passed"));
+        }
+        if let Some(invalidate_at) = invalidate_at {
+            if deliver_at >= invalidate_at {
+                return Err(Error::Validation(
+                    "deliver_at must be earlier than invalidate_at",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_groups(groups: &[String]) -> Result<(), Error> {
+        if groups.len() > MAX_NOTIFICATION_GROUPS {
+            return Err(Error::Validation("too many groups"));
+        }
+        if groups.iter().any(|group| group.is_empty()) {
+            return Err(Error::Validation("group cannot be empty"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_topic(topic: &Option<String>) -> Result<(), Error> {
+        if let Some(topic) = topic {
+            if topic.is_empty() {
+                return Err(Error::Validation("topic cannot be empty"));
+            }
+            if topic.len() > MAX_TOPIC_LEN {
+                return Err(Error::Validation("topic too long"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_collapse_key(collapse_key: &Option<String>) -> Result<(), Error> {
+        if let Some(collapse_key) = collapse_key {
+            if collapse_key.is_empty() {
+                return Err(Error::Validation("collapse_key cannot be empty"));
+            }
+            if collapse_key.len() > MAX_COLLAPSE_KEY_LEN {
+                return Err(Error::Validation("collapse_key too long"));
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Content type can be omitted only together with template,
+    /// which is already rendered at this point
+    ///
+    fn validate_content_type(content_type: &str) -> Result<(), Error> {
+        if content_type.is_empty() {
+            return Err(Error::Validation("content_type cannot be empty"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_localized_contents(
+        &self,
+        content_validator: &ContentValidator,
+        localized_contents: &[input::LocalizedContent],
+    ) -> Result<(), Error> {
+        if localized_contents.len() > MAX_LOCALIZED_CONTENTS {
+            return Err(Error::Validation("too many localized contents"));
+        }
+        for (idx, localized_content) in localized_contents.iter().enumerate() {
+            let locale = &localized_content.locale;
+            if locale.is_empty() || locale.len() > MAX_LOCALE_LEN {
+                return Err(Error::Validation("invalid locale length"));
+            }
+            if !locale
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(Error::Validation("locale contains invalid characters"));
+            }
+            if localized_contents[..idx]
+                .iter()
+                .any(|other| other.locale.eq_ignore_ascii_case(locale))
+            {
+                return Err(Error::Validation("duplicated locale"));
+            }
+            self.validate_content_not_too_long(&localized_content.content)?;
+            content_validator
+                .validate(&localized_content.content_type, &localized_content.content)?;
+        }
+
+        Ok(())
+    }
+
     fn validate_content_not_too_long(&self, content: &Vec<u8>) -> Result<(), Error> {
         if content.len() > self.config.max_content_len {
             return Err(Error::ValidationNotificationTooLarge {
@@ -79,19 +349,17 @@ impl NotificationsService for NotificationsServiceImpl {
         tracing::info!("creating notification");
         tracing::trace!(?notification);
 
-        self.validate_save_notification(&notification)?;
+        let content_types =
+            Self::content_types(&notification.content_type, &notification.localized_contents);
+        let content_validator = self.content_validator(producer_id, content_types).await?;
+        self.validate_save_notification(&content_validator, &notification)?;
+        self.attachments_service
+            .validate_attachments(producer_id, &notification.attachment_ids)
+            .await?;
 
         let inserted_notification = self
             .repository
-            .insert(
-                notification.user_ids,
-                OffsetDateTime::now_utc(),
-                notification.invalidate_at,
-                producer_id,
-                notification.producer_notification_id,
-                notification.content_type,
-                notification.content,
-            )
+            .insert(OffsetDateTime::now_utc(), producer_id, notification)
             .await
             .map_err(|err| match err {
                 repository::Error::InsertUniqueViolation => Error::NotificationAlreadySaved,
@@ -101,24 +369,71 @@ impl NotificationsService for NotificationsServiceImpl {
         let id = inserted_notification.id.to_hex();
         tracing::info!(id, "created notification");
 
-        self.notifications_producer_service
-            .send_new(
-                inserted_notification.user_ids,
-                inserted_notification.id,
-                inserted_notification.created_at,
-                inserted_notification.producer_id,
-                false,
-                inserted_notification.content_type,
-                inserted_notification.content,
-            )
-            .await;
+        self.outbox_relay_service.wake();
 
         Ok(output::NotificationId { id })
     }
 
+    async fn save_notifications(
+        &self,
+        producer_id: Uuid,
+        notifications: Vec<input::Notification>,
+    ) -> Result<Vec<output::NotificationSaveResult>, Error> {
+        tracing::info!(count = notifications.len(), "creating many notifications");
+        tracing::trace!(?notifications);
+
+        let content_types = notifications.iter().flat_map(|notification| {
+            Self::content_types(&notification.content_type, &notification.localized_contents)
+        });
+        let content_validator = self.content_validator(producer_id, content_types).await?;
+        self.validate_save_notifications(&content_validator, &notifications)?;
+        for notification in &notifications {
+            self.attachments_service
+                .validate_attachments(producer_id, &notification.attachment_ids)
+                .await?;
+        }
+
+        let results = self
+            .repository
+            .insert_many(OffsetDateTime::now_utc(), producer_id, notifications)
+            .await
+            .map_err(|err| match err {
+                repository::Error::InsertUniqueViolation => Error::NotificationAlreadySaved,
+                err => Error::Database(err),
+            })?
+            .into_iter()
+            .map(|result| match result {
+                Ok(inserted_notification) => Ok(output::NotificationSaveResult::Created {
+                    id: inserted_notification.id.to_hex(),
+                }),
+                Err(repository::Error::InsertUniqueViolation) => {
+                    Ok(output::NotificationSaveResult::Conflict)
+                }
+                Err(err) => Err(Error::Database(err)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let created_count = results
+            .iter()
+            .filter(|result| matches!(result, output::NotificationSaveResult::Created { .. }))
+            .count();
+        tracing::info!(
+            created_count,
+            conflict_count = results.len() - created_count,
+            "created many notifications"
+        );
+
+        if created_count > 0 {
+            self.outbox_relay_service.wake();
+        }
+
+        Ok(results)
+    }
+
     async fn find_undelivered_notifications(
         &self,
         user_id: Uuid,
+        locales: input::Locales,
     ) -> Result<Vec<output::Notification>, Error> {
         tracing::info!("finding undelivered notifications");
 
@@ -140,39 +455,76 @@ impl NotificationsService for NotificationsServiceImpl {
 
         let notifications = notifications
             .into_iter()
-            .map(output::Notification::from)
+            .map(|notification| output::Notification::localized(notification, &locales))
             .collect();
 
         Ok(notifications)
     }
 
+    async fn count_notifications(
+        &self,
+        user_id: Uuid,
+    ) -> Result<output::NotificationsCount, Error> {
+        tracing::info!("counting notifications");
+
+        let count = self.repository.count(user_id).await?;
+        tracing::info!(
+            undelivered = count.undelivered,
+            delivered = count.delivered,
+            delivered_unseen = count.delivered_unseen,
+            "counted notifications"
+        );
+
+        Ok(count.into())
+    }
+
     async fn find_delivered_notifications(
         &self,
         user_id: Uuid,
         pagination: input::Pagination,
         filters: input::NotificationFilters,
-    ) -> Result<Vec<output::Notification>, Error> {
+        locales: input::Locales,
+    ) -> Result<output::NotificationsPage, Error> {
         tracing::info!("finding delivered notifications");
         tracing::trace!(?filters);
 
+        Self::validate_pagination(&pagination)?;
+        Self::validate_filters(&filters)?;
+
+        let page_size = pagination.page_size as usize;
         let notifications = self
             .repository
             .find_many_delivered(user_id, pagination, filters)
             .await?;
         tracing::info!(count = notifications.len(), "found notifications");
 
+        // Full page means there might be more notifications
+        let next_cursor = match notifications.len() == page_size {
+            true => notifications
+                .last()
+                .map(|notification| output::NotificationsCursor {
+                    created_at: notification.created_at,
+                    id: notification.id,
+                }),
+            false => None,
+        };
+
         let notifications = notifications
             .into_iter()
-            .map(output::Notification::from)
+            .map(|notification| output::Notification::localized(notification, &locales))
             .collect();
 
-        Ok(notifications)
+        Ok(output::NotificationsPage {
+            notifications,
+            next_cursor,
+        })
     }
 
     async fn find_delivered_notification(
         &self,
         id: ObjectId,
         user_id: Uuid,
+        locales: input::Locales,
     ) -> Result<output::Notification, Error> {
         tracing::info!("finding notification");
 
@@ -184,7 +536,7 @@ impl NotificationsService for NotificationsServiceImpl {
 
         tracing::info!("found notification");
 
-        Ok(notification.into())
+        Ok(output::Notification::localized(notification, &locales))
     }
 
     async fn delete_notification(&self, id: ObjectId, user_id: Uuid) -> Result<(), Error> {
@@ -200,13 +552,162 @@ impl NotificationsService for NotificationsServiceImpl {
 
         tracing::info!("deleted notification");
 
-        self.notifications_producer_service
-            .send_deleted(user_id, id, OffsetDateTime::now_utc())
-            .await;
+        self.outbox_relay_service.wake();
+
+        Ok(())
+    }
+
+    async fn delete_notifications(
+        &self,
+        user_id: Uuid,
+        selection: input::NotificationsSelection,
+    ) -> Result<output::NotificationsAffected, Error> {
+        tracing::info!("deleting many notifications");
+        tracing::trace!(?selection);
+
+        Self::validate_selection(&selection)?;
+
+        let count = self.repository.delete_many(user_id, selection).await?;
+
+        tracing::info!(count, "deleted many notifications");
+
+        if count > 0 {
+            self.outbox_relay_service.wake();
+        }
+
+        Ok(output::NotificationsAffected { count })
+    }
+
+    async fn retract_notification(&self, id: ObjectId, producer_id: Uuid) -> Result<(), Error> {
+        tracing::info!("retracting notification");
+
+        self.repository
+            .retract(id, producer_id)
+            .await
+            .map_err(|err| match err {
+                repository::Error::NoDocumentUpdated => Error::NotificationNotExist,
+                err => Error::Database(err),
+            })?;
+
+        tracing::info!("retracted notification");
+
+        self.outbox_relay_service.wake();
 
         Ok(())
     }
 
+    async fn find_delivery_report(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        pagination: input::DeliveryReportPagination,
+    ) -> Result<output::DeliveryReport, Error> {
+        tracing::info!("finding delivery report");
+
+        if pagination.page_size == 0 {
+            return Err(Error::Validation("page_size must be greater than 0"));
+        }
+
+        let page_size = pagination.page_size as usize;
+        let report = self
+            .repository
+            .find_delivery_report(id, producer_id, pagination)
+            .await?
+            .ok_or(Error::NotificationNotExist)?;
+
+        tracing::info!(
+            confirmed_count = report.confirmed_count,
+            pending_count = report.pending_count,
+            "found delivery report"
+        );
+
+        // Full page means there might be more confirmations or pending recipients
+        let next_confirmations_cursor = match report.confirmations.len() == page_size {
+            true => report
+                .confirmations
+                .last()
+                .map(|confirmation| output::ConfirmationsCursor {
+                    delivered_at: confirmation.delivered_at,
+                    id: confirmation.id,
+                }),
+            false => None,
+        };
+        let next_pending_cursor = report
+            .pending_user_ids
+            .as_ref()
+            .filter(|pending_user_ids| pending_user_ids.len() == page_size)
+            .and_then(|pending_user_ids| pending_user_ids.last().copied());
+
+        Ok(output::DeliveryReport {
+            confirmed_count: report.confirmed_count,
+            confirmations: report
+                .confirmations
+                .into_iter()
+                .map(output::RecipientConfirmation::from)
+                .collect(),
+            next_confirmations_cursor,
+            pending_count: report.pending_count,
+            pending_user_ids: report.pending_user_ids,
+            next_pending_cursor,
+        })
+    }
+
+    async fn find_produced_notifications(
+        &self,
+        producer_id: Uuid,
+        pagination: input::Pagination,
+        filters: input::ProducedNotificationFilters,
+    ) -> Result<output::ProducedNotificationsPage, Error> {
+        tracing::info!("finding produced notifications");
+        tracing::trace!(?filters);
+
+        Self::validate_pagination(&pagination)?;
+        Self::validate_produced_filters(&filters)?;
+
+        let page_size = pagination.page_size as usize;
+        let notifications = self
+            .repository
+            .find_many_produced(producer_id, pagination, filters)
+            .await?;
+        tracing::info!(count = notifications.len(), "found notifications");
+
+        // Full page means there might be more notifications
+        let next_cursor = match notifications.len() == page_size {
+            true => notifications
+                .last()
+                .map(|notification| output::NotificationsCursor {
+                    created_at: notification.created_at,
+                    id: notification.id,
+                }),
+            false => None,
+        };
+
+        Ok(output::ProducedNotificationsPage {
+            notifications: notifications
+                .into_iter()
+                .map(output::ProducedNotification::from)
+                .collect(),
+            next_cursor,
+        })
+    }
+
+    async fn find_produced_notification(
+        &self,
+        producer_id: Uuid,
+        producer_notification_id: i64,
+    ) -> Result<output::ProducedNotification, Error> {
+        tracing::info!("finding produced notification");
+
+        let notification = self
+            .repository
+            .find_produced(producer_id, producer_notification_id)
+            .await?
+            .ok_or(Error::NotificationNotExist)?;
+        tracing::info!(id = notification.id.to_hex(), "found notification");
+
+        Ok(notification.into())
+    }
+
     async fn update_notification_invalidate_at(
         &self,
         id: ObjectId,
@@ -233,6 +734,43 @@ impl NotificationsService for NotificationsServiceImpl {
         }
     }
 
+    async fn update_notification_content(
+        &self,
+        id: ObjectId,
+        producer_id: Uuid,
+        content: input::NotificationContent,
+    ) -> Result<(), Error> {
+        tracing::info!("updating content");
+        tracing::trace!(?content);
+
+        Self::validate_content_type(&content.content_type)?;
+        let content_types = Self::content_types(&content.content_type, &content.localized_contents);
+        let content_validator = self.content_validator(producer_id, content_types).await?;
+        self.validate_content_not_too_long(&content.content)?;
+        content_validator.validate(&content.content_type, &content.content)?;
+        self.validate_localized_contents(&content_validator, &content.localized_contents)?;
+
+        let input::NotificationContent {
+            content_type,
+            content,
+            localized_contents,
+        } = content;
+
+        self.repository
+            .update_content(id, producer_id, content_type, content, localized_contents)
+            .await
+            .map_err(|err| match err {
+                repository::Error::NoDocumentUpdated => Error::NotificationNotExist,
+                err => Error::Database(err),
+            })?;
+
+        tracing::info!("updated content");
+
+        self.outbox_relay_service.wake();
+
+        Ok(())
+    }
+
     async fn update_notification_seen(
         &self,
         id: ObjectId,
@@ -254,65 +792,341 @@ impl NotificationsService for NotificationsServiceImpl {
 
         tracing::info!("updated seen");
 
-        self.notifications_producer_service
-            .send_updated(user_id, id, seen, OffsetDateTime::now_utc())
-            .await;
+        self.outbox_relay_service.wake();
 
         Ok(())
     }
+
+    async fn update_notifications_seen(
+        &self,
+        user_id: Uuid,
+        seen: input::NotificationsSeen,
+    ) -> Result<output::NotificationsAffected, Error> {
+        tracing::info!("updating seen of many notifications");
+        tracing::trace!(?seen);
+
+        let input::NotificationsSeen { seen, selection } = seen;
+
+        Self::validate_selection(&selection)?;
+
+        let count = self
+            .repository
+            .update_many_confirmations_seen(user_id, selection, seen)
+            .await?;
+
+        tracing::info!(count, "updated seen of many notifications");
+
+        if count > 0 {
+            self.outbox_relay_service.wake();
+        }
+
+        Ok(output::NotificationsAffected { count })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::service::notifications_producer_service::MockNotificationsProducerService;
+    use crate::{
+        dto::input::NotificationPriority,
+        service::{
+            attachments_service::MockAttachmentsService,
+            content_types_service::MockContentTypesService,
+            outbox_relay_service::MockOutboxRelayService,
+        },
+    };
     use bson::oid::ObjectId;
     use repository::{InsertedNotification, MockNotificationsRepository};
     use std::time::Duration;
     use time::macros::datetime;
 
-    #[tokio::test]
-    async fn save_notification_validation_invalidate_at_none_ok() {
-        let invalidate_at = None;
-        let invalidate_at_clone = invalidate_at.clone();
+    fn mock_content_types_service() -> MockContentTypesService {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service
+            .expect_content_validator()
+            .returning(|_, _| Ok(ContentValidator::default()));
+        content_types_service
+    }
 
-        let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_insert()
-            .return_once(move |_, _, _, _, _, _, _| {
-                Ok(InsertedNotification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    invalidate_at: invalidate_at_clone,
-                    user_ids: vec![],
-                    producer_id: Uuid::new_v4(),
-                    producer_notification_id: 1,
-                    content_type: "utf-8".to_string(),
-                    content: b"data".to_vec(),
-                })
+    fn mock_content_types_service_with_schema() -> MockContentTypesService {
+        let mut content_types_service = MockContentTypesService::new();
+        content_types_service
+            .expect_content_validator()
+            .withf(|_, content_types| content_types.contains(&"order".to_string()))
+            .returning(|_, _| {
+                ContentValidator::new(vec![repository::ContentType {
+                    content_type: "order".to_string(),
+                    schema: r#"{"type":"object","required":["id"]}"#.to_string(),
+                    updated_at: OffsetDateTime::now_utc(),
+                }])
             });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service
-            .expect_send_new()
-            .once()
-            .returning(|_, _, _, _, _, _, _| ());
+        content_types_service
+    }
+
+    fn mock_attachments_service() -> MockAttachmentsService {
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service
+            .expect_validate_attachments()
+            .returning(|_, _| Ok(()));
+        attachments_service
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_content_schema_mismatch() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service_with_schema()),
+            Arc::new(mock_attachments_service()),
         );
+        let mut notification = notification_with_producer_notification_id(1);
+        notification.content_type = "order".to_string();
+        notification.content = br#"{"name":"order"}"#.to_vec();
 
         let save_result = service
-            .save_notification(
+            .save_notification(Uuid::new_v4(), notification)
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_content_type_empty() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+        let mut notification = notification_with_producer_notification_id(1);
+        notification.content_type = String::new();
+
+        let save_result = service
+            .save_notification(Uuid::new_v4(), notification)
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_attachment_not_exist() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let mut attachments_service = MockAttachmentsService::new();
+        attachments_service
+            .expect_validate_attachments()
+            .withf(|_, attachment_ids| attachment_ids.len() == 1)
+            .once()
+            .returning(|_, _| Err(Error::Validation("attachment does not exist")));
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(attachments_service),
+        );
+        let mut notification = notification_with_producer_notification_id(1);
+        notification.attachment_ids = vec![ObjectId::new()];
+
+        let save_result = service
+            .save_notification(Uuid::new_v4(), notification)
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_localized_content_schema_mismatch() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service_with_schema()),
+            Arc::new(mock_attachments_service()),
+        );
+        let mut notification = notification_with_producer_notification_id(1);
+        notification.localized_contents = vec![input::LocalizedContent {
+            locale: "pl".to_string(),
+            content_type: "order".to_string(),
+            content: b"zamowienie".to_vec(),
+        }];
+
+        let save_result = service
+            .save_notification(Uuid::new_v4(), notification)
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_validation_content_schema_mismatch() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service_with_schema()),
+            Arc::new(mock_attachments_service()),
+        );
+        let mut invalid_notification = notification_with_producer_notification_id(2);
+        invalid_notification.content_type = "order".to_string();
+        invalid_notification.content = b"[]".to_vec();
+
+        let save_result = service
+            .save_notifications(
+                Uuid::new_v4(),
+                vec![
+                    notification_with_producer_notification_id(1),
+                    invalid_notification,
+                ],
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_notification_content_validation_content_schema_mismatch() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_update_content().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service_with_schema()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notification_content(
+                ObjectId::new(),
+                Uuid::new_v4(),
+                input::NotificationContent {
+                    content_type: "order".to_string(),
+                    content: b"{}".to_vec(),
+                    localized_contents: vec![],
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_notification_content_validation_content_type_empty() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_update_content().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notification_content(
+                ObjectId::new(),
+                Uuid::new_v4(),
+                input::NotificationContent {
+                    content_type: String::new(),
+                    content: b"data".to_vec(),
+                    localized_contents: vec![],
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_invalidate_at_none_ok() {
+        let invalidate_at = None;
+        let invalidate_at_clone = invalidate_at.clone();
+
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at: invalidate_at_clone,
+                deliver_at: None,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: b"data".to_vec(),
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
                 Uuid::from_u128(5890123809123),
                 input::Notification {
                     invalidate_at,
+                    deliver_at: None,
                     user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
                     content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
@@ -328,31 +1142,32 @@ mod test {
         let invalidate_at_clone = invalidate_at.clone();
 
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_insert()
-            .return_once(move |_, _, _, _, _, _, _| {
-                Ok(InsertedNotification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    invalidate_at: invalidate_at_clone,
-                    user_ids: vec![],
-                    producer_id: Uuid::new_v4(),
-                    producer_notification_id: 1,
-                    content_type: "utf-8".to_string(),
-                    content: b"data".to_vec(),
-                })
-            });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service
-            .expect_send_new()
-            .once()
-            .returning(|_, _, _, _, _, _, _| ());
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at: invalidate_at_clone,
+                deliver_at: None,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: b"data".to_vec(),
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let save_result = service
@@ -360,10 +1175,18 @@ mod test {
                 Uuid::from_u128(5890123809123),
                 input::Notification {
                     invalidate_at,
+                    deliver_at: None,
                     user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
                     content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
@@ -379,28 +1202,32 @@ mod test {
         let invalidate_at_clone = invalidate_at.clone();
 
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_insert()
-            .return_once(move |_, _, _, _, _, _, _| {
-                Ok(InsertedNotification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    invalidate_at: invalidate_at_clone,
-                    user_ids: vec![],
-                    producer_id: Uuid::new_v4(),
-                    producer_notification_id: 1,
-                    content_type: "utf-8".to_string(),
-                    content: b"data".to_vec(),
-                })
-            });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_new().never();
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at: invalidate_at_clone,
+                deliver_at: None,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: b"data".to_vec(),
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let save_result = service
@@ -408,10 +1235,18 @@ mod test {
                 Uuid::from_u128(5890123809123),
                 input::Notification {
                     invalidate_at,
+                    deliver_at: None,
                     user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
                     content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
@@ -420,50 +1255,56 @@ mod test {
     }
 
     #[tokio::test]
-    async fn save_notification_validation_content_length_ok() {
-        const MAX_CONTENT_LEN: usize = 8;
-
-        let content = b"MTIzNA==".to_vec();
-        let content_clone = content.clone();
-        assert!(content.len() <= MAX_CONTENT_LEN);
+    async fn save_notification_validation_deliver_at_ok() {
+        let invalidate_at = Some(OffsetDateTime::now_utc() + Duration::from_secs(1200));
+        let deliver_at = Some(OffsetDateTime::now_utc() + Duration::from_secs(600));
 
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_insert()
-            .return_once(move |_, _, _, _, _, _, _| {
-                Ok(InsertedNotification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    invalidate_at: None,
-                    user_ids: vec![],
-                    producer_id: Uuid::new_v4(),
-                    producer_notification_id: 1,
-                    content_type: "utf-8".to_string(),
-                    content: content_clone,
-                })
-            });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service
-            .expect_send_new()
-            .once()
-            .returning(|_, _, _, _, _, _, _| ());
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at,
+                deliver_at,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: b"data".to_vec(),
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
-                max_content_len: MAX_CONTENT_LEN,
+                max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let save_result = service
             .save_notification(
                 Uuid::from_u128(5890123809123),
                 input::Notification {
-                    invalidate_at: None,
+                    invalidate_at,
+                    deliver_at,
                     user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
-                    content,
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
@@ -472,471 +1313,2114 @@ mod test {
     }
 
     #[tokio::test]
-    async fn save_notification_validation_content_length_err() {
-        const MAX_CONTENT_LEN: usize = 8;
-
-        let content = b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec();
-        let content_clone = content.clone();
-        assert!(content.len() > MAX_CONTENT_LEN);
+    async fn save_notification_validation_deliver_at_passed_err() {
+        let invalidate_at = None;
+        let deliver_at = Some(OffsetDateTime::now_utc() - Duration::from_secs(600));
 
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_insert()
-            .return_once(move |_, _, _, _, _, _, _| {
-                Ok(InsertedNotification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    invalidate_at: None,
-                    user_ids: vec![],
-                    producer_id: Uuid::new_v4(),
-                    producer_notification_id: 1,
-                    content_type: "utf-8".to_string(),
-                    content: content_clone,
-                })
-            });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_new().never();
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at,
+                deliver_at,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: b"data".to_vec(),
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
-                max_content_len: MAX_CONTENT_LEN,
+                max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let save_result = service
             .save_notification(
                 Uuid::from_u128(5890123809123),
                 input::Notification {
-                    invalidate_at: None,
+                    invalidate_at,
+                    deliver_at,
                     user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
-                    content,
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
 
-        assert!(matches!(
-            save_result,
-            Err(Error::ValidationNotificationTooLarge {
-                size: _,
-                max_size: _
-            })
-        ));
+        assert!(matches!(save_result, Err(Error::Validation(_))));
     }
 
     #[tokio::test]
-    async fn save_notification_already_saved() {
+    async fn save_notification_validation_deliver_at_after_invalidate_at_err() {
+        let invalidate_at = Some(OffsetDateTime::now_utc() + Duration::from_secs(600));
+        let deliver_at = Some(OffsetDateTime::now_utc() + Duration::from_secs(1200));
+
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_insert()
-            .returning(|_, _, _, _, _, _, _| Err(repository::Error::InsertUniqueViolation));
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_new().never();
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at,
+                deliver_at,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: b"data".to_vec(),
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let save_result = service
             .save_notification(
-                Uuid::from_u128(12371928379128),
+                Uuid::from_u128(5890123809123),
                 input::Notification {
-                    invalidate_at: None,
-                    user_ids: vec![],
+                    invalidate_at,
+                    deliver_at,
+                    user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
                     content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
 
-        assert!(matches!(save_result, Err(Error::NotificationAlreadySaved)));
+        assert!(matches!(save_result, Err(Error::Validation(_))));
     }
 
     #[tokio::test]
-    async fn save_notification_database_error() {
+    async fn save_notification_validation_content_length_ok() {
+        const MAX_CONTENT_LEN: usize = 8;
+
+        let content = b"MTIzNA==".to_vec();
+        let content_clone = content.clone();
+        assert!(content.len() <= MAX_CONTENT_LEN);
+
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_insert().returning(|_, _, _, _, _, _, _| {
-            Err(repository::Error::Mongo(
-                mongodb::error::ErrorKind::Custom(Arc::new("unexpected database error")).into(),
-            ))
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at: None,
+                deliver_at: None,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: content_clone,
+            })
         });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_new().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
-                max_content_len: usize::MAX,
+                max_content_len: MAX_CONTENT_LEN,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let save_result = service
             .save_notification(
-                Uuid::from_u128(12371928379128),
+                Uuid::from_u128(5890123809123),
                 input::Notification {
                     invalidate_at: None,
-                    user_ids: vec![],
+                    deliver_at: None,
+                    user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
                     producer_notification_id: 1,
                     content_type: "utf-8".to_string(),
-                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    content,
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
                 },
             )
             .await;
 
-        assert!(matches!(save_result, Err(Error::Database(_))));
+        assert!(save_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_too_many_groups_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec!["admins".to_string(); MAX_NOTIFICATION_GROUPS + 1],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_topic_empty_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: Some("".to_string()),
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_collapse_key_empty_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: Some("".to_string()),
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_collapse_key_too_long_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: Some("k".repeat(MAX_COLLAPSE_KEY_LEN + 1)),
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_locale_invalid_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![input::LocalizedContent {
+                        locale: "en US".to_string(),
+                        content_type: "utf-8".to_string(),
+                        content: b"content".to_vec(),
+                    }],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_locale_duplicated_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![
+                        input::LocalizedContent {
+                            locale: "en-US".to_string(),
+                            content_type: "utf-8".to_string(),
+                            content: b"content".to_vec(),
+                        },
+                        input::LocalizedContent {
+                            locale: "en-us".to_string(),
+                            content_type: "utf-8".to_string(),
+                            content: b"content".to_vec(),
+                        },
+                    ],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notification_validation_content_length_err() {
+        const MAX_CONTENT_LEN: usize = 8;
+
+        let content = b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec();
+        let content_clone = content.clone();
+        assert!(content.len() > MAX_CONTENT_LEN);
+
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().return_once(move |_, _, _| {
+            Ok(InsertedNotification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                invalidate_at: None,
+                deliver_at: None,
+                user_ids: vec![],
+                groups: vec![],
+                topic: None,
+                priority: NotificationPriority::Normal,
+                producer_id: Uuid::new_v4(),
+                producer_notification_id: 1,
+                content_type: "utf-8".to_string(),
+                content: content_clone,
+            })
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: MAX_CONTENT_LEN,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(5890123809123),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: Vec::new(),
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content,
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(
+            save_result,
+            Err(Error::ValidationNotificationTooLarge {
+                size: _,
+                max_size: _
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn save_notification_already_saved() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_insert()
+            .returning(|_, _, _| Err(repository::Error::InsertUniqueViolation));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(12371928379128),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::NotificationAlreadySaved)));
+    }
+
+    #[tokio::test]
+    async fn save_notification_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert().returning(|_, _, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("unexpected database error")).into(),
+            ))
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notification(
+                Uuid::from_u128(12371928379128),
+                input::Notification {
+                    invalidate_at: None,
+                    deliver_at: None,
+                    user_ids: vec![],
+                    groups: vec![],
+                    topic: None,
+                    priority: NotificationPriority::Normal,
+                    collapse_key: None,
+                    producer_notification_id: 1,
+                    content_type: "utf-8".to_string(),
+                    content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                    template: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Database(_))));
+    }
+
+    fn notification_with_producer_notification_id(
+        producer_notification_id: i64,
+    ) -> input::Notification {
+        input::Notification {
+            invalidate_at: None,
+            deliver_at: None,
+            user_ids: vec![],
+            groups: vec![],
+            topic: None,
+            priority: NotificationPriority::Normal,
+            collapse_key: None,
+            producer_notification_id,
+            content_type: "utf-8".to_string(),
+            content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+            localized_contents: vec![],
+            attachment_ids: vec![],
+            template: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn save_notifications_validation_empty() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notifications(Uuid::from_u128(3910283019), vec![])
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_validation_too_many() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                (0..=MAX_BATCH_NOTIFICATIONS as i64)
+                    .map(notification_with_producer_notification_id)
+                    .collect(),
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_validation_invalidate_at_passed() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let mut invalidated_notification = notification_with_producer_notification_id(2);
+        invalidated_notification.invalidate_at =
+            Some(OffsetDateTime::now_utc() - Duration::from_secs(60));
+
+        let save_result = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                vec![
+                    notification_with_producer_notification_id(1),
+                    invalidated_notification,
+                ],
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_validation_content_length_err() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig { max_content_len: 8 },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                vec![notification_with_producer_notification_id(1)],
+            )
+            .await;
+
+        assert!(matches!(
+            save_result,
+            Err(Error::ValidationNotificationTooLarge {
+                size: _,
+                max_size: _
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_concurrently_saved() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_insert_many()
+            .returning(|_, _, _| Err(repository::Error::InsertUniqueViolation));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                vec![notification_with_producer_notification_id(1)],
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::NotificationAlreadySaved)));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().returning(|_, _, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("unexpected database error")).into(),
+            ))
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let save_result = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                vec![notification_with_producer_notification_id(1)],
+            )
+            .await;
+
+        assert!(matches!(save_result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn save_notifications_only_conflicts() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_insert_many().returning(|_, _, _| {
+            Ok(vec![
+                Err(repository::Error::InsertUniqueViolation),
+                Err(repository::Error::InsertUniqueViolation),
+            ])
+        });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let results = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                vec![
+                    notification_with_producer_notification_id(1),
+                    notification_with_producer_notification_id(1),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                output::NotificationSaveResult::Conflict,
+                output::NotificationSaveResult::Conflict,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn save_notifications_ok() {
+        let id = ObjectId::new();
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_insert_many()
+            .returning(move |created_at, producer_id, _| {
+                Ok(vec![
+                    Ok(InsertedNotification {
+                        id,
+                        created_at,
+                        invalidate_at: None,
+                        deliver_at: None,
+                        user_ids: vec![],
+                        groups: vec![],
+                        topic: None,
+                        priority: NotificationPriority::Normal,
+                        producer_id,
+                        producer_notification_id: 1,
+                        content_type: "utf-8".to_string(),
+                        content: b"VGhpcyBjb25lbnQgaXMgbm90IGltcG9ydGFudA==".to_vec(),
+                    }),
+                    Err(repository::Error::InsertUniqueViolation),
+                ])
+            });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let results = service
+            .save_notifications(
+                Uuid::from_u128(3910283019),
+                vec![
+                    notification_with_producer_notification_id(1),
+                    notification_with_producer_notification_id(2),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                output::NotificationSaveResult::Created { id: id.to_hex() },
+                output::NotificationSaveResult::Conflict,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn count_notifications_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_count().returning(|_| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .count_notifications(Uuid::from_u128(9120938102))
+            .await;
+
+        assert!(matches!(result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn count_notifications_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_count().returning(|_| {
+            Ok(repository::NotificationsCount {
+                undelivered: 3,
+                delivered: 10,
+                delivered_unseen: 4,
+            })
+        });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let count = service
+            .count_notifications(Uuid::from_u128(9120938102))
+            .await
+            .unwrap();
+
+        assert_eq!(count.undelivered, 3);
+        assert_eq!(count.delivered, 10);
+        assert_eq!(count.delivered_unseen, 4);
+    }
+
+    #[tokio::test]
+    async fn find_undelivered_notifications_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_undelivered().returning(|_| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .find_undelivered_notifications(
+                Uuid::from_u128(124801283012),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn find_undelivered_notifications_insert_confirmations_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_undelivered().returning(|_| {
+            let notifications = vec![repository::Notification {
+                id: ObjectId::new(),
+                created_at: datetime!(2024-02-12 18:57:00 UTC),
+                producer_id: Uuid::new_v4().into(),
+                seen: false,
+                priority: NotificationPriority::Normal,
+                content_type: "utf-8".to_string(),
+                content: b"It's just a mock notification".to_vec(),
+                localized_contents: vec![],
+                attachment_ids: vec![],
+            }];
+            Ok(notifications)
+        });
+        repository
+            .expect_insert_many_confirmations()
+            .returning(|_, _| {
+                Err(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                ))
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .find_undelivered_notifications(
+                Uuid::from_u128(124801283012),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn find_undelivered_notifications_no_notifications() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_many_undelivered()
+            .returning(|_| Ok(vec![]));
+        repository.expect_insert_many_confirmations().never(); // makes sure method is not called when there's no notifications
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .find_undelivered_notifications(
+                Uuid::from_u128(124801283012),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn find_undelivered_notifications_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_undelivered().returning(|_| {
+            Ok(vec![
+                repository::Notification {
+                    id: ObjectId::new(),
+                    created_at: OffsetDateTime::now_utc(),
+                    producer_id: Uuid::new_v4().into(),
+                    seen: false,
+                    priority: NotificationPriority::Normal,
+                    content_type: "utf-8".to_string(),
+                    content: b"abc".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                },
+                repository::Notification {
+                    id: ObjectId::new(),
+                    created_at: OffsetDateTime::now_utc(),
+                    producer_id: Uuid::new_v4().into(),
+                    seen: false,
+                    priority: NotificationPriority::Normal,
+                    content_type: "utf-8".to_string(),
+                    content: b"abc2".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                },
+            ])
+        });
+        repository
+            .expect_insert_many_confirmations()
+            .returning(|_, _| Ok(()));
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let notifications = service
+            .find_undelivered_notifications(
+                Uuid::from_u128(124801283012),
+                input::Locales::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(notifications.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_many_delivered()
+            .returning(|_, _, _| {
+                Err(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                ))
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: Some(0),
+                    page_size: u32::MAX,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_many_delivered()
+            .returning(|_, _, _| {
+                Ok(vec![
+                    repository::Notification {
+                        id: ObjectId::new(),
+                        created_at: OffsetDateTime::now_utc(),
+                        producer_id: Uuid::new_v4().into(),
+                        seen: false,
+                        priority: NotificationPriority::Normal,
+                        content_type: "utf-8".to_string(),
+                        content: b"abc".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                    },
+                    repository::Notification {
+                        id: ObjectId::new(),
+                        created_at: OffsetDateTime::now_utc(),
+                        producer_id: Uuid::new_v4().into(),
+                        seen: false,
+                        priority: NotificationPriority::Normal,
+                        content_type: "utf-8".to_string(),
+                        content: b"abc2".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                    },
+                ])
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let page = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: Some(0),
+                    page_size: u32::MAX,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+                input::Locales::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.notifications.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_page_idx_and_cursor_validation_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_delivered().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: Some(0),
+                    page_size: 10,
+                    cursor: Some(input::NotificationsCursor {
+                        created_at: OffsetDateTime::now_utc(),
+                        id: ObjectId::new(),
+                    }),
+                },
+                input::NotificationFilters::default(),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_zero_page_size_validation_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_delivered().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 0,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_created_at_range_validation_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_delivered().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 10,
+                    cursor: None,
+                },
+                input::NotificationFilters {
+                    created_at_from: Some(datetime!(2024-01-02 00:00 UTC)),
+                    created_at_to: Some(datetime!(2024-01-01 00:00 UTC)),
+                    ..Default::default()
+                },
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_delivered_at_range_validation_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_many_delivered().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 10,
+                    cursor: None,
+                },
+                input::NotificationFilters {
+                    delivered_at_from: Some(datetime!(2024-01-01 00:00 UTC)),
+                    delivered_at_to: Some(datetime!(2024-01-01 00:00 UTC)),
+                    ..Default::default()
+                },
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_full_page_returns_next_cursor() {
+        let last_id = ObjectId::new();
+        let last_created_at = datetime!(2024-01-01 12:00 UTC);
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_many_delivered()
+            .returning(move |_, _, _| {
+                Ok(vec![
+                    repository::Notification {
+                        id: ObjectId::new(),
+                        created_at: OffsetDateTime::now_utc(),
+                        producer_id: Uuid::new_v4().into(),
+                        seen: false,
+                        priority: NotificationPriority::Normal,
+                        content_type: "utf-8".to_string(),
+                        content: b"abc".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                    },
+                    repository::Notification {
+                        id: last_id,
+                        created_at: last_created_at,
+                        producer_id: Uuid::new_v4().into(),
+                        seen: false,
+                        priority: NotificationPriority::Normal,
+                        content_type: "utf-8".to_string(),
+                        content: b"abc2".to_vec(),
+                        localized_contents: vec![],
+                        attachment_ids: vec![],
+                    },
+                ])
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let page = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 2,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+                input::Locales::default(),
+            )
+            .await
+            .unwrap();
+
+        let next_cursor = page.next_cursor.unwrap();
+        assert_eq!(next_cursor.id, last_id);
+        assert_eq!(next_cursor.created_at, last_created_at);
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notifications_partial_page_returns_no_cursor() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_many_delivered()
+            .returning(|_, _, _| {
+                Ok(vec![repository::Notification {
+                    id: ObjectId::new(),
+                    created_at: OffsetDateTime::now_utc(),
+                    producer_id: Uuid::new_v4().into(),
+                    seen: false,
+                    priority: NotificationPriority::Normal,
+                    content_type: "utf-8".to_string(),
+                    content: b"abc".to_vec(),
+                    localized_contents: vec![],
+                    attachment_ids: vec![],
+                }])
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let page = service
+            .find_delivered_notifications(
+                Uuid::from_u128(58190832021938),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 2,
+                    cursor: None,
+                },
+                input::NotificationFilters::default(),
+                input::Locales::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notification_not_found() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_delivered()
+            .returning(|_, _| Ok(None));
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notification(
+                ObjectId::new(),
+                Uuid::from_u128(75098123),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::NotificationNotExist)));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notification_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_delivered().returning(|_, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notification(
+                ObjectId::new(),
+                Uuid::from_u128(75098123),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivered_notification_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_find_delivered().returning(|_, _| {
+            Ok(Some(repository::Notification {
+                id: ObjectId::new(),
+                created_at: OffsetDateTime::now_utc(),
+                producer_id: Uuid::new_v4().into(),
+                seen: false,
+                priority: NotificationPriority::Normal,
+                content_type: "utf-8".to_string(),
+                content: b"abc".to_vec(),
+                localized_contents: vec![],
+                attachment_ids: vec![],
+            }))
+        });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let find_result = service
+            .find_delivered_notification(
+                ObjectId::new(),
+                Uuid::from_u128(75098123),
+                input::Locales::default(),
+            )
+            .await;
+
+        assert!(find_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn delete_notification_no_document_updated() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_delete()
+            .returning(|_, _| Err(repository::Error::NoDocumentUpdated));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .delete_notification(ObjectId::new(), Uuid::from_u128(31985781293))
+            .await;
+
+        assert!(matches!(result, Err(Error::NotificationNotExist)));
     }
 
     #[tokio::test]
-    async fn find_undelivered_notifications_database_error() {
+    async fn delete_notification_database_error() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_find_many_undelivered().returning(|_| {
+        repository.expect_delete().returning(|_, _| {
             Err(repository::Error::Mongo(
                 mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
             ))
         });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
-            .find_undelivered_notifications(Uuid::from_u128(124801283012))
+            .delete_notification(ObjectId::new(), Uuid::from_u128(31985781293))
             .await;
 
         assert!(matches!(result, Err(Error::Database(_))));
     }
 
     #[tokio::test]
-    async fn find_undelivered_notifications_insert_confirmations_error() {
+    async fn delete_notification_ok() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_find_many_undelivered().returning(|_| {
-            let notifications = vec![repository::Notification {
-                id: ObjectId::new(),
-                created_at: datetime!(2024-02-12 18:57:00 UTC),
-                producer_id: Uuid::new_v4().into(),
-                seen: false,
-                content_type: "utf-8".to_string(),
-                content: b"It's just a mock notification".to_vec(),
-            }];
-            Ok(notifications)
-        });
-        repository
-            .expect_insert_many_confirmations()
-            .returning(|_, _| {
-                Err(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                ))
-            });
+        repository.expect_delete().returning(|_, _| Ok(()));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
-            .find_undelivered_notifications(Uuid::from_u128(124801283012))
+            .delete_notification(ObjectId::new(), Uuid::from_u128(31985781293))
             .await;
 
-        assert!(matches!(result, Err(Error::Database(_))));
+        assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn find_undelivered_notifications_no_notifications() {
+    async fn delete_notifications_validation_nothing_selected() {
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_find_many_undelivered()
-            .returning(|_| Ok(vec![]));
-        repository.expect_insert_many_confirmations().never(); // makes sure method is not called when there's no notifications
+        repository.expect_delete_many().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
-            .find_undelivered_notifications(Uuid::from_u128(124801283012))
+            .delete_notifications(
+                Uuid::from_u128(7301928301),
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: None,
+                },
+            )
             .await;
 
-        assert!(result.is_ok());
+        assert!(matches!(result, Err(Error::Validation(_))));
     }
 
     #[tokio::test]
-    async fn find_undelivered_notifications_ok() {
+    async fn delete_notifications_validation_empty_created_at_range() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_find_many_undelivered().returning(|_| {
-            Ok(vec![
-                repository::Notification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    producer_id: Uuid::new_v4().into(),
-                    seen: false,
-                    content_type: "utf-8".to_string(),
-                    content: b"abc".to_vec(),
-                },
-                repository::Notification {
-                    id: ObjectId::new(),
-                    created_at: OffsetDateTime::now_utc(),
-                    producer_id: Uuid::new_v4().into(),
-                    seen: false,
-                    content_type: "utf-8".to_string(),
-                    content: b"abc2".to_vec(),
+        repository.expect_delete_many().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .delete_notifications(
+                Uuid::from_u128(7301928301),
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters {
+                        created_at_from: Some(datetime!(2024-02-01 00:00 UTC)),
+                        created_at_to: Some(datetime!(2024-01-01 00:00 UTC)),
+                        ..Default::default()
+                    }),
                 },
-            ])
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn delete_notifications_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_delete_many().returning(|_, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
         });
-        repository
-            .expect_insert_many_confirmations()
-            .returning(|_, _| Ok(()));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let notifications = service
-            .find_undelivered_notifications(Uuid::from_u128(124801283012))
-            .await
-            .unwrap();
+        let result = service
+            .delete_notifications(
+                Uuid::from_u128(7301928301),
+                input::NotificationsSelection {
+                    ids: Some(vec![ObjectId::new()]),
+                    filters: None,
+                },
+            )
+            .await;
 
-        assert_eq!(notifications.len(), 2);
+        assert!(matches!(result, Err(Error::Database(_))));
     }
 
     #[tokio::test]
-    async fn find_delivered_notifications_database_error() {
+    async fn delete_notifications_nothing_deleted() {
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_find_many_delivered()
-            .returning(|_, _, _| {
-                Err(repository::Error::Mongo(
-                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-                ))
-            });
+        repository.expect_delete_many().returning(|_, _| Ok(0));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let find_result = service
-            .find_delivered_notifications(
-                Uuid::from_u128(58190832021938),
-                input::Pagination {
-                    page_idx: 0,
-                    page_size: u32::MAX,
+        let affected = service
+            .delete_notifications(
+                Uuid::from_u128(7301928301),
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters::default()),
                 },
-                input::NotificationFilters { seen: None },
             )
-            .await;
+            .await
+            .unwrap();
 
-        assert!(matches!(find_result, Err(Error::Database(_))));
+        assert_eq!(affected.count, 0);
     }
 
     #[tokio::test]
-    async fn find_delivered_notifications_ok() {
+    async fn delete_notifications_ok() {
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_find_many_delivered()
-            .returning(|_, _, _| {
-                Ok(vec![
-                    repository::Notification {
-                        id: ObjectId::new(),
-                        created_at: OffsetDateTime::now_utc(),
-                        producer_id: Uuid::new_v4().into(),
-                        seen: false,
-                        content_type: "utf-8".to_string(),
-                        content: b"abc".to_vec(),
-                    },
-                    repository::Notification {
-                        id: ObjectId::new(),
-                        created_at: OffsetDateTime::now_utc(),
-                        producer_id: Uuid::new_v4().into(),
-                        seen: false,
-                        content_type: "utf-8".to_string(),
-                        content: b"abc2".to_vec(),
-                    },
-                ])
-            });
+        repository.expect_delete_many().returning(|_, _| Ok(2));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let notifications = service
-            .find_delivered_notifications(
-                Uuid::from_u128(58190832021938),
-                input::Pagination {
-                    page_idx: 0,
-                    page_size: u32::MAX,
+        let affected = service
+            .delete_notifications(
+                Uuid::from_u128(7301928301),
+                input::NotificationsSelection {
+                    ids: None,
+                    filters: Some(input::NotificationFilters {
+                        seen: Some(true),
+                        ..Default::default()
+                    }),
                 },
-                input::NotificationFilters { seen: None },
             )
             .await
             .unwrap();
 
-        assert_eq!(notifications.len(), 2);
+        assert_eq!(affected.count, 2);
     }
 
     #[tokio::test]
-    async fn find_delivered_notification_not_found() {
+    async fn retract_notification_no_document_updated() {
         let mut repository = MockNotificationsRepository::new();
         repository
-            .expect_find_delivered()
-            .returning(|_, _| Ok(None));
+            .expect_retract()
+            .returning(|_, _| Err(repository::Error::NoDocumentUpdated));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let find_result = service
-            .find_delivered_notification(ObjectId::new(), Uuid::from_u128(75098123))
+        let result = service
+            .retract_notification(ObjectId::new(), Uuid::from_u128(8192038102))
             .await;
 
-        assert!(matches!(find_result, Err(Error::NotificationNotExist)));
+        assert!(matches!(result, Err(Error::NotificationNotExist)));
     }
 
     #[tokio::test]
-    async fn find_delivered_notification_database_error() {
+    async fn retract_notification_database_error() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_find_delivered().returning(|_, _| {
+        repository.expect_retract().returning(|_, _| {
             Err(repository::Error::Mongo(
                 mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
             ))
         });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let find_result = service
-            .find_delivered_notification(ObjectId::new(), Uuid::from_u128(75098123))
+        let result = service
+            .retract_notification(ObjectId::new(), Uuid::from_u128(8192038102))
             .await;
 
-        assert!(matches!(find_result, Err(Error::Database(_))));
+        assert!(matches!(result, Err(Error::Database(_))));
     }
 
     #[tokio::test]
-    async fn find_delivered_notification_ok() {
+    async fn retract_notification_ok() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_find_delivered().returning(|_, _| {
-            Ok(Some(repository::Notification {
-                id: ObjectId::new(),
-                created_at: OffsetDateTime::now_utc(),
-                producer_id: Uuid::new_v4().into(),
-                seen: false,
-                content_type: "utf-8".to_string(),
-                content: b"abc".to_vec(),
-            }))
-        });
+        repository.expect_retract().returning(|_, _| Ok(()));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .retract_notification(ObjectId::new(), Uuid::from_u128(8192038102))
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_not_exist() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_delivery_report()
+            .returning(|_, _, _| Ok(None));
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .find_delivery_report(
+                ObjectId::new(),
+                Uuid::from_u128(8192038102),
+                delivery_report_pagination(10),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::NotificationNotExist)));
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_zero_page_size() {
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(MockNotificationsRepository::new()),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .find_delivery_report(
+                ObjectId::new(),
+                Uuid::from_u128(8192038102),
+                delivery_report_pagination(0),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_delivery_report()
+            .returning(|_, _, _| {
+                Ok(Some(repository::DeliveryReport {
+                    confirmed_count: 1,
+                    confirmations: vec![repository::RecipientConfirmation {
+                        id: ObjectId::new(),
+                        user_id: Uuid::from_u128(1),
+                        delivered_at: datetime!(2024-01-22 12:34:31 UTC),
+                        seen: true,
+                        deleted: false,
+                    }],
+                    pending_count: Some(2),
+                    pending_user_ids: Some(vec![Uuid::from_u128(2), Uuid::from_u128(3)]),
+                }))
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let report = service
+            .find_delivery_report(
+                ObjectId::new(),
+                Uuid::from_u128(8192038102),
+                delivery_report_pagination(10),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.confirmed_count, 1);
+        assert_eq!(report.confirmations[0].user_id, Uuid::from_u128(1));
+        assert!(report.confirmations[0].seen);
+        assert!(report.next_confirmations_cursor.is_none());
+        assert_eq!(report.pending_count, Some(2));
+        assert_eq!(
+            report.pending_user_ids,
+            Some(vec![Uuid::from_u128(2), Uuid::from_u128(3)])
+        );
+        assert!(report.next_pending_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn find_delivery_report_full_pages_next_cursors() {
+        let confirmation_id = ObjectId::new();
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_delivery_report()
+            .returning(move |_, _, _| {
+                Ok(Some(repository::DeliveryReport {
+                    confirmed_count: 5,
+                    confirmations: vec![repository::RecipientConfirmation {
+                        id: confirmation_id,
+                        user_id: Uuid::from_u128(1),
+                        delivered_at: datetime!(2024-01-22 12:34:31 UTC),
+                        seen: false,
+                        deleted: false,
+                    }],
+                    pending_count: Some(3),
+                    pending_user_ids: Some(vec![Uuid::from_u128(2)]),
+                }))
+            });
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let report = service
+            .find_delivery_report(
+                ObjectId::new(),
+                Uuid::from_u128(8192038102),
+                delivery_report_pagination(1),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report.next_confirmations_cursor,
+            Some(output::ConfirmationsCursor {
+                delivered_at: datetime!(2024-01-22 12:34:31 UTC),
+                id: confirmation_id,
+            })
+        );
+        assert_eq!(report.next_pending_cursor, Some(Uuid::from_u128(2)));
+    }
+
+    fn delivery_report_pagination(page_size: u32) -> input::DeliveryReportPagination {
+        input::DeliveryReportPagination {
+            page_size,
+            confirmations_cursor: None,
+            pending_cursor: None,
+        }
+    }
+
+    fn produced_notification(created_at: OffsetDateTime) -> repository::ProducedNotification {
+        repository::ProducedNotification {
+            id: ObjectId::new(),
+            producer_notification_id: 1,
+            created_at,
+            invalidate_at: None,
+            deliver_at: None,
+            retracted_at: None,
+            user_ids: vec![Uuid::from_u128(1)],
+            groups: vec![],
+            topic: None,
+            priority: NotificationPriority::Normal,
+            collapse_key: None,
+            content_type: "utf-8".to_string(),
+            content: b"abc".to_vec(),
+            localized_contents: vec![],
+            attachment_ids: vec![],
+            confirmed_count: 1,
+            seen_count: 1,
+            deleted_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn find_produced_notifications_full_page_returns_next_cursor() {
+        let last = produced_notification(datetime!(2024-01-01 12:00 UTC));
+        let last_id = last.id;
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_find_many_produced()
+            .return_once(move |_, _, _| {
+                Ok(vec![
+                    produced_notification(datetime!(2024-01-02 12:00 UTC)),
+                    last,
+                ])
+            });
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let find_result = service
-            .find_delivered_notification(ObjectId::new(), Uuid::from_u128(75098123))
-            .await;
+        let page = service
+            .find_produced_notifications(
+                Uuid::from_u128(8192038102),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 2,
+                    cursor: None,
+                },
+                input::ProducedNotificationFilters::default(),
+            )
+            .await
+            .unwrap();
 
-        assert!(find_result.is_ok());
+        assert_eq!(page.notifications.len(), 2);
+        assert_eq!(page.notifications[1].confirmed_count, 1);
+        let next_cursor = page.next_cursor.unwrap();
+        assert_eq!(next_cursor.id, last_id);
+        assert_eq!(next_cursor.created_at, datetime!(2024-01-01 12:00 UTC));
     }
 
     #[tokio::test]
-    async fn delete_notification_no_document_updated() {
+    async fn find_produced_notifications_created_at_range_validation_error() {
         let mut repository = MockNotificationsRepository::new();
-        repository
-            .expect_delete()
-            .returning(|_, _| Err(repository::Error::NoDocumentUpdated));
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_deleted().never();
+        repository.expect_find_many_produced().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
-            .delete_notification(ObjectId::new(), Uuid::from_u128(31985781293))
+            .find_produced_notifications(
+                Uuid::from_u128(8192038102),
+                input::Pagination {
+                    page_idx: None,
+                    page_size: 10,
+                    cursor: None,
+                },
+                input::ProducedNotificationFilters {
+                    created_at_from: Some(datetime!(2024-01-02 00:00 UTC)),
+                    created_at_to: Some(datetime!(2024-01-01 00:00 UTC)),
+                    ..Default::default()
+                },
+            )
             .await;
 
-        assert!(matches!(result, Err(Error::NotificationNotExist)));
+        assert!(matches!(result, Err(Error::Validation(_))));
     }
 
     #[tokio::test]
-    async fn delete_notification_database_error() {
+    async fn find_produced_notification_not_exist() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_delete().returning(|_, _| {
-            Err(repository::Error::Mongo(
-                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
-            ))
-        });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_deleted().never();
+        repository.expect_find_produced().returning(|_, _| Ok(None));
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
-            .delete_notification(ObjectId::new(), Uuid::from_u128(31985781293))
+            .find_produced_notification(Uuid::from_u128(8192038102), 1)
             .await;
 
-        assert!(matches!(result, Err(Error::Database(_))));
+        assert!(matches!(result, Err(Error::NotificationNotExist)));
     }
 
     #[tokio::test]
-    async fn delete_notification_ok() {
+    async fn find_produced_notification_ok() {
         let mut repository = MockNotificationsRepository::new();
-        repository.expect_delete().returning(|_, _| Ok(()));
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service
-            .expect_send_deleted()
-            .once()
-            .returning(|_, _, _| ());
+        repository
+            .expect_find_produced()
+            .withf(|_, producer_notification_id| *producer_notification_id == 1)
+            .returning(|_, _| Ok(Some(produced_notification(OffsetDateTime::now_utc()))));
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
-        let result = service
-            .delete_notification(ObjectId::new(), Uuid::from_u128(31985781293))
-            .await;
+        let notification = service
+            .find_produced_notification(Uuid::from_u128(8192038102), 1)
+            .await
+            .unwrap();
 
-        assert!(result.is_ok());
+        assert_eq!(notification.producer_notification_id, 1);
+        assert_eq!(notification.user_ids, vec![Uuid::from_u128(1)]);
+        assert_eq!(notification.seen_count, 1);
     }
 
     #[tokio::test]
@@ -950,7 +3434,9 @@ mod test {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let passed_invalidate_at = OffsetDateTime::now_utc() - Duration::from_secs(300);
@@ -979,7 +3465,9 @@ mod test {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
@@ -1010,7 +3498,9 @@ mod test {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
@@ -1037,7 +3527,9 @@ mod test {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(MockNotificationsProducerService::new()),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
@@ -1053,20 +3545,160 @@ mod test {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn update_notification_content_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_update_content()
+            .returning(|_, _, _, _, _| Ok(()));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notification_content(
+                ObjectId::new(),
+                Uuid::from_u128(5019283019283),
+                input::NotificationContent {
+                    content_type: "utf-8".to_string(),
+                    content: b"MTIzNA==".to_vec(),
+                    localized_contents: vec![],
+                },
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_notification_content_content_too_large() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_update_content().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig { max_content_len: 4 },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notification_content(
+                ObjectId::new(),
+                Uuid::from_u128(5019283019283),
+                input::NotificationContent {
+                    content_type: "utf-8".to_string(),
+                    content: b"MTIzNA==".to_vec(),
+                    localized_contents: vec![],
+                },
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::ValidationNotificationTooLarge {
+                size: _,
+                max_size: _
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_notification_content_no_document_updated() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_update_content()
+            .returning(|_, _, _, _, _| Err(repository::Error::NoDocumentUpdated));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notification_content(
+                ObjectId::new(),
+                Uuid::from_u128(5019283019283),
+                input::NotificationContent {
+                    content_type: "utf-8".to_string(),
+                    content: b"MTIzNA==".to_vec(),
+                    localized_contents: vec![],
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::NotificationNotExist)));
+    }
+
+    #[tokio::test]
+    async fn update_notification_content_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_update_content()
+            .returning(|_, _, _, _, _| {
+                Err(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                ))
+            });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notification_content(
+                ObjectId::new(),
+                Uuid::from_u128(5019283019283),
+                input::NotificationContent {
+                    content_type: "utf-8".to_string(),
+                    content: b"MTIzNA==".to_vec(),
+                    localized_contents: vec![],
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Database(_))));
+    }
+
     #[tokio::test]
     async fn update_notification_seen_no_document_updated() {
         let mut repository = MockNotificationsRepository::new();
         repository
             .expect_update_confirmation_seen()
             .returning(|_, _, _| Err(repository::Error::NoDocumentUpdated));
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_updated().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
@@ -1090,14 +3722,16 @@ mod test {
                     mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
                 ))
             });
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service.expect_send_updated().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
@@ -1117,17 +3751,16 @@ mod test {
         repository
             .expect_update_confirmation_seen()
             .returning(|_, _, _| Ok(()));
-        let mut notifications_producer_service = MockNotificationsProducerService::new();
-        notifications_producer_service
-            .expect_send_updated()
-            .once()
-            .returning(|_, _, _, _| ());
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
         let service = NotificationsServiceImpl::new(
             NotificationsServiceConfig {
                 max_content_len: usize::MAX,
             },
             Arc::new(repository),
-            Arc::new(notifications_producer_service),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
         );
 
         let result = service
@@ -1140,4 +3773,204 @@ mod test {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn update_notifications_seen_validation_nothing_selected() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_update_many_confirmations_seen().never();
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notifications_seen(
+                Uuid::from_u128(5019283019283),
+                input::NotificationsSeen {
+                    seen: true,
+                    selection: input::NotificationsSelection {
+                        ids: None,
+                        filters: None,
+                    },
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_notifications_seen_validation_empty_ids() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_update_many_confirmations_seen().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notifications_seen(
+                Uuid::from_u128(5019283019283),
+                input::NotificationsSeen {
+                    seen: true,
+                    selection: input::NotificationsSelection {
+                        ids: Some(vec![]),
+                        filters: None,
+                    },
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_notifications_seen_validation_too_many_ids() {
+        let mut repository = MockNotificationsRepository::new();
+        repository.expect_update_many_confirmations_seen().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(MockOutboxRelayService::new()),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notifications_seen(
+                Uuid::from_u128(5019283019283),
+                input::NotificationsSeen {
+                    seen: true,
+                    selection: input::NotificationsSelection {
+                        ids: Some((0..=MAX_SELECTION_IDS).map(|_| ObjectId::new()).collect()),
+                        filters: None,
+                    },
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_notifications_seen_database_error() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_update_many_confirmations_seen()
+            .returning(|_, _, _| {
+                Err(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                ))
+            });
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let result = service
+            .update_notifications_seen(
+                Uuid::from_u128(5019283019283),
+                input::NotificationsSeen {
+                    seen: true,
+                    selection: input::NotificationsSelection {
+                        ids: None,
+                        filters: Some(input::NotificationFilters::default()),
+                    },
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn update_notifications_seen_nothing_updated() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_update_many_confirmations_seen()
+            .returning(|_, _, _| Ok(0));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().never();
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let affected = service
+            .update_notifications_seen(
+                Uuid::from_u128(5019283019283),
+                input::NotificationsSeen {
+                    seen: true,
+                    selection: input::NotificationsSelection {
+                        ids: None,
+                        filters: Some(input::NotificationFilters::default()),
+                    },
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(affected.count, 0);
+    }
+
+    #[tokio::test]
+    async fn update_notifications_seen_ok() {
+        let mut repository = MockNotificationsRepository::new();
+        repository
+            .expect_update_many_confirmations_seen()
+            .returning(|_, _, _| Ok(3));
+        let mut outbox_relay_service = MockOutboxRelayService::new();
+        outbox_relay_service.expect_wake().once().return_const(());
+        let service = NotificationsServiceImpl::new(
+            NotificationsServiceConfig {
+                max_content_len: usize::MAX,
+            },
+            Arc::new(repository),
+            Arc::new(outbox_relay_service),
+            Arc::new(mock_content_types_service()),
+            Arc::new(mock_attachments_service()),
+        );
+
+        let affected = service
+            .update_notifications_seen(
+                Uuid::from_u128(5019283019283),
+                input::NotificationsSeen {
+                    seen: true,
+                    selection: input::NotificationsSelection {
+                        ids: Some(vec![ObjectId::new(), ObjectId::new(), ObjectId::new()]),
+                        filters: None,
+                    },
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(affected.count, 3);
+    }
 }
@@ -0,0 +1,3 @@
+mod outbox_relay_service_config;
+
+pub use outbox_relay_service_config::*;
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+pub struct OutboxRelayServiceConfig {
+    ///
+    /// Time between consecutive checks for pending messages
+    ///
+    pub interval: Duration,
+
+    ///
+    /// Maximum number of messages published at once
+    ///
+    pub batch_size: u32,
+
+    ///
+    /// How long to wait for publisher confirms.
+    /// Messages that were not confirmed in time are published again
+    ///
+    pub confirm_timeout: Duration,
+}
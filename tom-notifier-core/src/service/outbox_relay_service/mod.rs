@@ -0,0 +1,8 @@
+mod dto;
+mod outbox_relay_service;
+mod outbox_relay_service_impl;
+mod outbox_relay_service_worker;
+
+pub use dto::*;
+pub use outbox_relay_service::*;
+pub use outbox_relay_service_impl::*;
@@ -0,0 +1,11 @@
+///
+/// Service that publishes notification state changes saved in the outbox
+///
+#[cfg_attr(test, mockall::automock)]
+pub trait OutboxRelayService: Send + Sync {
+    ///
+    /// Informs service that new messages were saved in the outbox
+    /// so they can be published without waiting for the next interval
+    ///
+    fn wake(&self);
+}
@@ -0,0 +1,62 @@
+use super::{
+    outbox_relay_service_worker::OutboxRelayServiceWorker, OutboxRelayService,
+    OutboxRelayServiceConfig,
+};
+use crate::{
+    repository::OutboxRepository,
+    service::notifications_producer_service::NotificationsProducerService,
+};
+use std::sync::Arc;
+use tokio::{sync::Notify, task::JoinHandle};
+
+pub struct OutboxRelayServiceImpl {
+    wake_notify: Arc<Notify>,
+
+    worker_task: JoinHandle<()>,
+    worker_close_notify: Arc<Notify>,
+}
+
+impl OutboxRelayServiceImpl {
+    pub fn new(
+        config: OutboxRelayServiceConfig,
+        outbox_repository: Arc<dyn OutboxRepository>,
+        notifications_producer_service: Arc<dyn NotificationsProducerService>,
+    ) -> Self {
+        let worker = OutboxRelayServiceWorker::new(
+            config,
+            outbox_repository,
+            notifications_producer_service,
+        );
+
+        let wake_notify = Arc::new(Notify::new());
+        let close_notify = Arc::new(Notify::new());
+
+        let wake_notify_clone = Arc::clone(&wake_notify);
+        let close_notify_clone = Arc::clone(&close_notify);
+        let worker_task = tokio::spawn(async move {
+            tracing::info!("outbox relay service worker started");
+            worker.run(wake_notify_clone, close_notify_clone).await;
+            tracing::info!("outbox relay service worker finished");
+        });
+
+        Self {
+            wake_notify,
+            worker_task,
+            worker_close_notify: close_notify,
+        }
+    }
+
+    pub async fn close(self) {
+        self.worker_close_notify.notify_one();
+        if let Err(err) = self.worker_task.await {
+            // This should never happen
+            tracing::error!(%err, "outbox relay worker task failed");
+        }
+    }
+}
+
+impl OutboxRelayService for OutboxRelayServiceImpl {
+    fn wake(&self) {
+        self.wake_notify.notify_one();
+    }
+}
@@ -0,0 +1,647 @@
+use super::OutboxRelayServiceConfig;
+use crate::{
+    repository::{self, OutboxMessage, OutboxMessageStatus, OutboxRepository},
+    service::notifications_producer_service::{
+        NewNotification, NotificationsProducerService, UpdatedNotification,
+    },
+};
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tokio::{
+    sync::{oneshot, Notify},
+    time::{interval, sleep_until, timeout_at, Instant, Interval, MissedTickBehavior},
+};
+
+pub struct OutboxRelayServiceWorker {
+    config: OutboxRelayServiceConfig,
+    outbox_repository: Arc<dyn OutboxRepository>,
+    notifications_producer_service: Arc<dyn NotificationsProducerService>,
+
+    interval: Interval,
+}
+
+impl OutboxRelayServiceWorker {
+    pub fn new(
+        config: OutboxRelayServiceConfig,
+        outbox_repository: Arc<dyn OutboxRepository>,
+        notifications_producer_service: Arc<dyn NotificationsProducerService>,
+    ) -> Self {
+        let mut interval = interval(config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        Self {
+            config,
+            outbox_repository,
+            notifications_producer_service,
+            interval,
+        }
+    }
+
+    #[tracing::instrument(name = "Outbox Relay", skip_all)]
+    pub async fn run(mut self, wake_notify: Arc<Notify>, close_notify: Arc<Notify>) {
+        tokio::select! {
+            biased;
+
+            // Wait for signal to close
+            _ = close_notify.notified() => {},
+
+            // Run infinite loop and publish pending messages
+            // periodically, when woken up or when scheduled message is due
+            _ = async {
+                let mut next_scheduled = None;
+                loop {
+                    let scheduled = async move {
+                        match next_scheduled {
+                            Some(deadline) => sleep_until(deadline).await,
+                            None => std::future::pending().await,
+                        }
+                    };
+
+                    tokio::select! {
+                        _ = self.interval.tick() => {},
+                        _ = wake_notify.notified() => {},
+                        _ = scheduled => {},
+                    }
+
+                    self.relay_pending().await;
+                    next_scheduled = self.next_scheduled().await;
+                }
+            } => {}
+        }
+    }
+
+    async fn relay_pending(&self) {
+        tracing::debug!("relaying pending messages");
+
+        loop {
+            match self.relay_batch().await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(err) => {
+                    tracing::warn!(%err, "failed to relay pending messages");
+                    break;
+                }
+            }
+        }
+
+        tracing::debug!("relayed pending messages");
+    }
+
+    ///
+    /// Finds when the next scheduled message becomes due.
+    /// Messages scheduled by other instances of the application
+    /// that are not known yet are published on the next interval
+    ///
+    async fn next_scheduled(&self) -> Option<Instant> {
+        let publish_at = match self.outbox_repository.find_next_publish_at().await {
+            Ok(publish_at) => publish_at?,
+            Err(err) => {
+                tracing::warn!(%err, "failed to find next scheduled message");
+                return None;
+            }
+        };
+
+        // Negative durations mean message is already due
+        let until_publish: std::time::Duration = (publish_at - OffsetDateTime::now_utc())
+            .try_into()
+            .unwrap_or_default();
+        tracing::debug!(%publish_at, "next scheduled message");
+
+        Some(Instant::now() + until_publish)
+    }
+
+    ///
+    /// Publishes single batch of pending messages
+    ///
+    /// ### Returns
+    /// true when the whole batch was published and there might be more pending messages
+    ///
+    async fn relay_batch(&self) -> Result<bool, repository::Error> {
+        let messages = self
+            .outbox_repository
+            .lock_pending(self.config.batch_size, self.config.confirm_timeout)
+            .await?;
+        if messages.is_empty() {
+            return Ok(false);
+        }
+
+        let count = messages.len();
+        tracing::info!(count, "publishing outbox messages");
+
+        let mut confirms = Vec::with_capacity(count);
+        for message in messages {
+            let id = message.id;
+            let confirm_rx = self.publish(message).await;
+            confirms.push((id, confirm_rx));
+        }
+
+        // Messages are locked for confirm_timeout so there's no reason
+        // to wait any longer. Other relay will publish them again
+        let deadline = Instant::now() + self.config.confirm_timeout;
+        let mut published = 0;
+        for (id, confirm_rx) in confirms {
+            match timeout_at(deadline, confirm_rx).await {
+                Ok(Ok(())) => match self.outbox_repository.mark_published(id).await {
+                    Ok(()) => published += 1,
+                    Err(repository::Error::NoDocumentUpdated) => {
+                        tracing::debug!(id = id.to_hex(), "message already marked as published");
+                        published += 1;
+                    }
+                    Err(err) => {
+                        tracing::warn!(id = id.to_hex(), %err, "failed to mark message as published")
+                    }
+                },
+                Ok(Err(_)) => tracing::warn!(id = id.to_hex(), "producer closed before confirm"),
+                Err(_) => tracing::warn!(id = id.to_hex(), "message not confirmed in time"),
+            }
+        }
+
+        tracing::info!(count, published, "published outbox messages");
+
+        Ok(published == count && count == self.config.batch_size as usize)
+    }
+
+    async fn publish(&self, message: OutboxMessage) -> oneshot::Receiver<()> {
+        // Empty user_ids would make the message a broadcast, so message
+        // of groups without members or topic without subscribers is not sent at all
+        if message.user_ids.is_empty() && (!message.groups.is_empty() || message.topic.is_some()) {
+            tracing::debug!(id = message.id.to_hex(), "message has no recipients");
+            return Self::not_sent();
+        }
+
+        match message.status {
+            OutboxMessageStatus::New => {
+                self.notifications_producer_service
+                    .send_new(NewNotification {
+                        user_ids: message.user_ids,
+                        topic: message.topic,
+                        excluded_user_ids: message.excluded_user_ids,
+                        id: message.notification_id,
+                        timestamp: message.timestamp,
+                        created_by: message.created_by.unwrap_or_default(),
+                        seen: message.seen.unwrap_or_default(),
+                        content_type: message.content_type.unwrap_or_default(),
+                        content: message.content.unwrap_or_default(),
+                        priority: message.priority.unwrap_or_default(),
+                        localized_contents: message.localized_contents,
+                    })
+                    .await
+            }
+            OutboxMessageStatus::Updated if !message.notification_ids.is_empty() => {
+                // Message would be relayed again and again, so it is dropped
+                let Some(&user_id) = message.user_ids.first() else {
+                    tracing::warn!(
+                        id = message.id.to_hex(),
+                        "seen message has no user, dropping it"
+                    );
+                    return Self::not_sent();
+                };
+
+                self.notifications_producer_service
+                    .send_updated_many_seen(
+                        user_id,
+                        message.notification_ids,
+                        message.seen.unwrap_or_default(),
+                        message.timestamp,
+                    )
+                    .await
+            }
+            OutboxMessageStatus::Updated => {
+                self.notifications_producer_service
+                    .send_updated(UpdatedNotification {
+                        user_ids: message.user_ids,
+                        topic: message.topic,
+                        id: message.notification_id,
+                        seen: message.seen,
+                        content_type: message.content_type,
+                        content: message.content,
+                        localized_contents: message.localized_contents,
+                        timestamp: message.timestamp,
+                    })
+                    .await
+            }
+            OutboxMessageStatus::Deleted => {
+                self.notifications_producer_service
+                    .send_deleted(
+                        message.user_ids,
+                        message.topic,
+                        message.notification_id,
+                        message.timestamp,
+                    )
+                    .await
+            }
+        }
+    }
+
+    ///
+    /// Creates confirm of the message that is not sent at all,
+    /// so it is marked as published and not relayed again
+    ///
+    fn not_sent() -> oneshot::Receiver<()> {
+        let (confirm_tx, confirm_rx) = oneshot::channel();
+        let _ = confirm_tx.send(());
+
+        confirm_rx
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        dto::output::NotificationPriority, repository::MockOutboxRepository,
+        service::notifications_producer_service::MockNotificationsProducerService,
+    };
+    use bson::oid::ObjectId;
+    use std::time::Duration;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    fn config(batch_size: u32) -> OutboxRelayServiceConfig {
+        OutboxRelayServiceConfig {
+            interval: Duration::from_secs(60),
+            batch_size,
+            confirm_timeout: Duration::from_millis(100),
+        }
+    }
+
+    fn outbox_message(status: OutboxMessageStatus) -> OutboxMessage {
+        OutboxMessage {
+            id: ObjectId::new(),
+            status,
+            user_ids: vec![Uuid::from_u128(1)],
+            groups: vec![],
+            topic: None,
+            excluded_user_ids: vec![],
+            notification_id: ObjectId::new(),
+            notification_ids: vec![],
+            priority: None,
+            timestamp: OffsetDateTime::now_utc(),
+            created_by: Some(Uuid::from_u128(2)),
+            seen: Some(false),
+            content_type: Some("utf-8".to_string()),
+            content: Some(b"data".to_vec()),
+            localized_contents: vec![],
+        }
+    }
+
+    fn confirmed() -> oneshot::Receiver<()> {
+        let (confirm_tx, confirm_rx) = oneshot::channel();
+        confirm_tx.send(()).unwrap();
+        confirm_rx
+    }
+
+    fn not_confirmed() -> oneshot::Receiver<()> {
+        let (_, confirm_rx) = oneshot::channel();
+        confirm_rx
+    }
+
+    #[tokio::test]
+    async fn relay_batch_publish_by_status() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository.expect_lock_pending().return_once(|_, _| {
+            Ok(vec![
+                outbox_message(OutboxMessageStatus::New),
+                outbox_message(OutboxMessageStatus::Updated),
+                outbox_message(OutboxMessageStatus::Deleted),
+            ])
+        });
+        outbox_repository
+            .expect_mark_published()
+            .times(3)
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service
+            .expect_send_new()
+            .once()
+            .returning(|_| confirmed());
+        notifications_producer_service
+            .expect_send_updated()
+            .once()
+            .returning(|_| confirmed());
+        notifications_producer_service
+            .expect_send_deleted()
+            .once()
+            .returning(|_, _, _, _| confirmed());
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_many_seen_published_as_single_message() {
+        let ids = vec![ObjectId::new(), ObjectId::new()];
+        let mut message = outbox_message(OutboxMessageStatus::Updated);
+        message.notification_id = ids[0];
+        message.notification_ids = ids.clone();
+        message.seen = Some(true);
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![message]));
+        outbox_repository
+            .expect_mark_published()
+            .once()
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service
+            .expect_send_updated_many_seen()
+            .once()
+            .withf(move |&user_id, sent_ids, &seen, _| {
+                user_id == Uuid::from_u128(1) && *sent_ids == ids && seen
+            })
+            .returning(|_, _, _, _| confirmed());
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_many_seen_without_user_not_sent() {
+        let mut message = outbox_message(OutboxMessageStatus::Updated);
+        message.user_ids = vec![];
+        message.notification_ids = vec![ObjectId::new(), ObjectId::new()];
+        message.seen = Some(true);
+        let message_id = message.id;
+
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![message]));
+        outbox_repository
+            .expect_mark_published()
+            .withf(move |id| *id == message_id)
+            .once()
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service
+            .expect_send_updated_many_seen()
+            .never();
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_full_batch_published() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository.expect_lock_pending().return_once(|_, _| {
+            Ok(vec![
+                outbox_message(OutboxMessageStatus::Deleted),
+                outbox_message(OutboxMessageStatus::Deleted),
+            ])
+        });
+        outbox_repository
+            .expect_mark_published()
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service
+            .expect_send_deleted()
+            .times(2)
+            .returning(|_, _, _, _| confirmed());
+        let worker = OutboxRelayServiceWorker::new(
+            config(2),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(true)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_not_confirmed_not_marked_published() {
+        let message = outbox_message(OutboxMessageStatus::Deleted);
+        let confirmed_message = outbox_message(OutboxMessageStatus::Deleted);
+        let confirmed_message_id = confirmed_message.id;
+        let confirmed_notification_id = confirmed_message.notification_id;
+
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![message, confirmed_message]));
+        outbox_repository
+            .expect_mark_published()
+            .withf(move |id| *id == confirmed_message_id)
+            .once()
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service
+            .expect_send_deleted()
+            .times(2)
+            .returning(move |_, _, notification_id, _| match notification_id {
+                id if id == confirmed_notification_id => confirmed(),
+                _ => not_confirmed(),
+            });
+        let worker = OutboxRelayServiceWorker::new(
+            config(2),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_groups_without_members_not_sent() {
+        let mut message = outbox_message(OutboxMessageStatus::New);
+        message.user_ids = vec![];
+        message.groups = vec!["admins".to_string()];
+        let message_id = message.id;
+
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![message]));
+        outbox_repository
+            .expect_mark_published()
+            .withf(move |id| *id == message_id)
+            .once()
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service.expect_send_new().never();
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_topic_without_subscribers_not_sent() {
+        let mut message = outbox_message(OutboxMessageStatus::New);
+        message.user_ids = vec![];
+        message.topic = Some("billing".to_string());
+        let message_id = message.id;
+
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![message]));
+        outbox_repository
+            .expect_mark_published()
+            .withf(move |id| *id == message_id)
+            .once()
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service.expect_send_new().never();
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_new_message_priority_sent() {
+        let mut message = outbox_message(OutboxMessageStatus::New);
+        message.priority = Some(NotificationPriority::Urgent);
+
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![message]));
+        outbox_repository
+            .expect_mark_published()
+            .once()
+            .returning(|_| Ok(()));
+        let mut notifications_producer_service = MockNotificationsProducerService::new();
+        notifications_producer_service
+            .expect_send_new()
+            .withf(|notification| notification.priority == NotificationPriority::Urgent)
+            .once()
+            .returning(|_| confirmed());
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(notifications_producer_service),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_nothing_pending() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![]));
+        outbox_repository.expect_mark_published().never();
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(MockNotificationsProducerService::new()),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn next_scheduled_in_the_future() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_find_next_publish_at()
+            .return_once(|| Ok(Some(OffsetDateTime::now_utc() + Duration::from_secs(60))));
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(MockNotificationsProducerService::new()),
+        );
+
+        let next_scheduled = worker.next_scheduled().await.unwrap();
+
+        assert!(next_scheduled > Instant::now() + Duration::from_secs(50));
+        assert!(next_scheduled <= Instant::now() + Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn next_scheduled_already_due() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_find_next_publish_at()
+            .return_once(|| Ok(Some(OffsetDateTime::now_utc() - Duration::from_secs(60))));
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(MockNotificationsProducerService::new()),
+        );
+
+        let next_scheduled = worker.next_scheduled().await.unwrap();
+
+        assert!(next_scheduled <= Instant::now());
+    }
+
+    #[tokio::test]
+    async fn next_scheduled_database_error() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository
+            .expect_find_next_publish_at()
+            .return_once(|| {
+                Err(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                ))
+            });
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(MockNotificationsProducerService::new()),
+        );
+
+        let next_scheduled = worker.next_scheduled().await;
+
+        assert!(next_scheduled.is_none());
+    }
+
+    #[tokio::test]
+    async fn relay_batch_database_error() {
+        let mut outbox_repository = MockOutboxRepository::new();
+        outbox_repository.expect_lock_pending().return_once(|_, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let worker = OutboxRelayServiceWorker::new(
+            config(10),
+            Arc::new(outbox_repository),
+            Arc::new(MockNotificationsProducerService::new()),
+        );
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Err(repository::Error::Mongo(_))));
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{
+    dto::{input, output},
+    error::Error,
+};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PreferencesService: Send + Sync {
+    ///
+    /// Finds notification preferences of the user
+    ///
+    /// ### Returns
+    /// Default preferences without mutes and quiet hours
+    /// when user has never saved preferences
+    ///
+    async fn find_preferences(&self, user_id: Uuid) -> Result<output::Preferences, Error>;
+
+    ///
+    /// Replaces notification preferences of the user.
+    /// Notifications of muted producers and content types are hidden,
+    /// notifications are not pushed to the user in quiet hours
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - there are too many muted producers or content types
+    ///     - muted content type is empty
+    ///     - quiet hours are not in "HH:MM" format or are empty
+    ///     - quiet hours utc_offset is out of range
+    ///
+    async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: input::Preferences,
+    ) -> Result<(), Error>;
+}
@@ -0,0 +1,327 @@
+use super::PreferencesService;
+use crate::{
+    dto::{input, output},
+    error::Error,
+    repository::{self, PreferencesRepository},
+};
+use axum::async_trait;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Maximum number of muted producers
+///
+pub const MAX_MUTED_PRODUCERS: usize = 1000;
+
+///
+/// Maximum number of muted content types
+///
+pub const MAX_MUTED_CONTENT_TYPES: usize = 100;
+
+///
+/// Maximum absolute offset of user's local time from UTC in minutes
+///
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+pub struct PreferencesServiceImpl {
+    repository: Arc<dyn PreferencesRepository>,
+}
+
+impl PreferencesServiceImpl {
+    pub fn new(repository: Arc<dyn PreferencesRepository>) -> Self {
+        Self { repository }
+    }
+
+    fn validate_muted(preferences: &input::Preferences) -> Result<(), Error> {
+        if preferences.muted_producers.len() > MAX_MUTED_PRODUCERS {
+            return Err(Error::Validation("too many muted producers"));
+        }
+        if preferences.muted_content_types.len() > MAX_MUTED_CONTENT_TYPES {
+            return Err(Error::Validation("too many muted content types"));
+        }
+        if preferences
+            .muted_content_types
+            .iter()
+            .any(|content_type| content_type.is_empty())
+        {
+            return Err(Error::Validation("muted content type cannot be empty"));
+        }
+
+        Ok(())
+    }
+
+    fn parse_quiet_hours(quiet_hours: input::QuietHours) -> Result<repository::QuietHours, Error> {
+        let from_minute = Self::parse_minute_of_day(&quiet_hours.from).ok_or(Error::Validation(
+            "quiet hours from must be in HH:MM format",
+        ))?;
+        let to_minute = Self::parse_minute_of_day(&quiet_hours.to)
+            .ok_or(Error::Validation("quiet hours to must be in HH:MM format"))?;
+
+        if from_minute == to_minute {
+            return Err(Error::Validation("quiet hours cannot be empty"));
+        }
+        if quiet_hours.utc_offset.abs() > MAX_UTC_OFFSET_MINUTES {
+            return Err(Error::Validation("quiet hours utc_offset out of range"));
+        }
+
+        Ok(repository::QuietHours {
+            from_minute,
+            to_minute,
+            utc_offset_minutes: quiet_hours.utc_offset,
+        })
+    }
+
+    fn parse_minute_of_day(time: &str) -> Option<u32> {
+        let (hour, minute) = time.split_once(':')?;
+        if hour.len() != 2 || minute.len() != 2 {
+            return None;
+        }
+
+        let hour = hour.parse::<u32>().ok().filter(|hour| *hour < 24)?;
+        let minute = minute.parse::<u32>().ok().filter(|minute| *minute < 60)?;
+
+        Some(hour * 60 + minute)
+    }
+}
+
+#[async_trait]
+impl PreferencesService for PreferencesServiceImpl {
+    async fn find_preferences(&self, user_id: Uuid) -> Result<output::Preferences, Error> {
+        tracing::info!(%user_id, "finding preferences");
+
+        let preferences = self
+            .repository
+            .find(user_id)
+            .await?
+            .map(output::Preferences::from)
+            .unwrap_or_default();
+
+        tracing::info!("found preferences");
+
+        Ok(preferences)
+    }
+
+    async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: input::Preferences,
+    ) -> Result<(), Error> {
+        tracing::info!(%user_id, "updating preferences");
+
+        Self::validate_muted(&preferences)?;
+        let quiet_hours = preferences
+            .quiet_hours
+            .map(Self::parse_quiet_hours)
+            .transpose()?;
+
+        self.repository
+            .upsert(
+                user_id,
+                repository::Preferences {
+                    muted_producers: preferences.muted_producers,
+                    muted_content_types: preferences.muted_content_types,
+                    quiet_hours,
+                    updated_at: OffsetDateTime::now_utc(),
+                },
+            )
+            .await?;
+
+        tracing::info!("updated preferences");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use repository::MockPreferencesRepository;
+
+    fn preferences(quiet_hours: Option<input::QuietHours>) -> input::Preferences {
+        input::Preferences {
+            muted_producers: vec![Uuid::from_u128(1)],
+            muted_content_types: vec!["marketing".to_string()],
+            quiet_hours,
+        }
+    }
+
+    fn quiet_hours(from: &str, to: &str, utc_offset: i32) -> input::QuietHours {
+        input::QuietHours {
+            from: from.to_string(),
+            to: to.to_string(),
+            utc_offset,
+        }
+    }
+
+    #[tokio::test]
+    async fn find_preferences_not_saved_default() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_find().returning(|_| Ok(None));
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let preferences = service.find_preferences(Uuid::new_v4()).await.unwrap();
+
+        assert!(preferences.muted_producers.is_empty());
+        assert!(preferences.muted_content_types.is_empty());
+        assert!(preferences.quiet_hours.is_none());
+        assert!(preferences.updated_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn find_preferences_quiet_hours_formatted() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_find().returning(|_| {
+            Ok(Some(repository::Preferences {
+                muted_producers: vec![],
+                muted_content_types: vec![],
+                quiet_hours: Some(repository::QuietHours {
+                    from_minute: 22 * 60 + 30,
+                    to_minute: 7 * 60,
+                    utc_offset_minutes: 60,
+                }),
+                updated_at: OffsetDateTime::now_utc(),
+            }))
+        });
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let preferences = service.find_preferences(Uuid::new_v4()).await.unwrap();
+
+        let quiet_hours = preferences.quiet_hours.unwrap();
+        assert_eq!(quiet_hours.from, "22:30");
+        assert_eq!(quiet_hours.to, "07:00");
+        assert_eq!(quiet_hours.utc_offset, 60);
+    }
+
+    #[tokio::test]
+    async fn update_preferences_ok() {
+        let user_id = Uuid::new_v4();
+
+        let mut repository = MockPreferencesRepository::new();
+        repository
+            .expect_upsert()
+            .withf(move |id, preferences| {
+                *id == user_id
+                    && preferences.muted_producers == vec![Uuid::from_u128(1)]
+                    && preferences.muted_content_types == vec!["marketing"]
+                    && preferences.quiet_hours
+                        == Some(repository::QuietHours {
+                            from_minute: 22 * 60,
+                            to_minute: 7 * 60 + 30,
+                            utc_offset_minutes: -300,
+                        })
+            })
+            .once()
+            .returning(|_, _| Ok(()));
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let update_result = service
+            .update_preferences(
+                user_id,
+                preferences(Some(quiet_hours("22:00", "07:30", -300))),
+            )
+            .await;
+
+        assert!(update_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_preferences_validation_too_many_muted_producers() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_upsert().never();
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let mut preferences = preferences(None);
+        preferences.muted_producers = vec![Uuid::new_v4(); MAX_MUTED_PRODUCERS + 1];
+
+        let update_result = service
+            .update_preferences(Uuid::new_v4(), preferences)
+            .await;
+
+        assert!(matches!(update_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_preferences_validation_muted_content_type_empty() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_upsert().never();
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let mut preferences = preferences(None);
+        preferences.muted_content_types = vec!["".to_string()];
+
+        let update_result = service
+            .update_preferences(Uuid::new_v4(), preferences)
+            .await;
+
+        assert!(matches!(update_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_preferences_validation_quiet_hours_invalid_format() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_upsert().never();
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        for (from, to) in [("22", "07:00"), ("24:00", "07:00"), ("22:00", "7:60")] {
+            let update_result = service
+                .update_preferences(Uuid::new_v4(), preferences(Some(quiet_hours(from, to, 0))))
+                .await;
+
+            assert!(matches!(update_result, Err(Error::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn update_preferences_validation_quiet_hours_empty() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_upsert().never();
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let update_result = service
+            .update_preferences(
+                Uuid::new_v4(),
+                preferences(Some(quiet_hours("22:00", "22:00", 0))),
+            )
+            .await;
+
+        assert!(matches!(update_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_preferences_validation_utc_offset_out_of_range() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_upsert().never();
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let update_result = service
+            .update_preferences(
+                Uuid::new_v4(),
+                preferences(Some(quiet_hours(
+                    "22:00",
+                    "07:00",
+                    MAX_UTC_OFFSET_MINUTES + 1,
+                ))),
+            )
+            .await;
+
+        assert!(matches!(update_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_preferences_database_error() {
+        let mut repository = MockPreferencesRepository::new();
+        repository.expect_upsert().returning(|_, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let service = PreferencesServiceImpl::new(Arc::new(repository));
+
+        let update_result = service
+            .update_preferences(Uuid::new_v4(), preferences(None))
+            .await;
+
+        assert!(matches!(update_result, Err(Error::Database(_))));
+    }
+}
@@ -0,0 +1,5 @@
+mod purge_report;
+mod retention_service_config;
+
+pub use purge_report::*;
+pub use retention_service_config::*;
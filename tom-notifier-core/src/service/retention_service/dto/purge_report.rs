@@ -0,0 +1,10 @@
+///
+/// Number of notifications and attachments deleted by single purge
+///
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PurgeReport {
+    pub expired: u64,
+    pub invalidated: u64,
+    pub deleted: u64,
+    pub orphaned_attachments: u64,
+}
@@ -0,0 +1,43 @@
+use std::{collections::HashMap, time::Duration};
+use uuid::Uuid;
+
+pub struct RetentionServiceConfig {
+    ///
+    /// Time between consecutive purges
+    ///
+    pub interval: Duration,
+
+    ///
+    /// Maximum number of notifications deleted at once
+    ///
+    pub batch_size: u32,
+
+    ///
+    /// Notifications older than max_age are deleted regardless of their state
+    ///
+    pub max_age: Duration,
+
+    ///
+    /// max_age of notifications created by specific producers,
+    /// overrides default max_age
+    ///
+    pub producers_max_age: HashMap<Uuid, Duration>,
+
+    ///
+    /// How long notifications are kept after their invalidate_at passes.
+    /// Notifications still listed as delivered to any user are kept longer
+    ///
+    pub invalidated_retention: Duration,
+
+    ///
+    /// How long notifications are kept after being retracted.
+    /// Notifications deleted by all of their recipients are kept
+    /// for the same time since their creation
+    ///
+    pub deleted_retention: Duration,
+
+    ///
+    /// How long uploaded attachments are kept when no notification references them
+    ///
+    pub orphaned_attachments_retention: Duration,
+}
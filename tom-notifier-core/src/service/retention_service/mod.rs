@@ -0,0 +1,6 @@
+mod dto;
+mod retention_service;
+mod retention_service_worker;
+
+pub use dto::*;
+pub use retention_service::*;
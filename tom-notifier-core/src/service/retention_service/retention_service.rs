@@ -0,0 +1,44 @@
+use super::{retention_service_worker::RetentionServiceWorker, RetentionServiceConfig};
+use crate::repository::NotificationsRepository;
+use std::sync::Arc;
+use tokio::{sync::Notify, task::JoinHandle};
+
+///
+/// Service that periodically deletes notifications
+/// that should not be kept anymore
+///
+pub struct RetentionService {
+    worker_task: JoinHandle<()>,
+    worker_close_notify: Arc<Notify>,
+}
+
+impl RetentionService {
+    pub fn new(
+        config: RetentionServiceConfig,
+        notifications_repository: Arc<dyn NotificationsRepository>,
+    ) -> Self {
+        let worker = RetentionServiceWorker::new(config, notifications_repository);
+
+        let close_notify = Arc::new(Notify::new());
+
+        let close_notify_clone = Arc::clone(&close_notify);
+        let worker_task = tokio::spawn(async move {
+            tracing::info!("retention service worker started");
+            worker.run(close_notify_clone).await;
+            tracing::info!("retention service worker finished");
+        });
+
+        Self {
+            worker_task,
+            worker_close_notify: close_notify,
+        }
+    }
+
+    pub async fn close(self) {
+        self.worker_close_notify.notify_one();
+        if let Err(err) = self.worker_task.await {
+            // This should never happen
+            tracing::error!(%err, "retention worker task failed");
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use super::{PurgeReport, RetentionServiceConfig};
+use crate::repository::{self, NotificationsRepository, PurgeProducers};
+use std::{future::Future, sync::Arc};
+use time::OffsetDateTime;
+use tokio::{
+    sync::Notify,
+    time::{interval, Interval, MissedTickBehavior},
+};
+
+pub struct RetentionServiceWorker {
+    config: RetentionServiceConfig,
+    notifications_repository: Arc<dyn NotificationsRepository>,
+
+    interval: Interval,
+}
+
+impl RetentionServiceWorker {
+    pub fn new(
+        config: RetentionServiceConfig,
+        notifications_repository: Arc<dyn NotificationsRepository>,
+    ) -> Self {
+        let mut interval = interval(config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        Self {
+            config,
+            notifications_repository,
+            interval,
+        }
+    }
+
+    #[tracing::instrument(name = "Retention", skip_all)]
+    pub async fn run(mut self, close_notify: Arc<Notify>) {
+        tokio::select! {
+            biased;
+
+            // Wait for signal to close
+            _ = close_notify.notified() => {},
+
+            // Run infinite loop and purge notifications periodically
+            _ = async { loop {
+                self.interval.tick().await;
+                self.purge().await;
+            }} => {}
+        }
+    }
+
+    ///
+    /// Deletes notifications and attachments that should not be kept anymore
+    /// according to retention configuration
+    ///
+    /// ### Returns
+    /// number of deleted notifications and attachments by reason
+    ///
+    async fn purge(&self) -> PurgeReport {
+        tracing::debug!("purging notifications");
+
+        let now = OffsetDateTime::now_utc();
+        let limit = self.config.batch_size;
+
+        let mut expired = 0;
+        for (&producer_id, &max_age) in &self.config.producers_max_age {
+            let created_before = now - max_age;
+            expired += self
+                .purge_in_batches("expired", || {
+                    self.notifications_repository.purge_created_before(
+                        created_before,
+                        PurgeProducers::Only(producer_id),
+                        limit,
+                    )
+                })
+                .await;
+        }
+
+        let producer_ids = self
+            .config
+            .producers_max_age
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        let created_before = now - self.config.max_age;
+        expired += self
+            .purge_in_batches("expired", || {
+                self.notifications_repository.purge_created_before(
+                    created_before,
+                    PurgeProducers::AllExcept(producer_ids.clone()),
+                    limit,
+                )
+            })
+            .await;
+
+        let invalidated_before = now - self.config.invalidated_retention;
+        let invalidated = self
+            .purge_in_batches("invalidated", || {
+                self.notifications_repository
+                    .purge_invalidated_before(invalidated_before, limit)
+            })
+            .await;
+
+        let deleted_before = now - self.config.deleted_retention;
+        let deleted = self
+            .purge_in_batches("deleted", || {
+                self.notifications_repository
+                    .purge_deleted_before(deleted_before, limit)
+            })
+            .await;
+
+        let uploaded_before = now - self.config.orphaned_attachments_retention;
+        let orphaned_attachments = self
+            .purge_in_batches("orphaned attachments", || {
+                self.notifications_repository
+                    .purge_orphaned_attachments(uploaded_before, limit)
+            })
+            .await;
+
+        let report = PurgeReport {
+            expired,
+            invalidated,
+            deleted,
+            orphaned_attachments,
+        };
+        match report == PurgeReport::default() {
+            true => tracing::debug!("nothing to purge"),
+            false => tracing::info!(
+                expired,
+                invalidated,
+                deleted,
+                orphaned_attachments,
+                "purged notifications"
+            ),
+        }
+
+        report
+    }
+
+    ///
+    /// Purges batches until a batch is not full. Purging stops on the first
+    /// error, remaining notifications are purged on the next run
+    ///
+    /// ### Returns
+    /// number of deleted notifications or attachments
+    ///
+    async fn purge_in_batches<F, Fut>(&self, reason: &'static str, purge_batch: F) -> u64
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<u64, repository::Error>>,
+    {
+        let mut purged = 0;
+        loop {
+            match purge_batch().await {
+                Ok(count) => {
+                    purged += count;
+                    if count < self.config.batch_size as u64 {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(reason, %err, "failed to purge notifications");
+                    break;
+                }
+            }
+        }
+
+        purged
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repository::MockNotificationsRepository;
+    use std::{collections::HashMap, time::Duration};
+    use uuid::Uuid;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn config(
+        batch_size: u32,
+        producers_max_age: HashMap<Uuid, Duration>,
+    ) -> RetentionServiceConfig {
+        RetentionServiceConfig {
+            interval: Duration::from_secs(60),
+            batch_size,
+            max_age: 365 * DAY,
+            producers_max_age,
+            invalidated_retention: 30 * DAY,
+            deleted_retention: 7 * DAY,
+            orphaned_attachments_retention: DAY,
+        }
+    }
+
+    fn is_about(datetime: &OffsetDateTime, expected: OffsetDateTime) -> bool {
+        (*datetime - expected).abs() < time::Duration::seconds(10)
+    }
+
+    #[tokio::test]
+    async fn purge_reports_counts() {
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_purge_created_before()
+            .withf(|created_before, producers, _| {
+                is_about(created_before, OffsetDateTime::now_utc() - 365 * DAY)
+                    && *producers == PurgeProducers::AllExcept(vec![])
+            })
+            .once()
+            .returning(|_, _, _| Ok(3));
+        notifications_repository
+            .expect_purge_invalidated_before()
+            .withf(|invalidated_before, _| {
+                is_about(invalidated_before, OffsetDateTime::now_utc() - 30 * DAY)
+            })
+            .once()
+            .returning(|_, _| Ok(2));
+        notifications_repository
+            .expect_purge_deleted_before()
+            .withf(|deleted_before, _| {
+                is_about(deleted_before, OffsetDateTime::now_utc() - 7 * DAY)
+            })
+            .once()
+            .returning(|_, _| Ok(1));
+        notifications_repository
+            .expect_purge_orphaned_attachments()
+            .withf(|uploaded_before, _| is_about(uploaded_before, OffsetDateTime::now_utc() - DAY))
+            .once()
+            .returning(|_, _| Ok(4));
+        let worker = RetentionServiceWorker::new(
+            config(100, HashMap::new()),
+            Arc::new(notifications_repository),
+        );
+
+        let report = worker.purge().await;
+
+        assert_eq!(
+            report,
+            PurgeReport {
+                expired: 3,
+                invalidated: 2,
+                deleted: 1,
+                orphaned_attachments: 4,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn purge_producers_max_age() {
+        let producer_id = Uuid::from_u128(4812903);
+        let producers_max_age = HashMap::from([(producer_id, 10 * DAY)]);
+
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_purge_created_before()
+            .withf(move |created_before, producers, _| {
+                is_about(created_before, OffsetDateTime::now_utc() - 10 * DAY)
+                    && *producers == PurgeProducers::Only(producer_id)
+            })
+            .once()
+            .returning(|_, _, _| Ok(4));
+        notifications_repository
+            .expect_purge_created_before()
+            .withf(move |created_before, producers, _| {
+                is_about(created_before, OffsetDateTime::now_utc() - 365 * DAY)
+                    && *producers == PurgeProducers::AllExcept(vec![producer_id])
+            })
+            .once()
+            .returning(|_, _, _| Ok(1));
+        notifications_repository
+            .expect_purge_invalidated_before()
+            .returning(|_, _| Ok(0));
+        notifications_repository
+            .expect_purge_deleted_before()
+            .returning(|_, _| Ok(0));
+        notifications_repository
+            .expect_purge_orphaned_attachments()
+            .returning(|_, _| Ok(0));
+        let worker = RetentionServiceWorker::new(
+            config(100, producers_max_age),
+            Arc::new(notifications_repository),
+        );
+
+        let report = worker.purge().await;
+
+        assert_eq!(report.expired, 5);
+    }
+
+    #[tokio::test]
+    async fn purge_in_batches_until_batch_not_full() {
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_purge_created_before()
+            .returning(|_, _, _| Ok(0));
+        let mut counts = vec![2, 2, 1].into_iter();
+        notifications_repository
+            .expect_purge_invalidated_before()
+            .withf(|_, limit| *limit == 2)
+            .times(3)
+            .returning(move |_, _| Ok(counts.next().unwrap()));
+        notifications_repository
+            .expect_purge_deleted_before()
+            .returning(|_, _| Ok(0));
+        notifications_repository
+            .expect_purge_orphaned_attachments()
+            .returning(|_, _| Ok(0));
+        let worker = RetentionServiceWorker::new(
+            config(2, HashMap::new()),
+            Arc::new(notifications_repository),
+        );
+
+        let report = worker.purge().await;
+
+        assert_eq!(report.invalidated, 5);
+    }
+
+    #[tokio::test]
+    async fn purge_database_error_other_reasons_purged() {
+        let mut notifications_repository = MockNotificationsRepository::new();
+        notifications_repository
+            .expect_purge_created_before()
+            .returning(|_, _, _| Ok(1));
+        notifications_repository
+            .expect_purge_invalidated_before()
+            .once()
+            .return_once(|_, _| {
+                Err(repository::Error::Mongo(
+                    mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+                ))
+            });
+        notifications_repository
+            .expect_purge_deleted_before()
+            .returning(|_, _| Ok(1));
+        notifications_repository
+            .expect_purge_orphaned_attachments()
+            .returning(|_, _| Ok(1));
+        let worker = RetentionServiceWorker::new(
+            config(100, HashMap::new()),
+            Arc::new(notifications_repository),
+        );
+
+        let report = worker.purge().await;
+
+        assert_eq!(
+            report,
+            PurgeReport {
+                expired: 1,
+                invalidated: 0,
+                deleted: 1,
+                orphaned_attachments: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod subscriptions_service;
+mod subscriptions_service_impl;
+
+pub use subscriptions_service::*;
+pub use subscriptions_service_impl::*;
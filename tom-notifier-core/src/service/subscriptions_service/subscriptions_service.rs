@@ -0,0 +1,35 @@
+use crate::{dto::output, error::Error};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SubscriptionsService: Send + Sync {
+    ///
+    /// Finds topics the user is subscribed to
+    ///
+    /// ### Returns
+    /// Vec of subscriptions sorted ascending by topic
+    ///
+    async fn find_subscriptions(&self, user_id: Uuid) -> Result<Vec<output::Subscription>, Error>;
+
+    ///
+    /// Subscribes user to the topic.
+    /// Subscribing to the already subscribed topic does nothing
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - topic is empty, too long or contains invalid characters
+    ///
+    async fn subscribe(&self, user_id: Uuid, topic: String) -> Result<(), Error>;
+
+    ///
+    /// Unsubscribes user from the topic.
+    /// Notifications published to the topic are no longer delivered
+    /// to the user unless they are targeted otherwise
+    ///
+    /// ### Errors
+    /// - [Error::SubscriptionNotExist] when user is not subscribed to the topic
+    ///
+    async fn unsubscribe(&self, user_id: Uuid, topic: String) -> Result<(), Error>;
+}
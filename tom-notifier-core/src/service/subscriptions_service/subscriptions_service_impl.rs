@@ -0,0 +1,204 @@
+use super::SubscriptionsService;
+use crate::{
+    dto::output,
+    error::Error,
+    repository::{self, SubscriptionsRepository},
+};
+use axum::async_trait;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Maximum length of the topic
+///
+pub const MAX_TOPIC_LEN: usize = 64;
+
+pub struct SubscriptionsServiceImpl {
+    repository: Arc<dyn SubscriptionsRepository>,
+}
+
+impl SubscriptionsServiceImpl {
+    pub fn new(repository: Arc<dyn SubscriptionsRepository>) -> Self {
+        Self { repository }
+    }
+
+    fn validate_topic(topic: &str) -> Result<(), Error> {
+        if topic.is_empty() {
+            return Err(Error::Validation("topic cannot be empty"));
+        }
+        if topic.len() > MAX_TOPIC_LEN {
+            return Err(Error::Validation("topic too long"));
+        }
+        // Topic is used as path segment, so it's limited to URL safe characters
+        let is_valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+        if !topic.chars().all(is_valid_char) {
+            return Err(Error::Validation("topic contains invalid characters"));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubscriptionsService for SubscriptionsServiceImpl {
+    async fn find_subscriptions(&self, user_id: Uuid) -> Result<Vec<output::Subscription>, Error> {
+        tracing::info!(%user_id, "finding subscriptions");
+
+        let subscriptions = self.repository.find_many(user_id).await?;
+
+        tracing::info!(count = subscriptions.len(), "found subscriptions");
+
+        Ok(subscriptions
+            .into_iter()
+            .map(output::Subscription::from)
+            .collect())
+    }
+
+    async fn subscribe(&self, user_id: Uuid, topic: String) -> Result<(), Error> {
+        tracing::info!(%user_id, topic, "subscribing to topic");
+
+        Self::validate_topic(&topic)?;
+
+        let insert_result = self
+            .repository
+            .insert(user_id, topic, OffsetDateTime::now_utc())
+            .await;
+
+        match insert_result {
+            Ok(()) => tracing::info!("subscribed to topic"),
+            Err(repository::Error::InsertUniqueViolation) => {
+                tracing::info!("already subscribed to topic")
+            }
+            Err(err) => return Err(Error::Database(err)),
+        }
+
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, user_id: Uuid, topic: String) -> Result<(), Error> {
+        tracing::info!(%user_id, topic, "unsubscribing from topic");
+
+        self.repository
+            .delete(user_id, topic)
+            .await
+            .map_err(|err| match err {
+                repository::Error::NoDocumentUpdated => Error::SubscriptionNotExist,
+                err => Error::Database(err),
+            })?;
+
+        tracing::info!("unsubscribed from topic");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use repository::MockSubscriptionsRepository;
+
+    #[tokio::test]
+    async fn subscribe_ok() {
+        let user_id = Uuid::new_v4();
+
+        let mut repository = MockSubscriptionsRepository::new();
+        repository
+            .expect_insert()
+            .withf(move |id, topic, _| *id == user_id && topic == "billing")
+            .once()
+            .returning(|_, _, _| Ok(()));
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let subscribe_result = service.subscribe(user_id, "billing".to_string()).await;
+
+        assert!(subscribe_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn subscribe_already_subscribed() {
+        let mut repository = MockSubscriptionsRepository::new();
+        repository
+            .expect_insert()
+            .returning(|_, _, _| Err(repository::Error::InsertUniqueViolation));
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let subscribe_result = service
+            .subscribe(Uuid::new_v4(), "billing".to_string())
+            .await;
+
+        assert!(subscribe_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn subscribe_validation_topic_empty() {
+        let mut repository = MockSubscriptionsRepository::new();
+        repository.expect_insert().never();
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let subscribe_result = service.subscribe(Uuid::new_v4(), "".to_string()).await;
+
+        assert!(matches!(subscribe_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn subscribe_validation_topic_too_long() {
+        let mut repository = MockSubscriptionsRepository::new();
+        repository.expect_insert().never();
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let subscribe_result = service
+            .subscribe(Uuid::new_v4(), "a".repeat(MAX_TOPIC_LEN + 1))
+            .await;
+
+        assert!(matches!(subscribe_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn subscribe_validation_topic_invalid_characters() {
+        let mut repository = MockSubscriptionsRepository::new();
+        repository.expect_insert().never();
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let subscribe_result = service
+            .subscribe(Uuid::new_v4(), "billing/all".to_string())
+            .await;
+
+        assert!(matches!(subscribe_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn subscribe_database_error() {
+        let mut repository = MockSubscriptionsRepository::new();
+        repository.expect_insert().returning(|_, _, _| {
+            Err(repository::Error::Mongo(
+                mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
+            ))
+        });
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let subscribe_result = service
+            .subscribe(Uuid::new_v4(), "billing".to_string())
+            .await;
+
+        assert!(matches!(subscribe_result, Err(Error::Database(_))));
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_not_subscribed() {
+        let mut repository = MockSubscriptionsRepository::new();
+        repository
+            .expect_delete()
+            .returning(|_, _| Err(repository::Error::NoDocumentUpdated));
+        let service = SubscriptionsServiceImpl::new(Arc::new(repository));
+
+        let unsubscribe_result = service
+            .unsubscribe(Uuid::new_v4(), "billing".to_string())
+            .await;
+
+        assert!(matches!(
+            unsubscribe_result,
+            Err(Error::SubscriptionNotExist)
+        ));
+    }
+}
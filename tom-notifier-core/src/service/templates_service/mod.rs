@@ -0,0 +1,6 @@
+mod template_body;
+mod templates_service;
+mod templates_service_impl;
+
+pub use templates_service::*;
+pub use templates_service_impl::*;
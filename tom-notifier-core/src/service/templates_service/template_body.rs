@@ -0,0 +1,146 @@
+use crate::error::Error;
+use std::collections::HashMap;
+
+///
+/// Part of the parsed template body
+///
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Parameter(&'a str),
+}
+
+///
+/// Template body with Handlebars-like `{{parameter}}` placeholders.
+/// Whitespace around parameter name is ignored, so `{{ parameter }}` works as well.
+/// Parameter values are inserted as they are, without any escaping
+///
+#[derive(Debug)]
+pub struct TemplateBody<'a> {
+    segments: Vec<Segment<'a>>,
+}
+
+impl<'a> TemplateBody<'a> {
+    ///
+    /// Splits body into text and placeholders
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - placeholder is not closed
+    ///     - placeholder does not contain valid parameter name
+    ///
+    pub fn parse(body: &'a str) -> Result<Self, Error> {
+        let mut segments = Vec::new();
+        let mut rest = body;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(&rest[..start]));
+            }
+
+            let placeholder = &rest[start + 2..];
+            let end = placeholder
+                .find("}}")
+                .ok_or(Error::Validation("template placeholder is not closed"))?;
+
+            let parameter = placeholder[..end].trim();
+            if !is_valid_parameter_name(parameter) {
+                return Err(Error::Validation("template placeholder is invalid"));
+            }
+            segments.push(Segment::Parameter(parameter));
+
+            rest = &placeholder[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest));
+        }
+
+        Ok(Self { segments })
+    }
+
+    ///
+    /// Names of the parameters used in placeholders
+    ///
+    pub fn parameters(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Text(_) => None,
+            Segment::Parameter(parameter) => Some(*parameter),
+        })
+    }
+
+    ///
+    /// Replaces placeholders with parameter values,
+    /// parameters without value are replaced with empty string
+    ///
+    pub fn render(&self, parameters: &HashMap<String, String>) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => *text,
+                Segment::Parameter(parameter) => parameters
+                    .get(*parameter)
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+///
+/// Parameter names are limited to ASCII letters, digits and underscores
+///
+pub fn is_valid_parameter_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_text_and_parameters() {
+        let body = TemplateBody::parse("Hello {{ name }}, order {{order_id}} shipped").unwrap();
+
+        assert_eq!(
+            body.segments,
+            vec![
+                Segment::Text("Hello "),
+                Segment::Parameter("name"),
+                Segment::Text(", order "),
+                Segment::Parameter("order_id"),
+                Segment::Text(" shipped"),
+            ]
+        );
+        assert_eq!(
+            body.parameters().collect::<Vec<_>>(),
+            vec!["name", "order_id"]
+        );
+    }
+
+    #[test]
+    fn parse_placeholder_not_closed() {
+        let parse_result = TemplateBody::parse("Hello {{name");
+
+        assert!(matches!(parse_result, Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn parse_placeholder_invalid() {
+        let parse_result = TemplateBody::parse("Hello {{first name}}");
+
+        assert!(matches!(parse_result, Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn render_parameters_replaced() {
+        let body = TemplateBody::parse("{{greeting}} {{name}}!").unwrap();
+        let parameters = HashMap::from([
+            ("greeting".to_string(), "Hi".to_string()),
+            ("name".to_string(), "{{name}}".to_string()),
+        ]);
+
+        let rendered = body.render(&parameters);
+
+        assert_eq!(rendered, "Hi {{name}}!");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    dto::{input, output},
+    error::Error,
+};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TemplatesService: Send + Sync {
+    ///
+    /// Creates the first version of the producer template
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - id is empty, too long or contains invalid characters
+    ///     - body is too long or has invalid placeholders
+    ///     - parameters are invalid, duplicated or do not cover placeholders of the body
+    /// - [Error::TemplateAlreadyExist] when producer already has template with the id
+    ///
+    async fn create_template(
+        &self,
+        producer_id: Uuid,
+        template: input::Template,
+    ) -> Result<output::TemplateVersion, Error>;
+
+    ///
+    /// Creates the next version of the producer template.
+    /// Previous versions stay available
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - body is too long or has invalid placeholders
+    ///     - parameters are invalid, duplicated or do not cover placeholders of the body
+    /// - [Error::TemplateNotExist] when template does not exist
+    /// - [Error::TemplateAlreadyExist] when the same version was created concurrently
+    ///
+    async fn update_template(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        content: input::TemplateContent,
+    ) -> Result<output::TemplateVersion, Error>;
+
+    ///
+    /// Finds the latest versions of the producer templates
+    ///
+    /// ### Returns
+    /// Vec of templates sorted ascending by id
+    ///
+    async fn find_templates(&self, producer_id: Uuid) -> Result<Vec<output::Template>, Error>;
+
+    ///
+    /// Finds version of the producer template
+    ///
+    /// ### Errors
+    /// - [Error::TemplateNotExist] when template or its version does not exist
+    ///
+    async fn find_template(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        version: input::TemplateVersion,
+    ) -> Result<output::Template, Error>;
+
+    ///
+    /// Renders template of the notification into its content_type and content.
+    /// Notification without template is returned unchanged
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when
+    ///     - content_type or content is set together with template
+    ///     - template or its version does not exist
+    ///     - any declared parameter is missing or unknown parameter is passed
+    ///
+    async fn render_notification(
+        &self,
+        producer_id: Uuid,
+        notification: input::Notification,
+    ) -> Result<input::Notification, Error>;
+
+    ///
+    /// Renders templates of many notifications, see [TemplatesService::render_notification]
+    ///
+    async fn render_notifications(
+        &self,
+        producer_id: Uuid,
+        notifications: Vec<input::Notification>,
+    ) -> Result<Vec<input::Notification>, Error>;
+}
@@ -0,0 +1,615 @@
+use super::{
+    template_body::{is_valid_parameter_name, TemplateBody},
+    TemplatesService,
+};
+use crate::{
+    dto::{input, output},
+    error::Error,
+    repository::{self, TemplatesRepository},
+};
+use axum::async_trait;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Maximum length of the template id
+///
+const MAX_TEMPLATE_ID_LEN: usize = 64;
+
+///
+/// Maximum length of the template body (in bytes)
+///
+const MAX_TEMPLATE_BODY_LEN: usize = 64 * 1024;
+
+///
+/// Maximum number of parameters a single template can declare
+///
+const MAX_TEMPLATE_PARAMETERS: usize = 32;
+
+///
+/// Maximum length of the template parameter name
+///
+const MAX_TEMPLATE_PARAMETER_LEN: usize = 64;
+
+pub struct TemplatesServiceImpl {
+    repository: Arc<dyn TemplatesRepository>,
+}
+
+impl TemplatesServiceImpl {
+    pub fn new(repository: Arc<dyn TemplatesRepository>) -> Self {
+        Self { repository }
+    }
+
+    fn validate_id(id: &str) -> Result<(), Error> {
+        if id.is_empty() {
+            return Err(Error::Validation("id cannot be empty"));
+        }
+        if id.len() > MAX_TEMPLATE_ID_LEN {
+            return Err(Error::Validation("id too long"));
+        }
+        // Id is used as path segment, so it's limited to URL safe characters
+        let is_valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+        if !id.chars().all(is_valid_char) {
+            return Err(Error::Validation("id contains invalid characters"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_content(body: &str, parameters: &[String]) -> Result<(), Error> {
+        if body.len() > MAX_TEMPLATE_BODY_LEN {
+            return Err(Error::Validation("body too long"));
+        }
+        if parameters.len() > MAX_TEMPLATE_PARAMETERS {
+            return Err(Error::Validation("too many parameters"));
+        }
+        for (idx, parameter) in parameters.iter().enumerate() {
+            if parameter.len() > MAX_TEMPLATE_PARAMETER_LEN || !is_valid_parameter_name(parameter) {
+                return Err(Error::Validation("parameter name is invalid"));
+            }
+            if parameters[..idx].contains(parameter) {
+                return Err(Error::Validation("duplicated parameter"));
+            }
+        }
+
+        let body = TemplateBody::parse(body)?;
+        if body
+            .parameters()
+            .any(|used| !parameters.iter().any(|declared| declared == used))
+        {
+            return Err(Error::Validation("body uses undeclared parameter"));
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Finds template referenced by the notification
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when template or its version does not exist
+    ///
+    async fn find_notification_template(
+        &self,
+        producer_id: Uuid,
+        notification_template: &input::NotificationTemplate,
+    ) -> Result<repository::Template, Error> {
+        self.repository
+            .find(
+                producer_id,
+                notification_template.id.clone(),
+                notification_template.version,
+            )
+            .await?
+            .ok_or(Error::Validation("template does not exist"))
+    }
+
+    fn render(
+        template: &repository::Template,
+        notification_template: input::NotificationTemplate,
+        notification: &mut input::Notification,
+    ) -> Result<(), Error> {
+        if !notification.content_type.is_empty() || !notification.content.is_empty() {
+            return Err(Error::Validation(
+                "content cannot be set together with template",
+            ));
+        }
+
+        let parameters = notification_template.parameters;
+        if template
+            .parameters
+            .iter()
+            .any(|declared| !parameters.contains_key(declared))
+        {
+            return Err(Error::Validation("missing template parameter"));
+        }
+        if parameters
+            .keys()
+            .any(|passed| !template.parameters.contains(passed))
+        {
+            return Err(Error::Validation("unknown template parameter"));
+        }
+
+        let body = TemplateBody::parse(&template.body)?;
+        notification.content_type = template.content_type.clone();
+        notification.content = body.render(&parameters).into_bytes();
+
+        Ok(())
+    }
+
+    fn map_template_errors(err: repository::Error) -> Error {
+        match err {
+            repository::Error::NoDocumentUpdated => Error::TemplateNotExist,
+            repository::Error::InsertUniqueViolation => Error::TemplateAlreadyExist,
+            err => Error::Database(err),
+        }
+    }
+}
+
+#[async_trait]
+impl TemplatesService for TemplatesServiceImpl {
+    async fn create_template(
+        &self,
+        producer_id: Uuid,
+        template: input::Template,
+    ) -> Result<output::TemplateVersion, Error> {
+        tracing::info!(id = template.id, "creating template");
+        tracing::trace!(?template);
+
+        Self::validate_id(&template.id)?;
+        Self::validate_content(&template.body, &template.parameters)?;
+
+        self.repository
+            .insert(
+                producer_id,
+                template.id.clone(),
+                OffsetDateTime::now_utc(),
+                template.content_type,
+                template.body,
+                template.parameters,
+            )
+            .await
+            .map_err(Self::map_template_errors)?;
+
+        tracing::info!("created template");
+
+        Ok(output::TemplateVersion {
+            id: template.id,
+            version: 1,
+        })
+    }
+
+    async fn update_template(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        content: input::TemplateContent,
+    ) -> Result<output::TemplateVersion, Error> {
+        tracing::info!(id, "updating template");
+        tracing::trace!(?content);
+
+        Self::validate_content(&content.body, &content.parameters)?;
+
+        let version = self
+            .repository
+            .insert_next_version(
+                producer_id,
+                id.clone(),
+                OffsetDateTime::now_utc(),
+                content.content_type,
+                content.body,
+                content.parameters,
+            )
+            .await
+            .map_err(Self::map_template_errors)?;
+
+        tracing::info!(version, "updated template");
+
+        Ok(output::TemplateVersion { id, version })
+    }
+
+    async fn find_templates(&self, producer_id: Uuid) -> Result<Vec<output::Template>, Error> {
+        tracing::info!("finding templates");
+
+        let templates = self.repository.find_many(producer_id).await?;
+
+        tracing::info!(count = templates.len(), "found templates");
+
+        Ok(templates.into_iter().map(output::Template::from).collect())
+    }
+
+    async fn find_template(
+        &self,
+        producer_id: Uuid,
+        id: String,
+        version: input::TemplateVersion,
+    ) -> Result<output::Template, Error> {
+        tracing::info!(id, version = ?version.version, "finding template");
+
+        let template = self
+            .repository
+            .find(producer_id, id, version.version)
+            .await?
+            .ok_or(Error::TemplateNotExist)?;
+
+        tracing::info!(version = template.version, "found template");
+
+        Ok(output::Template::from(template))
+    }
+
+    async fn render_notification(
+        &self,
+        producer_id: Uuid,
+        mut notification: input::Notification,
+    ) -> Result<input::Notification, Error> {
+        let Some(notification_template) = notification.template.take() else {
+            return Ok(notification);
+        };
+
+        tracing::info!(id = notification_template.id, "rendering template");
+
+        let template = self
+            .find_notification_template(producer_id, &notification_template)
+            .await?;
+        Self::render(&template, notification_template, &mut notification)?;
+
+        tracing::info!(version = template.version, "rendered template");
+
+        Ok(notification)
+    }
+
+    async fn render_notifications(
+        &self,
+        producer_id: Uuid,
+        notifications: Vec<input::Notification>,
+    ) -> Result<Vec<input::Notification>, Error> {
+        // Notifications of the batch usually share templates,
+        // so every template version is fetched only once
+        let mut templates = HashMap::<(String, Option<u32>), repository::Template>::new();
+        let mut rendered_notifications = Vec::with_capacity(notifications.len());
+
+        for mut notification in notifications {
+            if let Some(notification_template) = notification.template.take() {
+                let key = (
+                    notification_template.id.clone(),
+                    notification_template.version,
+                );
+                let template = match templates.entry(key) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(
+                        self.find_notification_template(producer_id, &notification_template)
+                            .await?,
+                    ),
+                };
+                Self::render(template, notification_template, &mut notification)?;
+            }
+            rendered_notifications.push(notification);
+        }
+
+        if !templates.is_empty() {
+            tracing::info!(
+                count = templates.len(),
+                "rendered templates of many notifications"
+            );
+        }
+
+        Ok(rendered_notifications)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dto::input::NotificationPriority;
+    use repository::MockTemplatesRepository;
+
+    #[tokio::test]
+    async fn create_template_ok() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_insert()
+            .withf(|_, id, _, _, body, parameters| {
+                id == "welcome" && body == "Hello {{name}}" && parameters == &["name"]
+            })
+            .once()
+            .returning(|_, _, _, _, _, _| Ok(()));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_template(
+                Uuid::new_v4(),
+                template("welcome", "Hello {{name}}", &["name"]),
+            )
+            .await;
+
+        assert!(matches!(
+            create_result,
+            Ok(output::TemplateVersion { version: 1, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_template_already_exist() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_insert()
+            .once()
+            .returning(|_, _, _, _, _, _| Err(repository::Error::InsertUniqueViolation));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_template(Uuid::new_v4(), template("welcome", "Hello", &[]))
+            .await;
+
+        assert!(matches!(create_result, Err(Error::TemplateAlreadyExist)));
+    }
+
+    #[tokio::test]
+    async fn create_template_validation_id_invalid_characters() {
+        let mut repository = MockTemplatesRepository::new();
+        repository.expect_insert().never();
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_template(Uuid::new_v4(), template("wel/come", "Hello", &[]))
+            .await;
+
+        assert!(matches!(create_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_template_validation_duplicated_parameter() {
+        let mut repository = MockTemplatesRepository::new();
+        repository.expect_insert().never();
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_template(
+                Uuid::new_v4(),
+                template("welcome", "Hello {{name}}", &["name", "name"]),
+            )
+            .await;
+
+        assert!(matches!(create_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_template_validation_undeclared_parameter() {
+        let mut repository = MockTemplatesRepository::new();
+        repository.expect_insert().never();
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let create_result = service
+            .create_template(Uuid::new_v4(), template("welcome", "Hello {{name}}", &[]))
+            .await;
+
+        assert!(matches!(create_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_template_not_exist() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_insert_next_version()
+            .once()
+            .returning(|_, _, _, _, _, _| Err(repository::Error::NoDocumentUpdated));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let update_result = service
+            .update_template(
+                Uuid::new_v4(),
+                "welcome".to_string(),
+                input::TemplateContent {
+                    content_type: "text/plain".to_string(),
+                    body: "Hello".to_string(),
+                    parameters: vec![],
+                },
+            )
+            .await;
+
+        assert!(matches!(update_result, Err(Error::TemplateNotExist)));
+    }
+
+    #[tokio::test]
+    async fn find_template_not_exist() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .once()
+            .returning(|_, _, _| Ok(None));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let find_result = service
+            .find_template(
+                Uuid::new_v4(),
+                "welcome".to_string(),
+                input::TemplateVersion { version: Some(2) },
+            )
+            .await;
+
+        assert!(matches!(find_result, Err(Error::TemplateNotExist)));
+    }
+
+    #[tokio::test]
+    async fn render_notification_ok() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .withf(|_, id, version| id == "welcome" && version == &Some(1))
+            .once()
+            .returning(|_, _, _| Ok(Some(stored_template())));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let render_result = service
+            .render_notification(
+                Uuid::new_v4(),
+                notification_with_template(&[("name", "Tom")]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(render_result.content_type, "text/plain");
+        assert_eq!(render_result.content, b"Hello Tom!");
+        assert!(render_result.template.is_none());
+    }
+
+    #[tokio::test]
+    async fn render_notification_without_template() {
+        let mut repository = MockTemplatesRepository::new();
+        repository.expect_find().never();
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+        let mut notification = notification_with_template(&[]);
+        notification.template = None;
+        notification.content_type = "utf-8".to_string();
+        notification.content = b"Hello".to_vec();
+
+        let render_result = service
+            .render_notification(Uuid::new_v4(), notification)
+            .await
+            .unwrap();
+
+        assert_eq!(render_result.content, b"Hello");
+    }
+
+    #[tokio::test]
+    async fn render_notification_validation_content_with_template() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .returning(|_, _, _| Ok(Some(stored_template())));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+        let mut notification = notification_with_template(&[("name", "Tom")]);
+        notification.content = b"Hello".to_vec();
+
+        let render_result = service
+            .render_notification(Uuid::new_v4(), notification)
+            .await;
+
+        assert!(matches!(render_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn render_notification_validation_missing_parameter() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .returning(|_, _, _| Ok(Some(stored_template())));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let render_result = service
+            .render_notification(Uuid::new_v4(), notification_with_template(&[]))
+            .await;
+
+        assert!(matches!(render_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn render_notification_validation_unknown_parameter() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .returning(|_, _, _| Ok(Some(stored_template())));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let render_result = service
+            .render_notification(
+                Uuid::new_v4(),
+                notification_with_template(&[("name", "Tom"), ("surname", "Smith")]),
+            )
+            .await;
+
+        assert!(matches!(render_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn render_notification_validation_template_not_exist() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .once()
+            .returning(|_, _, _| Ok(None));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let render_result = service
+            .render_notification(
+                Uuid::new_v4(),
+                notification_with_template(&[("name", "Tom")]),
+            )
+            .await;
+
+        assert!(matches!(render_result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn render_notifications_find_template_once() {
+        let mut repository = MockTemplatesRepository::new();
+        repository
+            .expect_find()
+            .once()
+            .returning(|_, _, _| Ok(Some(stored_template())));
+        let service = TemplatesServiceImpl::new(Arc::new(repository));
+
+        let render_result = service
+            .render_notifications(
+                Uuid::new_v4(),
+                vec![
+                    notification_with_template(&[("name", "Tom")]),
+                    notification_with_template(&[("name", "Ann")]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(render_result[0].content, b"Hello Tom!");
+        assert_eq!(render_result[1].content, b"Hello Ann!");
+    }
+
+    fn template(id: &str, body: &str, parameters: &[&str]) -> input::Template {
+        input::Template {
+            id: id.to_string(),
+            content_type: "text/plain".to_string(),
+            body: body.to_string(),
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn stored_template() -> repository::Template {
+        repository::Template {
+            id: "welcome".to_string(),
+            version: 1,
+            created_at: OffsetDateTime::now_utc(),
+            content_type: "text/plain".to_string(),
+            body: "Hello {{ name }}!".to_string(),
+            parameters: vec!["name".to_string()],
+        }
+    }
+
+    fn notification_with_template(parameters: &[(&str, &str)]) -> input::Notification {
+        input::Notification {
+            invalidate_at: None,
+            deliver_at: None,
+            user_ids: vec![Uuid::new_v4()],
+            groups: vec![],
+            topic: None,
+            priority: NotificationPriority::Normal,
+            collapse_key: None,
+            producer_notification_id: 0,
+            content_type: String::new(),
+            content: vec![],
+            localized_contents: vec![],
+            attachment_ids: vec![],
+            template: Some(input::NotificationTemplate {
+                id: "welcome".to_string(),
+                version: Some(1),
+                parameters: parameters
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+            }),
+        }
+    }
+}
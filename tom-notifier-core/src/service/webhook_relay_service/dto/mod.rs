@@ -0,0 +1,3 @@
+mod webhook_relay_service_config;
+
+pub use webhook_relay_service_config::*;
@@ -0,0 +1,40 @@
+use std::{collections::HashSet, time::Duration};
+
+pub struct WebhookRelayServiceConfig {
+    ///
+    /// Time between consecutive checks for pending receipts
+    ///
+    pub interval: Duration,
+
+    ///
+    /// Maximum number of receipts sent at once
+    ///
+    pub batch_size: u32,
+
+    ///
+    /// Timeout of the request posting receipt to the webhook
+    ///
+    pub timeout: Duration,
+
+    ///
+    /// Number of attempts of sending receipt
+    /// before it is moved to dead letters
+    ///
+    pub max_attempts: u32,
+
+    ///
+    /// Delay after the first failed attempt,
+    /// doubled after every next failed attempt
+    ///
+    pub backoff: Duration,
+
+    ///
+    /// Maximum delay between attempts
+    ///
+    pub max_backoff: Duration,
+
+    ///
+    /// Hosts of webhooks allowed to be non-public addresses
+    ///
+    pub allowed_hosts: HashSet<String>,
+}
@@ -0,0 +1,6 @@
+mod dto;
+mod webhook_relay_service;
+mod webhook_relay_service_worker;
+
+pub use dto::*;
+pub use webhook_relay_service::*;
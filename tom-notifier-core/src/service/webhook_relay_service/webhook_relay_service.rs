@@ -0,0 +1,46 @@
+use super::{webhook_relay_service_worker::WebhookRelayServiceWorker, WebhookRelayServiceConfig};
+use crate::repository::{ReceiptsRepository, WebhooksRepository};
+use std::sync::Arc;
+use tokio::{sync::Notify, task::JoinHandle};
+
+///
+/// Service that posts receipts of notification
+/// state changes to webhooks of their producers
+///
+pub struct WebhookRelayService {
+    worker_task: JoinHandle<()>,
+    worker_close_notify: Arc<Notify>,
+}
+
+impl WebhookRelayService {
+    pub fn new(
+        config: WebhookRelayServiceConfig,
+        receipts_repository: Arc<dyn ReceiptsRepository>,
+        webhooks_repository: Arc<dyn WebhooksRepository>,
+    ) -> anyhow::Result<Self> {
+        let worker =
+            WebhookRelayServiceWorker::new(config, receipts_repository, webhooks_repository)?;
+
+        let close_notify = Arc::new(Notify::new());
+
+        let close_notify_clone = Arc::clone(&close_notify);
+        let worker_task = tokio::spawn(async move {
+            tracing::info!("webhook relay service worker started");
+            worker.run(close_notify_clone).await;
+            tracing::info!("webhook relay service worker finished");
+        });
+
+        Ok(Self {
+            worker_task,
+            worker_close_notify: close_notify,
+        })
+    }
+
+    pub async fn close(self) {
+        self.worker_close_notify.notify_one();
+        if let Err(err) = self.worker_task.await {
+            // This should never happen
+            tracing::error!(%err, "webhook relay worker task failed");
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use super::WebhookRelayServiceConfig;
+use crate::{
+    dto::output,
+    repository::{self, Receipt, ReceiptsRepository, Webhook, WebhooksRepository},
+    service::webhooks_service::WebhookAddressPolicy,
+};
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, redirect, Client, Url};
+use sha2::Sha256;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+use time::OffsetDateTime;
+use tokio::{
+    sync::Notify,
+    time::{interval, sleep_until, Instant, Interval, MissedTickBehavior},
+};
+use uuid::Uuid;
+
+const TIMESTAMP_HEADER: &str = "X-Tom-Notifier-Timestamp";
+const SIGNATURE_HEADER: &str = "X-Tom-Notifier-Signature";
+
+const WEBHOOK_NOT_REGISTERED: &str = "webhook not registered";
+const WEBHOOK_HOST_NOT_PUBLIC: &str = "webhook host is not public";
+
+pub struct WebhookRelayServiceWorker {
+    config: WebhookRelayServiceConfig,
+    receipts_repository: Arc<dyn ReceiptsRepository>,
+    webhooks_repository: Arc<dyn WebhooksRepository>,
+    address_policy: WebhookAddressPolicy,
+    client: Client,
+
+    interval: Interval,
+}
+
+impl WebhookRelayServiceWorker {
+    pub fn new(
+        config: WebhookRelayServiceConfig,
+        receipts_repository: Arc<dyn ReceiptsRepository>,
+        webhooks_repository: Arc<dyn WebhooksRepository>,
+    ) -> reqwest::Result<Self> {
+        // Redirects are not followed, webhook has to respond by itself.
+        // Names are resolved only to addresses allowed by the policy
+        let address_policy = WebhookAddressPolicy::new(config.allowed_hosts.clone());
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .redirect(redirect::Policy::none())
+            .dns_resolver(Arc::new(address_policy.clone()))
+            .build()?;
+
+        let mut interval = interval(config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        Ok(Self {
+            config,
+            receipts_repository,
+            webhooks_repository,
+            address_policy,
+            client,
+            interval,
+        })
+    }
+
+    #[tracing::instrument(name = "Webhook Relay", skip_all)]
+    pub async fn run(mut self, close_notify: Arc<Notify>) {
+        tokio::select! {
+            biased;
+
+            // Wait for signal to close
+            _ = close_notify.notified() => {},
+
+            // Run infinite loop and send pending receipts
+            // periodically or when the next attempt is due
+            _ = async {
+                let mut next_scheduled = None;
+                loop {
+                    let scheduled = async move {
+                        match next_scheduled {
+                            Some(deadline) => sleep_until(deadline).await,
+                            None => std::future::pending().await,
+                        }
+                    };
+
+                    tokio::select! {
+                        _ = self.interval.tick() => {},
+                        _ = scheduled => {},
+                    }
+
+                    self.relay_pending().await;
+                    next_scheduled = self.next_scheduled().await;
+                }
+            } => {}
+        }
+    }
+
+    async fn relay_pending(&self) {
+        tracing::debug!("relaying pending receipts");
+
+        loop {
+            match self.relay_batch().await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(err) => {
+                    tracing::warn!(%err, "failed to relay pending receipts");
+                    break;
+                }
+            }
+        }
+
+        tracing::debug!("relayed pending receipts");
+    }
+
+    ///
+    /// Finds when the next attempt of sending receipt becomes due.
+    /// Receipts saved in the meantime are sent on the next interval
+    ///
+    async fn next_scheduled(&self) -> Option<Instant> {
+        let next_attempt_at = match self.receipts_repository.find_next_attempt_at().await {
+            Ok(next_attempt_at) => next_attempt_at?,
+            Err(err) => {
+                tracing::warn!(%err, "failed to find next attempt of sending receipt");
+                return None;
+            }
+        };
+
+        // Negative durations mean attempt is already due
+        let until_attempt: Duration = (next_attempt_at - OffsetDateTime::now_utc())
+            .try_into()
+            .unwrap_or_default();
+        tracing::debug!(%next_attempt_at, "next attempt of sending receipt");
+
+        Some(Instant::now() + until_attempt)
+    }
+
+    ///
+    /// Sends single batch of pending receipts
+    ///
+    /// ### Returns
+    /// true when the batch was full and there might be more pending receipts
+    ///
+    async fn relay_batch(&self) -> Result<bool, repository::Error> {
+        // Receipts are locked for twice the request timeout,
+        // so there's time left to record results of timed out requests
+        let receipts = self
+            .receipts_repository
+            .lock_pending(self.config.batch_size, self.config.timeout * 2)
+            .await?;
+        if receipts.is_empty() {
+            return Ok(false);
+        }
+
+        let count = receipts.len();
+        tracing::info!(count, "sending receipts");
+
+        let mut webhooks = HashMap::<Uuid, Option<Webhook>>::new();
+        for receipt in &receipts {
+            if let Entry::Vacant(entry) = webhooks.entry(receipt.producer_id) {
+                entry.insert(self.webhooks_repository.find(receipt.producer_id).await?);
+            }
+        }
+
+        let webhooks = &webhooks;
+        let results = join_all(receipts.iter().map(|receipt| async move {
+            match &webhooks[&receipt.producer_id] {
+                Some(webhook) => self.send(receipt, webhook).await.map_err(Some),
+                None => Err(None),
+            }
+        }))
+        .await;
+
+        let mut delivered = 0;
+        for (receipt, result) in receipts.iter().zip(results) {
+            let id = receipt.id;
+            let result = match result {
+                Ok(()) => {
+                    delivered += 1;
+                    self.receipts_repository.mark_delivered(id).await
+                }
+                // Receipts of producers that deleted their webhook are never sent
+                Err(None) => {
+                    let error = WEBHOOK_NOT_REGISTERED.to_string();
+                    self.receipts_repository.mark_dead(id, error).await
+                }
+                Err(Some(error)) => self.mark_failed(receipt, error).await,
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(repository::Error::NoDocumentUpdated) => {
+                    tracing::debug!(id = id.to_hex(), "receipt already sent")
+                }
+                Err(err) => tracing::warn!(id = id.to_hex(), %err, "failed to update receipt"),
+            }
+        }
+
+        tracing::info!(count, delivered, "sent receipts");
+
+        Ok(count == self.config.batch_size as usize)
+    }
+
+    ///
+    /// Posts signed receipt to the webhook
+    ///
+    /// ### Errors
+    /// Description of the failure when the webhook
+    /// could not be reached or did not respond with success
+    ///
+    async fn send(&self, receipt: &Receipt, webhook: &Webhook) -> Result<(), String> {
+        // IP address of the URL is never resolved, so it has to be checked here
+        let url = Url::parse(&webhook.url).map_err(|err| err.to_string())?;
+        if !url
+            .host_str()
+            .is_some_and(|host| self.address_policy.is_host_allowed(host))
+        {
+            return Err(WEBHOOK_HOST_NOT_PUBLIC.to_string());
+        }
+
+        let body =
+            serde_json::to_vec(&output::Receipt::from(receipt)).map_err(|err| err.to_string())?;
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let signature = sign(&webhook.secret, timestamp, &body);
+
+        let response = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            status => Err(format!("webhook responded with status {status}")),
+        }
+    }
+
+    ///
+    /// Reschedules receipt after failed attempt
+    /// or moves it to dead letters after the last one
+    ///
+    async fn mark_failed(&self, receipt: &Receipt, error: String) -> Result<(), repository::Error> {
+        let id = receipt.id.to_hex();
+        let attempts = receipt.attempts + 1;
+        if attempts >= self.config.max_attempts {
+            tracing::warn!(id, attempts, %error, "receipt moved to dead letters");
+            return self.receipts_repository.mark_dead(receipt.id, error).await;
+        }
+
+        tracing::debug!(id, attempts, %error, "failed to send receipt");
+        let next_attempt_at = OffsetDateTime::now_utc() + self.backoff(attempts);
+        self.receipts_repository
+            .mark_failed(receipt.id, next_attempt_at, error)
+            .await
+    }
+
+    ///
+    /// Delay before the next attempt, doubled after every failed attempt
+    ///
+    fn backoff(&self, attempts: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.config
+            .backoff
+            .saturating_mul(multiplier)
+            .min(self.config.max_backoff)
+    }
+}
+
+///
+/// Signs `timestamp` and `body` of the request with HMAC-SHA256
+///
+/// ### Returns
+/// Hex encoded signature of `{timestamp}.{body}`
+///
+fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repository::{MockReceiptsRepository, MockWebhooksRepository, ReceiptEvent};
+    use axum::{body::Bytes, http::HeaderMap, http::StatusCode, routing::post, Router};
+    use bson::oid::ObjectId;
+    use std::{collections::HashSet, sync::Mutex};
+    use tokio::net::TcpListener;
+
+    const SECRET: &str = "secret";
+
+    type Requests = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    fn config() -> WebhookRelayServiceConfig {
+        WebhookRelayServiceConfig {
+            interval: Duration::from_secs(60),
+            batch_size: 10,
+            timeout: Duration::from_secs(1),
+            max_attempts: 3,
+            backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(30),
+            allowed_hosts: HashSet::from(["127.0.0.1".to_string()]),
+        }
+    }
+
+    fn receipt(producer_id: Uuid, attempts: u32) -> Receipt {
+        Receipt {
+            id: ObjectId::new(),
+            attempts,
+            producer_id,
+            event: ReceiptEvent::Seen,
+            notification_id: ObjectId::new(),
+            user_id: Uuid::new_v4(),
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    fn webhook(url: String) -> Webhook {
+        Webhook {
+            url,
+            secret: SECRET.to_string(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    ///
+    /// Starts local HTTP server responding to every receipt with `status`
+    ///
+    /// ### Returns
+    /// URL of the webhook and requests received by it
+    ///
+    async fn start_webhook_stub(status: StatusCode) -> (String, Requests) {
+        let requests = Requests::default();
+
+        let requests_clone = Arc::clone(&requests);
+        let app = Router::new().route(
+            "/receipts",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                requests_clone.lock().unwrap().push((headers, body));
+                status
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        (format!("http://{address}/receipts"), requests)
+    }
+
+    fn webhooks_repository(url: Option<String>) -> MockWebhooksRepository {
+        let mut webhooks_repository = MockWebhooksRepository::new();
+        webhooks_repository
+            .expect_find()
+            .once()
+            .return_once(move |_| Ok(url.map(webhook)));
+        webhooks_repository
+    }
+
+    #[tokio::test]
+    async fn relay_batch_delivered_signed() {
+        let (url, requests) = start_webhook_stub(StatusCode::NO_CONTENT).await;
+        let receipt = receipt(Uuid::new_v4(), 0);
+        let receipt_id = receipt.id;
+
+        let mut receipts_repository = MockReceiptsRepository::new();
+        receipts_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![receipt]));
+        receipts_repository
+            .expect_mark_delivered()
+            .withf(move |id| *id == receipt_id)
+            .once()
+            .returning(|_| Ok(()));
+        let worker = WebhookRelayServiceWorker::new(
+            config(),
+            Arc::new(receipts_repository),
+            Arc::new(webhooks_repository(Some(url))),
+        )
+        .unwrap();
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        let (headers, body) = &requests[0];
+        let timestamp = headers[TIMESTAMP_HEADER].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            format!("sha256={}", sign(SECRET, timestamp, body))
+        );
+        let body = serde_json::from_slice::<serde_json::Value>(body).unwrap();
+        assert_eq!(body["id"], receipt_id.to_hex());
+        assert_eq!(body["event"], "SEEN");
+    }
+
+    #[tokio::test]
+    async fn relay_batch_failed_rescheduled() {
+        let (url, requests) = start_webhook_stub(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let receipt = receipt(Uuid::new_v4(), 1);
+
+        let mut receipts_repository = MockReceiptsRepository::new();
+        receipts_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![receipt]));
+        receipts_repository.expect_mark_delivered().never();
+        receipts_repository.expect_mark_dead().never();
+        receipts_repository
+            .expect_mark_failed()
+            .withf(|_, next_attempt_at, error| {
+                let backoff = *next_attempt_at - OffsetDateTime::now_utc();
+                backoff > time::Duration::seconds(15)
+                    && backoff <= time::Duration::seconds(20)
+                    && error.contains("500")
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+        let worker = WebhookRelayServiceWorker::new(
+            config(),
+            Arc::new(receipts_repository),
+            Arc::new(webhooks_repository(Some(url))),
+        )
+        .unwrap();
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn relay_batch_last_attempt_dead() {
+        let (url, _) = start_webhook_stub(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let receipt = receipt(Uuid::new_v4(), 2);
+
+        let mut receipts_repository = MockReceiptsRepository::new();
+        receipts_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![receipt]));
+        receipts_repository.expect_mark_failed().never();
+        receipts_repository
+            .expect_mark_dead()
+            .withf(|_, error| error.contains("500"))
+            .once()
+            .returning(|_, _| Ok(()));
+        let worker = WebhookRelayServiceWorker::new(
+            config(),
+            Arc::new(receipts_repository),
+            Arc::new(webhooks_repository(Some(url))),
+        )
+        .unwrap();
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_webhook_not_registered_dead() {
+        let producer_id = Uuid::new_v4();
+
+        let mut receipts_repository = MockReceiptsRepository::new();
+        receipts_repository
+            .expect_lock_pending()
+            .return_once(move |_, _| Ok(vec![receipt(producer_id, 0), receipt(producer_id, 0)]));
+        receipts_repository
+            .expect_mark_dead()
+            .withf(|_, error| error == WEBHOOK_NOT_REGISTERED)
+            .times(2)
+            .returning(|_, _| Ok(()));
+        let worker = WebhookRelayServiceWorker::new(
+            config(),
+            Arc::new(receipts_repository),
+            Arc::new(webhooks_repository(None)),
+        )
+        .unwrap();
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn relay_batch_host_not_public_not_sent() {
+        let (url, requests) = start_webhook_stub(StatusCode::NO_CONTENT).await;
+        let receipt = receipt(Uuid::new_v4(), 0);
+
+        let mut receipts_repository = MockReceiptsRepository::new();
+        receipts_repository
+            .expect_lock_pending()
+            .return_once(|_, _| Ok(vec![receipt]));
+        receipts_repository.expect_mark_delivered().never();
+        receipts_repository
+            .expect_mark_failed()
+            .withf(|_, _, error| error == WEBHOOK_HOST_NOT_PUBLIC)
+            .once()
+            .returning(|_, _, _| Ok(()));
+        let worker = WebhookRelayServiceWorker::new(
+            WebhookRelayServiceConfig {
+                allowed_hosts: HashSet::new(),
+                ..config()
+            },
+            Arc::new(receipts_repository),
+            Arc::new(webhooks_repository(Some(url))),
+        )
+        .unwrap();
+
+        let result = worker.relay_batch().await;
+
+        assert!(matches!(result, Ok(false)));
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn backoff_doubled_and_capped() {
+        let worker = WebhookRelayServiceWorker::new(
+            config(),
+            Arc::new(MockReceiptsRepository::new()),
+            Arc::new(MockWebhooksRepository::new()),
+        )
+        .unwrap();
+
+        assert_eq!(worker.backoff(1), Duration::from_secs(10));
+        assert_eq!(worker.backoff(2), Duration::from_secs(20));
+        assert_eq!(worker.backoff(3), Duration::from_secs(30));
+        assert_eq!(worker.backoff(64), Duration::from_secs(30));
+    }
+}
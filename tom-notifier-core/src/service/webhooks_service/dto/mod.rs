@@ -0,0 +1,3 @@
+mod webhooks_service_config;
+
+pub use webhooks_service_config::*;
@@ -0,0 +1,8 @@
+use std::collections::HashSet;
+
+pub struct WebhooksServiceConfig {
+    ///
+    /// Hosts of webhooks allowed to be non-public addresses
+    ///
+    pub allowed_hosts: HashSet<String>,
+}
@@ -0,0 +1,9 @@
+mod dto;
+mod webhook_address_policy;
+mod webhooks_service;
+mod webhooks_service_impl;
+
+pub use dto::*;
+pub use webhook_address_policy::*;
+pub use webhooks_service::*;
+pub use webhooks_service_impl::*;
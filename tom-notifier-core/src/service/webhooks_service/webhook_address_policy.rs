@@ -0,0 +1,181 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+};
+use tokio::net::lookup_host;
+
+///
+/// Decides which addresses receipts can be sent to, so webhooks can't reach
+/// loopback, private, link-local or other non-public addresses,
+/// unless their host is explicitly allowed
+///
+#[derive(Clone, Default)]
+pub struct WebhookAddressPolicy {
+    allowed_hosts: Arc<HashSet<String>>,
+}
+
+impl WebhookAddressPolicy {
+    pub fn new(allowed_hosts: HashSet<String>) -> Self {
+        Self {
+            allowed_hosts: Arc::new(allowed_hosts),
+        }
+    }
+
+    ///
+    /// Checks host of the webhook URL without resolving it,
+    /// so only IP addresses and names that are always local are rejected.
+    /// Addresses of other names are checked when they are resolved
+    ///
+    pub fn is_host_allowed(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        if self.allowed_hosts.contains(&host) {
+            return true;
+        }
+
+        match host.trim_start_matches('[').trim_end_matches(']').parse() {
+            Ok(address) => is_public(address),
+            Err(_) => host != "localhost" && !host.ends_with(".localhost"),
+        }
+    }
+
+    fn is_address_allowed(&self, host: &str, address: IpAddr) -> bool {
+        is_public(address) || self.allowed_hosts.contains(&host.to_ascii_lowercase())
+    }
+}
+
+impl Resolve for WebhookAddressPolicy {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            let addresses = lookup_host((host, 0))
+                .await?
+                .filter(|address| policy.is_address_allowed(host, address.ip()))
+                .collect::<Vec<_>>();
+            if addresses.is_empty() {
+                return Err("webhook host has no public address".into());
+            }
+
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+fn is_public(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_v4(address),
+        IpAddr::V6(address) => match address.to_ipv4_mapped() {
+            Some(address) => is_public_v4(address),
+            None => is_public_v6(address),
+        },
+    }
+}
+
+fn is_public_v4(address: Ipv4Addr) -> bool {
+    let [first, second, third, _] = address.octets();
+    let is_this_network = first == 0;
+    let is_shared = first == 100 && (64..128).contains(&second);
+    let is_protocol_assignment = first == 192 && second == 0 && third == 0;
+    let is_reserved = first >= 240;
+
+    !(address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_multicast()
+        || address.is_documentation()
+        || is_this_network
+        || is_shared
+        || is_protocol_assignment
+        || is_reserved)
+}
+
+fn is_public_v6(address: Ipv6Addr) -> bool {
+    let [first, second, ..] = address.segments();
+    let is_unique_local = first & 0xfe00 == 0xfc00;
+    let is_link_local = first & 0xffc0 == 0xfe80;
+    let is_documentation = first == 0x2001 && second == 0x0db8;
+
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || address.is_multicast()
+        || is_unique_local
+        || is_link_local
+        || is_documentation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_host_allowed_public() {
+        let policy = WebhookAddressPolicy::default();
+
+        for host in ["example.com", "93.184.216.34", "[2606:2800:220:1::]"] {
+            assert!(policy.is_host_allowed(host), "{host}");
+        }
+    }
+
+    #[test]
+    fn is_host_allowed_not_public() {
+        let policy = WebhookAddressPolicy::default();
+
+        for host in [
+            "localhost",
+            "api.LOCALHOST",
+            "0.0.0.0",
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "255.255.255.255",
+            "[::]",
+            "[::1]",
+            "[fd00::1]",
+            "[fe80::1]",
+            "[::ffff:127.0.0.1]",
+        ] {
+            assert!(!policy.is_host_allowed(host), "{host}");
+        }
+    }
+
+    #[test]
+    fn is_host_allowed_allowed_hosts() {
+        let policy = WebhookAddressPolicy::new(HashSet::from([
+            "localhost".to_string(),
+            "10.1.2.3".to_string(),
+        ]));
+
+        assert!(policy.is_host_allowed("LocalHost"));
+        assert!(policy.is_host_allowed("10.1.2.3"));
+        assert!(!policy.is_host_allowed("10.1.2.4"));
+    }
+
+    #[tokio::test]
+    async fn resolve_not_public_rejected() {
+        let policy = WebhookAddressPolicy::default();
+
+        let result = policy.resolve("localhost".parse().unwrap()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn resolve_allowed_host_resolved() {
+        let policy = WebhookAddressPolicy::new(HashSet::from(["localhost".to_string()]));
+
+        let addresses = policy
+            .resolve("localhost".parse().unwrap())
+            .await
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert!(addresses.iter().all(|address| address.ip().is_loopback()));
+        assert!(!addresses.is_empty());
+    }
+}
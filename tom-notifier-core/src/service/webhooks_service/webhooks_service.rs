@@ -0,0 +1,45 @@
+use crate::{
+    dto::{input, output},
+    error::Error,
+};
+use axum::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait WebhooksService: Send + Sync {
+    ///
+    /// Registers webhook of the producer, replacing the previous one.
+    /// Receipts of recipients confirming, seeing and deleting notifications
+    /// of the producer are posted to the webhook.
+    /// New secret is generated on every registration
+    ///
+    /// ### Returns
+    /// secret of HMAC signatures of receipts
+    ///
+    /// ### Errors
+    /// - [Error::Validation] when url is not absolute http(s) URL or is too long
+    ///
+    async fn register_webhook(
+        &self,
+        producer_id: Uuid,
+        webhook: input::Webhook,
+    ) -> Result<output::WebhookSecret, Error>;
+
+    ///
+    /// Finds webhook of the producer
+    ///
+    /// ### Errors
+    /// - [Error::WebhookNotExist] when producer has not registered webhook
+    ///
+    async fn find_webhook(&self, producer_id: Uuid) -> Result<output::Webhook, Error>;
+
+    ///
+    /// Deletes webhook of the producer, receipts that
+    /// have not been sent yet are moved to dead letters
+    ///
+    /// ### Errors
+    /// - [Error::WebhookNotExist] when producer has not registered webhook
+    ///
+    async fn delete_webhook(&self, producer_id: Uuid) -> Result<(), Error>;
+}
@@ -0,0 +1,246 @@
+use super::{WebhookAddressPolicy, WebhooksService, WebhooksServiceConfig};
+use crate::{
+    dto::{input, output},
+    error::Error,
+    repository::{self, WebhooksRepository},
+};
+use axum::async_trait;
+use rand::{rngs::OsRng, RngCore};
+use reqwest::Url;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+///
+/// Maximum length of the webhook URL
+///
+const MAX_URL_LEN: usize = 2048;
+
+pub struct WebhooksServiceImpl {
+    address_policy: WebhookAddressPolicy,
+    repository: Arc<dyn WebhooksRepository>,
+}
+
+impl WebhooksServiceImpl {
+    pub fn new(config: WebhooksServiceConfig, repository: Arc<dyn WebhooksRepository>) -> Self {
+        Self {
+            address_policy: WebhookAddressPolicy::new(config.allowed_hosts),
+            repository,
+        }
+    }
+
+    fn validate_url(&self, url: &str) -> Result<(), Error> {
+        if url.len() > MAX_URL_LEN {
+            return Err(Error::Validation("url too long"));
+        }
+
+        let url = Url::parse(url).map_err(|_| Error::Validation("url is not valid"))?;
+        if !matches!(url.scheme(), "http" | "https") || !url.has_host() {
+            return Err(Error::Validation("url must be absolute http(s) URL"));
+        }
+        if !url
+            .host_str()
+            .is_some_and(|host| self.address_policy.is_host_allowed(host))
+        {
+            return Err(Error::Validation("url host is not public"));
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Generates 256 bit secret from OS random number generator encoded as hex
+    ///
+    fn generate_secret() -> String {
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        hex::encode(secret)
+    }
+}
+
+#[async_trait]
+impl WebhooksService for WebhooksServiceImpl {
+    async fn register_webhook(
+        &self,
+        producer_id: Uuid,
+        webhook: input::Webhook,
+    ) -> Result<output::WebhookSecret, Error> {
+        tracing::info!(url = webhook.url, "registering webhook");
+
+        self.validate_url(&webhook.url)?;
+        let secret = Self::generate_secret();
+
+        self.repository
+            .upsert(
+                producer_id,
+                repository::Webhook {
+                    url: webhook.url,
+                    secret: secret.clone(),
+                    updated_at: OffsetDateTime::now_utc(),
+                },
+            )
+            .await?;
+
+        tracing::info!("registered webhook");
+
+        Ok(output::WebhookSecret { secret })
+    }
+
+    async fn find_webhook(&self, producer_id: Uuid) -> Result<output::Webhook, Error> {
+        tracing::info!("finding webhook");
+
+        let webhook = self
+            .repository
+            .find(producer_id)
+            .await?
+            .ok_or(Error::WebhookNotExist)?;
+
+        tracing::info!("found webhook");
+
+        Ok(webhook.into())
+    }
+
+    async fn delete_webhook(&self, producer_id: Uuid) -> Result<(), Error> {
+        tracing::info!("deleting webhook");
+
+        self.repository
+            .delete(producer_id)
+            .await
+            .map_err(|err| match err {
+                repository::Error::NoDocumentUpdated => Error::WebhookNotExist,
+                err => Error::Database(err),
+            })?;
+
+        tracing::info!("deleted webhook");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use repository::MockWebhooksRepository;
+    use std::collections::HashSet;
+
+    fn config() -> WebhooksServiceConfig {
+        WebhooksServiceConfig {
+            allowed_hosts: HashSet::from(["receipts.internal".to_string()]),
+        }
+    }
+
+    #[tokio::test]
+    async fn register_webhook_ok() {
+        let mut repository = MockWebhooksRepository::new();
+        repository
+            .expect_upsert()
+            .withf(|_, webhook| {
+                webhook.url == "https://example.com/receipts" && webhook.secret.len() == 64
+            })
+            .once()
+            .returning(|_, _| Ok(()));
+        let service = WebhooksServiceImpl::new(config(), Arc::new(repository));
+
+        let secret = service
+            .register_webhook(
+                Uuid::new_v4(),
+                input::Webhook {
+                    url: "https://example.com/receipts".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(secret.secret.len(), 64);
+        assert!(secret.secret.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[tokio::test]
+    async fn register_webhook_validation_url_invalid() {
+        let mut repository = MockWebhooksRepository::new();
+        repository.expect_upsert().never();
+        let service = WebhooksServiceImpl::new(config(), Arc::new(repository));
+
+        for url in [
+            "".to_string(),
+            "/receipts".to_string(),
+            "ftp://example.com/receipts".to_string(),
+            "mailto:producer@example.com".to_string(),
+            format!("https://example.com/{}", "a".repeat(MAX_URL_LEN)),
+        ] {
+            let result = service
+                .register_webhook(Uuid::new_v4(), input::Webhook { url })
+                .await;
+
+            assert!(matches!(result, Err(Error::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn register_webhook_validation_url_not_public() {
+        let mut repository = MockWebhooksRepository::new();
+        repository.expect_upsert().never();
+        let service = WebhooksServiceImpl::new(config(), Arc::new(repository));
+
+        for url in [
+            "http://localhost:8080/receipts",
+            "http://127.0.0.1/receipts",
+            "http://10.0.0.1/receipts",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/receipts",
+        ] {
+            let result = service
+                .register_webhook(
+                    Uuid::new_v4(),
+                    input::Webhook {
+                        url: url.to_string(),
+                    },
+                )
+                .await;
+
+            assert!(matches!(result, Err(Error::Validation(_))), "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn register_webhook_allowed_host_ok() {
+        let mut repository = MockWebhooksRepository::new();
+        repository.expect_upsert().once().returning(|_, _| Ok(()));
+        let service = WebhooksServiceImpl::new(config(), Arc::new(repository));
+
+        let result = service
+            .register_webhook(
+                Uuid::new_v4(),
+                input::Webhook {
+                    url: "http://receipts.internal/receipts".to_string(),
+                },
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn find_webhook_not_exist() {
+        let mut repository = MockWebhooksRepository::new();
+        repository.expect_find().returning(|_| Ok(None));
+        let service = WebhooksServiceImpl::new(config(), Arc::new(repository));
+
+        let result = service.find_webhook(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(Error::WebhookNotExist)));
+    }
+
+    #[tokio::test]
+    async fn delete_webhook_not_exist() {
+        let mut repository = MockWebhooksRepository::new();
+        repository
+            .expect_delete()
+            .returning(|_| Err(repository::Error::NoDocumentUpdated));
+        let service = WebhooksServiceImpl::new(config(), Arc::new(repository));
+
+        let result = service.delete_webhook(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(Error::WebhookNotExist)));
+    }
+}
@@ -1082,3 +1082,123 @@ async fn broadcast_delete_does_not_affect_other_users() {
         task.await.unwrap();
     }
 }
+
+// ATTACHMENTS
+
+#[tokio::test]
+#[parallel]
+async fn attachment_downloaded_only_by_recipient_of_delivered_notification() {
+    init_env();
+
+    // after uploading attachment and producing notification referencing it
+    // attachment can be downloaded only by the recipient
+    // and only after the notification is delivered
+
+    let client = Client::new();
+    let user_id = Uuid::new_v4();
+    let user = create_consumer_jwt_with_id(user_id);
+    let other_user = create_consumer_jwt();
+    let producer = create_producer_jwt();
+
+    // upload attachment
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/attachments?filename=report.txt",
+            address()
+        ))
+        .bearer_auth(&producer)
+        .header(CONTENT_TYPE, "text/plain")
+        .body("attached report")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response_body = response.bytes().await.unwrap();
+    let response_body = serde_json::from_slice::<Value>(&response_body).unwrap();
+    let attachment_id = response_body
+        .get("id")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // create notification
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/notifications/undelivered",
+            address()
+        ))
+        .bearer_auth(&producer)
+        .header(CONTENT_TYPE, "application/json")
+        .body(
+            json!({
+                "user_ids": [user_id],
+                "producer_notification_id": 1,
+                "content_type": "utf-8",
+                "content": "RG9uJ3QgeW91IGRhcmUgZGVjb2RpbmcgbWUh",
+                "attachment_ids": [attachment_id],
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response_body = response.bytes().await.unwrap();
+    let response_body = serde_json::from_slice::<Value>(&response_body).unwrap();
+    let notification_id = response_body
+        .get("id")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string();
+    let attachment_uri = format!(
+        "http://{}/api/v1/notifications/delivered/{}/attachments/{}",
+        address(),
+        notification_id,
+        attachment_id
+    );
+
+    // download attachment before delivery
+    // should return 404
+    let response = client
+        .get(&attachment_uri)
+        .bearer_auth(&user)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // fetch undelivered notifications
+    let response = client
+        .get(format!(
+            "http://{}/api/v1/notifications/undelivered",
+            address()
+        ))
+        .bearer_auth(&user)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // download attachment by other user
+    // should return 404
+    let response = client
+        .get(&attachment_uri)
+        .bearer_auth(&other_user)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // download attachment by recipient
+    let response = client
+        .get(&attachment_uri)
+        .bearer_auth(&user)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/plain");
+    assert_eq!(response.bytes().await.unwrap(), "attached report");
+}
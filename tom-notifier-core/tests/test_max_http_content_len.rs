@@ -35,3 +35,31 @@ async fn max_content_length_http() {
         .unwrap();
     assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
 }
+
+#[tokio::test]
+async fn max_content_length_attachment() {
+    init_env();
+
+    // sending attachment larger than max attachment size should result in 413,
+    // attachments are not limited by max http content size
+
+    let max_attachment_len: usize = std::env::var("TOM_NOTIFIER_CORE_MAX_ATTACHMENT_LEN")
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let client = Client::new();
+
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/attachments?filename=large.bin",
+            address()
+        ))
+        .bearer_auth(create_producer_jwt())
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(vec![0u8; max_attachment_len + 1])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -98,7 +98,7 @@ async fn new_notification() {
         .unwrap();
     assert!(time_before_send <= notification_datetime && notification_datetime <= time_now);
     assert_eq!(notification.created_by.unwrap(), producer_id.to_string());
-    assert_eq!(notification.seen.unwrap(), false);
+    assert!(!notification.seen.unwrap());
     assert_eq!(notification.content_type.unwrap(), content_type);
     assert_eq!(notification.content.unwrap(), content);
 
@@ -220,6 +220,110 @@ async fn updated_notification() {
     destroy_rabbitmq(connection, channel, &queue).await;
 }
 
+#[tokio::test]
+async fn updated_content_notification() {
+    init_env();
+
+    // after producing notification
+    // and replacing its content
+    // fetching notification from queue bound to 'UPDATED' routing key
+    // should return notification with new content
+
+    let client = Client::new();
+    let user_id = Uuid::new_v4();
+    let producer = create_producer_jwt();
+
+    // create notification
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/notifications/undelivered",
+            address()
+        ))
+        .bearer_auth(&producer)
+        .header(CONTENT_TYPE, "application/json")
+        .body(
+            json!({
+                "invalidate_at": None as Option<OffsetDateTime>,
+                "user_ids": [user_id],
+                "producer_notification_id": 1,
+                "content_type": "ascii",
+                "content": BASE64_STANDARD.encode(b"there's some of my content"),
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response_body = response.bytes().await.unwrap();
+    let notification = serde_json::from_slice::<Value>(&response_body).unwrap();
+    let id = notification.get("id").unwrap().as_str().unwrap();
+
+    let queue = format!(
+        "test core updated_content_notification {}",
+        OffsetDateTime::now_utc()
+    );
+    let (connection, channel) = init_rabbitmq(&queue, "UPDATED").await;
+
+    let time_before_send = OffsetDateTime::now_utc();
+
+    let content_type = "utf-8";
+    let content = b"there's my fixed content".to_vec();
+
+    let response = client
+        .put(format!(
+            "http://{}/api/v1/notifications/undelivered/{id}/content",
+            address(),
+        ))
+        .bearer_auth(&producer)
+        .header(CONTENT_TYPE, "application/json")
+        .body(
+            json! ({
+                "content_type": content_type,
+                "content": BASE64_STANDARD.encode(&content),
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let (_get_ok, _basic_properties, bytes) = timeout(Duration::from_secs(5), async {
+        let mut message = None;
+        while message.is_none() {
+            let args = BasicGetArguments::new(&queue);
+            message = channel.basic_get(args).await.unwrap();
+        }
+        message.unwrap()
+    })
+    .await
+    .unwrap();
+
+    let time_now = OffsetDateTime::now_utc();
+
+    let rabbitmq_notification = RabbitmqNotificationProtobuf::decode(bytes.as_slice()).unwrap();
+    assert_eq!(rabbitmq_notification.user_ids.len(), 1);
+    let notification_user_id_str = rabbitmq_notification.user_ids.first().unwrap();
+    let notification_user_id = Uuid::from_str(notification_user_id_str).unwrap();
+    assert_eq!(notification_user_id, user_id);
+    let notification = rabbitmq_notification.notification.unwrap();
+    assert_eq!(notification.id, id);
+    assert_eq!(notification.status(), NotificationStatusProtobuf::Updated);
+    let notification_timestamp = notification.timestamp.unwrap();
+    let notification_datetime = OffsetDateTime::from_unix_timestamp(notification_timestamp.seconds)
+        .unwrap()
+        .replace_nanosecond(notification_timestamp.nanos as u32)
+        .unwrap();
+    assert!(time_before_send <= notification_datetime && notification_datetime <= time_now);
+    assert!(notification.created_by.is_none());
+    assert!(notification.seen.is_none());
+    assert_eq!(notification.content_type.unwrap(), content_type);
+    assert_eq!(notification.content.unwrap(), content);
+
+    destroy_rabbitmq(connection, channel, &queue).await;
+}
+
 #[tokio::test]
 async fn deleted_notification() {
     init_env();
@@ -326,6 +430,88 @@ async fn deleted_notification() {
     destroy_rabbitmq(connection, channel, &queue).await;
 }
 
+#[tokio::test]
+async fn retracted_notification() {
+    init_env();
+
+    // after producing multicast notification
+    // and retracting it
+    // fetching notification from queue bound to 'DELETED' routing key
+    // should return notification addressed to all recipients
+
+    let client = Client::new();
+    let user_1_id = Uuid::new_v4();
+    let user_2_id = Uuid::new_v4();
+    let producer = create_producer_jwt();
+
+    // create notification
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/notifications/undelivered",
+            address()
+        ))
+        .bearer_auth(&producer)
+        .header(CONTENT_TYPE, "application/json")
+        .body(
+            json!({
+                "invalidate_at": None as Option<OffsetDateTime>,
+                "user_ids": [user_1_id, user_2_id],
+                "producer_notification_id": 1,
+                "content_type": "ascii",
+                "content": BASE64_STANDARD.encode(b"there's some of my content"),
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response_body = response.bytes().await.unwrap();
+    let notification = serde_json::from_slice::<Value>(&response_body).unwrap();
+    let id = notification.get("id").unwrap().as_str().unwrap();
+
+    let queue = format!(
+        "test core retracted_notification {}",
+        OffsetDateTime::now_utc()
+    );
+    let (connection, channel) = init_rabbitmq(&queue, "DELETED").await;
+
+    let response = client
+        .delete(format!(
+            "http://{}/api/v1/notifications/undelivered/{id}",
+            address(),
+        ))
+        .bearer_auth(&producer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let (_get_ok, _basic_properties, bytes) = timeout(Duration::from_secs(5), async {
+        let mut message = None;
+        while message.is_none() {
+            let args = BasicGetArguments::new(&queue);
+            message = channel.basic_get(args).await.unwrap();
+        }
+        message.unwrap()
+    })
+    .await
+    .unwrap();
+
+    let rabbitmq_notification = RabbitmqNotificationProtobuf::decode(bytes.as_slice()).unwrap();
+    let notification_user_ids = rabbitmq_notification
+        .user_ids
+        .iter()
+        .map(|user_id| Uuid::from_str(user_id).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(notification_user_ids, vec![user_1_id, user_2_id]);
+    let notification = rabbitmq_notification.notification.unwrap();
+    assert_eq!(notification.id, id);
+    assert_eq!(notification.status(), NotificationStatusProtobuf::Deleted);
+
+    destroy_rabbitmq(connection, channel, &queue).await;
+}
+
 async fn init_rabbitmq(queue: &str, routing_key: &str) -> (Connection, Channel) {
     let connection_string = std::env::var("TOM_NOTIFIER_CORE_RABBITMQ_CONNECTION_STRING").unwrap();
 
@@ -27,6 +27,24 @@ async fn post_notifications_undelivered() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn post_notifications_undelivered_batch() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/notifications/undelivered/batch",
+            address()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
 #[tokio::test]
 async fn get_notifications_undelivered() {
     init_env();
@@ -45,6 +63,25 @@ async fn get_notifications_undelivered() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn delete_notification_undelivered() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!(
+            "http://{}/api/v1/notifications/undelivered/{}",
+            address(),
+            ObjectId::new().to_hex()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
 #[tokio::test]
 async fn put_notifications_undelivered_invalidate_at() {
     init_env();
@@ -64,6 +101,95 @@ async fn put_notifications_undelivered_invalidate_at() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn put_notifications_undelivered_content() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!(
+            "http://{}/api/v1/notifications/undelivered/{}/content",
+            address(),
+            ObjectId::new().to_hex()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_notification_undelivered_delivery_report() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!(
+            "http://{}/api/v1/notifications/undelivered/{}/delivery_report",
+            address(),
+            ObjectId::new().to_hex()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_notifications_produced() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!(
+            "http://{}/api/v1/notifications/produced?page_size=10",
+            address()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_notification_produced() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!(
+            "http://{}/api/v1/notifications/produced/1",
+            address()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_notifications_count() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/notifications/count", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
 #[tokio::test]
 async fn get_notifications_delivered() {
     init_env();
@@ -120,6 +246,24 @@ async fn delete_notification_delivered() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn delete_notifications_delivered() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!(
+            "http://{}/api/v1/notifications/delivered",
+            address()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
 #[tokio::test]
 async fn put_notification_delivered_seen() {
     init_env();
@@ -139,6 +283,377 @@ async fn put_notification_delivered_seen() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn get_notification_delivered_attachment() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!(
+            "http://{}/api/v1/notifications/delivered/{}/attachments/{}",
+            address(),
+            ObjectId::new().to_hex(),
+            ObjectId::new().to_hex()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_notifications_delivered_seen() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!(
+            "http://{}/api/v1/notifications/delivered/seen",
+            address()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn post_groups() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .post(format!("http://{}/api/v1/groups", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_groups() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/groups", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delete_group() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!("http://{}/api/v1/groups/admins", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_group_members() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/groups/admins/members", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_group_members() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!("http://{}/api/v1/groups/admins/members", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delete_group_members() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!("http://{}/api/v1/groups/admins/members", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_subscriptions() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/subscriptions", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_subscription() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!("http://{}/api/v1/subscriptions/billing", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delete_subscription() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!("http://{}/api/v1/subscriptions/billing", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_preferences() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/preferences", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_preferences() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!("http://{}/api/v1/preferences", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn post_templates() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .post(format!("http://{}/api/v1/templates", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_templates() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/templates", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_template() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/templates/welcome", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_template() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!("http://{}/api/v1/templates/welcome", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_content_type() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!("http://{}/api/v1/content_types", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_content_types() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/content_types", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delete_content_type() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!("http://{}/api/v1/content_types", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_webhook() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .get(format!("http://{}/api/v1/webhook", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn put_webhook() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .put(format!("http://{}/api/v1/webhook", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delete_webhook() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .delete(format!("http://{}/api/v1/webhook", address()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn post_attachments() {
+    init_env();
+
+    let client = Client::new();
+
+    let response = client
+        .post(format!(
+            "http://{}/api/v1/attachments?filename=report.txt",
+            address()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
 #[tokio::test]
 async fn get_non_existent_uri() {
     init_env();
@@ -16,6 +16,7 @@ pub struct ApplicationEnv {
     pub websocket_ping_interval: Duration,
     pub websocket_retry_max_count: u8,
     pub websocket_retry_interval: Duration,
+    pub websocket_priority_retry_interval: Duration,
     pub websocket_connection_buffer_size: u8,
 
     /// Algorithms must belong to the same family
@@ -49,6 +50,16 @@ impl ApplicationEnv {
         let websocket_retry_interval =
             Self::env_var("TOM_NOTIFIER_WS_DELIVERY_WEBSOCKET_RETRY_INTERVAL")?.parse()?;
         let websocket_retry_interval = Duration::from_secs(websocket_retry_interval);
+        let websocket_priority_retry_interval =
+            Self::env_var("TOM_NOTIFIER_WS_DELIVERY_WEBSOCKET_PRIORITY_RETRY_INTERVAL")?.parse()?;
+        let websocket_priority_retry_interval =
+            Duration::from_secs(websocket_priority_retry_interval);
+        if websocket_priority_retry_interval >= websocket_retry_interval {
+            anyhow::bail!(
+                "TOM_NOTIFIER_WS_DELIVERY_WEBSOCKET_PRIORITY_RETRY_INTERVAL \
+                has to be shorter than TOM_NOTIFIER_WS_DELIVERY_WEBSOCKET_RETRY_INTERVAL"
+            );
+        }
         let websocket_connection_buffer_size =
             Self::env_var("TOM_NOTIFIER_WS_DELIVERY_WEBSOCKET_CONNECTION_BUFFER_SIZE")?.parse()?;
         let websocket_ticket_lifespan = Duration::from_secs(websocket_ticket_lifespan);
@@ -94,6 +105,7 @@ impl ApplicationEnv {
             websocket_ping_interval,
             websocket_retry_max_count,
             websocket_retry_interval,
+            websocket_priority_retry_interval,
             websocket_connection_buffer_size,
             jwt_algorithms,
             jwt_key,
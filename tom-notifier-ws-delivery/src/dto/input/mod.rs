@@ -1,4 +1,5 @@
 pub use super::inoutput::WebSocketTicket;
+pub use jwt_auth::Locales;
 
 #[allow(unused_imports)]
 pub use super::protobuf::{
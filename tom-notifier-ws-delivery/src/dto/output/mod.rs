@@ -1,7 +1,15 @@
 pub use super::inoutput::WebSocketTicket;
 
+#[allow(unused_imports)]
 pub use super::protobuf::{
-    notification::{NotificationProtobuf, NotificationStatusProtobuf},
+    notification::{
+        // LocalizedContentProtobuf is consumed by the field of NotificationProtobuf,
+        // it is constructed only in tests so the compiler produces warning
+        LocalizedContentProtobuf,
+        NotificationPriorityProtobuf,
+        NotificationProtobuf,
+        NotificationStatusProtobuf,
+    },
     rabbitmq_confirmation::RabbitmqConfirmationProtobuf,
     websocket_notification::{NetworkStatusProtobuf, WebSocketNotificationProtobuf},
 };
@@ -10,6 +10,11 @@ pub struct Ticket {
 
     pub user_id: Uuid,
 
+    ///
+    /// Locales preferred by the user when the ticket was created
+    ///
+    pub locales: Vec<String>,
+
     pub issued_at: OffsetDateTime,
     pub expire_at: OffsetDateTime,
     pub used_at: Option<OffsetDateTime>,
@@ -21,6 +26,7 @@ impl From<TicketFindEntity> for Ticket {
             _id: value._id,
             ticket: value.ticket,
             user_id: value.user_id.into(),
+            locales: value.locales,
             issued_at: value.issued_at.into(),
             expire_at: value.expire_at.into(),
             used_at: value.used_at.map(OffsetDateTime::from),
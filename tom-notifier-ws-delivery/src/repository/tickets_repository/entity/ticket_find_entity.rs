@@ -8,6 +8,8 @@ pub struct TicketFindEntity {
     pub ticket: String,
 
     pub user_id: Uuid,
+    #[serde(default)]
+    pub locales: Vec<String>,
 
     pub issued_at: DateTime,
     pub expire_at: DateTime,
@@ -6,6 +6,7 @@ pub struct TicketInsertEntity<'a> {
     pub ticket: &'a str,
 
     pub user_id: Uuid,
+    pub locales: &'a [String],
 
     pub issued_at: DateTime,
     pub expire_at: DateTime,
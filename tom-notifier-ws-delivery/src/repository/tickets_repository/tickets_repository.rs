@@ -12,6 +12,7 @@ pub trait TicketsRepository: Send + Sync {
         &self,
         ticket: &str,
         user_id: Uuid,
+        locales: &[String],
         issued_at: OffsetDateTime,
         expire_at: OffsetDateTime,
     ) -> Result<ObjectId, repository::Error>;
@@ -72,12 +72,14 @@ impl TicketsRepository for TicketsRepositoryImpl {
         &self,
         ticket: &str,
         user_id: Uuid,
+        locales: &[String],
         issued_at: OffsetDateTime,
         expire_at: OffsetDateTime,
     ) -> Result<ObjectId, repository::Error> {
         let insert_entity = TicketInsertEntity {
             ticket,
             user_id: user_id.into(),
+            locales,
             issued_at: issued_at.into(),
             expire_at: expire_at.into(),
             used_at: None,
@@ -189,11 +191,12 @@ mod test {
 
         let ticket = "my very unique ticket";
         let user_id = Uuid::new_v4();
+        let locales = vec!["pl-PL".to_string(), "en".to_string()];
         let mut issued_at = OffsetDateTime::now_utc();
         let mut expire_at = issued_at + Duration::from_secs(30);
 
         let id = repository
-            .insert(ticket, user_id, issued_at, expire_at)
+            .insert(ticket, user_id, &locales, issued_at, expire_at)
             .await
             .unwrap();
 
@@ -213,6 +216,7 @@ mod test {
 
         assert_eq!(entity.ticket, ticket);
         assert_eq!(Uuid::from(entity.user_id), user_id);
+        assert_eq!(entity.locales, locales);
         assert_eq!(OffsetDateTime::from(entity.issued_at), issued_at);
         assert_eq!(OffsetDateTime::from(entity.expire_at), expire_at);
         assert_eq!(entity.used_at, None);
@@ -233,6 +237,7 @@ mod test {
             .insert(
                 ticket,
                 Uuid::new_v4(),
+                &[],
                 OffsetDateTime::now_utc(),
                 OffsetDateTime::now_utc() + Duration::from_secs(30),
             )
@@ -243,6 +248,7 @@ mod test {
             .insert(
                 ticket,
                 Uuid::new_v4(),
+                &[],
                 OffsetDateTime::now_utc() + Duration::from_secs(50),
                 OffsetDateTime::now_utc() + Duration::from_secs(80),
             )
@@ -7,7 +7,7 @@ use crate::{
 };
 use axum::{
     extract::{ConnectInfo, Path, Query, State, WebSocketUpgrade},
-    http::StatusCode,
+    http::{header::ACCEPT_LANGUAGE, HeaderMap, StatusCode},
     response::Response,
     routing::{delete, get},
     Extension, Json, Router,
@@ -25,7 +25,9 @@ pub fn routing(application_middleware: &ApplicationMiddleware) -> Router<Applica
 }
 
 ///
-/// Create ticket that can be used to establish WebSocket connection with the server
+/// Create ticket that can be used to establish WebSocket connection with the server.
+/// Notifications sent through the connection are localized using the locale claim
+/// of the token or the Accept-Language header
 ///
 /// ### Returns
 /// 200 on success
@@ -33,8 +35,14 @@ pub fn routing(application_middleware: &ApplicationMiddleware) -> Router<Applica
 async fn get_ticket(
     State(tickets_service): State<Arc<dyn TicketsSerivce>>,
     Extension(user): Extension<User>,
+    headers: HeaderMap,
 ) -> Result<(StatusCode, Json<output::WebSocketTicket>), Error> {
-    let ticket = tickets_service.create_ticket(user.id).await?;
+    let accept_language = headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok());
+    let locales = input::Locales::new(user.locale.as_deref(), accept_language);
+
+    let ticket = tickets_service.create_ticket(user.id, locales).await?;
     Ok((StatusCode::OK, Json(ticket)))
 }
 
@@ -77,7 +85,7 @@ async fn websocket_upgrade(
     let ticket = tickets_service.consume_ticket(ticket).await?;
     let response = websocket_upgrade.on_upgrade(move |websocket| async move {
         websockets_service
-            .handle_client(ticket.user_id, address, websocket)
+            .handle_client(ticket.user_id, ticket.locales.into(), address, websocket)
             .await;
     });
     Ok(response)
@@ -144,7 +152,7 @@ mod test {
         BEFORE_ALL.call_once(init_env_variables);
 
         let mut tickets_service = MockTicketsSerivce::new();
-        tickets_service.expect_create_ticket().returning(|_| {
+        tickets_service.expect_create_ticket().returning(|_, _| {
             Ok(output::WebSocketTicket {
                 ticket: "some ticket".to_string(),
             })
@@ -169,12 +177,46 @@ mod test {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn get_ticket_accept_language_passed() {
+        BEFORE_ALL.call_once(init_env_variables);
+
+        let mut tickets_service = MockTicketsSerivce::new();
+        tickets_service
+            .expect_create_ticket()
+            .withf(|_, locales| *locales == input::Locales::new(None, Some("pl-PL, en;q=0.5")))
+            .returning(|_, _| {
+                Ok(output::WebSocketTicket {
+                    ticket: "some ticket".to_string(),
+                })
+            });
+
+        let mut application_state = mock_application_state();
+        application_state.tickets_service = Arc::new(tickets_service);
+
+        let response = routing()
+            .with_state(application_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/v1/ticket")
+                    .header(AUTHORIZATION, create_user_bearer())
+                    .header(ACCEPT_LANGUAGE, "pl-PL, en;q=0.5")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn get_ticket_database_error() {
         BEFORE_ALL.call_once(init_env_variables);
 
         let mut tickets_service = MockTicketsSerivce::new();
-        tickets_service.expect_create_ticket().returning(|_| {
+        tickets_service.expect_create_ticket().returning(|_, _| {
             Err(Error::Database(repository::Error::Mongo(
                 mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
             )))
@@ -96,11 +96,18 @@ impl RabbitmqConsumerDeliveryCallback for DeliveryCallback {
                 ConsumeError { requeue: false }
             })?;
 
-        let notification = message.notification.ok_or_else(|| {
+        let mut notification = message.notification.ok_or_else(|| {
             tracing::warn!("invalid notification: notification cannot be null");
             ConsumeError { requeue: false }
         })?;
 
+        if message.content_compressed {
+            decompress_contents(&mut notification).map_err(|err| {
+                tracing::warn!(%err, "invalid notification: content cannot be decompressed");
+                ConsumeError { requeue: false }
+            })?;
+        }
+
         let mut user_ids = Vec::with_capacity(message.user_ids.len());
         for uuid_str in message.user_ids {
             let uuid = Uuid::from_str(&uuid_str).map_err(|err| {
@@ -110,6 +117,55 @@ impl RabbitmqConsumerDeliveryCallback for DeliveryCallback {
             user_ids.push(uuid);
         }
 
+        let mut excluded_user_ids = Vec::with_capacity(message.excluded_user_ids.len());
+        for uuid_str in message.excluded_user_ids {
+            let uuid = Uuid::from_str(&uuid_str).map_err(|err| {
+                tracing::warn!(%err, "invalid excluded_user_id");
+                ConsumeError { requeue: false }
+            })?;
+            excluded_user_ids.push(uuid);
+        }
+
+        // Empty user_ids would make the notification a broadcast,
+        // so notification of topic without subscribers is dropped
+        if user_ids.is_empty() && message.topic.is_some() {
+            tracing::debug!(
+                topic = message.topic,
+                "topic notification has no recipients"
+            );
+            return Ok(());
+        }
+
+        // Single UPDATED message of seen may change many notifications,
+        // clients always receive a separate message for every notification
+        let notifications = if notification.ids.is_empty() {
+            vec![notification]
+        } else {
+            std::mem::take(&mut notification.ids)
+                .into_iter()
+                .map(|id| input::NotificationProtobuf {
+                    id,
+                    ..notification.clone()
+                })
+                .collect()
+        };
+
+        for notification in notifications {
+            self.deliver(&user_ids, &excluded_user_ids, notification)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeliveryCallback {
+    async fn deliver(
+        &self,
+        user_ids: &[Uuid],
+        excluded_user_ids: &[Uuid],
+        notification: input::NotificationProtobuf,
+    ) -> Result<(), ConsumeError> {
         let notification_status_update = NotificationStatusUpdate::try_from(&notification)
             .map_err(|err| {
                 tracing::warn!(%err, "notification invalid");
@@ -124,7 +180,9 @@ impl RabbitmqConsumerDeliveryCallback for DeliveryCallback {
         {
             Ok(()) => {
                 tracing::info!(id = notification.id, "sending notification to clients");
-                self.websockets_service.send(&user_ids, notification).await;
+                self.websockets_service
+                    .send(user_ids, excluded_user_ids, notification)
+                    .await;
                 Ok(())
             }
             Err(Error::Duplicate) => {
@@ -141,6 +199,21 @@ impl RabbitmqConsumerDeliveryCallback for DeliveryCallback {
     }
 }
 
+///
+/// Decompresses content and all localized contents of the notification,
+/// so clients always receive them uncompressed
+///
+fn decompress_contents(notification: &mut input::NotificationProtobuf) -> std::io::Result<()> {
+    if let Some(content) = &mut notification.content {
+        *content = zstd::stream::decode_all(content.as_slice())?;
+    }
+    for localized_content in &mut notification.localized_contents {
+        localized_content.content = zstd::stream::decode_all(localized_content.content.as_slice())?;
+    }
+
+    Ok(())
+}
+
 struct StatusCallback {
     websockets_service: Arc<dyn WebSocketsService>,
 }
@@ -23,12 +23,17 @@ impl TicketsServiceImpl {
 #[async_trait]
 impl TicketsSerivce for TicketsServiceImpl {
     ///
-    /// Creates ticket that is used to establish WebSocket connection with the server
+    /// Creates ticket that is used to establish WebSocket connection with the server.
+    /// Locales are stored with the ticket, so they are known once the connection is established
     ///
     /// ### Returns
     /// [output::WebSocketTicket]
     ///
-    async fn create_ticket(&self, user_id: Uuid) -> Result<output::WebSocketTicket, Error> {
+    async fn create_ticket(
+        &self,
+        user_id: Uuid,
+        locales: input::Locales,
+    ) -> Result<output::WebSocketTicket, Error> {
         tracing::info!("creating ticket");
 
         let issued_at = OffsetDateTime::now_utc();
@@ -37,7 +42,7 @@ impl TicketsSerivce for TicketsServiceImpl {
 
         let id = self
             .repository
-            .insert(&ticket, user_id, issued_at, expire_at)
+            .insert(&ticket, user_id, locales.as_slice(), issued_at, expire_at)
             .await?;
         tracing::info!(%id, "ticket created");
 
@@ -106,12 +111,18 @@ mod test {
         let mut repository = MockTicketsRepository::new();
         repository
             .expect_insert()
-            .returning(|_, _, _, _| Ok(ObjectId::new()));
+            .returning(|_, _, _, _, _| Ok(ObjectId::new()));
         let service = TicketsServiceImpl::new(config, Arc::new(repository));
 
         let user_id = Uuid::new_v4();
-        let ticket_1 = service.create_ticket(user_id).await.unwrap();
-        let ticket_2 = service.create_ticket(user_id).await.unwrap();
+        let ticket_1 = service
+            .create_ticket(user_id, input::Locales::default())
+            .await
+            .unwrap();
+        let ticket_2 = service
+            .create_ticket(user_id, input::Locales::default())
+            .await
+            .unwrap();
 
         assert_ne!(ticket_1.ticket, ticket_2.ticket);
     }
@@ -124,10 +135,12 @@ mod test {
         let mut repository = MockTicketsRepository::new();
         repository
             .expect_insert()
-            .returning(|_, _, _, _| Err(repository::Error::InsertUniqueViolation));
+            .returning(|_, _, _, _, _| Err(repository::Error::InsertUniqueViolation));
         let service = TicketsServiceImpl::new(config, Arc::new(repository));
 
-        let create_result = service.create_ticket(Uuid::new_v4()).await;
+        let create_result = service
+            .create_ticket(Uuid::new_v4(), input::Locales::default())
+            .await;
 
         assert!(matches!(create_result, Err(Error::Database(_))));
     }
@@ -138,14 +151,16 @@ mod test {
             ticket_lifespan: Duration::from_secs(30),
         };
         let mut repository = MockTicketsRepository::new();
-        repository.expect_insert().returning(|_, _, _, _| {
+        repository.expect_insert().returning(|_, _, _, _, _| {
             Err(repository::Error::Mongo(
                 mongodb::error::ErrorKind::Custom(Arc::new("any database error")).into(),
             ))
         });
         let service = TicketsServiceImpl::new(config, Arc::new(repository));
 
-        let create_result = service.create_ticket(Uuid::new_v4()).await;
+        let create_result = service
+            .create_ticket(Uuid::new_v4(), input::Locales::default())
+            .await;
 
         assert!(matches!(create_result, Err(Error::Database(_))));
     }
@@ -201,6 +216,7 @@ mod test {
                 _id: ObjectId::new(),
                 ticket: ticket.to_string(),
                 user_id: Uuid::new_v4(),
+                locales: vec![],
                 issued_at: OffsetDateTime::now_utc() - Duration::from_secs(60),
                 expire_at: OffsetDateTime::now_utc() + Duration::from_secs(30),
                 used_at: Some(OffsetDateTime::now_utc() - Duration::from_secs(40)),
@@ -228,6 +244,7 @@ mod test {
                 _id: ObjectId::new(),
                 ticket: ticket.to_string(),
                 user_id: Uuid::new_v4(),
+                locales: vec![],
                 issued_at: OffsetDateTime::now_utc() - Duration::from_secs(60),
                 expire_at: OffsetDateTime::now_utc() - Duration::from_secs(30),
                 used_at: None,
@@ -256,6 +273,7 @@ mod test {
                 _id: ObjectId::new(),
                 ticket: ticket.to_string(),
                 user_id: Uuid::new_v4(),
+                locales: vec![],
                 issued_at: OffsetDateTime::now_utc() - Duration::from_secs(60),
                 expire_at: OffsetDateTime::now_utc() + Duration::from_secs(60),
                 used_at: None,
@@ -291,6 +309,7 @@ mod test {
                 _id: ObjectId::new(),
                 ticket: ticket.to_string(),
                 user_id: Uuid::new_v4(),
+                locales: vec![],
                 issued_at: OffsetDateTime::now_utc() - Duration::from_secs(60),
                 expire_at: OffsetDateTime::now_utc() + Duration::from_secs(60),
                 used_at: None,
@@ -321,6 +340,7 @@ mod test {
                 _id: ObjectId::new(),
                 ticket: ticket.to_string(),
                 user_id: Uuid::new_v4(),
+                locales: vec![],
                 issued_at: OffsetDateTime::now_utc() - Duration::from_secs(60),
                 expire_at: OffsetDateTime::now_utc() + Duration::from_secs(60),
                 used_at: None,
@@ -1,12 +1,44 @@
-use crate::service::websockets_service::websocket_confirmation_callback::WebSocketConfirmationCallback;
+use crate::{
+    dto::{input, output},
+    service::websockets_service::websocket_confirmation_callback::WebSocketConfirmationCallback,
+};
 use uuid::Uuid;
 
 pub struct WebSocketMessage {
     pub message_id: Uuid,
     pub payload: Vec<u8>,
 
+    ///
+    /// Payloads with localized content of the notification paired with their locales
+    ///
+    pub localized_payloads: Vec<(String, Vec<u8>)>,
+
+    ///
+    /// Priority of the notification, messages without notification have normal priority
+    ///
+    pub priority: output::NotificationPriorityProtobuf,
+
     ///
     /// Callback executed when user confirms the message
     ///
     pub delivered_callback: Option<WebSocketConfirmationCallback>,
 }
+
+impl WebSocketMessage {
+    ///
+    /// Payload with the content variant best matching locales.
+    /// When none of the variants matches, payload that is not localized is used
+    ///
+    pub fn payload(&self, locales: &input::Locales) -> &[u8] {
+        let best_match = locales.best_match(
+            self.localized_payloads
+                .iter()
+                .map(|(locale, _)| locale.as_str()),
+        );
+
+        match best_match {
+            Some(idx) => &self.localized_payloads[idx].1,
+            None => &self.payload,
+        }
+    }
+}
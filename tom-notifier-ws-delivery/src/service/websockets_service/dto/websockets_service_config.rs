@@ -6,5 +6,11 @@ pub struct WebSocketsServiceConfig {
     pub retry_max_count: u8,
     pub retry_interval: Duration,
 
+    ///
+    /// Retry interval of high and urgent priority messages,
+    /// shorter than retry_interval
+    ///
+    pub priority_retry_interval: Duration,
+
     pub connection_buffer_size: u8,
 }
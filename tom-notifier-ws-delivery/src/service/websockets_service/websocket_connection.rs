@@ -2,12 +2,15 @@ use super::{
     dto::{WebSocketMessage, WebSocketUnconfirmedMessage, WebSocketsServiceConfig},
     error::Error,
 };
-use crate::dto::input;
+use crate::dto::{input, output};
 use anyhow::anyhow;
 use axum::extract::ws::Message;
 use futures::{Sink, SinkExt, Stream, StreamExt};
 use prost::Message as ProstMessage;
-use std::{collections::VecDeque, fmt::Display, net::SocketAddr, str::FromStr, sync::Arc};
+use std::{
+    cmp::Reverse, collections::VecDeque, fmt::Display, net::SocketAddr, str::FromStr, sync::Arc,
+    time::Duration,
+};
 use tokio::{
     sync::broadcast,
     time::{sleep_until, Instant},
@@ -18,6 +21,7 @@ pub struct WebSocketConnection<WebSocketSink, WebSocketStream> {
     config: Arc<WebSocketsServiceConfig>,
 
     user_id: Uuid,
+    user_locales: input::Locales,
     user_address: SocketAddr,
 
     messages_rx: broadcast::Receiver<Arc<WebSocketMessage>>,
@@ -42,6 +46,7 @@ where
     pub fn new(
         config: Arc<WebSocketsServiceConfig>,
         user_id: Uuid,
+        user_locales: input::Locales,
         user_address: SocketAddr,
         messages_rx: broadcast::Receiver<Arc<WebSocketMessage>>,
         ws_tx: WebSocketSink,
@@ -55,6 +60,7 @@ where
         Self {
             config,
             user_id,
+            user_locales,
             user_address,
             messages_rx,
             ws_tx,
@@ -260,38 +266,73 @@ where
         &mut self,
         message: Result<Arc<WebSocketMessage>, broadcast::error::RecvError>,
     ) -> Result<(), Error> {
-        match message {
-            Err(broadcast::error::RecvError::Lagged(count)) => Err(Error::Anyhow(anyhow!(
-                "connection lagged. skipped messages: {count}"
-            ))),
+        let mut messages = match message {
+            Err(broadcast::error::RecvError::Lagged(count)) => {
+                return Err(Self::lagged_error(count));
+            }
             Err(broadcast::error::RecvError::Closed) => {
-                Err(Error::Close("connection forcefully closed"))
+                return Err(Error::Close("connection forcefully closed"));
             }
-            Ok(message) => {
-                let message_id_str = message.message_id.to_string();
-
-                tracing::info!(message_id = message_id_str, "sending message");
-                self.ws_tx
-                    .send(Message::Binary(message.payload.clone()))
-                    .await
-                    .map_err(|err| anyhow!("sending message failed: {err}"))?;
-
-                let message = WebSocketUnconfirmedMessage {
-                    retry_at: Instant::now() + self.config.retry_interval,
-                    retries_remaining: self.config.retry_max_count,
-                    message,
-                };
-                self.unconfirmed_messages.push_back(message);
-                tracing::debug!(
-                    message_id = message_id_str,
-                    "message waits for confirmation"
-                );
-
-                tracing::info!(message_id = message_id_str, "sent message");
-
-                Ok(())
+            Ok(message) => vec![message],
+        };
+
+        // Messages that are already waiting in the channel are sent together,
+        // so higher priority messages can overtake lower priority ones.
+        // Closed channel is reported by the next recv()
+        loop {
+            match self.messages_rx.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(broadcast::error::TryRecvError::Lagged(count)) => {
+                    return Err(Self::lagged_error(count));
+                }
+                Err(broadcast::error::TryRecvError::Empty)
+                | Err(broadcast::error::TryRecvError::Closed) => break,
             }
         }
+
+        // Sort is stable so messages of the same priority keep their order
+        messages.sort_by_key(|message| Reverse(message.priority));
+
+        for message in messages {
+            self.send_message(message).await?;
+        }
+
+        Ok(())
+    }
+
+    fn lagged_error(count: u64) -> Error {
+        Error::Anyhow(anyhow!("connection lagged. skipped messages: {count}"))
+    }
+
+    async fn send_message(&mut self, message: Arc<WebSocketMessage>) -> anyhow::Result<()> {
+        let message_id_str = message.message_id.to_string();
+
+        tracing::info!(
+            message_id = message_id_str,
+            priority = ?message.priority,
+            "sending message"
+        );
+        self.ws_tx
+            .send(Message::Binary(
+                message.payload(&self.user_locales).to_vec(),
+            ))
+            .await
+            .map_err(|err| anyhow!("sending message failed: {err}"))?;
+
+        let message = WebSocketUnconfirmedMessage {
+            retry_at: Instant::now() + self.retry_interval(message.priority),
+            retries_remaining: self.config.retry_max_count,
+            message,
+        };
+        self.push_unconfirmed_message(message);
+        tracing::debug!(
+            message_id = message_id_str,
+            "message waits for confirmation"
+        );
+
+        tracing::info!(message_id = message_id_str, "sent message");
+
+        Ok(())
     }
 
     async fn process_unconfirmed_message(
@@ -311,7 +352,7 @@ where
             ));
         }
 
-        unconfirmed.retry_at = Instant::now() + self.config.retry_interval;
+        unconfirmed.retry_at = Instant::now() + self.retry_interval(unconfirmed.message.priority);
         unconfirmed.retries_remaining -= 1;
 
         tracing::debug!(
@@ -320,16 +361,38 @@ where
             "resending message"
         );
         self.ws_tx
-            .send(Message::Binary(unconfirmed.message.payload.clone()))
+            .send(Message::Binary(
+                unconfirmed.message.payload(&self.user_locales).to_vec(),
+            ))
             .await
             .map_err(|err| anyhow!("failed to resend message: {err}"))?;
 
-        self.unconfirmed_messages.push_back(unconfirmed);
+        self.push_unconfirmed_message(unconfirmed);
 
         tracing::debug!(message_id = message_id_str, "processed unconfirmed message");
 
         Ok(())
     }
+
+    fn retry_interval(&self, priority: output::NotificationPriorityProtobuf) -> Duration {
+        match priority {
+            output::NotificationPriorityProtobuf::High
+            | output::NotificationPriorityProtobuf::Urgent => self.config.priority_retry_interval,
+            output::NotificationPriorityProtobuf::Low
+            | output::NotificationPriorityProtobuf::Normal => self.config.retry_interval,
+        }
+    }
+
+    ///
+    /// Messages are retried on different intervals depending on their priority,
+    /// so the queue has to be kept sorted by retry_at
+    ///
+    fn push_unconfirmed_message(&mut self, unconfirmed: WebSocketUnconfirmedMessage) {
+        let idx = self
+            .unconfirmed_messages
+            .partition_point(|queued| queued.retry_at <= unconfirmed.retry_at);
+        self.unconfirmed_messages.insert(idx, unconfirmed);
+    }
 }
 
 #[cfg(test)]
@@ -485,6 +548,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id,
             payload: b"payload does not matter".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: Some(WebSocketConfirmationCallback::new(
                 Arc::new(confirmations_service),
                 "any string will do".to_string(),
@@ -650,6 +715,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id: Uuid::new_v4(),
             payload: notification.clone(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: None,
         });
 
@@ -666,6 +733,85 @@ mod test {
         assert_eq!(received_bytes, notification);
     }
 
+    #[tokio::test]
+    async fn new_message_localized_payload_sent_to_the_user() {
+        let config = create_test_config();
+
+        let locales = input::Locales::new(Some("pl"), None);
+        let (_handle, _ws_tx, mut ws_rx, notifications_tx) =
+            start_localized_test_connection(config, locales);
+
+        let message = Arc::new(WebSocketMessage {
+            message_id: Uuid::new_v4(),
+            payload: b"default".to_vec(),
+            localized_payloads: vec![
+                ("en".to_string(), b"english".to_vec()),
+                ("pl-PL".to_string(), b"polish".to_vec()),
+            ],
+            priority: output::NotificationPriorityProtobuf::Normal,
+            delivered_callback: None,
+        });
+
+        let _ = notifications_tx.send(message);
+
+        let received_message = timeout(Duration::from_secs(1), ws_rx.next())
+            .await
+            .unwrap() // timeout
+            .unwrap();
+        let Message::Binary(received_bytes) = received_message else {
+            panic!("invalid message type");
+        };
+
+        assert_eq!(received_bytes, b"polish");
+    }
+
+    #[tokio::test]
+    async fn new_messages_sent_by_priority() {
+        let config = create_test_config();
+
+        let (_handle, _ws_tx, mut ws_rx, notifications_tx) = start_test_connection(config);
+
+        // messages are queued before the connection task gets to run
+        let priorities = [
+            output::NotificationPriorityProtobuf::Low,
+            output::NotificationPriorityProtobuf::Normal,
+            output::NotificationPriorityProtobuf::Urgent,
+            output::NotificationPriorityProtobuf::High,
+        ];
+        for priority in priorities {
+            let message = Arc::new(WebSocketMessage {
+                message_id: Uuid::new_v4(),
+                payload: vec![priority as u8],
+                localized_payloads: vec![],
+                priority,
+                delivered_callback: None,
+            });
+            let _ = notifications_tx.send(message);
+        }
+
+        let mut received_priorities = Vec::with_capacity(priorities.len());
+        for _ in 0..priorities.len() {
+            let received_message = timeout(Duration::from_secs(1), ws_rx.next())
+                .await
+                .unwrap() // timeout
+                .unwrap();
+            let Message::Binary(received_bytes) = received_message else {
+                panic!("invalid message type");
+            };
+            received_priorities.push(received_bytes[0]);
+        }
+
+        assert_eq!(
+            received_priorities,
+            [
+                output::NotificationPriorityProtobuf::Urgent as u8,
+                output::NotificationPriorityProtobuf::High as u8,
+                output::NotificationPriorityProtobuf::Normal as u8,
+                output::NotificationPriorityProtobuf::Low as u8,
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn new_message_connection_closed() {
         let config = create_test_config();
@@ -677,6 +823,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id: Uuid::new_v4(),
             payload: b"ignore me".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: None,
         });
 
@@ -698,6 +846,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id: Uuid::new_v4(),
             payload: b"ignore".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: None,
         });
 
@@ -745,6 +895,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id,
             payload: b"ignore".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: None,
         });
 
@@ -790,6 +942,57 @@ mod test {
         assert!(timeout_result.is_err());
     }
 
+    #[tokio::test]
+    async fn queued_message_high_priority_resent_first() {
+        let mut config = create_test_config();
+        config.retry_interval = Duration::from_millis(500);
+        config.priority_retry_interval = Duration::from_millis(50);
+
+        let (_handle, _ws_tx, mut ws_rx, notifications_tx) = start_test_connection(config);
+
+        let normal_message = Arc::new(WebSocketMessage {
+            message_id: Uuid::new_v4(),
+            payload: b"normal".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
+            delivered_callback: None,
+        });
+        let high_message = Arc::new(WebSocketMessage {
+            message_id: Uuid::new_v4(),
+            payload: b"high".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::High,
+            delivered_callback: None,
+        });
+
+        let _ = notifications_tx.send(normal_message);
+        let _ = notifications_tx.send(high_message);
+
+        let mut received_payloads = Vec::new();
+        for _ in 0..4 {
+            let received_message = timeout(Duration::from_secs(1), ws_rx.next())
+                .await
+                .unwrap() // timeout
+                .unwrap();
+            let Message::Binary(received_bytes) = received_message else {
+                panic!("invalid message type");
+            };
+            received_payloads.push(received_bytes);
+        }
+
+        // high priority message is sent first and resent
+        // before normal priority message is resent for the first time
+        assert_eq!(
+            received_payloads,
+            [
+                b"high".to_vec(),
+                b"normal".to_vec(),
+                b"high".to_vec(),
+                b"high".to_vec(),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn queued_message_resend_until_limit_reached() {
         let retry_max_count = 4;
@@ -803,6 +1006,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id: Uuid::new_v4(),
             payload: b"ignore".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: None,
         });
 
@@ -841,6 +1046,8 @@ mod test {
         let message = Arc::new(WebSocketMessage {
             message_id: Uuid::new_v4(),
             payload: b"ignore".to_vec(),
+            localized_payloads: vec![],
+            priority: output::NotificationPriorityProtobuf::Normal,
             delivered_callback: None,
         });
 
@@ -870,6 +1077,7 @@ mod test {
         WebSocketsServiceConfig {
             ping_interval: Duration::from_secs(1200),
             retry_interval: Duration::from_secs(1200),
+            priority_retry_interval: Duration::from_secs(1200),
             retry_max_count: u8::MAX,
             connection_buffer_size: u8::MAX,
         }
@@ -891,6 +1099,24 @@ mod test {
         futures::channel::mpsc::UnboundedSender<Result<Message, axum::Error>>,
         futures::channel::mpsc::UnboundedReceiver<Message>,
         broadcast::Sender<Arc<WebSocketMessage>>,
+    ) {
+        start_localized_test_connection(config, input::Locales::default())
+    }
+
+    ///
+    /// Starts task with connection of the user preferring passed locales.
+    ///
+    /// ### returns
+    /// the same values as [start_test_connection]
+    ///
+    fn start_localized_test_connection(
+        config: WebSocketsServiceConfig,
+        locales: input::Locales,
+    ) -> (
+        tokio::task::JoinHandle<()>,
+        futures::channel::mpsc::UnboundedSender<Result<Message, axum::Error>>,
+        futures::channel::mpsc::UnboundedReceiver<Message>,
+        broadcast::Sender<Arc<WebSocketMessage>>,
     ) {
         let (ws_server_tx, ws_client_rx) = futures::channel::mpsc::unbounded();
         let (ws_client_tx, ws_server_rx) = futures::channel::mpsc::unbounded();
@@ -899,6 +1125,7 @@ mod test {
         let ws_connection = WebSocketConnection::new(
             Arc::new(config),
             Uuid::new_v4(),
+            locales,
             "0.0.0.0:1234".parse().unwrap(),
             messages_rx,
             ws_server_tx,
@@ -1,4 +1,4 @@
-use crate::dto::output;
+use crate::dto::{input, output};
 use axum::{async_trait, extract::ws::WebSocket};
 use std::net::SocketAddr;
 use uuid::Uuid;
@@ -6,11 +6,26 @@ use uuid::Uuid;
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait WebSocketsService: Send + Sync {
-    async fn handle_client(&self, user_id: Uuid, address: SocketAddr, websocket: WebSocket);
+    async fn handle_client(
+        &self,
+        user_id: Uuid,
+        locales: input::Locales,
+        address: SocketAddr,
+        websocket: WebSocket,
+    );
 
     async fn close_connections(&self, user_id: Uuid);
 
-    async fn send(&self, user_ids: &[Uuid], notification: output::NotificationProtobuf);
+    ///
+    /// Sends notification to connected users from user_ids, or to every
+    /// connected user when user_ids is empty, skipping users from excluded_user_ids
+    ///
+    async fn send(
+        &self,
+        user_ids: &[Uuid],
+        excluded_user_ids: &[Uuid],
+        notification: output::NotificationProtobuf,
+    );
 
     async fn update_network_status(&self, status: output::NetworkStatusProtobuf);
 }
@@ -4,7 +4,7 @@ use super::{
     WebSocketsService,
 };
 use crate::{
-    dto::output,
+    dto::{input, output},
     service::{
         confirmations_service::ConfirmationsService,
         websockets_service::websocket_connection::WebSocketConnection,
@@ -59,6 +59,11 @@ impl WebSocketsServiceImpl {
     ) -> Arc<WebSocketMessage> {
         let now = OffsetDateTime::now_utc();
         let message_id = Uuid::new_v4();
+        let priority = notification
+            .as_ref()
+            .and_then(|notification| notification.priority)
+            .and_then(|priority| output::NotificationPriorityProtobuf::try_from(priority).ok())
+            .unwrap_or(output::NotificationPriorityProtobuf::Normal);
         let delivered_callback = notification
             .as_ref()
             .filter(|notification| notification.status() == output::NotificationStatusProtobuf::New)
@@ -69,7 +74,7 @@ impl WebSocketsServiceImpl {
                 )
             });
 
-        let websocket_message = output::WebSocketNotificationProtobuf {
+        let mut websocket_message = output::WebSocketNotificationProtobuf {
             message_id: message_id.to_string(),
             message_timestamp: Some(Timestamp {
                 seconds: now.unix_timestamp(),
@@ -79,19 +84,48 @@ impl WebSocketsServiceImpl {
             notification,
         };
 
+        // Every user receives only the content variant matching their locales,
+        // so variants are encoded upfront instead of once per connection
+        let localized_contents = websocket_message
+            .notification
+            .as_mut()
+            .map(|notification| std::mem::take(&mut notification.localized_contents))
+            .unwrap_or_default();
+
         let payload = websocket_message.encode_to_vec();
 
+        let localized_payloads = localized_contents
+            .into_iter()
+            .map(|localized_content| {
+                let mut localized_message = websocket_message.clone();
+                if let Some(notification) = localized_message.notification.as_mut() {
+                    notification.content_type = Some(localized_content.content_type);
+                    notification.content = Some(localized_content.content);
+                }
+
+                (localized_content.locale, localized_message.encode_to_vec())
+            })
+            .collect();
+
         Arc::new(WebSocketMessage {
             message_id,
             payload,
+            localized_payloads,
+            priority,
             delivered_callback,
         })
     }
 
-    async fn send_multicast(&self, user_ids: &[Uuid], message: Arc<WebSocketMessage>) {
+    async fn send_multicast(
+        &self,
+        user_ids: &[Uuid],
+        excluded_user_ids: &[Uuid],
+        message: Arc<WebSocketMessage>,
+    ) {
         let connections = self.users_connections.read().await;
         user_ids
             .into_iter()
+            .filter(|user_id| !excluded_user_ids.contains(user_id))
             .filter_map(|user_id| connections.get_key_value(user_id))
             .for_each(|(user_id, tx)| {
                 let _ = tx.send(message.clone());
@@ -103,22 +137,31 @@ impl WebSocketsServiceImpl {
             });
     }
 
-    async fn send_broadcast(&self, message: Arc<WebSocketMessage>) {
+    async fn send_broadcast(&self, excluded_user_ids: &[Uuid], message: Arc<WebSocketMessage>) {
         let connections = self.users_connections.read().await;
-        connections.iter().for_each(|(user_id, tx)| {
-            let _ = tx.send(message.clone());
-            tracing::info!(
-                message_id = message.message_id.to_string(),
-                %user_id,
-                "queued message to be sent",
-            );
-        });
+        connections
+            .iter()
+            .filter(|(user_id, _)| !excluded_user_ids.contains(user_id))
+            .for_each(|(user_id, tx)| {
+                let _ = tx.send(message.clone());
+                tracing::info!(
+                    message_id = message.message_id.to_string(),
+                    %user_id,
+                    "queued message to be sent",
+                );
+            });
     }
 }
 
 #[async_trait]
 impl WebSocketsService for WebSocketsServiceImpl {
-    async fn handle_client(&self, user_id: Uuid, address: SocketAddr, websocket: WebSocket) {
+    async fn handle_client(
+        &self,
+        user_id: Uuid,
+        locales: input::Locales,
+        address: SocketAddr,
+        websocket: WebSocket,
+    ) {
         let user_id_str = user_id.to_string();
         let address_str = address.to_string();
 
@@ -174,6 +217,7 @@ impl WebSocketsService for WebSocketsServiceImpl {
         let connection = WebSocketConnection::new(
             Arc::clone(&self.config),
             user_id,
+            locales,
             address,
             messages_rx,
             ws_tx,
@@ -219,11 +263,19 @@ impl WebSocketsService for WebSocketsServiceImpl {
         tracing::info!(%user_id, connections_count, "closing user connections");
     }
 
-    async fn send(&self, user_ids: &[Uuid], notification: output::NotificationProtobuf) {
+    async fn send(
+        &self,
+        user_ids: &[Uuid],
+        excluded_user_ids: &[Uuid],
+        notification: output::NotificationProtobuf,
+    ) {
         let message = self.create_message(output::NetworkStatusProtobuf::Ok, Some(notification));
         match user_ids.is_empty() {
-            true => self.send_broadcast(message).await,
-            false => self.send_multicast(user_ids, message).await,
+            true => self.send_broadcast(excluded_user_ids, message).await,
+            false => {
+                self.send_multicast(user_ids, excluded_user_ids, message)
+                    .await
+            }
         }
     }
 
@@ -240,7 +292,7 @@ impl WebSocketsService for WebSocketsServiceImpl {
         // Send information about network problems to every connected user
         // so they can start using long polling
         let message = self.create_message(status, None);
-        self.send_broadcast(message).await;
+        self.send_broadcast(&[], message).await;
 
         tracing::info!(?status, "sending network status update scheduled");
     }
@@ -289,7 +341,7 @@ mod test {
         lock.insert(user_2_id, tx_2);
         drop(lock);
 
-        service.send(&[user_1_id], create_notification()).await;
+        service.send(&[user_1_id], &[], create_notification()).await;
 
         let (t1, t2) = tokio::join!(
             tokio::time::timeout(Duration::from_millis(100), rx_1.recv()),
@@ -318,7 +370,7 @@ mod test {
         drop(lock);
 
         service
-            .send(&[user_1_id, user_3_id], create_notification())
+            .send(&[user_1_id, user_3_id], &[], create_notification())
             .await;
 
         let (t1, t2, t3) = tokio::join!(
@@ -349,7 +401,7 @@ mod test {
         lock.insert(user_3_id, tx_3);
         drop(lock);
 
-        service.send(&[], create_notification()).await;
+        service.send(&[], &[], create_notification()).await;
 
         let (t1, t2, t3) = tokio::join!(
             tokio::time::timeout(Duration::from_millis(100), rx_1.recv()),
@@ -362,6 +414,65 @@ mod test {
         assert!(t3.is_ok());
     }
 
+    #[tokio::test]
+    async fn send_multicast_excluded_channels_not_received_message() {
+        let service = create_service();
+        let user_1_id = Uuid::new_v4();
+        let user_2_id = Uuid::new_v4();
+
+        // simulate connection
+        let (tx_1, mut rx_1) = broadcast::channel(8);
+        let (tx_2, mut rx_2) = broadcast::channel(8);
+        let mut lock = service.users_connections.write().await;
+        lock.insert(user_1_id, tx_1);
+        lock.insert(user_2_id, tx_2);
+        drop(lock);
+
+        service
+            .send(&[user_1_id, user_2_id], &[user_2_id], create_notification())
+            .await;
+
+        let (t1, t2) = tokio::join!(
+            tokio::time::timeout(Duration::from_millis(100), rx_1.recv()),
+            tokio::time::timeout(Duration::from_millis(100), rx_2.recv()),
+        );
+
+        assert!(t1.is_ok());
+        assert!(t2.is_err());
+    }
+
+    #[tokio::test]
+    async fn send_broadcast_excluded_channels_not_received_message() {
+        let service = create_service();
+        let user_1_id = Uuid::new_v4();
+        let user_2_id = Uuid::new_v4();
+        let user_3_id = Uuid::new_v4();
+
+        // simulate connection
+        let (tx_1, mut rx_1) = broadcast::channel(8);
+        let (tx_2, mut rx_2) = broadcast::channel(8);
+        let (tx_3, mut rx_3) = broadcast::channel(8);
+        let mut lock = service.users_connections.write().await;
+        lock.insert(user_1_id, tx_1);
+        lock.insert(user_2_id, tx_2);
+        lock.insert(user_3_id, tx_3);
+        drop(lock);
+
+        service
+            .send(&[], &[user_1_id, user_3_id], create_notification())
+            .await;
+
+        let (t1, t2, t3) = tokio::join!(
+            tokio::time::timeout(Duration::from_millis(100), rx_1.recv()),
+            tokio::time::timeout(Duration::from_millis(100), rx_2.recv()),
+            tokio::time::timeout(Duration::from_millis(100), rx_3.recv()),
+        );
+
+        assert!(t1.is_err());
+        assert!(t2.is_ok());
+        assert!(t3.is_err());
+    }
+
     #[tokio::test]
     async fn send_new_callback_present() {
         let service = create_service();
@@ -385,9 +496,12 @@ mod test {
             seen: Some(false),
             content_type: Some("content type".to_string()),
             content: Some(b"content".to_vec()),
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         };
 
-        service.send(&[user_id], notification).await;
+        service.send(&[user_id], &[], notification).await;
 
         let message = tokio::time::timeout(Duration::from_millis(100), rx.recv())
             .await
@@ -420,9 +534,12 @@ mod test {
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         };
 
-        service.send(&[user_id], notification).await;
+        service.send(&[user_id], &[], notification).await;
 
         let message = tokio::time::timeout(Duration::from_millis(100), rx.recv())
             .await
@@ -455,9 +572,12 @@ mod test {
             seen: None,
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         };
 
-        service.send(&[user_id], notification).await;
+        service.send(&[user_id], &[], notification).await;
 
         let message = tokio::time::timeout(Duration::from_millis(100), rx.recv())
             .await
@@ -467,6 +587,55 @@ mod test {
         assert!(message.delivered_callback.is_none());
     }
 
+    #[tokio::test]
+    async fn send_localized_contents_encoded_separately() {
+        let service = create_service();
+        let user_id = Uuid::new_v4();
+
+        // simulate connection
+        let (tx, mut rx) = broadcast::channel(8);
+        {
+            let mut lock = service.users_connections.write().await;
+            lock.insert(user_id, tx);
+        }
+
+        let mut notification = create_notification();
+        notification.content_type = Some("utf-8".to_string());
+        notification.content = Some(b"default".to_vec());
+        notification.localized_contents = vec![output::LocalizedContentProtobuf {
+            locale: "pl-PL".to_string(),
+            content_type: "json".to_string(),
+            content: b"polish".to_vec(),
+        }];
+
+        service.send(&[user_id], &[], notification).await;
+
+        let message = tokio::time::timeout(Duration::from_millis(100), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let localized = output::WebSocketNotificationProtobuf::decode(
+            message.payload(&input::Locales::new(None, Some("pl"))),
+        )
+        .unwrap()
+        .notification
+        .unwrap();
+        let fallback = output::WebSocketNotificationProtobuf::decode(
+            message.payload(&input::Locales::new(None, Some("en"))),
+        )
+        .unwrap()
+        .notification
+        .unwrap();
+
+        assert_eq!(localized.content_type(), "json");
+        assert_eq!(localized.content(), b"polish");
+        assert!(localized.localized_contents.is_empty());
+        assert_eq!(fallback.content_type(), "utf-8");
+        assert_eq!(fallback.content(), b"default");
+        assert!(fallback.localized_contents.is_empty());
+    }
+
     #[tokio::test]
     async fn update_network_status_all_users_receive_network_status_ok_update() {
         test_update_network_status_all_users_receive_network_status_update(
@@ -528,6 +697,7 @@ mod test {
             ping_interval: Duration::from_secs(600),
             retry_max_count: 10,
             retry_interval: Duration::from_secs(10),
+            priority_retry_interval: Duration::from_secs(3),
             connection_buffer_size: 16,
         };
 
@@ -546,6 +716,9 @@ mod test {
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }
     }
 }
@@ -92,6 +92,9 @@ async fn test_confirmation_send_after_response(
     let now = OffsetDateTime::now_utc();
     let notification = protobuf::rabbitmq_notification::RabbitmqNotificationProtobuf {
         user_ids: vec![user_id.to_string()],
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(protobuf::notification::NotificationProtobuf {
             id: id.to_hex(),
             status: status.into(),
@@ -103,6 +106,9 @@ async fn test_confirmation_send_after_response(
             seen: Some(false),
             content_type: Some("utf-8".to_string()),
             content: Some(b"test_confirmation_send_after_response".to_vec()),
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
     channel
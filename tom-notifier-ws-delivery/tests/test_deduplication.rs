@@ -31,6 +31,9 @@ async fn deduplication_not_needed() -> anyhow::Result<()> {
     let notifications = [
         RabbitmqNotificationProtobuf {
             user_ids: vec![user_id.to_string()],
+            topic: None,
+            excluded_user_ids: vec![],
+            content_compressed: false,
             notification: Some(NotificationProtobuf {
                 id: id_1.to_hex(),
                 status: NotificationStatusProtobuf::Updated.into(),
@@ -42,10 +45,16 @@ async fn deduplication_not_needed() -> anyhow::Result<()> {
                 seen: Some(false),
                 content_type: None,
                 content: None,
+                priority: None,
+                localized_contents: vec![],
+                ids: vec![],
             }),
         },
         RabbitmqNotificationProtobuf {
             user_ids: vec![user_id.to_string()],
+            topic: None,
+            excluded_user_ids: vec![],
+            content_compressed: false,
             notification: Some(NotificationProtobuf {
                 id: id_2.to_hex(),
                 status: NotificationStatusProtobuf::Updated.into(),
@@ -57,6 +66,9 @@ async fn deduplication_not_needed() -> anyhow::Result<()> {
                 seen: Some(true),
                 content_type: None,
                 content: None,
+                priority: None,
+                localized_contents: vec![],
+                ids: vec![],
             }),
         },
     ];
@@ -97,6 +109,9 @@ async fn deduplication_same_notification_sent_twice() -> anyhow::Result<()> {
 
     let notification = RabbitmqNotificationProtobuf {
         user_ids: vec![user_id.to_string()],
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -108,6 +123,9 @@ async fn deduplication_same_notification_sent_twice() -> anyhow::Result<()> {
             seen: Some(false),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
     let notifications = [notification.clone(), notification.clone()];
@@ -145,6 +163,9 @@ async fn deduplication_older_notifications_are_ignored() -> anyhow::Result<()> {
     let notifications = [
         RabbitmqNotificationProtobuf {
             user_ids: vec![user_id.to_string()],
+            topic: None,
+            excluded_user_ids: vec![],
+            content_compressed: false,
             notification: Some(NotificationProtobuf {
                 id: id.to_hex(),
                 status: NotificationStatusProtobuf::Updated.into(),
@@ -156,10 +177,16 @@ async fn deduplication_older_notifications_are_ignored() -> anyhow::Result<()> {
                 seen: Some(false),
                 content_type: None,
                 content: None,
+                priority: None,
+                localized_contents: vec![],
+                ids: vec![],
             }),
         },
         RabbitmqNotificationProtobuf {
             user_ids: vec![user_id.to_string()],
+            topic: None,
+            excluded_user_ids: vec![],
+            content_compressed: false,
             notification: Some(NotificationProtobuf {
                 id: id.to_hex(),
                 status: NotificationStatusProtobuf::Updated.into(),
@@ -171,6 +198,9 @@ async fn deduplication_older_notifications_are_ignored() -> anyhow::Result<()> {
                 seen: Some(true),
                 content_type: None,
                 content: None,
+                priority: None,
+                localized_contents: vec![],
+                ids: vec![],
             }),
         },
     ];
@@ -33,6 +33,9 @@ async fn notification_delivered_to_the_user() -> anyhow::Result<()> {
     let now = OffsetDateTime::now_utc();
     let notification = RabbitmqNotificationProtobuf {
         user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -44,6 +47,9 @@ async fn notification_delivered_to_the_user() -> anyhow::Result<()> {
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
 
@@ -65,6 +71,108 @@ async fn notification_delivered_to_the_user() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[parallel]
+async fn compressed_notification_delivered_decompressed() -> anyhow::Result<()> {
+    let id = ObjectId::new();
+    let user_ids = [Uuid::new_v4()];
+    let content = "compressible content ".repeat(64).into_bytes();
+
+    let now = OffsetDateTime::now_utc();
+    let notification = RabbitmqNotificationProtobuf {
+        user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: true,
+        notification: Some(NotificationProtobuf {
+            id: id.to_hex(),
+            status: NotificationStatusProtobuf::Updated.into(),
+            timestamp: Some(Timestamp {
+                seconds: now.unix_timestamp(),
+                nanos: now.nanosecond() as i32,
+            }),
+            created_by: None,
+            seen: None,
+            content_type: Some("utf-8".to_string()),
+            content: Some(zstd::bulk::compress(&content, 3)?),
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
+        }),
+    };
+
+    let assertions_fn = |mut websockets: Vec<WebSocketStream<MaybeTlsStream<TcpStream>>>| async move {
+        let ws = websockets.first_mut().unwrap();
+
+        let ws_message = timeout(Duration::from_secs(5), ws.next()).await?.unwrap()?;
+        let Message::Binary(bytes) = ws_message else {
+            panic!("invalid message type");
+        };
+        let ws_message = WebSocketNotificationProtobuf::decode(bytes.as_slice())?;
+        let notification = ws_message.notification.unwrap();
+        assert_eq!(notification.id, id.to_hex());
+        assert_eq!(notification.content, Some(content));
+
+        Ok(())
+    };
+
+    test_notification_delivered(&user_ids, notification, assertions_fn).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[parallel]
+async fn many_seen_notifications_delivered_separately() -> anyhow::Result<()> {
+    let ids = [ObjectId::new(), ObjectId::new()];
+    let user_ids = [Uuid::new_v4()];
+
+    let now = OffsetDateTime::now_utc();
+    let notification = RabbitmqNotificationProtobuf {
+        user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
+        notification: Some(NotificationProtobuf {
+            id: String::new(),
+            status: NotificationStatusProtobuf::Updated.into(),
+            timestamp: Some(Timestamp {
+                seconds: now.unix_timestamp(),
+                nanos: now.nanosecond() as i32,
+            }),
+            created_by: None,
+            seen: Some(true),
+            content_type: None,
+            content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: ids.into_iter().map(ObjectId::to_hex).collect(),
+        }),
+    };
+
+    let assertions_fn = |mut websockets: Vec<WebSocketStream<MaybeTlsStream<TcpStream>>>| async move {
+        let ws = websockets.first_mut().unwrap();
+
+        for id in ids {
+            let ws_message = timeout(Duration::from_secs(5), ws.next()).await?.unwrap()?;
+            let Message::Binary(bytes) = ws_message else {
+                panic!("invalid message type");
+            };
+            let ws_message = WebSocketNotificationProtobuf::decode(bytes.as_slice())?;
+            let notification = ws_message.notification.unwrap();
+            assert_eq!(notification.id, id.to_hex());
+            assert_eq!(notification.seen, Some(true));
+            assert!(notification.ids.is_empty());
+        }
+
+        Ok(())
+    };
+
+    test_notification_delivered(&user_ids, notification, assertions_fn).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 #[parallel]
 async fn notification_delivered_to_all_user_devices() -> anyhow::Result<()> {
@@ -76,6 +184,9 @@ async fn notification_delivered_to_all_user_devices() -> anyhow::Result<()> {
     let now = OffsetDateTime::now_utc();
     let notification = RabbitmqNotificationProtobuf {
         user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -87,6 +198,9 @@ async fn notification_delivered_to_all_user_devices() -> anyhow::Result<()> {
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
 
@@ -132,6 +246,9 @@ async fn notification_delivered_after_other_device_disconnected() -> anyhow::Res
     let now = OffsetDateTime::now_utc();
     let notification = RabbitmqNotificationProtobuf {
         user_ids: vec![user_id.to_string()],
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -143,6 +260,9 @@ async fn notification_delivered_after_other_device_disconnected() -> anyhow::Res
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
 
@@ -185,6 +305,9 @@ async fn notification_delivered_after_other_device_disconnected() -> anyhow::Res
     let now = OffsetDateTime::now_utc();
     let notification = RabbitmqNotificationProtobuf {
         user_ids: vec![user_id.to_string()],
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -196,6 +319,9 @@ async fn notification_delivered_after_other_device_disconnected() -> anyhow::Res
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
 
@@ -235,6 +361,9 @@ async fn notification_delivered_only_to_selected_users() -> anyhow::Result<()> {
     let notification = RabbitmqNotificationProtobuf {
         // user_2 should not receive message
         user_ids: vec![user_1_id.to_string(), user_3_id.to_string()],
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -246,6 +375,9 @@ async fn notification_delivered_only_to_selected_users() -> anyhow::Result<()> {
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
 
@@ -300,6 +432,9 @@ async fn notification_delivered_to_all_users() -> anyhow::Result<()> {
     let now = OffsetDateTime::now_utc();
     let notification = RabbitmqNotificationProtobuf {
         user_ids: vec![],
+        topic: None,
+        excluded_user_ids: vec![],
+        content_compressed: false,
         notification: Some(NotificationProtobuf {
             id: id.to_hex(),
             status: NotificationStatusProtobuf::Updated.into(),
@@ -311,6 +446,9 @@ async fn notification_delivered_to_all_users() -> anyhow::Result<()> {
             seen: Some(true),
             content_type: None,
             content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
         }),
     };
 
@@ -345,6 +483,117 @@ async fn notification_delivered_to_all_users() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn topic_notification_without_recipients_not_delivered() -> anyhow::Result<()> {
+    let id = ObjectId::new();
+    let user_1_id = Uuid::new_v4();
+    let user_2_id = Uuid::new_v4();
+    // Connect multiple users
+    let user_ids = [user_1_id, user_2_id];
+
+    let now = OffsetDateTime::now_utc();
+    let notification = RabbitmqNotificationProtobuf {
+        // topic without subscribers must not become a broadcast
+        user_ids: vec![],
+        topic: Some("billing".to_string()),
+        excluded_user_ids: vec![],
+        content_compressed: false,
+        notification: Some(NotificationProtobuf {
+            id: id.to_hex(),
+            status: NotificationStatusProtobuf::Updated.into(),
+            timestamp: Some(Timestamp {
+                seconds: now.unix_timestamp(),
+                nanos: now.nanosecond() as i32,
+            }),
+            created_by: None,
+            seen: Some(true),
+            content_type: None,
+            content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
+        }),
+    };
+
+    let assertions_fn = |websockets: Vec<WebSocketStream<MaybeTlsStream<TcpStream>>>| async move {
+        let mut tasks = Vec::with_capacity(websockets.len());
+        for mut ws in websockets {
+            let task =
+                tokio::spawn(async move { timeout(Duration::from_secs(5), ws.next()).await });
+            tasks.push(task);
+        }
+
+        for task in tasks {
+            let timeout = task.await?;
+            assert!(timeout.is_err());
+        }
+
+        Ok(())
+    };
+
+    test_notification_delivered(&user_ids, notification, assertions_fn).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[parallel]
+async fn notification_not_delivered_to_excluded_user() -> anyhow::Result<()> {
+    let id = ObjectId::new();
+    let user_1_id = Uuid::new_v4();
+    let user_2_id = Uuid::new_v4();
+    let user_ids = [user_1_id, user_2_id];
+
+    let now = OffsetDateTime::now_utc();
+    let notification = RabbitmqNotificationProtobuf {
+        user_ids: user_ids.into_iter().map(|uuid| uuid.to_string()).collect(),
+        topic: None,
+        // user muted the notification or is in quiet hours
+        excluded_user_ids: vec![user_2_id.to_string()],
+        content_compressed: false,
+        notification: Some(NotificationProtobuf {
+            id: id.to_hex(),
+            status: NotificationStatusProtobuf::Updated.into(),
+            timestamp: Some(Timestamp {
+                seconds: now.unix_timestamp(),
+                nanos: now.nanosecond() as i32,
+            }),
+            created_by: None,
+            seen: Some(true),
+            content_type: None,
+            content: None,
+            priority: None,
+            localized_contents: vec![],
+            ids: vec![],
+        }),
+    };
+
+    let assertions_fn = |websockets: Vec<WebSocketStream<MaybeTlsStream<TcpStream>>>| async move {
+        let mut websockets = websockets;
+        let mut ws_2 = websockets.pop().unwrap();
+        let mut ws_1 = websockets.pop().unwrap();
+
+        let ws_message = timeout(Duration::from_secs(5), ws_1.next())
+            .await?
+            .unwrap()?;
+        let Message::Binary(bytes) = ws_message else {
+            panic!("invalid message type");
+        };
+        let ws_message = WebSocketNotificationProtobuf::decode(bytes.as_slice())?;
+        assert_eq!(ws_message.notification.unwrap().id, id.to_hex());
+
+        let result = timeout(Duration::from_secs(1), ws_2.next()).await;
+        assert!(result.is_err());
+
+        Ok(())
+    };
+
+    test_notification_delivered(&user_ids, notification, assertions_fn).await?;
+
+    Ok(())
+}
+
 async fn test_notification_delivered<F, Fut>(
     user_ids_to_connect: &[Uuid],
     notification: RabbitmqNotificationProtobuf,